@@ -0,0 +1,127 @@
+use crate::archive::{archive_dir, pgn_tag};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+
+const API_BASE: &str = "https://api.chess.com/pub/player";
+// chess.com asks API clients to identify themselves
+const USER_AGENT: &str = concat!("chessrl/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Deserialize)]
+struct ArchiveList {
+    archives: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct MonthlyArchive {
+    games: Vec<Game>,
+}
+
+#[derive(Deserialize)]
+struct Game {
+    #[serde(default)]
+    pgn: String,
+    #[serde(default)]
+    rules: String,
+    white: Player,
+    black: Player,
+}
+
+#[derive(Deserialize)]
+struct Player {
+    username: String,
+    result: String,
+}
+
+#[derive(Default)]
+pub struct ImportSummary {
+    pub months: usize,
+    pub games: usize,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    // opening name -> (games, wins, draws, losses) from the user's side
+    pub openings: HashMap<String, (usize, usize, usize, usize)>,
+}
+
+fn get_json<T: serde::de::DeserializeOwned>(url: &str) -> io::Result<T> {
+    let body = ureq::get(url)
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .map_err(|e| io::Error::other(e.to_string()))?
+        .into_string()?;
+    serde_json::from_str(&body).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+// chess.com reports a per-player result code; anything that isn't a win or a
+// drawn ending counts as a loss
+fn outcome(result: &str) -> i8 {
+    match result {
+        "win" => 1,
+        "agreed" | "repetition" | "stalemate" | "insufficient" | "50move"
+        | "timevsinsufficient" => 0,
+        _ => -1,
+    }
+}
+
+// "https://www.chess.com/openings/Sicilian-Defense-Najdorf..." -> "Sicilian Defense Najdorf..."
+fn opening_name(pgn: &str) -> String {
+    pgn_tag(pgn, "ECOUrl")
+        .and_then(|url| url.rsplit('/').next().map(|s| s.replace('-', " ")))
+        .or_else(|| pgn_tag(pgn, "ECO"))
+        .unwrap_or_else(|| "Unknown".to_string())
+}
+
+// pulls the last `months` monthly archives (all of them if None) into
+// <data dir>/archive/chesscom/<user>/YYYY-MM.pgn, overwriting earlier imports
+pub fn import(user: &str, months: Option<usize>) -> io::Result<ImportSummary> {
+    let dir = archive_dir("chesscom", user)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+    fs::create_dir_all(&dir)?;
+
+    let list: ArchiveList = get_json(&format!("{}/{}/games/archives", API_BASE, user.to_lowercase()))?;
+    let skip = months.map_or(0, |m| list.archives.len().saturating_sub(m));
+
+    let mut summary = ImportSummary::default();
+    for url in list.archives.iter().skip(skip) {
+        let archive: MonthlyArchive = get_json(url)?;
+
+        // archive urls end in .../games/YYYY/MM
+        let mut parts = url.rsplit('/');
+        let month = parts.next().unwrap_or("00");
+        let year = parts.next().unwrap_or("0000");
+
+        let mut pgns = Vec::new();
+        for game in archive.games.iter().filter(|g| g.rules == "chess" && !g.pgn.is_empty()) {
+            let side = if game.white.username.eq_ignore_ascii_case(user) {
+                &game.white
+            } else {
+                &game.black
+            };
+            let entry = summary.openings.entry(opening_name(&game.pgn)).or_default();
+            entry.0 += 1;
+            match outcome(&side.result) {
+                1 => {
+                    summary.wins += 1;
+                    entry.1 += 1;
+                }
+                0 => {
+                    summary.draws += 1;
+                    entry.2 += 1;
+                }
+                _ => {
+                    summary.losses += 1;
+                    entry.3 += 1;
+                }
+            }
+            pgns.push(game.pgn.trim().to_string());
+        }
+
+        summary.games += pgns.len();
+        summary.months += 1;
+        fs::write(dir.join(format!("{}-{}.pgn", year, month)), pgns.join("\n\n") + "\n")?;
+    }
+
+    Ok(summary)
+}
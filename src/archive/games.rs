@@ -0,0 +1,330 @@
+use crate::archive::pgn_tag;
+use crate::game::board::Board;
+use crate::game::handicap::Handicap;
+use crate::game::notation::parse_san;
+use crate::game::piece::Color;
+use crate::game::record::GameRecord;
+use crate::game::variant::{self, Standard, Variant};
+use crate::utils::{data_dir, pgn_date_time};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+// games played here, one PGN file each under <data dir>/games/
+pub fn games_dir() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("games"))
+}
+
+pub struct SavedGame {
+    pub date: String,
+    pub white: String,
+    pub black: String,
+    pub result: String,
+    pub termination: String,
+    pub opening: String,
+    pub variant: String,
+    pub handicap: String,
+    pub bot_level: String,
+    pub bot_time: String,
+    // the [FEN] tag of a game set up in the editor, empty otherwise
+    pub fen: String,
+    pub moves: Vec<String>,
+}
+
+impl SavedGame {
+    // which side the human had, if either
+    pub fn human_color(&self) -> Option<Color> {
+        if self.white == HUMAN {
+            Some(Color::White)
+        } else if self.black == HUMAN {
+            Some(Color::Black)
+        } else {
+            None
+        }
+    }
+
+    // "won", "lost" or "drew" from the human's side
+    pub fn outcome(&self) -> &'static str {
+        match (self.result.as_str(), self.human_color()) {
+            ("1-0", Some(Color::White)) | ("0-1", Some(Color::Black)) => "won",
+            ("1-0", _) | ("0-1", _) => "lost",
+            _ => "drew",
+        }
+    }
+
+    pub fn variant(&self) -> Arc<dyn Variant> {
+        variant::by_title(&self.variant).unwrap_or_else(|| Arc::new(Standard))
+    }
+
+    // the starting position and who moves first in it
+    pub fn start(&self) -> (Board, Color) {
+        if let Some(start) = self.variant().read_fen(&self.fen) {
+            return start;
+        }
+        let mut board = self.variant().initial_board();
+        if let Some(odds) = Handicap::from_name(&self.handicap) {
+            odds.apply(&mut board, self.human_color().unwrap_or(Color::White).opposite());
+        }
+        (board, Color::White)
+    }
+
+    // the game played through, stopping early at a move that doesn't
+    // parse. moves aren't forced to alternate sides, so the other color is
+    // tried when the expected one has no match
+    pub fn record(&self) -> GameRecord {
+        let variant = self.variant();
+        let (mut board, first) = self.start();
+        let mut record = GameRecord::new(board.clone(), first);
+        for san in &self.moves {
+            let to_move = record.to_move();
+            let found = [to_move, to_move.opposite()]
+                .into_iter()
+                .find_map(|color| parse_san(&board, &variant.moves(&board, color), san).map(|mv| (mv, color)));
+            let Some((mv, color)) = found else {
+                break;
+            };
+            variant.make_move(&mut board, mv.from, mv.to);
+            record.push(mv, color, board.clone()).san = san.clone();
+        }
+        record
+    }
+}
+
+pub const HUMAN: &str = "You";
+pub const BOT: &str = "ChessRL";
+
+pub struct NewGame<'a> {
+    pub unix_time: u64,
+    pub human: Color,
+    pub bot_time_secs: f32,
+    pub bot_level: String,
+    pub result: &'a str,
+    pub termination: &'a str,
+    pub opening: &'a str,
+    pub variant: &'a str,
+    pub handicap: &'a str,
+    // where a game set up in the editor started
+    pub fen: Option<&'a str>,
+    pub record: &'a GameRecord,
+}
+
+pub fn save(game: &NewGame) -> io::Result<PathBuf> {
+    let dir = games_dir().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+    fs::create_dir_all(&dir)?;
+    let pgn = to_pgn(game);
+
+    // timestamps name the files so they list in playing order
+    let mut path = dir.join(format!("{}.pgn", game.unix_time));
+    let mut n = 1;
+    while path.exists() {
+        path = dir.join(format!("{}-{}.pgn", game.unix_time, n));
+        n += 1;
+    }
+    fs::write(&path, pgn)?;
+    Ok(path)
+}
+
+// the game under way, rewritten after every move so a crash or a dropped
+// ssh session doesn't lose it. a finished game goes to games_dir instead
+fn autosave_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("autosave.pgn"))
+}
+
+// written beside the old one and renamed over it, so a crash halfway
+// through the write leaves the last complete save
+pub fn autosave(game: &NewGame) -> io::Result<()> {
+    let path = autosave_path().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let partial = path.with_extension("pgn.partial");
+    fs::write(&partial, to_pgn(game))?;
+    fs::rename(&partial, &path)
+}
+
+// the autosaved game, if there's one with moves in it
+pub fn load_autosave() -> Option<SavedGame> {
+    let pgn = fs::read_to_string(autosave_path()?).ok()?;
+    Some(parse(&pgn)).filter(|game| !game.moves.is_empty())
+}
+
+pub fn clear_autosave() {
+    if let Some(path) = autosave_path() {
+        let _ = fs::remove_file(path);
+    }
+}
+
+// the game with its tags, as save writes it
+pub fn to_pgn(game: &NewGame) -> String {
+    let (date, time) = pgn_date_time(game.unix_time);
+    let (white, black) = match game.human {
+        Color::White => (HUMAN, BOT),
+        Color::Black => (BOT, HUMAN),
+    };
+    let mut pgn = String::new();
+    for (tag, value) in [
+        ("Event", "Casual game"),
+        ("Variant", game.variant),
+        ("Handicap", game.handicap),
+        ("Site", "chessrl"),
+        ("Date", &date),
+        ("UTCTime", &time),
+        ("White", white),
+        ("Black", black),
+        ("Result", game.result),
+        ("Termination", game.termination),
+        ("Opening", game.opening),
+        ("BotLevel", &game.bot_level),
+        ("BotTime", &game.bot_time_secs.to_string()),
+    ] {
+        pgn.push_str(&format!("[{} \"{}\"]\n", tag, value));
+    }
+    if let Some(fen) = game.fen {
+        pgn.push_str(&format!("[SetUp \"1\"]\n[FEN \"{}\"]\n", fen));
+    }
+    pgn.push('\n');
+    let record = game.record;
+    pgn.push_str(&format_movetext(&record.sans(), &record.comments(), game.result, record.first_to_move()));
+    pgn
+}
+
+// numbered moves wrapped at 80 columns, each followed by its comment if it
+// has one, then the result. `first` is the side that made the first move
+pub fn format_movetext(moves: &[String], comments: &[Option<String>], result: &str, first: Color) -> String {
+    let mut tokens = Vec::new();
+    let mut after_comment = false;
+    let offset = if first == Color::Black { 1 } else { 0 };
+    for (idx, san) in moves.iter().enumerate() {
+        let ply = idx + offset;
+        tokens.push(if ply % 2 == 0 {
+            format!("{}. {}", ply / 2 + 1, san)
+        } else if after_comment || idx == 0 {
+            format!("{}... {}", ply / 2 + 1, san)
+        } else {
+            san.clone()
+        });
+        let comment = comments.get(idx).and_then(Option::as_ref);
+        after_comment = comment.is_some();
+        if let Some(comment) = comment {
+            tokens.extend(format!("{{{}}}", comment).split(' ').map(str::to_string));
+        }
+    }
+    tokens.push(result.to_string());
+
+    let mut text = String::new();
+    let mut line = String::new();
+    for token in tokens {
+        if !line.is_empty() && line.len() + token.len() + 1 > 80 {
+            text.push_str(&line);
+            text.push('\n');
+            line.clear();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&token);
+    }
+    text.push_str(&line);
+    text.push('\n');
+    text
+}
+
+// every saved game, newest first; unreadable files are skipped
+pub fn load_all() -> Vec<SavedGame> {
+    let Some(entries) = games_dir().and_then(|dir| fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "pgn"))
+        .collect();
+    paths.sort_by(|a, b| b.cmp(a));
+    paths
+        .iter()
+        .filter_map(|p| fs::read_to_string(p).ok())
+        .map(|pgn| parse(&pgn))
+        .collect()
+}
+
+// a file can hold many games, e.g. a chess.com monthly archive. each one
+// starts where a tag line follows movetext
+pub fn split_games(text: &str) -> Vec<String> {
+    let mut games = Vec::new();
+    let mut current = String::new();
+    let mut in_moves = false;
+    for line in text.lines() {
+        let is_tag = line.trim_start().starts_with('[');
+        if is_tag && in_moves {
+            games.push(std::mem::take(&mut current));
+            in_moves = false;
+        }
+        if !is_tag && !line.trim().is_empty() {
+            in_moves = true;
+        }
+        current.push_str(line);
+        current.push('\n');
+    }
+    if !current.trim().is_empty() {
+        games.push(current);
+    }
+    games
+}
+
+pub fn parse(pgn: &str) -> SavedGame {
+    let tag = |name| pgn_tag(pgn, name).unwrap_or_default();
+    SavedGame {
+        date: tag("Date"),
+        white: tag("White"),
+        black: tag("Black"),
+        result: tag("Result"),
+        termination: tag("Termination"),
+        opening: tag("Opening"),
+        variant: tag("Variant"),
+        handicap: tag("Handicap"),
+        bot_level: tag("BotLevel"),
+        bot_time: tag("BotTime"),
+        fen: tag("FEN"),
+        moves: movetext(pgn),
+    }
+}
+
+// the SAN tokens, without move numbers, comments or the result
+fn movetext(pgn: &str) -> Vec<String> {
+    let body: String = pgn
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('['))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut moves = Vec::new();
+    let mut in_comment = false;
+    // variations in parentheses are skipped whole
+    let mut depth = 0usize;
+    for token in body.split_whitespace() {
+        if in_comment {
+            in_comment = !token.ends_with('}');
+            continue;
+        }
+        if token.starts_with('{') {
+            in_comment = !token.ends_with('}');
+            continue;
+        }
+        if depth > 0 || token.starts_with('(') {
+            depth += token.matches('(').count();
+            depth = depth.saturating_sub(token.matches(')').count());
+            continue;
+        }
+        if token.starts_with('$') {
+            continue;
+        }
+        if matches!(token, "1-0" | "0-1" | "1/2-1/2" | "*") {
+            continue;
+        }
+        let token = token.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+        if token.is_empty() {
+            continue;
+        }
+        moves.push(token.to_string());
+    }
+    moves
+}
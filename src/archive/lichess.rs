@@ -0,0 +1,131 @@
+use crate::logging;
+use crate::utils::{data_dir, pgn_date_time};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const DAILY_URL: &str = "https://lichess.org/api/puzzle/daily";
+const USER_AGENT: &str = concat!("chessrl/", env!("CARGO_PKG_VERSION"));
+// long enough for a slow connection, short enough that no network says so soon
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Deserialize)]
+struct Daily {
+    game: DailyGame,
+    puzzle: DailyPuzzle,
+}
+
+#[derive(Deserialize)]
+struct DailyGame {
+    // the game's moves in SAN up to the puzzle, separated by spaces
+    pgn: String,
+    #[serde(default)]
+    players: Vec<DailyPlayer>,
+}
+
+#[derive(Deserialize)]
+struct DailyPlayer {
+    #[serde(default)]
+    name: String,
+    color: String,
+    rating: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct DailyPuzzle {
+    id: String,
+    rating: u32,
+    solution: Vec<String>,
+    #[serde(default)]
+    themes: Vec<String>,
+}
+
+// a puzzle as chessrl keeps it: the game up to the puzzle, then the moves
+// that solve it, the solver's first. kept in the data directory so the
+// day's puzzle is there without a connection
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Puzzle {
+    pub id: String,
+    pub rating: u32,
+    pub themes: Vec<String>,
+    pub white: String,
+    pub black: String,
+    // SAN, the last one the move the solver answers
+    pub moves: Vec<String>,
+    // coordinates with a promotion letter if any, e.g. "e7e8q"
+    pub solution: Vec<String>,
+    // the day it was fetched, as in PGN dates
+    pub date: String,
+}
+
+impl Puzzle {
+    pub fn url(&self) -> String {
+        format!("https://lichess.org/training/{}", self.id)
+    }
+}
+
+fn cache_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("puzzles").join("daily.json"))
+}
+
+// today, in the same form as Puzzle::date
+pub fn today() -> String {
+    let unix_secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    pgn_date_time(unix_secs).0
+}
+
+// the last puzzle fetched, whatever day that was
+pub fn cached() -> Option<Puzzle> {
+    let text = fs::read_to_string(cache_path()?).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+// asks lichess for today's puzzle and keeps it for offline use
+pub fn fetch_daily() -> io::Result<Puzzle> {
+    let body = ureq::get(DAILY_URL)
+        .set("User-Agent", USER_AGENT)
+        .timeout(TIMEOUT)
+        .call()
+        .map_err(|e| io::Error::other(e.to_string()))?
+        .into_string()?;
+    let daily: Daily = serde_json::from_str(&body).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let player = |color: &str| {
+        daily
+            .game
+            .players
+            .iter()
+            .find(|p| p.color == color)
+            .map(|p| match p.rating {
+                Some(rating) => format!("{} ({})", p.name, rating),
+                None => p.name.clone(),
+            })
+            .unwrap_or_default()
+    };
+    let puzzle = Puzzle {
+        id: daily.puzzle.id.clone(),
+        rating: daily.puzzle.rating,
+        themes: daily.puzzle.themes.clone(),
+        white: player("white"),
+        black: player("black"),
+        moves: daily.game.pgn.split_whitespace().map(str::to_string).collect(),
+        solution: daily.puzzle.solution.clone(),
+        date: today(),
+    };
+    if let Err(e) = save(&puzzle) {
+        logging::warn(&format!("couldn't keep the daily puzzle: {}", e));
+    }
+    Ok(puzzle)
+}
+
+fn save(puzzle: &Puzzle) -> io::Result<()> {
+    let path = cache_path().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_string_pretty(puzzle).map_err(io::Error::other)?;
+    fs::write(path, json)
+}
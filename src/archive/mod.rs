@@ -0,0 +1,21 @@
+pub mod chesscom;
+pub mod games;
+pub mod lichess;
+
+use crate::utils::data_dir;
+use std::path::PathBuf;
+
+// imported and played games live under <data dir>/archive/<source>/<user>/
+pub fn archive_dir(source: &str, user: &str) -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("archive").join(source).join(user.to_lowercase()))
+}
+
+// value of a PGN header tag like [ECO "B20"]
+pub fn pgn_tag(pgn: &str, tag: &str) -> Option<String> {
+    let prefix = format!("[{} \"", tag);
+    pgn.lines()
+        .map(str::trim)
+        .find_map(|line| line.strip_prefix(prefix.as_str()))
+        .and_then(|rest| rest.strip_suffix("\"]"))
+        .map(str::to_string)
+}
@@ -0,0 +1,864 @@
+use crate::archive::{self, chesscom};
+use crate::config::Config;
+use crate::archive::games;
+use crate::engine::analysis::{self, Judgement, MoveReport};
+use crate::engine::arena::{self, ArenaSettings, Entrant, Format, Tournament};
+use crate::engine::material::PieceValues;
+use crate::engine::personality::Personality;
+use crate::engine::rl::{mate_in, white_score, EvalTerms, RLEngine};
+use crate::engine::curriculum::{Curriculum, Opponent, Scripted};
+use crate::engine::selfplay::{self, Adjudication, Player};
+use crate::engine::strength::{self, REFERENCES};
+use crate::engine::weights::{self, WeightsFile};
+use crate::game::board::Board;
+use crate::game::movement::coordinates;
+use crate::game::notation::san;
+use crate::game::piece::Color;
+use crate::game::variant;
+use crate::hooks::HookEvent;
+use crate::json_events;
+use crate::ui::relay;
+use crate::utils::{expand_home, format_bytes};
+use serde::Serialize;
+use std::io::{self, BufRead, Result};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// a line of a command's report: on stdout as usual, or on stderr when
+// --json-events has stdout to itself
+macro_rules! report {
+    ($($arg:tt)*) => {
+        if json_events::enabled() {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
+const USAGE: &str = "usage:
+  chessrl [--seed N] [--config PATH] [--accessible]
+                                            start the game; a seed makes the
+                                            bot's searches reproducible, and
+                                            --config reads and saves settings
+                                            somewhere other than the usual
+                                            ~/.config/chessrl/config.toml.
+                                            --accessible draws plain text for
+                                            screen readers, see [accessibility]
+  chessrl --follow PATH|-|URL               show the last game in a PGN file as
+                                            it's written, read one from stdin
+                                            with -, or watch another chessrl's
+                                            --serve at http://host:port, see
+                                            [following a game]
+  chessrl --serve [PORT]                    play as usual, and let spectators
+                                            watch the game on screen live from a
+                                            browser or --follow (port 7879 if
+                                            not given)
+  chessrl --export-weights PATH             on quitting, write what the bot
+                                            learned this session to PATH
+                                            (JSON for .json, TOML otherwise)
+  chessrl --weights FILE                    play with the weights in FILE, a
+                                            name in ~/.local/share/chessrl/weights
+                                            or a path; works for match too
+  chessrl import chesscom <user> [--months N]
+                                            download a chess.com user's games
+                                            into the local archive
+  chessrl match [--games N] [--time SECS] [--variant NAME]
+                [--white STYLE] [--black STYLE] [--max-moves N]
+                [--simulations N] [--white-temperature T]
+                [--black-temperature T] [--export-weights PATH]
+                [--white-weights FILE] [--black-weights FILE]
+                [--white-values SET] [--black-values SET]
+                [--white-cutoff CP] [--black-cutoff CP]
+                                            play the engine against itself, calling
+                                            games early once they're clearly decided
+                                            (also takes --seed N; with --simulations
+                                            too, a match replays exactly). the
+                                            temperatures set each side's rollout
+                                            policy, see [engine] in the readme.
+                                            --export-weights writes white's learned
+                                            weights to PATH and black's next to it,
+                                            and each side can start from its own
+                                            weights file and play with its own
+                                            piece values (simplified, classical
+                                            or kaufman) and rollout cutoff (0 for
+                                            none)
+  chessrl train [--games N] [--window N] [--threshold R] [--time SECS]
+                [--simulations N] [--export-weights PATH] [--weights FILE]
+                [--learn-values]
+                                            train the engine against scripted
+                                            opponents of rising strength: a random
+                                            mover, a greedy material grabber, a
+                                            1-ply search and a 3-ply alpha-beta.
+                                            it moves up once it scores R (0.7) over
+                                            the last N (10) games against one, and
+                                            stops after beating the last or at
+                                            --games (200). --learn-values tunes
+                                            its piece values as well
+  chessrl arena ENTRANT ENTRANT... [--gauntlet] [--rounds N]
+                [--openings N] [--time SECS] [--simulations N]
+                                            hold a round robin (or with --gauntlet,
+                                            the first entrant against each other)
+                                            from N (4) book openings played both
+                                            ways round, then print a crosstable and
+                                            Elo estimates. an entrant is a style, a
+                                            set of piece values, a weights file, or
+                                            any of them joined by ':'
+  chessrl analyze <file.pgn> [--time SECS] [--out PATH]
+                                            run the engine over every move, write
+                                            an annotated copy (file.annotated.pgn
+                                            by default) and print each side's
+                                            accuracy and mistakes
+  chessrl bench [--time SECS] [--simulations N] [--hash MB]
+                                            search a few fixed positions and print
+                                            the engine's speed and tree statistics
+  chessrl eval [--fen FEN] [--time SECS] [--simulations N]
+                                            print a JSON line for the position with
+                                            its static eval term by term and the
+                                            engine's best move after SECS (1); reads
+                                            one FEN a line from stdin without --fen.
+                                            takes --weights too
+  chessrl calibrate [--games N]             measure what each step of the bot's
+                                            target Elo ladder is rated, from N (2)
+                                            games against each scripted opponent,
+                                            and keep the results for the game's
+                                            'elo' setting. takes a while
+
+--config works with every command. match, train, arena, analyze, bench and
+eval set their engines up from its [engine] and [bot] as the game's bot is,
+flags winning over it; calibrate always measures the built-in engine. all of
+them but arena and calibrate take --weights. --json-events with match, train,
+arena or calibrate prints a JSON line on stdout for every game start, search,
+move and result, the usual report going to stderr instead";
+
+// headless subcommands; anything here runs without touching the terminal UI.
+// their engines are set up from `config` as the game's bot is, flags winning
+// over it
+pub fn run(args: &[String], config: &Config, seed: Option<u64>, export: Option<&Path>, weights: Option<&str>) -> Result<()> {
+    if export.is_some() && args[0] != "match" && args[0] != "train" {
+        return Err(usage_error("--export-weights works with the game, match and train only"));
+    }
+    if weights.is_some() && !["match", "train", "analyze", "bench", "eval"].contains(&args[0].as_str()) {
+        return Err(usage_error("--weights works with the game, match, train, analyze, bench and eval only"));
+    }
+    if json_events::enabled() && !["match", "train", "arena", "calibrate"].contains(&args[0].as_str()) {
+        return Err(usage_error("--json-events works with match, train, arena and calibrate only"));
+    }
+    let weights = weights.or(config.bot.weights.as_deref());
+    match args[0].as_str() {
+        "import" => import(&args[1..]),
+        "match" => play_match(&args[1..], config, seed, export, weights),
+        "train" => train(&args[1..], config, seed, export, weights),
+        "arena" => arena(&args[1..], config, seed),
+        "analyze" => analyze(&args[1..], config, seed, weights),
+        "bench" => bench(&args[1..], config, seed, weights),
+        "eval" => eval(&args[1..], config, seed, weights),
+        "calibrate" => calibrate(&args[1..], seed),
+        "help" | "-h" | "--help" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        other => Err(usage_error(&format!("unknown command '{}'", other))),
+    }
+}
+
+// pulls `--seed N` out of the arguments, wherever it is, since it applies to
+// the game and the subcommands alike
+pub fn take_seed(args: &mut Vec<String>) -> Result<Option<u64>> {
+    take_flag(args, "--seed")?
+        .map(|n| n.parse().map_err(|_| usage_error("--seed takes a number")))
+        .transpose()
+}
+
+// the same for `--config PATH`
+pub fn take_config(args: &mut Vec<String>) -> Result<Option<PathBuf>> {
+    Ok(take_flag(args, "--config")?.map(|path| expand_home(&path)))
+}
+
+// the same for `--export-weights PATH`
+pub fn take_export_weights(args: &mut Vec<String>) -> Result<Option<PathBuf>> {
+    Ok(take_flag(args, "--export-weights")?.map(|path| expand_home(&path)))
+}
+
+// the same for `--weights FILE`, checked by loading it so a bad file stops
+// chessrl before the game starts
+pub fn take_weights(args: &mut Vec<String>) -> Result<Option<String>> {
+    let Some(name) = take_flag(args, "--weights")? else {
+        return Ok(None);
+    };
+    weights::load_into(&mut RLEngine::new(), Some(&name))?;
+    Ok(Some(name))
+}
+
+// the same for `--follow PATH`, `-` for stdin
+pub fn take_follow(args: &mut Vec<String>) -> Result<Option<String>> {
+    take_flag(args, "--follow")
+}
+
+// the same for `--serve [PORT]`, the port being optional
+pub fn take_serve(args: &mut Vec<String>) -> Result<Option<u16>> {
+    let Some(idx) = args.iter().position(|a| a == "--serve") else {
+        return Ok(None);
+    };
+    args.remove(idx);
+    match args.get(idx).filter(|arg| !arg.starts_with('-')) {
+        Some(port) => {
+            let port = port.parse().map_err(|_| usage_error("--serve takes a port number"))?;
+            args.remove(idx);
+            Ok(Some(port))
+        }
+        None => Ok(Some(relay::DEFAULT_PORT)),
+    }
+}
+
+// writes `engine`'s weights to `path` and says where they went
+pub fn export_weights(engine: &RLEngine, path: &Path) -> Result<()> {
+    WeightsFile::from_engine(engine).save(path)?;
+    report!("Weights written to {}", path.display());
+    Ok(())
+}
+
+// true when `--accessible` was given, taking it out of the arguments
+pub fn take_accessible(args: &mut Vec<String>) -> bool {
+    take_switch(args, "--accessible")
+}
+
+// the same for `--json-events`
+pub fn take_json_events(args: &mut Vec<String>) -> bool {
+    take_switch(args, "--json-events")
+}
+
+fn take_switch(args: &mut Vec<String>, switch: &str) -> bool {
+    let Some(idx) = args.iter().position(|a| a == switch) else {
+        return false;
+    };
+    args.remove(idx);
+    true
+}
+
+fn take_flag(args: &mut Vec<String>, flag: &str) -> Result<Option<String>> {
+    let Some(idx) = args.iter().position(|a| a == flag) else {
+        return Ok(None);
+    };
+    if idx + 1 >= args.len() {
+        return Err(usage_error(&format!("{} takes a value", flag)));
+    }
+    let value = args.remove(idx + 1);
+    args.remove(idx);
+    Ok(Some(value))
+}
+
+fn usage_error(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, format!("{}\n\n{}", msg, USAGE))
+}
+
+// value following `--flag`, if present
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
+}
+
+// how long each search may take, from --time and --simulations
+struct SearchLimits {
+    think_time: Duration,
+    simulations: Option<u32>,
+}
+
+// `default_time` is for when neither is given. a simulation budget alone
+// decides how long a move takes
+fn search_limits(args: &[String], default_time: Duration) -> Result<SearchLimits> {
+    let simulations = match flag_value(args, "--simulations") {
+        Some(n) => Some(n.parse().map_err(|_| usage_error("--simulations takes a number"))?),
+        None => None,
+    };
+    let think_time = match simulations {
+        Some(_) if flag_value(args, "--time").is_none() => Duration::from_secs(3600),
+        _ => think_time(args, default_time)?,
+    };
+    Ok(SearchLimits { think_time, simulations })
+}
+
+// --time alone, for the commands without a simulation budget
+fn think_time(args: &[String], default: Duration) -> Result<Duration> {
+    match flag_value(args, "--time") {
+        Some(secs) => secs
+            .parse::<f32>()
+            .ok()
+            .filter(|s| *s > 0.0)
+            .map(Duration::from_secs_f32)
+            .ok_or_else(|| usage_error("--time takes a number of seconds")),
+        None => Ok(default),
+    }
+}
+
+// a count like --games, 1 or more
+fn count(args: &[String], flag: &str, default: usize) -> Result<usize> {
+    match flag_value(args, flag) {
+        Some(n) => n.parse().ok().filter(|n| *n > 0).ok_or_else(|| usage_error(&format!("{} takes a number, 1 or more", flag))),
+        None => Ok(default),
+    }
+}
+
+fn import(args: &[String]) -> Result<()> {
+    let (source, user) = match args {
+        [source, user, ..] => (source.as_str(), user.as_str()),
+        _ => return Err(usage_error("import needs a source and a username")),
+    };
+    let months = match flag_value(args, "--months") {
+        Some(n) => Some(n.parse().map_err(|_| usage_error("--months takes a number"))?),
+        None => None,
+    };
+
+    match source {
+        "chesscom" | "chess.com" => {
+            println!("Importing chess.com games for {}...", user);
+            let summary = chesscom::import(user, months)?;
+            let done = format!(
+                "Imported {} games from {} monthly archives (+{} ={} -{})",
+                summary.games, summary.months, summary.wins, summary.draws, summary.losses
+            );
+            println!("{}", done);
+            Config::load().hooks.fire(HookEvent::ImportFinished, &done);
+            if let Some(dir) = archive::archive_dir("chesscom", user) {
+                println!("Saved to {}", dir.display());
+            }
+
+            let mut openings: Vec<_> = summary.openings.into_iter().collect();
+            openings.sort_by_key(|(_, stats)| std::cmp::Reverse(stats.0));
+            if !openings.is_empty() {
+                println!();
+                println!("{:<50} {:>5} {:>5} {:>5} {:>5}", "Opening", "Games", "Won", "Drawn", "Lost");
+                for (name, (games, wins, draws, losses)) in openings.iter().take(10) {
+                    let name: String = name.chars().take(50).collect();
+                    println!("{:<50} {:>5} {:>5} {:>5} {:>5}", name, games, wins, draws, losses);
+                }
+            }
+            Ok(())
+        }
+        other => Err(usage_error(&format!("unknown import source '{}'", other))),
+    }
+}
+
+fn play_match(args: &[String], config: &Config, seed: Option<u64>, export: Option<&Path>, weights: Option<&str>) -> Result<()> {
+    let games = count(args, "--games", 1)?;
+    let SearchLimits { think_time, simulations } = search_limits(args, Duration::from_millis(500))?;
+    let variant = match flag_value(args, "--variant") {
+        Some(name) => variant::by_name(name).ok_or_else(|| usage_error(&format!("unknown variant '{}'", name)))?,
+        None => variant::by_name("standard").expect("standard is always there"),
+    };
+    let personality = |flag| match flag_value(args, flag) {
+        Some(name) => Personality::from_name(name).ok_or_else(|| usage_error(&format!("unknown style '{}'", name))),
+        None => Ok(config.bot.personality),
+    };
+    let (white_style, black_style) = (personality("--white")?, personality("--black")?);
+    let temperature = |flag| match flag_value(args, flag) {
+        Some(t) => t
+            .parse::<f32>()
+            .ok()
+            .filter(|t| *t >= 0.0)
+            .ok_or_else(|| usage_error(&format!("{} takes a number, 0 or more", flag))),
+        None => Ok(config.engine.rollout_temperature),
+    };
+    let (white_temperature, black_temperature) = (temperature("--white-temperature")?, temperature("--black-temperature")?);
+    let values = |flag| match flag_value(args, flag) {
+        Some(name) => PieceValues::from_name(name)
+            .map(Some)
+            .ok_or_else(|| usage_error(&format!("unknown piece values '{}'", name))),
+        None => Ok(None),
+    };
+    let (white_values, black_values) = (values("--white-values")?, values("--black-values")?);
+    let cutoff = |flag| match flag_value(args, flag) {
+        Some(cp) => cp
+            .parse::<i32>()
+            .ok()
+            .filter(|cp| *cp >= 0)
+            .ok_or_else(|| usage_error(&format!("{} takes a number of centipawns, 0 for none", flag))),
+        None => Ok(config.engine.rollout_cutoff),
+    };
+    let (white_cutoff, black_cutoff) = (cutoff("--white-cutoff")?, cutoff("--black-cutoff")?);
+    let mut rules = Adjudication::default();
+    if let Some(n) = flag_value(args, "--max-moves") {
+        let moves: usize = n.parse().map_err(|_| usage_error("--max-moves takes a number"))?;
+        rules.max_plies = moves * 2;
+    }
+
+    // the engines keep what they learn from one game to the next
+    let engine = |offset: u64, file| config.engine(seed.map(|seed| seed.wrapping_add(offset)), file);
+    let mut white = engine(0, flag_value(args, "--white-weights").or(weights))?;
+    white.set_personality(white_style);
+    let mut black = engine(1, flag_value(args, "--black-weights").or(weights))?;
+    black.set_personality(black_style);
+    white.set_max_simulations(simulations);
+    black.set_max_simulations(simulations);
+    white.set_rollout_temperature(white_temperature);
+    black.set_rollout_temperature(black_temperature);
+    white.set_rollout_cutoff(Some(white_cutoff));
+    black.set_rollout_cutoff(Some(black_cutoff));
+    if let Some(values) = white_values {
+        white.set_piece_values(values);
+    }
+    if let Some(values) = black_values {
+        black.set_piece_values(values);
+    }
+
+    report!(
+        "White ({}, rollout temperature {}) vs Black ({}, rollout temperature {}), {} {} game(s) at {:.1}s a move",
+        white_style.name(),
+        white_temperature,
+        black_style.name(),
+        black_temperature,
+        games,
+        variant.title(),
+        think_time.as_secs_f32()
+    );
+    let mut score = 0.0;
+    for game in 1..=games {
+        let played = selfplay::play_game(
+            Player::Engine(&mut white),
+            Player::Engine(&mut black),
+            variant.clone(),
+            &[],
+            think_time,
+            rules,
+        );
+        score += match played.result.winner() {
+            Some(Color::White) => 1.0,
+            Some(_) => 0.0,
+            None => 0.5,
+        };
+        report!(
+            "Game {}: {} ({}) in {} moves",
+            game,
+            played.result.score(),
+            played.result.termination(),
+            played.record.len().div_ceil(2)
+        );
+        let movetext: Vec<String> = played
+            .record
+            .sans()
+            .chunks(2)
+            .enumerate()
+            .map(|(n, pair)| format!("{}. {}", n + 1, pair.join(" ")))
+            .collect();
+        report!("  {}", movetext.join(" "));
+    }
+    report!("Score: White {} - Black {}", score, games as f32 - score);
+    if let Some(path) = export {
+        export_weights(&white, path)?;
+        export_weights(&black, &black_path(path))?;
+    }
+    Ok(())
+}
+
+fn train(args: &[String], config: &Config, seed: Option<u64>, export: Option<&Path>, weights: Option<&str>) -> Result<()> {
+    let (max_games, window) = (count(args, "--games", 200)?, count(args, "--window", 10)?);
+    let threshold = match flag_value(args, "--threshold") {
+        Some(r) => r
+            .parse::<f32>()
+            .ok()
+            .filter(|r| (0.0..=1.0).contains(r))
+            .ok_or_else(|| usage_error("--threshold takes a score between 0 and 1"))?,
+        None => 0.7,
+    };
+    let SearchLimits { think_time, simulations } = search_limits(args, Duration::from_millis(200))?;
+    let variant = variant::by_name("standard").expect("standard is always there");
+
+    let mut learner = config.engine(seed, weights)?;
+    learner.set_max_simulations(simulations);
+    if args.iter().any(|a| a == "--learn-values") {
+        learner.set_learn_piece_values(true);
+    }
+    let mut curriculum = Curriculum::new(threshold, window);
+
+    let budget = match simulations {
+        Some(n) => format!("{} simulations", n),
+        None => format!("{:.1}s", think_time.as_secs_f32()),
+    };
+    report!(
+        "Training against {} scripted opponents, moving up at {:.0}% over {} games, at most {} games at {} a move",
+        Opponent::ALL.len(),
+        threshold * 100.0,
+        window,
+        max_games,
+        budget
+    );
+    let mut played = 0;
+    while let Some(opponent) = curriculum.opponent() {
+        if played == max_games {
+            break;
+        }
+        played += 1;
+        // the learner takes white and black in turn
+        let learner_color = if played % 2 == 1 { Color::White } else { Color::Black };
+        let mut scripted = Scripted::new(opponent, seed.map(|seed| seed.wrapping_add(played as u64)));
+        let (white, black) = match learner_color {
+            Color::White => (Player::Engine(&mut learner), Player::Scripted(&mut scripted)),
+            Color::Black => (Player::Scripted(&mut scripted), Player::Engine(&mut learner)),
+        };
+        let game = selfplay::play_game(white, black, variant.clone(), &[], think_time, Adjudication::default());
+        let score = match game.result.winner() {
+            Some(color) if color == learner_color => 1.0,
+            Some(_) => 0.0,
+            None => 0.5,
+        };
+        let promoted = curriculum.record(score);
+        report!(
+            "Game {} vs {} as {}: {} ({}) in {} moves",
+            played,
+            opponent.name(),
+            learner_color.name(),
+            game.result.score(),
+            game.result.termination(),
+            game.record.len().div_ceil(2)
+        );
+        if promoted {
+            report!("  Beat the {}, moving up", opponent.name());
+        }
+    }
+    match curriculum.opponent() {
+        None => report!("Beat every opponent in {} games", played),
+        Some(opponent) => report!(
+            "Stopped against the {} after {} games, scoring {:.0}% over the last {}",
+            opponent.name(),
+            played,
+            curriculum.score() * 100.0,
+            curriculum.games_at_tier()
+        ),
+    }
+    if let Some(path) = export {
+        export_weights(&learner, path)?;
+    }
+    Ok(())
+}
+
+fn arena(args: &[String], config: &Config, seed: Option<u64>) -> Result<()> {
+    let mut specs = Vec::new();
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--gauntlet" => {}
+            "--rounds" | "--openings" | "--time" | "--simulations" => {
+                rest.next();
+            }
+            flag if flag.starts_with("--") => return Err(usage_error(&format!("unknown arena option '{}'", flag))),
+            spec => specs.push(spec),
+        }
+    }
+    if specs.len() < 2 {
+        return Err(usage_error("arena needs at least two entrants"));
+    }
+    let mut entrants = Vec::new();
+    for spec in specs {
+        entrants.push(Entrant::parse(spec).map_err(|e| usage_error(&format!("entrant '{}': {}", spec, e)))?);
+    }
+    // the same entrant twice gets told apart by number
+    for idx in 1..entrants.len() {
+        let twins = entrants[..idx].iter().filter(|e| e.name == entrants[idx].name).count();
+        if twins > 0 {
+            entrants[idx].name = format!("{} #{}", entrants[idx].name, twins + 1);
+        }
+    }
+
+    let SearchLimits { think_time, simulations } = search_limits(args, Duration::from_millis(500))?;
+    let settings = ArenaSettings {
+        format: if args.iter().any(|a| a == "--gauntlet") { Format::Gauntlet } else { Format::RoundRobin },
+        rounds: count(args, "--rounds", 1)? as u32,
+        openings: count(args, "--openings", 4)?,
+        think_time,
+        simulations,
+        seed,
+        config: config.clone(),
+    };
+
+    let total = Tournament::total_games(&settings, entrants.len());
+    report!("{} of {} entrants, {} games", settings.format.name(), entrants.len(), total);
+    let tournament = arena::run(entrants, &settings, &mut |so_far, game| {
+        report!(
+            "Game {}/{}: {} - {}  {} ({}), {}",
+            so_far.games.len() + 1,
+            total,
+            so_far.entrants[game.white].name,
+            so_far.entrants[game.black].name,
+            game.result,
+            game.termination,
+            game.opening
+        );
+    })?;
+    report!();
+    for line in tournament.crosstable() {
+        report!("{}", line);
+    }
+    match tournament.save() {
+        Ok(path) => report!("\nResults saved to {}; type 'arena' in a game to see them again", path.display()),
+        Err(e) => eprintln!("couldn't save the results: {}", e),
+    }
+    Ok(())
+}
+
+// weights.toml -> weights.black.toml
+fn black_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().map_or("weights".into(), |s| s.to_string_lossy());
+    match path.extension() {
+        Some(ext) => path.with_file_name(format!("{}.black.{}", stem, ext.to_string_lossy())),
+        None => path.with_file_name(format!("{}.black", stem)),
+    }
+}
+
+fn analyze(args: &[String], config: &Config, seed: Option<u64>, weights: Option<&str>) -> Result<()> {
+    let Some(input) = args.first().filter(|a| !a.starts_with("--")) else {
+        return Err(usage_error("analyze needs a PGN file"));
+    };
+    let time = think_time(args, Duration::from_secs(1))?;
+    let input = PathBuf::from(input);
+    let output = match flag_value(args, "--out") {
+        Some(path) => PathBuf::from(path),
+        None => input.with_extension("annotated.pgn"),
+    };
+
+    let text = std::fs::read_to_string(&input)?;
+    let chunks = games::split_games(&text);
+    if chunks.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "no games in the file"));
+    }
+    let mut engine = config.engine(seed, weights)?;
+    let mut annotated = Vec::new();
+    for (number, chunk) in chunks.iter().enumerate() {
+        let game = games::parse(chunk);
+        let mut record = game.record();
+        let total = record.len();
+        let reports = analysis::analyze(&mut engine, game.variant(), &record, time, &mut |done| {
+            eprint!("\rGame {}: move {}/{}", number + 1, done, total);
+        });
+        eprintln!();
+
+        for (ply, report) in record.plies.iter_mut().zip(&reports) {
+            ply.eval = Some(report.eval_white());
+            ply.comment = Some(annotation(report));
+            if let Some(judgement) = report.judgement {
+                ply.san.push_str(judgement.symbol());
+            }
+        }
+        // whatever couldn't be followed goes out as it came in
+        let followed = record.len();
+        let mut moves = record.sans();
+        moves.extend_from_slice(&game.moves[followed..]);
+        let mut comments = record.comments();
+        if followed < game.moves.len() {
+            comments.push(Some("chessrl can't follow this move".to_string()));
+        }
+        let tags: String = chunk
+            .lines()
+            .filter(|line| line.trim_start().starts_with('['))
+            .map(|line| format!("{}\n", line.trim()))
+            .collect();
+        let result = if game.result.is_empty() { "*" } else { game.result.as_str() };
+        annotated.push(format!(
+            "{}[Annotator \"chessrl\"]\n\n{}",
+            tags,
+            games::format_movetext(&moves, &comments, result, record.first_to_move())
+        ));
+
+        print_summary(number + 1, &game, &reports);
+        if followed < game.moves.len() {
+            println!("  stopped at {}, a move chessrl can't play yet", game.moves[followed]);
+        }
+    }
+    std::fs::write(&output, annotated.join("\n"))?;
+    println!("\nAnnotated game(s) written to {}", output.display());
+    Ok(())
+}
+
+// "[%eval 0.35] Mistake. Best was Nf3 (Nf3 Nc6 Bb5)", with a word on
+// captures that lose material to the recaptures
+fn annotation(report: &MoveReport) -> String {
+    let eval = match mate_in(report.eval_white()) {
+        Some(moves) => format!("[%eval #{}]", moves),
+        None => format!("[%eval {:.2}]", report.eval_white() / 100.0),
+    };
+    let exchange = match report.exchange_loss {
+        Some(loss) => format!(" This capture loses {:.1} pawns to a recapture sequence.", loss as f32 / 100.0),
+        None => String::new(),
+    };
+    match report.judgement {
+        Some(judgement) => format!(
+            "{} {}.{} Best was {} ({})",
+            eval,
+            judgement.name(),
+            exchange,
+            report.best_san,
+            report.best_line.join(" ")
+        ),
+        None => eval,
+    }
+}
+
+fn print_summary(number: usize, game: &games::SavedGame, reports: &[MoveReport]) {
+    let name = |name: &str, fallback: &'static str| if name.is_empty() { fallback.to_string() } else { name.to_string() };
+    let (white, black) = (name(&game.white, "White"), name(&game.black, "Black"));
+    println!("Game {}: {} vs {} ({}), {} moves analyzed", number, white, black, game.result, reports.len());
+    println!(
+        "  {:<24} {:>8} {:>12} {:>8} {:>8} {:>9}",
+        "", "accuracy", "inaccuracies", "mistakes", "blunders", "avg loss"
+    );
+    for (color, player) in [(Color::White, white), (Color::Black, black)] {
+        let moves: Vec<&MoveReport> = reports.iter().filter(|r| r.color == color).collect();
+        let Some(accuracy) = analysis::accuracy(reports, color) else {
+            continue;
+        };
+        let avg_loss = moves.iter().map(|r| r.loss()).sum::<f32>() / moves.len() as f32;
+        let label: String = format!("{:?} ({})", color, player).chars().take(24).collect();
+        println!(
+            "  {:<24} {:>7.1}% {:>12} {:>8} {:>8} {:>9.0}",
+            label,
+            accuracy,
+            analysis::count(reports, color, Judgement::Inaccuracy),
+            analysis::count(reports, color, Judgement::Mistake),
+            analysis::count(reports, color, Judgement::Blunder),
+            avg_loss
+        );
+    }
+}
+
+// the positions `bench` searches: an opening, a middlegame and an endgame
+const BENCH_POSITIONS: [(&str, &str); 3] = [
+    ("start", "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1"),
+    ("italian", "r1bq1rk1/pppp1ppp/2n2n2/2b1p3/2B1P3/2PP1N2/PP3PPP/RNBQ1RK1 w - - 0 7"),
+    ("rook ending", "8/5pk1/6p1/R7/5P2/6PK/r7/8 b - - 0 40"),
+];
+
+fn bench(args: &[String], config: &Config, seed: Option<u64>, weights: Option<&str>) -> Result<()> {
+    let SearchLimits { think_time: time, simulations } = search_limits(args, Duration::from_secs(2))?;
+    let mut engine = config.engine(seed, weights)?;
+    engine.set_max_simulations(simulations);
+    // --tree-memory from before the eval table shared the budget
+    if let Some(mb) = flag_value(args, "--hash").or_else(|| flag_value(args, "--tree-memory")) {
+        let mb: usize = mb.parse().map_err(|_| usage_error("--hash takes a number of megabytes"))?;
+        engine.set_hash_budget((mb > 0).then_some(mb << 20));
+    }
+
+    println!(
+        "{:<12} {:>8} {:>9} {:>8} {:>8} {:>9} {:>8} {:>10} {:>9} {:>6} {:>7}  best",
+        "position", "sims", "sims/s", "rollout", "depth", "branching", "tree", "memory", "recycled", "hits", "1st cut"
+    );
+    let (mut total_sims, mut total_time) = (0u64, Duration::ZERO);
+    for (name, fen) in BENCH_POSITIONS {
+        let (board, color) = Board::from_fen(fen).expect("bench positions are valid FEN");
+        let result = engine.search(&board, color, None, time, &mut |_| {});
+        let stats = &engine.current_stats;
+        println!(
+            "{:<12} {:>8} {:>9.0} {:>8.1} {:>8} {:>9.2} {:>8} {:>10} {:>9} {:>5.1}% {:>6.1}%  {}",
+            name,
+            stats.total_simulations,
+            stats.simulations_per_sec(),
+            stats.avg_rollout_depth(),
+            stats.depth_reached,
+            stats.branching_factor(),
+            stats.tree_size,
+            format_bytes(stats.memory_bytes),
+            stats.recycled,
+            stats.table_hit_rate() * 100.0,
+            stats.first_move_cutoff_rate() * 100.0,
+            result.map_or("-".to_string(), |r| san(&board, r.best_move))
+        );
+        total_sims += stats.total_simulations as u64;
+        total_time += stats.elapsed;
+    }
+    println!(
+        "total: {} simulations in {:.1}s, {:.0} per second",
+        total_sims,
+        total_time.as_secs_f32(),
+        total_sims as f32 / total_time.as_secs_f32().max(0.001)
+    );
+    Ok(())
+}
+
+// one line of `eval` output. scores are centipawns from white's side,
+// whichever side is to move
+#[derive(Serialize)]
+struct EvalLine {
+    fen: String,
+    to_move: &'static str,
+    // each term after its weight; they add up to static_eval
+    terms: EvalTerms,
+    static_eval: f32,
+    best_move: Option<String>,
+    san: Option<String>,
+    score: Option<f32>,
+    // moves to mate, negative when black mates
+    mate: Option<i32>,
+    pv: Vec<String>,
+    simulations: u32,
+}
+
+fn eval(args: &[String], config: &Config, seed: Option<u64>, weights: Option<&str>) -> Result<()> {
+    let SearchLimits { think_time: time, simulations } = search_limits(args, Duration::from_secs(1))?;
+    let mut engine = config.engine(seed, weights)?;
+    engine.set_max_simulations(simulations);
+
+    let fens: Vec<String> = match flag_value(args, "--fen") {
+        Some(fen) => vec![fen.to_string()],
+        None => io::stdin().lock().lines().collect::<Result<_>>()?,
+    };
+    for fen in fens.iter().map(|fen| fen.trim()).filter(|fen| !fen.is_empty()) {
+        // a bad line gets an error line of its own, so a script can match
+        // answers to questions by counting
+        let Some((board, color)) = Board::from_fen(fen) else {
+            println!("{}", serde_json::json!({ "fen": fen, "error": "not a valid FEN" }));
+            continue;
+        };
+        let terms = engine.eval_terms(&board, color);
+        let term_weights = engine.eval_weights();
+        let result = engine.search(&board, color, None, time, &mut |_| {});
+        let line = EvalLine {
+            fen: fen.to_string(),
+            to_move: color.name(),
+            terms: terms.scaled(&term_weights).for_white(color),
+            static_eval: white_score(terms.weighted(&term_weights), color),
+            best_move: result.as_ref().map(|r| coordinates(r.best_move)),
+            san: result.as_ref().map(|r| san(&board, r.best_move)),
+            score: result.as_ref().map(|r| white_score(r.score, color)),
+            mate: result.as_ref().and_then(|r| mate_in(white_score(r.score, color))),
+            pv: result.as_ref().map_or(Vec::new(), |r| r.pv.iter().map(|mv| coordinates(*mv)).collect()),
+            simulations: engine.current_stats.total_simulations,
+        };
+        println!("{}", serde_json::to_string(&line).map_err(io::Error::other)?);
+    }
+    Ok(())
+}
+
+fn calibrate(args: &[String], seed: Option<u64>) -> Result<()> {
+    let games = count(args, "--games", 2)?;
+    let references: Vec<String> = REFERENCES.iter().map(|(opponent, rating)| format!("{} ({:.0})", opponent.name(), rating)).collect();
+    report!(
+        "Calibrating against {}, {} game{} each",
+        references.join(", "),
+        games,
+        if games == 1 { "" } else { "s" }
+    );
+    let calibration = strength::calibrate(games, seed, &mut |game| {
+        report!(
+            "Step {}: {} as {} against the {} ({})",
+            game.rung + 1,
+            match game.score {
+                s if s > 0.5 => "won",
+                s if s < 0.5 => "lost",
+                _ => "drew",
+            },
+            game.engine_color.name(),
+            game.opponent.name(),
+            game.termination
+        );
+    });
+    report!();
+    report!("{:>4}  {:>11}  {:>8}  {:>5}", "step", "simulations", "max loss", "Elo");
+    for (idx, rung) in calibration.rungs.iter().enumerate() {
+        report!("{:>4}  {:>11}  {:>8.0}  {:>5.0}", idx + 1, rung.simulations, rung.max_loss, rung.elo);
+    }
+    match calibration.save() {
+        Ok(path) => report!("\nSaved to {}; the game uses it from the next start", path.display()),
+        Err(e) => eprintln!("couldn't save the calibration: {}", e),
+    }
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use crate::engine::difficulty::{BotConfig, EngineConfig};
+use crate::engine::rl::RLEngine;
+use crate::engine::weights;
+use crate::engine::uci::UciConfig;
+use crate::hooks::Hooks;
+use crate::logging::LogConfig;
+use crate::ui::keymap::Keymap;
+use crate::ui::narration::AccessibilityConfig;
+use crate::ui::panels::Panels;
+use crate::ui::theme::UiConfig;
+use crate::utils::{config_dir, PathsConfig};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+// set by --config, in place of the usual location
+static PATH: OnceLock<PathBuf> = OnceLock::new();
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub keys: Keymap,
+    pub hooks: Hooks,
+    pub ui: UiConfig,
+    pub layout: Panels,
+    pub bot: BotConfig,
+    pub engine: EngineConfig,
+    pub uci: UciConfig,
+    pub paths: PathsConfig,
+    pub log: LogConfig,
+    pub accessibility: AccessibilityConfig,
+}
+
+impl Config {
+    pub fn path() -> Option<PathBuf> {
+        PATH.get().cloned().or_else(|| config_dir().map(|dir| dir.join("config.toml")))
+    }
+
+    // loads and saves go to `path` from now on
+    pub fn use_path(path: PathBuf) {
+        let _ = PATH.set(path);
+    }
+
+    // a missing or broken config file just means defaults
+    pub fn load() -> Self {
+        Self::try_load().unwrap_or_default()
+    }
+
+    // a missing file is still the defaults, but a broken one is an error
+    pub fn try_load() -> std::io::Result<Self> {
+        let Some(path) = Self::path() else {
+            return Ok(Self::default());
+        };
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e),
+        };
+        toml::from_str(&text).map_err(|e| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), e),
+            )
+        })
+    }
+
+    // [engine]'s search settings and [bot]'s style, contempt, piece values
+    // and terms, as the game's bot has them. the weights are left alone:
+    // load them first, since loading resets what this sets over them
+    pub fn configure(&self, engine: &mut RLEngine) -> std::io::Result<()> {
+        engine.set_personality(self.bot.personality);
+        engine.set_contempt(self.bot.contempt as f32);
+        engine.set_multi_pv(self.engine.multi_pv());
+        engine.set_rollout_temperature(self.engine.rollout_temperature);
+        engine.set_rollout_cutoff(self.engine.rollout_cutoff());
+        engine.set_hash_budget(self.engine.hash_budget());
+        weights::apply_config(engine, &self.bot)
+    }
+
+    // an engine set up like the game's bot, playing with the weights file
+    // `weights` (None for the built-in values) under the config's settings
+    pub fn engine(&self, seed: Option<u64>, weights: Option<&str>) -> std::io::Result<RLEngine> {
+        let mut engine = seed.map_or_else(RLEngine::new, RLEngine::with_seed);
+        weights::load_into(&mut engine, weights)?;
+        self.configure(&mut engine)?;
+        Ok(engine)
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let path = Self::path().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::NotFound, "no config directory")
+        })?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = toml::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        fs::write(path, text)
+    }
+}
@@ -0,0 +1,154 @@
+use crate::engine::rl::{white_score, MovePair, RLEngine};
+use crate::game::board::Board;
+use crate::game::notation::san;
+use crate::game::piece::Color;
+use crate::game::record::GameRecord;
+use crate::game::variant::Variant;
+use std::sync::Arc;
+use std::time::Duration;
+
+// how many centipawns a move can give away before it earns a mark
+const INACCURACY: f32 = 50.0;
+const MISTAKE: f32 = 100.0;
+const BLUNDER: f32 = 300.0;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Judgement {
+    Inaccuracy,
+    Mistake,
+    Blunder,
+}
+
+impl Judgement {
+    pub fn from_loss(loss: f32) -> Option<Judgement> {
+        if loss >= BLUNDER {
+            Some(Judgement::Blunder)
+        } else if loss >= MISTAKE {
+            Some(Judgement::Mistake)
+        } else if loss >= INACCURACY {
+            Some(Judgement::Inaccuracy)
+        } else {
+            None
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Judgement::Inaccuracy => "Inaccuracy",
+            Judgement::Mistake => "Mistake",
+            Judgement::Blunder => "Blunder",
+        }
+    }
+
+    // the PGN move suffix
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Judgement::Inaccuracy => "?!",
+            Judgement::Mistake => "?",
+            Judgement::Blunder => "??",
+        }
+    }
+}
+
+// what the engine made of one move. scores are in centipawns for the side
+// that moved
+pub struct MoveReport {
+    pub color: Color,
+    pub best_san: String,
+    pub best_score: f32,
+    pub played_score: f32,
+    // the engine's line after its own choice, in SAN
+    pub best_line: Vec<String>,
+    pub judgement: Option<Judgement>,
+    // what a capture gives back to the recaptures, when it loses material
+    // on the exchange
+    pub exchange_loss: Option<i32>,
+}
+
+impl MoveReport {
+    pub fn loss(&self) -> f32 {
+        (self.best_score - self.played_score).max(0.0)
+    }
+
+    // after the move, from white's side, the way PGN evals are written
+    pub fn eval_white(&self) -> f32 {
+        white_score(self.played_score, self.color)
+    }
+
+    // 0-100, from how much of the mover's winning chances the move kept
+    pub fn accuracy(&self) -> f32 {
+        let lost = (win_chance(self.best_score) - win_chance(self.played_score)).max(0.0);
+        (103.1668 * (-0.04354 * lost).exp() - 3.1669).clamp(0.0, 100.0)
+    }
+}
+
+// centipawns to a 0-100 chance of winning
+fn win_chance(score: f32) -> f32 {
+    50.0 + 50.0 * (2.0 / (1.0 + (-0.00368208 * score).exp()) - 1.0)
+}
+
+// searches every position of a game, once for the engine's own choice and
+// once for the move that was played. `on_move` hears after each move is done
+pub fn analyze(
+    engine: &mut RLEngine,
+    variant: Arc<dyn Variant>,
+    record: &GameRecord,
+    time: Duration,
+    on_move: &mut dyn FnMut(usize),
+) -> Vec<MoveReport> {
+    engine.set_variant(variant.clone());
+    let history = record.with_sides();
+    let mut reports = Vec::new();
+    for (idx, ply) in record.plies.iter().enumerate() {
+        let board = &record.positions()[idx];
+        engine.set_history(history[..=idx].to_vec());
+
+        let played = ply.mv.pair();
+        let Some(best) = engine.search(board, ply.color, None, time, &mut |_| {}) else {
+            break;
+        };
+        let played_score = if best.best_move == played {
+            best.score
+        } else {
+            match engine.search(board, ply.color, Some(&[played]), time, &mut |_| {}) {
+                Some(played) => played.score,
+                None => break,
+            }
+        };
+        let judgement = Judgement::from_loss(best.score - played_score);
+        let exchange = board.get_piece(played.1).map(|_| engine.see(board, played.0, played.1));
+        reports.push(MoveReport {
+            color: ply.color,
+            best_san: san(board, best.best_move),
+            best_score: best.score,
+            played_score,
+            best_line: line(variant.as_ref(), board, &best.pv),
+            judgement,
+            exchange_loss: exchange.filter(|&exchange| exchange < 0).map(|exchange| -exchange),
+        });
+        on_move(idx + 1);
+    }
+    reports
+}
+
+fn line(variant: &dyn Variant, board: &Board, pv: &[MovePair]) -> Vec<String> {
+    let mut board = board.clone();
+    let mut sans = Vec::new();
+    for &(from, to) in pv {
+        sans.push(san(&board, (from, to)));
+        if !variant.make_move(&mut board, from, to) {
+            break;
+        }
+    }
+    sans
+}
+
+// average accuracy over one side's moves
+pub fn accuracy(reports: &[MoveReport], color: Color) -> Option<f32> {
+    let scores: Vec<f32> = reports.iter().filter(|r| r.color == color).map(MoveReport::accuracy).collect();
+    (!scores.is_empty()).then(|| scores.iter().sum::<f32>() / scores.len() as f32)
+}
+
+pub fn count(reports: &[MoveReport], color: Color, judgement: Judgement) -> usize {
+    reports.iter().filter(|r| r.color == color && r.judgement == Some(judgement)).count()
+}
@@ -0,0 +1,343 @@
+use crate::config::Config;
+use crate::engine::material::PieceValues;
+use crate::engine::personality::Personality;
+use crate::engine::rl::RLEngine;
+use crate::engine::selfplay::{self, Adjudication, Player};
+use crate::engine::weights;
+use crate::game::openings;
+use crate::game::variant;
+use crate::utils::{data_dir, pgn_date_time};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+// how far an estimate can stray from the field's average; a clean sweep
+// would otherwise run off forever
+const MAX_ELO: f32 = 800.0;
+
+// one bot in a tournament: a style, with a weights file and a set of piece
+// values over it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Entrant {
+    pub name: String,
+    pub style: Personality,
+    pub weights: Option<String>,
+    // tournaments saved before entrants had them have none
+    #[serde(default)]
+    pub values: Option<PieceValues>,
+}
+
+impl Entrant {
+    // a style, a set of piece values and a weights file, any of them left
+    // out and the rest joined by ':' in any order: "aggressive",
+    // "kaufman", "aggressive:FILE", "positional:classical:FILE". with no
+    // style it plays the balanced one. files are checked by loading them
+    pub fn parse(spec: &str) -> Result<Entrant, String> {
+        let (mut style, mut values, mut file) = (None, None, None);
+        for part in spec.split(':') {
+            if let Some(named) = Personality::from_name(part) {
+                if style.replace(named).is_some() {
+                    return Err("more than one style".to_string());
+                }
+            } else if let Some(named) = PieceValues::from_name(part) {
+                if values.replace(named).is_some() {
+                    return Err("more than one set of piece values".to_string());
+                }
+            } else if file.replace(part).is_some() {
+                return Err("more than one weights file".to_string());
+            }
+        }
+        if let Some(file) = file {
+            weights::load_into(&mut RLEngine::new(), Some(file)).map_err(|e| e.to_string())?;
+        }
+        Ok(Entrant {
+            name: spec.to_string(),
+            style: style.unwrap_or(Personality::Balanced),
+            weights: file.map(str::to_string),
+            values,
+        })
+    }
+
+    // a fresh engine for every game, so nothing learned in one carries over
+    fn engine(&self, config: &Config, seed: Option<u64>) -> io::Result<RLEngine> {
+        let mut engine = config.engine(seed, self.weights.as_deref())?;
+        engine.set_personality(self.style);
+        if let Some(values) = self.values {
+            engine.set_piece_values(values);
+        }
+        Ok(engine)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Format {
+    // everyone plays everyone
+    RoundRobin,
+    // the first entrant plays each of the others
+    Gauntlet,
+}
+
+impl Format {
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::RoundRobin => "Round robin",
+            Format::Gauntlet => "Gauntlet",
+        }
+    }
+
+    fn pairings(self, entrants: usize) -> Vec<(usize, usize)> {
+        match self {
+            Format::RoundRobin => (0..entrants).flat_map(|a| (a + 1..entrants).map(move |b| (a, b))).collect(),
+            Format::Gauntlet => (1..entrants).map(|b| (0, b)).collect(),
+        }
+    }
+}
+
+pub struct ArenaSettings {
+    pub format: Format,
+    pub rounds: u32,
+    // how many of the book's lines each pairing plays, both ways round
+    pub openings: usize,
+    pub think_time: Duration,
+    pub simulations: Option<u32>,
+    pub seed: Option<u64>,
+    // [engine] and [bot], which every entrant plays under but for its own
+    // style and weights
+    pub config: Config,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ArenaGame {
+    // indexes into the entrants
+    pub white: usize,
+    pub black: usize,
+    pub opening: String,
+    pub result: String,
+    pub termination: String,
+    pub moves: Vec<String>,
+}
+
+impl ArenaGame {
+    // what `entrant` scored, if it played
+    fn score_for(&self, entrant: usize) -> Option<f32> {
+        let white = match self.result.as_str() {
+            "1-0" => 1.0,
+            "0-1" => 0.0,
+            _ => 0.5,
+        };
+        if entrant == self.white {
+            Some(white)
+        } else if entrant == self.black {
+            Some(1.0 - white)
+        } else {
+            None
+        }
+    }
+}
+
+// a finished tournament, kept in <data dir>/arena for the results screen
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Tournament {
+    pub unix_time: u64,
+    pub format: Format,
+    // what each move got, e.g. "0.5s" or "800 simulations"
+    pub budget: String,
+    pub entrants: Vec<Entrant>,
+    pub games: Vec<ArenaGame>,
+}
+
+impl Tournament {
+    pub fn total_games(settings: &ArenaSettings, entrants: usize) -> usize {
+        settings.format.pairings(entrants).len() * settings.rounds as usize * openings_used(settings) * 2
+    }
+
+    // (points, games) for one entrant
+    pub fn points(&self, entrant: usize) -> (f32, usize) {
+        let scores: Vec<f32> = self.games.iter().filter_map(|g| g.score_for(entrant)).collect();
+        (scores.iter().sum(), scores.len())
+    }
+
+    // what `entrant` scored against `opponent`, if they met
+    fn head_to_head(&self, entrant: usize, opponent: usize) -> Option<f32> {
+        let scores: Vec<f32> = self
+            .games
+            .iter()
+            .filter(|g| g.white == opponent || g.black == opponent)
+            .filter_map(|g| g.score_for(entrant))
+            .collect();
+        (!scores.is_empty()).then(|| scores.iter().sum())
+    }
+
+    // ratings relative to the field's average, fitted so each entrant's
+    // expected score against the ones it played matches what it scored
+    pub fn elo(&self) -> Vec<f32> {
+        let mut ratings = vec![0.0f32; self.entrants.len()];
+        for _ in 0..500 {
+            for entrant in 0..ratings.len() {
+                let (mut actual, mut expected, mut played) = (0.0, 0.0, 0);
+                for game in &self.games {
+                    let Some(score) = game.score_for(entrant) else {
+                        continue;
+                    };
+                    let opponent = if game.white == entrant { game.black } else { game.white };
+                    actual += score;
+                    expected += 1.0 / (1.0 + 10f32.powf((ratings[opponent] - ratings[entrant]) / 400.0));
+                    played += 1;
+                }
+                if played > 0 {
+                    ratings[entrant] += 32.0 * (actual - expected) / played as f32;
+                }
+            }
+            let mean = ratings.iter().sum::<f32>() / ratings.len().max(1) as f32;
+            for rating in &mut ratings {
+                *rating = (*rating - mean).clamp(-MAX_ELO, MAX_ELO);
+            }
+        }
+        ratings
+    }
+
+    // entrants by points, best first
+    pub fn standings(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.entrants.len()).collect();
+        order.sort_by(|a, b| self.points(*b).0.total_cmp(&self.points(*a).0));
+        order
+    }
+
+    // the table the CLI prints and the results screen shows: one row per
+    // entrant in order of standing, with its score against each of the others
+    pub fn crosstable(&self) -> Vec<String> {
+        let order = self.standings();
+        let elo = self.elo();
+        let width = self.entrants.iter().map(|e| e.name.len()).max().unwrap_or(0).max(7);
+        let mut header = format!(" #  {:<width$}  {:>5}  {:>9}", "Entrant", "Elo", "Points", width = width);
+        for column in 1..=order.len() {
+            header.push_str(&format!("  {:>5}", column));
+        }
+        let mut lines = vec![header];
+        for (rank, &entrant) in order.iter().enumerate() {
+            let (points, games) = self.points(entrant);
+            let mut row = format!(
+                "{:>2}  {:<width$}  {:>+5.0}  {:>9}",
+                rank + 1,
+                self.entrants[entrant].name,
+                elo[entrant],
+                format!("{}/{}", points, games),
+                width = width
+            );
+            for &opponent in &order {
+                let cell = match self.head_to_head(entrant, opponent) {
+                    _ if opponent == entrant => "x".to_string(),
+                    Some(points) => points.to_string(),
+                    None => "-".to_string(),
+                };
+                row.push_str(&format!("  {:>5}", cell));
+            }
+            lines.push(row);
+        }
+        lines
+    }
+
+    pub fn date(&self) -> String {
+        pgn_date_time(self.unix_time).0
+    }
+
+    pub fn save(&self) -> io::Result<PathBuf> {
+        let dir = arena_dir().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+        fs::create_dir_all(&dir)?;
+        let mut path = dir.join(format!("{}.json", self.unix_time));
+        let mut n = 1;
+        while path.exists() {
+            path = dir.join(format!("{}-{}.json", self.unix_time, n));
+            n += 1;
+        }
+        let text = serde_json::to_string_pretty(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(&path, text)?;
+        Ok(path)
+    }
+}
+
+fn arena_dir() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("arena"))
+}
+
+fn openings_used(settings: &ArenaSettings) -> usize {
+    settings.openings.clamp(1, openings::suite().len())
+}
+
+// every saved tournament, newest first; unreadable files are skipped
+pub fn load_all() -> Vec<Tournament> {
+    let Some(entries) = arena_dir().and_then(|dir| fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort_by(|a, b| b.cmp(a));
+    paths
+        .iter()
+        .filter_map(|p| fs::read_to_string(p).ok())
+        .filter_map(|text| serde_json::from_str(&text).ok())
+        .collect()
+}
+
+// plays the whole tournament: every pairing, each opening once with either
+// entrant as white, `rounds` times over. `on_game` hears about each game as
+// it finishes
+pub fn run(
+    entrants: Vec<Entrant>,
+    settings: &ArenaSettings,
+    on_game: &mut dyn FnMut(&Tournament, &ArenaGame),
+) -> io::Result<Tournament> {
+    let variant = variant::by_name("standard").expect("standard is always there");
+    let suite = openings::suite();
+    let suite = &suite[..openings_used(settings)];
+    let pairings = settings.format.pairings(entrants.len());
+    let mut tournament = Tournament {
+        unix_time: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+        format: settings.format,
+        budget: match settings.simulations {
+            Some(n) => format!("{} simulations", n),
+            None => format!("{:.1}s", settings.think_time.as_secs_f32()),
+        },
+        entrants,
+        games: Vec::new(),
+    };
+
+    for _ in 0..settings.rounds {
+        for (opening, moves) in suite {
+            for &(a, b) in &pairings {
+                for (white, black) in [(a, b), (b, a)] {
+                    let seed = settings.seed.map(|seed| seed.wrapping_add(2 * tournament.games.len() as u64));
+                    let mut white_engine = tournament.entrants[white].engine(&settings.config, seed)?;
+                    let mut black_engine = tournament.entrants[black].engine(&settings.config, seed.map(|seed| seed + 1))?;
+                    white_engine.set_max_simulations(settings.simulations);
+                    black_engine.set_max_simulations(settings.simulations);
+                    let played = selfplay::play_game(
+                        Player::Engine(&mut white_engine),
+                        Player::Engine(&mut black_engine),
+                        variant.clone(),
+                        moves,
+                        settings.think_time,
+                        Adjudication::default(),
+                    );
+                    let game = ArenaGame {
+                        white,
+                        black,
+                        opening: opening.to_string(),
+                        result: played.result.score().to_string(),
+                        termination: played.result.termination().to_string(),
+                        moves: played.record.sans(),
+                    };
+                    on_game(&tournament, &game);
+                    tournament.games.push(game);
+                }
+            }
+        }
+    }
+    Ok(tournament)
+}
@@ -0,0 +1,179 @@
+use crate::game::board::Board;
+use crate::game::movement::Move;
+use crate::game::piece::{Color, PieceType};
+use crate::game::variant::{squares, Variant};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+const WIN: i32 = 100_000;
+
+// the scripted opponents the learner works its way up through, weakest first
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Opponent {
+    // any legal move
+    Random,
+    // the biggest capture on offer, or any move
+    Greedy,
+    // the move that leaves it best off in material
+    OnePly,
+    // material searched three plies deep
+    AlphaBeta,
+}
+
+impl Opponent {
+    pub const ALL: [Opponent; 4] = [Opponent::Random, Opponent::Greedy, Opponent::OnePly, Opponent::AlphaBeta];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Opponent::Random => "random mover",
+            Opponent::Greedy => "greedy material grabber",
+            Opponent::OnePly => "1-ply search",
+            Opponent::AlphaBeta => "3-ply alpha-beta",
+        }
+    }
+}
+
+// an opponent and the dice it breaks ties with
+pub struct Scripted {
+    pub opponent: Opponent,
+    rng: StdRng,
+}
+
+impl Scripted {
+    pub fn new(opponent: Opponent, seed: Option<u64>) -> Self {
+        let rng = seed.map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+        Scripted { opponent, rng }
+    }
+
+    pub fn pick(&mut self, variant: &dyn Variant, board: &Board, color: Color) -> Option<Move> {
+        let mut moves = variant.moves(board, color);
+        // shuffled so equally good moves don't always go the same way
+        moves.shuffle(&mut self.rng);
+        match self.opponent {
+            Opponent::Random => moves.first().copied(),
+            Opponent::Greedy => moves
+                .iter()
+                .max_by_key(|mv| mv.captured.map_or(0, value))
+                .copied(),
+            Opponent::OnePly => best_move(variant, board, color, &moves, 1),
+            Opponent::AlphaBeta => best_move(variant, board, color, &moves, 3),
+        }
+    }
+}
+
+fn best_move(variant: &dyn Variant, board: &Board, color: Color, moves: &[Move], depth: u32) -> Option<Move> {
+    let mut best = None;
+    let mut alpha = -WIN - 1;
+    for &mv in moves {
+        let mut next = board.clone();
+        if !variant.make_move(&mut next, mv.from, mv.to) {
+            continue;
+        }
+        let score = -negamax(variant, &next, color.opposite(), depth - 1, -WIN - 1, -alpha);
+        if score > alpha {
+            alpha = score;
+            best = Some(mv);
+        }
+    }
+    best
+}
+
+// material from `to_move`'s side, `depth` plies on
+fn negamax(variant: &dyn Variant, board: &Board, to_move: Color, depth: u32, mut alpha: i32, beta: i32) -> i32 {
+    if let Some(result) = variant.terminal(board, to_move) {
+        return outcome(result.winner(), to_move);
+    }
+    if depth == 0 {
+        return material(board, to_move);
+    }
+    let moves = variant.moves(board, to_move);
+    if moves.is_empty() {
+        return variant.result(board, to_move).map_or(0, |result| outcome(result.winner(), to_move));
+    }
+    for mv in &moves {
+        let mut next = board.clone();
+        if !variant.make_move(&mut next, mv.from, mv.to) {
+            continue;
+        }
+        let score = -negamax(variant, &next, to_move.opposite(), depth - 1, -beta, -alpha);
+        if score >= beta {
+            return score;
+        }
+        alpha = alpha.max(score);
+    }
+    alpha
+}
+
+fn outcome(winner: Option<Color>, side: Color) -> i32 {
+    match winner {
+        Some(color) if color == side => WIN,
+        Some(_) => -WIN,
+        None => 0,
+    }
+}
+
+fn material(board: &Board, side: Color) -> i32 {
+    squares()
+        .filter_map(|square| board.get_piece(square))
+        .map(|piece| if piece.color == side { value(piece.piece_type) } else { -value(piece.piece_type) })
+        .sum()
+}
+
+fn value(piece: PieceType) -> i32 {
+    match piece {
+        PieceType::Pawn => 100,
+        PieceType::Knight => 320,
+        PieceType::Bishop => 330,
+        PieceType::Rook => 500,
+        PieceType::Queen => 900,
+        // taking the king ends the game, which the search sees for itself
+        PieceType::King => 0,
+    }
+}
+
+// moves the learner up a tier once it scores `threshold` over the last
+// `window` games against the current one
+pub struct Curriculum {
+    pub tier: usize,
+    pub threshold: f32,
+    pub window: usize,
+    // this tier's latest results, 1 a win, 0.5 a draw, 0 a loss
+    recent: Vec<f32>,
+}
+
+impl Curriculum {
+    pub fn new(threshold: f32, window: usize) -> Self {
+        Curriculum { tier: 0, threshold, window, recent: Vec::new() }
+    }
+
+    // None once the last tier is beaten
+    pub fn opponent(&self) -> Option<Opponent> {
+        Opponent::ALL.get(self.tier).copied()
+    }
+
+    pub fn score(&self) -> f32 {
+        if self.recent.is_empty() {
+            return 0.0;
+        }
+        self.recent.iter().sum::<f32>() / self.recent.len() as f32
+    }
+
+    pub fn games_at_tier(&self) -> usize {
+        self.recent.len()
+    }
+
+    // true when the result earns a promotion
+    pub fn record(&mut self, score: f32) -> bool {
+        self.recent.push(score);
+        if self.recent.len() > self.window {
+            self.recent.remove(0);
+        }
+        if self.recent.len() < self.window || self.score() < self.threshold {
+            return false;
+        }
+        self.tier += 1;
+        self.recent.clear();
+        true
+    }
+}
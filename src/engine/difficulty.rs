@@ -0,0 +1,237 @@
+use crate::engine::material::PieceValues;
+use crate::engine::personality::Personality;
+use crate::engine::rl::{DEFAULT_MULTI_PV, DEFAULT_ROLLOUT_CUTOFF, DEFAULT_ROLLOUT_TEMPERATURE, DEFAULT_HASH_MB};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+// how long the bot gets to think per move. the ratings are rough guesses
+// used as the opponent's strength when updating the player's Elo
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    #[default]
+    Hard,
+    Expert,
+    // a partner about the player's strength: it sometimes plays a lesser
+    // move on purpose, see engine::sparring
+    Sparring,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 5] = [
+        Difficulty::Easy,
+        Difficulty::Medium,
+        Difficulty::Hard,
+        Difficulty::Expert,
+        Difficulty::Sparring,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+            Difficulty::Expert => "expert",
+            Difficulty::Sparring => "sparring",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Difficulty> {
+        Difficulty::ALL.iter().copied().find(|d| d.name() == name)
+    }
+
+    // the default; config.toml can change it, see EngineConfig
+    pub fn think_time(self) -> Duration {
+        match self {
+            Difficulty::Easy => Duration::from_secs(1),
+            Difficulty::Medium => Duration::from_secs(3),
+            Difficulty::Hard => Duration::from_secs(5),
+            Difficulty::Expert => Duration::from_secs(10),
+            Difficulty::Sparring => Duration::from_secs(3),
+        }
+    }
+
+    pub fn rating(self) -> f32 {
+        match self {
+            Difficulty::Easy => 800.0,
+            Difficulty::Medium => 1100.0,
+            Difficulty::Hard => 1400.0,
+            Difficulty::Expert => 1700.0,
+            // never used, see rated
+            Difficulty::Sparring => 1100.0,
+        }
+    }
+
+    // sparring games are practice; the bot's strength changes with the
+    // player's, so there's nothing to rate them against
+    pub fn rated(self) -> bool {
+        self != Difficulty::Sparring
+    }
+}
+
+// [bot] in config.toml
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BotConfig {
+    pub level: Difficulty,
+    pub personality: Personality,
+    // what a draw is worth to the bot, in centipawns below zero
+    pub contempt: i32,
+    // a weights file to play with, by name in the weights directory or by
+    // path; the built-in values when unset
+    pub weights: Option<String>,
+    // a rating to hold the bot to instead of the level, see engine::strength
+    pub target_elo: Option<u32>,
+    // a named set of piece values over the built-in or weights file's ones
+    pub piece_values: Option<PieceValues>,
+    // centipawns for single pieces, by name, over the set
+    pub values: BTreeMap<String, i32>,
+    // evaluation term weights, by name, over the personality's and a
+    // weights file's
+    pub terms: BTreeMap<String, f64>,
+    // the bot tunes its piece values from its games as well as its tables
+    pub learn_values: bool,
+}
+
+impl Default for BotConfig {
+    fn default() -> Self {
+        BotConfig {
+            level: Difficulty::default(),
+            personality: Personality::default(),
+            contempt: 20,
+            weights: None,
+            target_elo: None,
+            piece_values: None,
+            values: BTreeMap::new(),
+            terms: BTreeMap::new(),
+            learn_values: false,
+        }
+    }
+}
+
+// more lines than this don't fit beside the board
+pub const MAX_MULTI_PV: usize = 8;
+
+// [engine] in config.toml
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EngineConfig {
+    // searches at once on the bot's move; their trees are merged at the end
+    pub threads: usize,
+    // seconds per move at each level
+    pub think_time: ThinkTimes,
+    // seconds for hints and why-not
+    pub analysis_time: f32,
+    // root moves shown in the analytics panel, each with its own line
+    pub multi_pv: usize,
+    // how greedily playouts follow the move ranking; 0 always plays the
+    // best-ranked move, higher gets closer to random
+    pub rollout_temperature: f32,
+    // centipawns of material one side has to win or lose during a playout,
+    // and keep through the reply, for it to stop there as decided. 0 plays
+    // every playout to its full length
+    pub rollout_cutoff: i32,
+    // megabytes for the search tree and eval table, like UCI's Hash and
+    // shared by the threads; past it the tree's least visited lines are
+    // dropped. 0 for no limit. configs from before the table call it
+    // tree_memory_mb
+    #[serde(alias = "tree_memory_mb")]
+    pub hash_mb: usize,
+    // milliseconds of a move's time kept back from the search for getting
+    // the move played: the bot's threads joining, a UCI engine's pipe
+    pub move_overhead_ms: u64,
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        EngineConfig {
+            threads: 1,
+            think_time: ThinkTimes::default(),
+            analysis_time: 3.0,
+            multi_pv: DEFAULT_MULTI_PV,
+            rollout_temperature: DEFAULT_ROLLOUT_TEMPERATURE,
+            rollout_cutoff: DEFAULT_ROLLOUT_CUTOFF,
+            hash_mb: DEFAULT_HASH_MB,
+            move_overhead_ms: 50,
+        }
+    }
+}
+
+impl EngineConfig {
+    pub fn move_overhead(&self) -> Duration {
+        Duration::from_millis(self.move_overhead_ms)
+    }
+
+    pub fn think_time(&self, level: Difficulty) -> Duration {
+        let secs = match level {
+            Difficulty::Easy => self.think_time.easy,
+            Difficulty::Medium => self.think_time.medium,
+            Difficulty::Hard => self.think_time.hard,
+            Difficulty::Expert => self.think_time.expert,
+            Difficulty::Sparring => self.think_time.sparring,
+        };
+        seconds(secs)
+    }
+
+    pub fn set_think_time(&mut self, level: Difficulty, secs: f32) {
+        let slot = match level {
+            Difficulty::Easy => &mut self.think_time.easy,
+            Difficulty::Medium => &mut self.think_time.medium,
+            Difficulty::Hard => &mut self.think_time.hard,
+            Difficulty::Expert => &mut self.think_time.expert,
+            Difficulty::Sparring => &mut self.think_time.sparring,
+        };
+        *slot = secs;
+    }
+
+    pub fn analysis_time(&self) -> Duration {
+        seconds(self.analysis_time)
+    }
+
+    pub fn threads(&self) -> usize {
+        self.threads.max(1)
+    }
+
+    pub fn multi_pv(&self) -> usize {
+        self.multi_pv.clamp(1, MAX_MULTI_PV)
+    }
+
+    pub fn hash_budget(&self) -> Option<usize> {
+        (self.hash_mb > 0).then_some(self.hash_mb << 20)
+    }
+
+    pub fn rollout_cutoff(&self) -> Option<i32> {
+        (self.rollout_cutoff > 0).then_some(self.rollout_cutoff)
+    }
+}
+
+// [engine.think_time]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThinkTimes {
+    pub easy: f32,
+    pub medium: f32,
+    pub hard: f32,
+    pub expert: f32,
+    pub sparring: f32,
+}
+
+impl Default for ThinkTimes {
+    fn default() -> Self {
+        ThinkTimes {
+            easy: Difficulty::Easy.think_time().as_secs_f32(),
+            medium: Difficulty::Medium.think_time().as_secs_f32(),
+            hard: Difficulty::Hard.think_time().as_secs_f32(),
+            expert: Difficulty::Expert.think_time().as_secs_f32(),
+            sparring: Difficulty::Sparring.think_time().as_secs_f32(),
+        }
+    }
+}
+
+// a hand-edited negative or NaN time would panic in Duration
+fn seconds(secs: f32) -> Duration {
+    Duration::try_from_secs_f32(secs.max(0.0)).unwrap_or(Duration::ZERO)
+}
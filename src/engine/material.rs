@@ -0,0 +1,47 @@
+use crate::game::piece::PieceType;
+use serde::{Deserialize, Serialize};
+
+// named sets of piece values the bot can evaluate with, in centipawns.
+// the king is never traded, so it isn't in any of them
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PieceValues {
+    // the engine's own: bishops a little over knights
+    #[default]
+    Simplified,
+    // the textbook 1/3/3/5/9
+    Classical,
+    // Larry Kaufman's 1/3.25/3.25/5/9.75
+    Kaufman,
+}
+
+impl PieceValues {
+    pub const ALL: [PieceValues; 3] = [PieceValues::Simplified, PieceValues::Classical, PieceValues::Kaufman];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PieceValues::Simplified => "simplified",
+            PieceValues::Classical => "classical",
+            PieceValues::Kaufman => "kaufman",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<PieceValues> {
+        PieceValues::ALL.iter().copied().find(|values| values.name() == name)
+    }
+
+    pub fn values(self) -> [(PieceType, i32); 5] {
+        let [knight, bishop, queen] = match self {
+            PieceValues::Simplified => [320, 330, 900],
+            PieceValues::Classical => [300, 300, 900],
+            PieceValues::Kaufman => [325, 325, 975],
+        };
+        [
+            (PieceType::Pawn, 100),
+            (PieceType::Knight, knight),
+            (PieceType::Bishop, bishop),
+            (PieceType::Rook, 500),
+            (PieceType::Queen, queen),
+        ]
+    }
+}
@@ -1 +1,17 @@
+pub mod analysis;
+pub mod arena;
+pub mod curriculum;
+pub mod difficulty;
+pub mod material;
+pub mod opponent;
+pub mod ordering;
+pub mod personality;
 pub mod rl;
+pub mod selfplay;
+pub mod sparring;
+pub mod strength;
+pub mod table;
+pub mod trace;
+pub mod uci;
+pub mod weights;
+pub mod worker;
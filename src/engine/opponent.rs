@@ -0,0 +1,111 @@
+use crate::engine::analysis::Judgement;
+use crate::engine::rl::{mate_in, Branch, MovePair};
+use crate::game::board::Board;
+use crate::game::piece::Color;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+// positions are only remembered this far into a game; later on the same
+// position rarely comes round twice
+pub const OPENING_PLIES: usize = 24;
+// how often a position has to have come up before the bot steers toward it
+const MIN_SEEN: u32 = 2;
+// how many centipawns the bot gives up on its best move to get there
+const STEER_MARGIN: f32 = 30.0;
+
+// what the bot has learned about the player across games, kept in the
+// profile: how often they go wrong, what they open with and which
+// positions they tend to mishandle
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OpponentModel {
+    pub moves_judged: u32,
+    pub mistakes: u32,
+    pub blunders: u32,
+    // games per opening
+    pub openings: BTreeMap<String, u32>,
+    // early positions the player had to move in, by position_key
+    pub positions: HashMap<String, PositionRecord>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct PositionRecord {
+    pub seen: u32,
+    // mistakes and blunders made from here
+    pub errors: u32,
+}
+
+impl OpponentModel {
+    // one of the player's moves; `key` is the position they moved from,
+    // if it's early enough to remember
+    pub fn observe(&mut self, key: Option<String>, judgement: Option<Judgement>) {
+        self.moves_judged += 1;
+        let erred = matches!(judgement, Some(Judgement::Mistake | Judgement::Blunder));
+        match judgement {
+            Some(Judgement::Blunder) => self.blunders += 1,
+            Some(Judgement::Mistake) => self.mistakes += 1,
+            _ => {}
+        }
+        if let Some(key) = key {
+            let record = self.positions.entry(key).or_default();
+            record.seen += 1;
+            record.errors += erred as u32;
+        }
+    }
+
+    pub fn record_opening(&mut self, name: &str) {
+        *self.openings.entry(name.to_string()).or_default() += 1;
+    }
+
+    // blunders per judged move
+    pub fn blunder_rate(&self) -> Option<f32> {
+        (self.moves_judged > 0).then(|| self.blunders as f32 / self.moves_judged as f32)
+    }
+
+    // most played first
+    pub fn favourite_openings(&self, count: usize) -> Vec<(&str, u32)> {
+        let mut openings: Vec<(&str, u32)> = self
+            .openings
+            .iter()
+            .filter(|(name, _)| name.as_str() != "Unknown")
+            .map(|(name, games)| (name.as_str(), *games))
+            .collect();
+        openings.sort_by_key(|(_, games)| std::cmp::Reverse(*games));
+        openings.truncate(count);
+        openings
+    }
+
+    // how often the player has gone wrong from this position, once it has
+    // come up often enough to say
+    pub fn error_rate(&self, key: &str) -> Option<f32> {
+        let record = self.positions.get(key)?;
+        (record.seen >= MIN_SEEN && record.errors > 0).then(|| record.errors as f32 / record.seen as f32)
+    }
+
+    // positions the bot will steer toward
+    pub fn weak_spots(&self) -> usize {
+        self.positions.keys().filter(|key| self.error_rate(key).is_some()).count()
+    }
+
+    // among root moves nearly as good as the best, the one leading where the
+    // player errs most often, with its value. `key_after` gives the position
+    // a move leads to. None leaves the choice to the search
+    pub fn steer(&self, tree: &[Branch], key_after: impl Fn(MovePair) -> String) -> Option<(MovePair, f32)> {
+        let best = tree.first()?;
+        if mate_in(best.value).is_some() {
+            return None;
+        }
+        let (branch, _) = tree
+            .iter()
+            .filter(|b| mate_in(b.value).is_none() && b.value >= best.value - STEER_MARGIN && b.visits * 4 >= best.visits)
+            .filter_map(|b| Some((b, self.error_rate(&key_after(b.mv))?)))
+            .max_by(|a, b| a.1.total_cmp(&b.1))?;
+        Some((branch.mv, branch.value))
+    }
+}
+
+// the placement and side to move, which is all that tells two opening
+// positions apart here
+pub fn position_key(board: &Board, to_move: Color) -> String {
+    board.fen(to_move, 1).split(' ').take(2).collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,72 @@
+use crate::engine::rl::MovePair;
+use crate::game::piece::Color;
+
+// killer moves kept for each ply, newest first
+const KILLER_SLOTS: usize = 2;
+// what a killer adds to a quiet move's priority, by slot. with the history
+// bonus on top it still comes to less than taking a pawn, so captures keep
+// coming first
+const KILLER_BONUS: [f32; KILLER_SLOTS] = [50.0, 40.0];
+// the most the history table adds, for the quiet move with the best record
+const HISTORY_BONUS: f32 = 30.0;
+
+fn index(color: Color, ((from_rank, from_file), (to_rank, to_file)): MovePair) -> usize {
+    let side = if color == Color::White { 0 } else { 1 };
+    side << 12 | (from_rank * 8 + from_file) << 6 | (to_rank * 8 + to_file)
+}
+
+// killer moves and a history table for one search thread. a quiet move
+// that turns out best somewhere gets tried early wherever it comes up
+// again: first at the same ply, as a killer, then anywhere for the side
+// that played it, by how often it's been best
+#[derive(Clone)]
+pub struct MoveOrdering {
+    killers: Vec<[Option<MovePair>; KILLER_SLOTS]>,
+    // by side and from and to square
+    history: Vec<u32>,
+    best_history: u32,
+    // moves that became the most visited at their node, and how many of
+    // them were the first one expanded there
+    pub cutoffs: u64,
+    pub first_move_cutoffs: u64,
+}
+
+impl Default for MoveOrdering {
+    fn default() -> Self {
+        MoveOrdering {
+            killers: Vec::new(),
+            history: vec![0; 2 << 12],
+            best_history: 0,
+            cutoffs: 0,
+            first_move_cutoffs: 0,
+        }
+    }
+}
+
+impl MoveOrdering {
+    // a quiet move by `color` that was best at `ply`
+    pub fn record(&mut self, ply: usize, color: Color, mv: MovePair) {
+        if self.killers.len() <= ply {
+            self.killers.resize(ply + 1, [None; KILLER_SLOTS]);
+        }
+        let killers = &mut self.killers[ply];
+        if killers[0] != Some(mv) {
+            killers.rotate_right(1);
+            killers[0] = Some(mv);
+        }
+        let count = &mut self.history[index(color, mv)];
+        *count += 1;
+        self.best_history = self.best_history.max(*count);
+    }
+
+    // what a quiet move's priority goes up by
+    pub fn bonus(&self, ply: usize, color: Color, mv: MovePair) -> f32 {
+        let killer = self
+            .killers
+            .get(ply)
+            .and_then(|killers| killers.iter().position(|&killer| killer == Some(mv)))
+            .map_or(0.0, |slot| KILLER_BONUS[slot]);
+        let history = self.history[index(color, mv)] as f32 / self.best_history.max(1) as f32 * HISTORY_BONUS;
+        killer + history
+    }
+}
@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+
+// playing styles. each one weighs the evaluation terms and the move
+// ordering bonuses differently; balanced is how the bot always played
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Personality {
+    #[default]
+    Balanced,
+    Aggressive,
+    Positional,
+    Gambit,
+    Defensive,
+}
+
+// multipliers for the terms in rl::EvalTerms, then the bonuses used to
+// decide which moves get searched first
+#[derive(Clone, Copy, Debug)]
+pub struct Weights {
+    pub material: f32,
+    pub king_safety: f32,
+    pub king_attack: f32,
+    pub mobility: f32,
+    pub threats: f32,
+    pub center: f32,
+    pub pawn_structure: f32,
+    pub position: f32,
+    pub activity: f32,
+    pub capture: f32,
+    pub check: f32,
+    pub escape: f32,
+    pub center_move: f32,
+    pub development: f32,
+}
+
+const BALANCED: Weights = Weights {
+    material: 1.0,
+    king_safety: 3.0,
+    king_attack: 2.5,
+    mobility: 1.0,
+    threats: 2.0,
+    center: 1.5,
+    pawn_structure: 10.0,
+    position: 10.0,
+    activity: 1.0,
+    capture: 1.0,
+    check: 0.0,
+    escape: 50.0,
+    center_move: 10.0,
+    development: 0.0,
+};
+
+impl Weights {
+    // every weight by name, as a weights file lists them
+    pub fn named(&self) -> [(&'static str, f32); 14] {
+        [
+            ("material", self.material),
+            ("king_safety", self.king_safety),
+            ("king_attack", self.king_attack),
+            ("mobility", self.mobility),
+            ("threats", self.threats),
+            ("center", self.center),
+            ("pawn_structure", self.pawn_structure),
+            ("position", self.position),
+            ("activity", self.activity),
+            ("capture", self.capture),
+            ("check", self.check),
+            ("escape", self.escape),
+            ("center_move", self.center_move),
+            ("development", self.development),
+        ]
+    }
+
+    // sets the weight called `name`; false when there's no such weight
+    pub fn set(&mut self, name: &str, value: f32) -> bool {
+        let weight = match name {
+            "material" => &mut self.material,
+            "king_safety" => &mut self.king_safety,
+            "king_attack" => &mut self.king_attack,
+            "mobility" => &mut self.mobility,
+            "threats" => &mut self.threats,
+            "center" => &mut self.center,
+            "pawn_structure" => &mut self.pawn_structure,
+            "position" => &mut self.position,
+            "activity" => &mut self.activity,
+            "capture" => &mut self.capture,
+            "check" => &mut self.check,
+            "escape" => &mut self.escape,
+            "center_move" => &mut self.center_move,
+            "development" => &mut self.development,
+            _ => return false,
+        };
+        *weight = value;
+        true
+    }
+}
+
+impl Personality {
+    pub const ALL: [Personality; 5] = [
+        Personality::Balanced,
+        Personality::Aggressive,
+        Personality::Positional,
+        Personality::Gambit,
+        Personality::Defensive,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Personality::Balanced => "balanced",
+            Personality::Aggressive => "aggressive",
+            Personality::Positional => "positional",
+            Personality::Gambit => "gambit",
+            Personality::Defensive => "defensive",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Personality> {
+        Personality::ALL.iter().copied().find(|p| p.name() == name)
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Personality::Balanced => "Weighs everything evenly",
+            Personality::Aggressive => "Goes after your king, looks at checks and captures first",
+            Personality::Positional => "Plays for the center, mobility and a sound pawn structure",
+            Personality::Gambit => "Gives up material for development and the initiative",
+            Personality::Defensive => "Keeps its king covered and its pieces out of danger",
+        }
+    }
+
+    pub fn weights(self) -> Weights {
+        match self {
+            Personality::Balanced => BALANCED,
+            Personality::Aggressive => Weights {
+                king_safety: 2.0,
+                king_attack: 6.0,
+                threats: 1.0,
+                capture: 1.2,
+                check: 60.0,
+                ..BALANCED
+            },
+            Personality::Positional => Weights {
+                mobility: 2.0,
+                center: 4.0,
+                pawn_structure: 25.0,
+                position: 15.0,
+                activity: 2.0,
+                center_move: 25.0,
+                ..BALANCED
+            },
+            Personality::Gambit => Weights {
+                material: 0.85,
+                king_attack: 4.0,
+                mobility: 2.5,
+                pawn_structure: 0.0,
+                check: 30.0,
+                development: 30.0,
+                ..BALANCED
+            },
+            Personality::Defensive => Weights {
+                king_safety: 6.0,
+                king_attack: 1.0,
+                threats: 5.0,
+                pawn_structure: 15.0,
+                escape: 120.0,
+                ..BALANCED
+            },
+        }
+    }
+}
@@ -1,47 +1,413 @@
+use std::cell::RefCell;
 use std::collections::HashMap;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use crate::game::{
     board::Board,
+    movement,
     piece::{Color, PieceType},
+    result::GameResult,
+    variant::{Standard, Variant},
 };
-use crate::utils::coordinate_to_string;
+use crate::engine::material::PieceValues;
+use crate::engine::personality::{Personality, Weights};
+use crate::engine::ordering::MoveOrdering;
+use crate::engine::table::EvalTable;
+use crate::game::zobrist;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use crate::session;
+use crate::game::movement::coordinates;
 
 const MAX_PLIES: i32 = 10;
+// plies a playout may run past MAX_PLIES because the side to move is in check
+const MAX_CHECK_EXTENSIONS: u32 = 4;
 const MAX_OPPONENT_MOVES: usize = 150;
 const UCT_CONSTANT: f32 = 1.414;
+// progressive widening: a node with n visits may have WIDENING_BASE * n^WIDENING_EXPONENT
+// children, so a long move list doesn't spread the visits thin before the
+// best-ranked moves have been looked at properly
+const WIDENING_BASE: f32 = 2.0;
+const WIDENING_EXPONENT: f32 = 0.5;
+// how many visits a new child's static eval counts for in its average
+const PRIOR_VISITS: f32 = 1.0;
+const PROGRESS_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+// a decided game, worth more than any amount of material. a mate found in
+// the search is worth WIN_SCORE less the plies it takes, so a faster mate
+// scores higher and a slower one being mated scores less badly
+const WIN_SCORE: f32 = 30000.0;
+// scores past this are mates
+const MATE_BOUND: f32 = WIN_SCORE - 1000.0;
+// the tree keeps values on a -1..1 scale, tanh(centipawns / VALUE_SCALE):
+// the same curve as the win chance in analysis.rs, so a won game is 1 and
+// no amount of material quite gets there
+const VALUE_SCALE: f32 = 543.0;
+// averages stop here when turned back into centipawns: only a result the
+// search has proven reads as a mate
+const MAX_VALUE: f32 = 0.9999;
+// non-pawn material at the start, counting knights and bishops 1, rooks 2, queens 4
+const OPENING_PHASE: f32 = 24.0;
+// learned piece-square values stay within this many pawns
+pub const MAX_POSITION_VALUE: f32 = 5.0;
+// learned piece values stay within this many centipawns; the pawn is the
+// unit the rest are measured in, so it's never learned
+const PIECE_VALUE_RANGE: std::ops::RangeInclusive<f32> = 100.0..=1500.0;
+// piece values learn much more slowly than the tables: they count in every
+// position, not just the ones with a piece on a square
+const PIECE_LEARNING_SHARE: f32 = 0.1;
+// piece activity bonuses, before the personality's weight
+const ROOK_OPEN_FILE: f32 = 20.0;
+const ROOK_HALF_OPEN_FILE: f32 = 10.0;
+const KNIGHT_OUTPOST: f32 = 25.0;
+const BISHOP_OUTPOST: f32 = 10.0;
+// how much of the attack weight on a king's zone counts, by number of
+// attackers: a lone attacker rarely gets anywhere
+const ATTACKER_SCALE: [f32; 7] = [0.0, 0.25, 0.5, 0.75, 0.9, 1.0, 1.0];
+// how much of the finished tree a search hands back: every root move, and
+// under each the most visited replies
+const TRACE_REPLIES: usize = 3;
+// how much memory one search's tree and eval table get unless
+// set_hash_budget says otherwise
+pub const DEFAULT_HASH_MB: usize = 256;
+// the eval table's share of the hash budget, as a fraction of it
+const TABLE_SHARE: usize = 8;
+// the eval table's size when the tree may grow without limit
+const UNLIMITED_TABLE_BYTES: usize = 32 << 20;
+// root moves reported in the stats unless set_multi_pv says otherwise
+pub const DEFAULT_MULTI_PV: usize = 3;
+// for the balanced bot, taking a pawn makes a move e times as likely to be played out
+pub const DEFAULT_ROLLOUT_TEMPERATURE: f32 = 100.0;
+// a playout that has swung this many centipawns of material from where it
+// started, and stayed there after the reply, is over: a rook up is decided
+pub const DEFAULT_ROLLOUT_CUTOFF: i32 = 500;
+
+pub type MovePair = ((usize, usize), (usize, usize));
+
+// piece-square values from white's side, one table for the middle game and
+// one for the endgame
+#[derive(Clone)]
+struct PhaseTables {
+    middle: [[f32; 8]; 8],
+    end: [[f32; 8]; 8],
+}
+
+impl PhaseTables {
+    // `phase` runs from 1.0 with all the pieces on to 0.0 with only kings and pawns
+    fn value(&self, (rank, file): (usize, usize), phase: f32) -> f32 {
+        self.middle[rank][file] * phase + self.end[rank][file] * (1.0 - phase)
+    }
+}
+
+// a move in two bytes, from and to square as 0..64 each; the tree holds
+// a lot of them
+type PackedMove = u16;
+
+fn pack(((from_rank, from_file), (to_rank, to_file)): MovePair) -> PackedMove {
+    ((from_rank * 8 + from_file) << 6 | (to_rank * 8 + to_file)) as PackedMove
+}
 
+fn unpack(mv: PackedMove) -> MovePair {
+    let (from, to) = ((mv >> 6) as usize, (mv & 63) as usize);
+    ((from / 8, from % 8), (to / 8, to % 8))
+}
+
+type NodeId = u32;
+const ROOT: NodeId = 0;
+const NO_PARENT: NodeId = NodeId::MAX;
+
+// values are negamax: a node's total_value is summed from the side of the
+// player who moved into it, so a parent always picks the child that's best
+// for itself. nodes don't keep a board; the search plays the moves down
+// from the root to get there
 struct MCTSNode {
-    board: Board,
+    // the move that led here, and its priority, so the parent can have it
+    // back if this node is recycled
+    mv: PackedMove,
+    priority: f32,
+    parent: NodeId,
     visits: u32,
     total_value: f32,
-    children: Vec<(((usize, usize), (usize, usize)), MCTSNode)>,
-    unexplored_moves: Vec<((usize, usize), (usize, usize))>,
+    children: Vec<NodeId>,
+    // best priority first, from evaluate_move_priority
+    unexplored_moves: Vec<(PackedMove, f32)>,
     current_player: Color,
+    // the static eval when the node was added, from the parent's side
+    prior: f32,
+    // set once the result here is known, because the game is over or every
+    // line has been searched to the end: centipawns for the side to move
+    outcome: Option<f32>,
+}
+
+fn to_value(centipawns: f32) -> f32 {
+    (centipawns / VALUE_SCALE).tanh()
+}
+
+fn to_centipawns(value: f32) -> f32 {
+    VALUE_SCALE * value.clamp(-MAX_VALUE, MAX_VALUE).atanh()
+}
+
+// a known result one ply further up: the other side's, and a mate one ply
+// further away
+fn from_parent(score: f32) -> f32 {
+    if score.abs() > MATE_BOUND {
+        -score + score.signum()
+    } else {
+        -score
+    }
+}
+
+// moves to mate for a search score: positive when the side it's for mates,
+// negative when it gets mated
+pub fn mate_in(score: f32) -> Option<i32> {
+    if score.abs() <= MATE_BOUND {
+        return None;
+    }
+    let moves = ((WIN_SCORE - score.abs()).round() as i32 + 1) / 2;
+    Some(if score > 0.0 { moves } else { -moves })
+}
+
+// searches score from the side that searched; everything the player sees
+// is from white's side, plus for white, the way PGN evals are written.
+// mates turn round with the sign, so mate_in and format_score still work
+pub fn white_score(score: f32, side: Color) -> f32 {
+    match side {
+        Color::White => score,
+        Color::Black => -score,
+    }
+}
+
+// "+0.35" in pawns, or "M3" / "-M3" for a mate
+pub fn format_score(score: f32) -> String {
+    match mate_in(score) {
+        Some(moves) if moves < 0 => format!("-M{}", -moves),
+        Some(moves) => format!("M{}", moves),
+        None => format!("{:+.2}", score / 100.0),
+    }
+}
+
+// which move a parent goes for: a forced win first, the fastest of them,
+// then the most visited, and a forced loss only when there's nothing else,
+// the slowest of them
+fn preference(score: f32, visits: u32) -> (u8, f32) {
+    if score > MATE_BOUND {
+        (2, score)
+    } else if score < -MATE_BOUND {
+        (0, score)
+    } else {
+        (1, visits as f32)
+    }
 }
 
 impl MCTSNode {
-    fn new(board: Board, current_player: Color, engine: &RLEngine) -> Self {
-        let moves = engine.generate_ranked_moves(&board, current_player);
+    // `ply` is how far below the root it is, for the killer moves
+    fn new(board: &Board, current_player: Color, ply: usize, engine: &RLEngine) -> Self {
+        let (moves, outcome) = match engine.variant.terminal(board, current_player) {
+            Some(result) => (Vec::new(), Some(result)),
+            None => {
+                let mut moves = engine.scored_moves(board, current_player);
+                engine.order_moves(&mut moves, board, current_player, ply);
+                // no moves is mate or stalemate, or whatever the variant says
+                let outcome = if moves.is_empty() { engine.variant.result(board, current_player) } else { None };
+                (moves.into_iter().map(|(mv, priority)| (pack(mv), priority)).collect(), outcome)
+            }
+        };
+        let outcome = outcome.map(|result| engine.result_score(result, current_player));
         MCTSNode {
-            board,
+            mv: 0,
+            priority: 0.0,
+            parent: NO_PARENT,
             visits: 0,
             total_value: 0.0,
             children: Vec::new(),
             unexplored_moves: moves,
             current_player,
+            prior: 0.0,
+            outcome,
+        }
+    }
+
+    // whether another child is allowed yet
+    fn can_widen(&self) -> bool {
+        let limit = (WIDENING_BASE * (self.visits as f32).powf(WIDENING_EXPONENT)).ceil() as usize;
+        !self.unexplored_moves.is_empty() && self.children.len() < limit
+    }
+
+    // the average so far, for the player who moved here
+    fn value(&self) -> f32 {
+        self.total_value / self.visits.max(1) as f32
+    }
+
+    // centipawns for the player who moved here: the known result if there
+    // is one, the average otherwise
+    fn score(&self) -> f32 {
+        match self.outcome {
+            Some(outcome) => from_parent(outcome),
+            None => to_centipawns(self.value()),
         }
     }
 
+    fn preference(&self) -> (u8, f32) {
+        preference(self.score(), self.visits)
+    }
+
+    // what the node holds on to beyond its slot
+    fn heap_bytes(&self) -> usize {
+        self.children.capacity() * std::mem::size_of::<NodeId>()
+            + self.unexplored_moves.capacity() * std::mem::size_of::<(PackedMove, f32)>()
+    }
+
     fn uct_value(&self, parent_visits: u32) -> f32 {
         if self.visits == 0 {
             return f32::INFINITY;
         }
-        let exploitation = self.total_value / self.visits as f32;
+        let exploitation = (self.total_value + self.prior * PRIOR_VISITS) / (self.visits as f32 + PRIOR_VISITS);
         let exploration = UCT_CONSTANT * ((parent_visits as f32).ln() / self.visits as f32).sqrt();
         exploitation + exploration
     }
 }
 
+// every node of one search, addressed by index. past `budget` bytes the
+// least visited subtrees are recycled: their slots go back on the free list
+// and their moves back to the parent to be tried again
+struct Tree {
+    nodes: Vec<Option<MCTSNode>>,
+    free: Vec<NodeId>,
+    // what the live nodes take up, slot and all. free slots are left out:
+    // they're reused before the slots grow again
+    bytes: usize,
+    budget: Option<usize>,
+    // nodes recycled so far
+    recycled: u32,
+}
+
+impl Tree {
+    fn new(root: MCTSNode, budget: Option<usize>) -> Self {
+        let mut tree = Tree { nodes: Vec::new(), free: Vec::new(), bytes: 0, budget, recycled: 0 };
+        tree.add(root);
+        tree
+    }
+
+    fn node(&self, id: NodeId) -> &MCTSNode {
+        self.nodes[id as usize].as_ref().expect("a live node")
+    }
+
+    fn node_mut(&mut self, id: NodeId) -> &mut MCTSNode {
+        self.nodes[id as usize].as_mut().expect("a live node")
+    }
+
+    fn children(&self, id: NodeId) -> impl Iterator<Item = (MovePair, &MCTSNode)> {
+        self.node(id).children.iter().map(|&child| {
+            let node = self.node(child);
+            (unpack(node.mv), node)
+        })
+    }
+
+    fn add(&mut self, node: MCTSNode) -> NodeId {
+        self.bytes += std::mem::size_of::<Option<MCTSNode>>() + node.heap_bytes();
+        match self.free.pop() {
+            Some(id) => {
+                self.nodes[id as usize] = Some(node);
+                id
+            }
+            None => {
+                self.nodes.push(Some(node));
+                (self.nodes.len() - 1) as NodeId
+            }
+        }
+    }
+
+    fn add_child(&mut self, parent: NodeId, mut child: MCTSNode, mv: MovePair, priority: f32) -> NodeId {
+        child.mv = pack(mv);
+        child.priority = priority;
+        child.parent = parent;
+        let id = self.add(child);
+        let before = self.node(parent).heap_bytes();
+        self.node_mut(parent).children.push(id);
+        self.bytes = self.bytes + self.node(parent).heap_bytes() - before;
+        id
+    }
+
+    // the preferred child
+    fn best_child(&self, id: NodeId) -> Option<(MovePair, &MCTSNode)> {
+        self.children(id).max_by(|(_, a), (_, b)| a.preference().partial_cmp(&b.preference()).unwrap())
+    }
+
+    // the result here is known once one move wins by force, or once every
+    // move has a known result and the best of them can be taken
+    fn solve(&mut self, id: NodeId) {
+        let node = self.node(id);
+        if node.outcome.is_some() {
+            return;
+        }
+        let mut complete = node.unexplored_moves.is_empty();
+        let mut best: Option<f32> = None;
+        for (_, child) in self.children(id) {
+            match child.outcome {
+                Some(_) => best = Some(best.map_or(child.score(), |best: f32| best.max(child.score()))),
+                None => complete = false,
+            }
+        }
+        self.node_mut(id).outcome = best.filter(|&best| complete || best > MATE_BOUND);
+    }
+
+    fn over_budget(&self) -> bool {
+        self.budget.is_some_and(|budget| self.bytes > budget)
+    }
+
+    // frees the least visited subtrees until a quarter of the budget is
+    // spare. the root's own moves and solved nodes stay, so the result and
+    // any proven line survive
+    fn recycle(&mut self) {
+        let Some(budget) = self.budget else {
+            return;
+        };
+        let target = budget / 4 * 3;
+        let mut candidates: Vec<(u32, NodeId)> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(id, node)| Some((id as NodeId, node.as_ref()?)))
+            .filter(|(_, node)| node.parent != NO_PARENT && node.parent != ROOT && node.outcome.is_none())
+            .map(|(id, node)| (node.visits, id))
+            .collect();
+        candidates.sort_unstable();
+        for (_, id) in candidates {
+            if self.bytes <= target {
+                break;
+            }
+            // gone already along with an ancestor
+            if self.nodes[id as usize].is_none() {
+                continue;
+            }
+            self.detach(id);
+        }
+    }
+
+    // frees `id` and everything under it; its parent gets the move back
+    fn detach(&mut self, id: NodeId) {
+        let (parent, mv, priority) = {
+            let node = self.node(id);
+            (node.parent, node.mv, node.priority)
+        };
+        let before = self.node(parent).heap_bytes();
+        let parent_node = self.node_mut(parent);
+        parent_node.children.retain(|&child| child != id);
+        let at = parent_node.unexplored_moves.iter().take_while(|(_, p)| *p >= priority).count();
+        parent_node.unexplored_moves.insert(at, (mv, priority));
+        self.bytes = self.bytes + self.node(parent).heap_bytes() - before;
+
+        let mut doomed = vec![id];
+        while let Some(id) = doomed.pop() {
+            if let Some(node) = self.nodes[id as usize].take() {
+                self.bytes -= std::mem::size_of::<Option<MCTSNode>>() + node.heap_bytes();
+                doomed.extend(&node.children);
+                self.free.push(id);
+                self.recycled += 1;
+            }
+        }
+    }
+}
+
 #[derive(Default, Clone)]
 pub struct SimulationStats {
     pub total_simulations: u32,
@@ -49,12 +415,85 @@ pub struct SimulationStats {
     pub best_line: Vec<String>,
     pub best_move_confidence: f32,
     pub current_eval: f32,
+    // who the search was for; current_eval and the top moves' scores are
+    // from its side
+    pub side: Option<Color>,
     pub depth_reached: i32,
-    pub top_moves: Vec<(String, f32, u32)>, // (move, score, visits)
-    pub thinking_line: String,
+    // the most visited root moves, as many as the engine's multi_pv
+    pub top_moves: Vec<PvLine>,
+    // how long the search has run
+    pub elapsed: std::time::Duration,
+    // random playouts from newly expanded nodes and the plies they played in all
+    pub rollouts: u32,
+    pub rollout_plies: u64,
+    // nodes kept in the search tree and roughly what they take up
+    pub tree_size: u32,
+    pub memory_bytes: usize,
+    // nodes freed to stay within the tree's memory budget
+    pub recycled: u32,
+    // lookups in the eval table, how many found their position, and how
+    // full it got
+    pub table_probes: u64,
+    pub table_hits: u64,
+    pub table_fill: f32,
+    // moves that became the most visited at their node, and how many of
+    // them were the first one expanded there: how often the move ordering
+    // had the best move first
+    pub cutoffs: u64,
+    pub first_move_cutoffs: u64,
+}
+
+// one root move with its own eval and the line the search expects after it
+#[derive(Clone, Debug, Default)]
+pub struct PvLine {
+    pub mv: String,
+    pub score: f32,
+    pub visits: u32,
+    pub line: Vec<String>,
+}
+
+impl PvLine {
+    pub fn from_branch(branch: &Branch) -> Self {
+        PvLine {
+            mv: coordinates(branch.mv),
+            score: branch.value,
+            visits: branch.visits,
+            line: branch.pv.iter().map(|mv| coordinates(*mv)).collect(),
+        }
+    }
 }
 
 impl SimulationStats {
+    pub fn simulations_per_sec(&self) -> f32 {
+        self.total_simulations as f32 / self.elapsed.as_secs_f32().max(0.001)
+    }
+
+    pub fn table_hit_rate(&self) -> f32 {
+        self.table_hits as f32 / self.table_probes.max(1) as f32
+    }
+
+    pub fn first_move_cutoff_rate(&self) -> f32 {
+        self.first_move_cutoffs as f32 / self.cutoffs.max(1) as f32
+    }
+
+    // a score from this search turned to white's side
+    pub fn for_white(&self, score: f32) -> f32 {
+        self.side.map_or(score, |side| white_score(score, side))
+    }
+
+    pub fn avg_rollout_depth(&self) -> f32 {
+        self.rollout_plies as f32 / self.rollouts.max(1) as f32
+    }
+
+    // the b for which a tree of b^depth nodes would be this big; lower means
+    // the search is going deep instead of wide
+    pub fn branching_factor(&self) -> f32 {
+        if self.depth_reached <= 0 {
+            return 0.0;
+        }
+        (self.tree_size as f32).powf(1.0 / self.depth_reached as f32)
+    }
+
     fn new() -> Self {
         SimulationStats {
             total_simulations: 0,
@@ -64,21 +503,195 @@ impl SimulationStats {
             current_eval: 0.0,
             depth_reached: 0,
             top_moves: Vec::new(),
-            thinking_line: String::new(),
+            ..Default::default()
+        }
+    }
+}
+
+pub struct SearchResult {
+    pub best_move: MovePair,
+    pub score: f32, // average value of best_move for the side that searched
+    pub pv: Vec<MovePair>,
+    pub tree: Vec<Branch>, // every root move, preferred first
+}
+
+impl SearchResult {
+    // searches of the same position run side by side: visits add up, values
+    // average out by visits unless one search has found a mate, and the
+    // preferred move overall wins
+    pub fn merge(results: Vec<SearchResult>) -> Option<SearchResult> {
+        let mut tree: Vec<Branch> = Vec::new();
+        for branch in results.into_iter().flat_map(|result| result.tree) {
+            let Some(existing) = tree.iter_mut().find(|b| b.mv == branch.mv) else {
+                tree.push(branch);
+                continue;
+            };
+            let visits = existing.visits + branch.visits;
+            existing.value = match (mate_in(existing.value), mate_in(branch.value)) {
+                (Some(_), Some(_)) => existing.value.max(branch.value),
+                (Some(_), None) => existing.value,
+                (None, Some(_)) => branch.value,
+                (None, None) => {
+                    (existing.value * existing.visits as f32 + branch.value * branch.visits as f32) / visits as f32
+                }
+            };
+            // the line from whichever search looked at the move hardest
+            if branch.visits > existing.visits {
+                existing.pv = branch.pv;
+                existing.replies = branch.replies;
+            }
+            existing.visits = visits;
         }
+        tree.sort_by(|a, b| {
+            preference(b.value, b.visits).partial_cmp(&preference(a.value, a.visits)).unwrap()
+        });
+        let best = tree.first()?;
+        Some(SearchResult {
+            best_move: best.mv,
+            score: best.value,
+            pv: best.pv.clone(),
+            tree,
+        })
     }
 }
 
+// one move from the finished search tree
+#[derive(Clone, Debug)]
+pub struct Branch {
+    pub mv: MovePair,
+    pub visits: u32,
+    pub value: f32, // average or proven mate, for the side that made `mv`
+    pub pv: Vec<MovePair>, // starts with `mv`
+    pub replies: Vec<Branch>,
+}
+
+#[derive(Clone)]
 pub struct RLEngine {
     piece_values: HashMap<PieceType, i32>,
-    position_values: HashMap<PieceType, [[f32; 8]; 8]>,
+    // whether update_piece_values changes anything
+    learn_piece_values: bool,
+    position_values: HashMap<PieceType, PhaseTables>,
     learning_rate: f32,
-    discount_factor: f32,
-    exploration_rate: f32,
-    move_history: Vec<((usize, usize), (usize, usize))>,
     simulation_depth: i32,
-    prune_threshold: f32,
     pub current_stats: SimulationStats,
+    // the rules it searches under
+    variant: Arc<dyn Variant>,
+    personality: Personality,
+    // term weights from a loaded weights file or the config, over the
+    // personality's
+    term_overrides: Vec<(String, f32)>,
+    // the personality's weights with the overrides applied
+    weights: Weights,
+    // a draw counts as this much below zero for the side searching, so it
+    // steers away from draws when ahead and toward them when behind
+    contempt: f32,
+    // positions already played this game and who was to move in them; any
+    // line that returns to one is scored as a draw
+    history: Vec<(Board, Color)>,
+    root_color: Color,
+    // every random choice goes through this, so a seeded engine always
+    // searches the same way
+    rng: RefCell<StdRng>,
+    // stop after this many simulations even if there's time left
+    max_simulations: Option<u32>,
+    // how many bytes one search's tree and eval table may take between
+    // them; the tree recycles nodes to stay within its part. None lets it
+    // grow
+    hash_budget: Option<usize>,
+    // the eval table of the search under way
+    table: RefCell<Option<EvalTable>>,
+    // killer moves and history of the search under way
+    ordering: Option<MoveOrdering>,
+    // how many root moves the stats report, each with its own line
+    multi_pv: usize,
+    // how strongly playouts favour high-priority moves, in priority points;
+    // see rollout_move
+    rollout_temperature: f32,
+    // the material swing that ends a playout early, in centipawns; None
+    // plays every one out to its depth
+    rollout_cutoff: Option<i32>,
+    // when the search under way has to be done by. playouts check it as
+    // they go, so one slow playout can't run the move past its time
+    deadline: Option<std::time::Instant>,
+    // set from outside to end the search with the best move so far; forks
+    // share it, so one flag stops every thread of a search
+    stop: Option<Arc<AtomicBool>>,
+    // stop once the principal variation is this many plies long
+    max_depth: Option<usize>,
+}
+
+// what one king's safety score is made of
+#[derive(Clone, Copy, Debug, Default)]
+pub struct KingSafety {
+    // enemy pieces hitting the king or the squares around it
+    pub attackers: usize,
+    // attacked zone squares, weighted by the piece doing it
+    pub attack_weight: f32,
+    // own pawns in front of the king
+    pub shield: f32,
+    // files next to the king without own pawns
+    pub open_files: f32,
+    pub score: f32,
+}
+
+// the evaluation from one side, term by term, before the personality weighs them
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EvalTerms {
+    pub material: f32,
+    pub king_safety: f32,
+    pub king_attack: f32,
+    pub mobility: f32,
+    pub threats: f32,
+    pub center: f32,
+    pub pawn_structure: f32,
+    pub position: f32,
+    pub activity: f32,
+}
+
+impl EvalTerms {
+    // each term times its weight, so they add up to `weighted`
+    pub fn scaled(&self, weights: &Weights) -> EvalTerms {
+        EvalTerms {
+            material: self.material * weights.material,
+            king_safety: self.king_safety * weights.king_safety,
+            king_attack: self.king_attack * weights.king_attack,
+            mobility: self.mobility * weights.mobility,
+            threats: self.threats * weights.threats,
+            center: self.center * weights.center,
+            pawn_structure: self.pawn_structure * weights.pawn_structure,
+            position: self.position * weights.position,
+            activity: self.activity * weights.activity,
+        }
+    }
+
+    // the terms of `side`'s eval turned to white's side
+    pub fn for_white(&self, side: Color) -> EvalTerms {
+        let flip = |term| white_score(term, side);
+        EvalTerms {
+            material: flip(self.material),
+            king_safety: flip(self.king_safety),
+            king_attack: flip(self.king_attack),
+            mobility: flip(self.mobility),
+            threats: flip(self.threats),
+            center: flip(self.center),
+            pawn_structure: flip(self.pawn_structure),
+            position: flip(self.position),
+            activity: flip(self.activity),
+        }
+    }
+
+    pub fn weighted(&self, weights: &Weights) -> f32 {
+        self.material * weights.material
+            + self.king_safety * weights.king_safety
+            + self.king_attack * weights.king_attack
+            + self.mobility * weights.mobility
+            + self.threats * weights.threats
+            + self.center * weights.center
+            + self.pawn_structure * weights.pawn_structure
+            + self.position * weights.position
+            + self.activity * weights.activity
+    }
 }
 
 struct BoardAnalysis {
@@ -92,54 +705,231 @@ struct BoardAnalysis {
 
 impl RLEngine {
     pub fn new() -> Self {
-        let mut piece_values = std::collections::HashMap::new();
-        piece_values.insert(PieceType::Pawn, 100);
-        piece_values.insert(PieceType::Knight, 320);
-        piece_values.insert(PieceType::Bishop, 330);
-        piece_values.insert(PieceType::Rook, 500);
-        piece_values.insert(PieceType::Queen, 900);
+        let mut piece_values: HashMap<PieceType, i32> = PieceValues::default().values().into_iter().collect();
         piece_values.insert(PieceType::King, 20000);
 
         RLEngine {
             piece_values,
+            learn_piece_values: false,
             position_values: Self::initialize_position_values(),
             learning_rate: 0.1,
-            discount_factor: 0.95,
-            exploration_rate: 0.1,
-            move_history: Vec::new(),
             simulation_depth: MAX_PLIES,
-            prune_threshold: -500.0,
             current_stats: SimulationStats::new(),
+            variant: Arc::new(Standard),
+            personality: Personality::Balanced,
+            term_overrides: Vec::new(),
+            weights: Personality::Balanced.weights(),
+            contempt: 0.0,
+            history: Vec::new(),
+            root_color: Color::White,
+            rng: RefCell::new(StdRng::from_entropy()),
+            max_simulations: None,
+            hash_budget: Some(DEFAULT_HASH_MB << 20),
+            table: RefCell::new(None),
+            ordering: None,
+            multi_pv: DEFAULT_MULTI_PV,
+            rollout_temperature: DEFAULT_ROLLOUT_TEMPERATURE,
+            rollout_cutoff: Some(DEFAULT_ROLLOUT_CUTOFF),
+            deadline: None,
+            stop: None,
+            max_depth: None,
         }
     }
 
+    pub fn with_seed(seed: u64) -> Self {
+        let mut engine = Self::new();
+        engine.rng = RefCell::new(StdRng::seed_from_u64(seed));
+        engine
+    }
+
+    // a copy to search with on another thread. it gets its own random
+    // stream, drawn from this one, so one seed still reproduces a whole game
+    pub fn fork(&mut self) -> RLEngine {
+        let mut copy = self.clone();
+        copy.rng = RefCell::new(StdRng::seed_from_u64(self.rng.get_mut().gen()));
+        copy
+    }
+
+    pub fn max_simulations(&self) -> Option<u32> {
+        self.max_simulations
+    }
+
+    pub fn set_max_simulations(&mut self, max: Option<u32>) {
+        self.max_simulations = max;
+    }
 
-    fn initialize_position_values() -> std::collections::HashMap<PieceType, [[f32; 8]; 8]> {
+    pub fn set_max_depth(&mut self, max: Option<usize>) {
+        self.max_depth = max;
+    }
+
+    pub fn set_stop(&mut self, stop: Option<Arc<AtomicBool>>) {
+        self.stop = stop;
+    }
+
+    fn stopped(&self) -> bool {
+        self.stop.as_ref().is_some_and(|stop| stop.load(Ordering::Relaxed))
+    }
+
+    pub fn hash_budget(&self) -> Option<usize> {
+        self.hash_budget
+    }
+
+    pub fn set_hash_budget(&mut self, bytes: Option<usize>) {
+        self.hash_budget = bytes;
+    }
+
+    pub fn multi_pv(&self) -> usize {
+        self.multi_pv
+    }
+
+    pub fn set_multi_pv(&mut self, lines: usize) {
+        self.multi_pv = lines.max(1);
+    }
+
+    pub fn set_rollout_temperature(&mut self, temperature: f32) {
+        self.rollout_temperature = temperature.max(0.0);
+    }
+
+    pub fn set_rollout_cutoff(&mut self, cutoff: Option<i32>) {
+        self.rollout_cutoff = cutoff.filter(|cutoff| *cutoff > 0);
+    }
+
+    fn initialize_position_values() -> std::collections::HashMap<PieceType, PhaseTables> {
         let mut values = std::collections::HashMap::new();
         
         // Initialize basic position values for each piece type
-        // These will be updated through learning
-        values.insert(PieceType::Pawn, Self::pawn_position_values());
-        values.insert(PieceType::Knight, Self::knight_position_values());
-        values.insert(PieceType::Bishop, Self::bishop_position_values());
-        values.insert(PieceType::Rook, Self::rook_position_values());
-        values.insert(PieceType::Queen, Self::queen_position_values());
-        values.insert(PieceType::King, Self::king_position_values());
+        // These will be updated through learning. only kings and pawns
+        // play differently in the endgame, the rest start out the same
+        let same = |table: [[f32; 8]; 8]| PhaseTables { middle: table, end: table };
+        values.insert(PieceType::Pawn, PhaseTables {
+            middle: Self::pawn_position_values(),
+            end: Self::pawn_endgame_values(),
+        });
+        values.insert(PieceType::Knight, same(Self::knight_position_values()));
+        values.insert(PieceType::Bishop, same(Self::bishop_position_values()));
+        values.insert(PieceType::Rook, same(Self::rook_position_values()));
+        values.insert(PieceType::Queen, same(Self::queen_position_values()));
+        values.insert(PieceType::King, PhaseTables {
+            middle: Self::king_position_values(),
+            end: Self::king_endgame_values(),
+        });
         
         values
     }
 
-    pub fn update_position_values(&mut self, board: &Board, _color: Color, reward: f32) {
-        // Update position values based on reward
+    // a piece's learned table for the middle or end game, from white's side
+    pub fn position_table(&self, piece: PieceType, endgame: bool) -> [[f32; 8]; 8] {
+        let tables = &self.position_values[&piece];
+        if endgame { tables.end } else { tables.middle }
+    }
+
+    // the same table before any learning
+    pub fn initial_position_table(piece: PieceType, endgame: bool) -> [[f32; 8]; 8] {
+        let tables = &Self::initialize_position_values()[&piece];
+        if endgame { tables.end } else { tables.middle }
+    }
+
+    // how much of the middle game is left, from 1.0 down to 0.0
+    pub fn game_phase(board: &Board) -> f32 {
+        let mut phase = 0.0;
+        for rank in 0..8 {
+            for file in 0..8 {
+                if let Some(piece) = board.get_piece((rank, file)) {
+                    phase += match piece.piece_type {
+                        PieceType::Knight | PieceType::Bishop => 1.0,
+                        PieceType::Rook => 2.0,
+                        PieceType::Queen => 4.0,
+                        _ => 0.0,
+                    };
+                }
+            }
+        }
+        (phase / OPENING_PHASE).min(1.0)
+    }
+
+    // tables are from white's side, so black's pieces look them up mirrored
+    fn table_square(color: Color, (rank, file): (usize, usize)) -> (usize, usize) {
+        match color {
+            Color::White => (rank, file),
+            Color::Black => (7 - rank, file),
+        }
+    }
+
+    // piece-square total for `color` minus the opponent's, blended by game phase
+    fn position_balance(&self, board: &Board, color: Color) -> f32 {
+        let phase = Self::game_phase(board);
+        let mut balance = 0.0;
+        for rank in 0..8 {
+            for file in 0..8 {
+                if let Some(piece) = board.get_piece((rank, file)) {
+                    let square = Self::table_square(piece.color, (rank, file));
+                    let value = self.position_values[&piece.piece_type].value(square, phase);
+                    if piece.color == color {
+                        balance += value;
+                    } else {
+                        balance -= value;
+                    }
+                }
+            }
+        }
+        balance
+    }
+
+    // nudges the squares the pieces stand on toward the result, in pawns, for
+    // `color`'s pieces and against for the opponent's. each phase's table
+    // learns as much as the position belongs to that phase
+    pub fn update_position_values(&mut self, board: &Board, color: Color, reward: f32) {
+        let phase = Self::game_phase(board);
+        let target = (reward / 100.0).clamp(-MAX_POSITION_VALUE, MAX_POSITION_VALUE);
         for rank in 0..8 {
             for file in 0..8 {
                 if let Some(piece) = board.get_piece((rank, file)) {
-                    let current_value = self.position_values.get_mut(&piece.piece_type).unwrap()[rank][file];
-                    let update = self.learning_rate * (reward - current_value);
-                    self.position_values.get_mut(&piece.piece_type).unwrap()[rank][file] += update;
+                    let target = if piece.color == color { target } else { -target };
+                    let (r, f) = Self::table_square(piece.color, (rank, file));
+                    let tables = self.position_values.get_mut(&piece.piece_type).unwrap();
+                    for (table, share) in [(&mut tables.middle, phase), (&mut tables.end, 1.0 - phase)] {
+                        let current_value = table[r][f];
+                        table[r][f] += self.learning_rate * share * (target - current_value);
+                    }
                 }
             }
         }
+        session::learned();
+    }
+
+    // moves the piece values so the material count alone comes nearer
+    // `reward`, in centipawns for `color`. only the pieces one side has more
+    // of than the other say anything, and those are what change
+    pub fn update_piece_values(&mut self, board: &Board, color: Color, reward: f32) {
+        if !self.learn_piece_values {
+            return;
+        }
+        let mut surplus: HashMap<PieceType, f32> = HashMap::new();
+        for rank in 0..8 {
+            for file in 0..8 {
+                if let Some(piece) = board.get_piece((rank, file)) {
+                    if matches!(piece.piece_type, PieceType::Pawn | PieceType::King) {
+                        continue;
+                    }
+                    *surplus.entry(piece.piece_type).or_default() += if piece.color == color { 1.0 } else { -1.0 };
+                }
+            }
+        }
+        let spread: f32 = surplus.values().map(|count| count * count).sum();
+        if spread == 0.0 {
+            return;
+        }
+        let error = reward - self.get_material_balance(board, color) as f32;
+        let rate = self.learning_rate * PIECE_LEARNING_SHARE;
+        for (piece, count) in surplus {
+            let value = self.piece_values.get_mut(&piece).unwrap();
+            let learned = *value as f32 + rate * error * count / spread;
+            *value = learned.clamp(*PIECE_VALUE_RANGE.start(), *PIECE_VALUE_RANGE.end()).round() as i32;
+        }
+    }
+
+    pub fn set_learn_piece_values(&mut self, learn: bool) {
+        self.learn_piece_values = learn;
     }
 
     pub fn get_material_balance(&self, board: &Board, color: Color) -> i32 {
@@ -159,12 +949,11 @@ impl RLEngine {
         balance
     }
 
-    pub fn get_king_safety(&self, board: &Board, color: Color) -> f32 {
+    pub fn get_king_safety(&self, board: &Board, color: Color) -> KingSafety {
         if let Some(king_pos) = self.find_king(board, color) {
-            let analysis = self.analyze_board(board, color);
-            self.evaluate_king_safety(board, king_pos, color, &analysis)
+            self.king_zone_safety(board, king_pos, color)
         } else {
-            0.0
+            KingSafety::default()
         }
     }
 
@@ -173,168 +962,511 @@ impl RLEngine {
         self.evaluate_center_control(&analysis.controlled_squares)
     }
 
-    pub fn evaluate_position(&self, board: &Board, color: Color) -> f32 {
+    pub fn set_variant(&mut self, variant: Arc<dyn Variant>) {
+        self.variant = variant;
+    }
+
+    pub fn set_personality(&mut self, personality: Personality) {
+        self.personality = personality;
+        self.update_weights();
+    }
+
+    pub fn term_overrides(&self) -> &[(String, f32)] {
+        &self.term_overrides
+    }
+
+    // term weights that win over the personality's, by name
+    pub fn set_term_overrides(&mut self, overrides: Vec<(String, f32)>) {
+        self.term_overrides = overrides;
+        self.update_weights();
+    }
+
+    fn update_weights(&mut self) {
+        self.weights = self.personality.weights();
+        for (name, value) in &self.term_overrides {
+            self.weights.set(name, *value);
+        }
+    }
+
+    pub fn set_piece_value(&mut self, piece: PieceType, value: i32) {
+        self.piece_values.insert(piece, value);
+    }
+
+    pub fn set_piece_values(&mut self, values: PieceValues) {
+        for (piece, value) in values.values() {
+            self.set_piece_value(piece, value);
+        }
+    }
+
+    pub fn set_position_table(&mut self, piece: PieceType, endgame: bool, table: [[f32; 8]; 8]) {
+        let tables = self.position_values.get_mut(&piece).unwrap();
+        if endgame {
+            tables.end = table;
+        } else {
+            tables.middle = table;
+        }
+    }
+
+    // back to the built-in values, forgetting anything learned or loaded
+    pub fn reset_weights(&mut self) {
+        let fresh = RLEngine::new();
+        self.piece_values = fresh.piece_values;
+        self.position_values = fresh.position_values;
+        self.set_term_overrides(Vec::new());
+    }
+
+    pub fn personality(&self) -> Personality {
+        self.personality
+    }
+
+    // what the evaluation terms are multiplied by
+    pub fn eval_weights(&self) -> Weights {
+        self.weights
+    }
+
+    pub fn piece_value(&self, piece: PieceType) -> i32 {
+        self.piece_values[&piece]
+    }
+
+    // the static exchange for taking on `to` from `from`, at this engine's
+    // piece values
+    pub fn see(&self, board: &Board, from: (usize, usize), to: (usize, usize)) -> i32 {
+        board.see(from, to, &|piece| self.piece_values[&piece])
+    }
+
+    pub fn set_contempt(&mut self, contempt: f32) {
+        self.contempt = contempt;
+    }
+
+    pub fn set_history(&mut self, history: Vec<(Board, Color)>) {
+        self.history = history;
+    }
+
+    fn repeats(&self, board: &Board, to_move: Color) -> bool {
+        self.history
+            .iter()
+            .any(|(seen, color)| *color == to_move && seen.same_position(board))
+    }
+
+    // a draw from `color`'s side
+    fn draw_value(&self, color: Color) -> f32 {
+        if color == self.root_color {
+            -self.contempt
+        } else {
+            self.contempt
+        }
+    }
+
+    pub fn eval_terms(&self, board: &Board, color: Color) -> EvalTerms {
         let analysis = self.analyze_board(board, color);
         let opponent_analysis = self.analyze_board(board, color.opposite());
 
-        // Base score from material and position
-        let mut score = analysis.material_balance as f32;
-
-        // King safety (heavily weighted)
-        score += analysis.king_safety * 3.0;
-        score -= opponent_analysis.king_safety * 2.5;
+        EvalTerms {
+            material: analysis.material_balance as f32,
+            king_safety: analysis.king_safety,
+            // the less safe their king, the better
+            king_attack: -opponent_analysis.king_safety,
+            mobility: Self::mobility_balance(board, &analysis, color),
+            threats: -(analysis.threats.len() as f32),
+            center: analysis.center_control,
+            pawn_structure: Self::pawn_weaknesses(board, color.opposite())
+                - Self::pawn_weaknesses(board, color),
+            position: self.position_balance(board, color),
+            activity: Self::piece_activity(board, color) - Self::piece_activity(board, color.opposite()),
+        }
+    }
 
-        // Mobility bonus
-        score += (analysis.piece_mobility.values().map(|moves| moves.len()).sum::<usize>() as f32) * 0.1;
+    // a finished game from `color`'s side
+    fn result_score(&self, result: GameResult, color: Color) -> f32 {
+        match result.winner() {
+            Some(winner) if winner == color => WIN_SCORE,
+            Some(_) => -WIN_SCORE,
+            None => self.draw_value(color),
+        }
+    }
 
-        // Threat penalty
-        score -= (analysis.threats.len() as f32) * 2.0;
+    pub fn evaluate_position(&self, board: &Board, color: Color) -> f32 {
+        if let Some(result) = self.variant.terminal(board, color) {
+            return self.result_score(result, color);
+        }
+        if self.repeats(board, color) {
+            return self.draw_value(color);
+        }
 
-        // Center control
-        score += analysis.center_control * 1.5;
+        let key = zobrist::hash(board, color);
+        let cached = self.table.borrow_mut().as_mut().and_then(|table| table.probe(key));
+        let mut score = match cached {
+            Some(score) => score,
+            None => {
+                let score = self.eval_terms(board, color).weighted(&self.weights) + self.variant.evaluate(board, color);
+                if let Some(table) = self.table.borrow_mut().as_mut() {
+                    table.store(key, score);
+                }
+                score
+            }
+        };
 
         // Randomization factor to avoid repetitive play
-        let mut rng = rand::thread_rng();
-        score += rng.gen_range(-0.2..0.2);
+        score += self.rng.borrow_mut().gen_range(-0.2..0.2);
 
         score
     }
 
-    pub fn get_best_move(&mut self, board: &Board, color: Color) -> Option<((usize, usize), (usize, usize))> {
-        self.current_stats = SimulationStats::default();
-        let mut root = MCTSNode::new(board.clone(), color, self);
+    // `search_moves` restricts which root moves get considered, like UCI's `go searchmoves`.
+    // `on_progress` gets a stats snapshot every PROGRESS_INTERVAL while searching
+    pub fn search(
+        &mut self,
+        board: &Board,
+        color: Color,
+        search_moves: Option<&[MovePair]>,
+        timeout: std::time::Duration,
+        on_progress: &mut dyn FnMut(&SimulationStats),
+    ) -> Option<SearchResult> {
+        self.current_stats = SimulationStats { side: Some(color), ..Default::default() };
+        self.root_color = color;
+        let table_bytes = self.hash_budget.map_or(UNLIMITED_TABLE_BYTES, |bytes| bytes / TABLE_SHARE);
+        *self.table.get_mut() = Some(EvalTable::new(table_bytes));
+        self.ordering = Some(MoveOrdering::default());
+        let mut root = MCTSNode::new(board, color, 0, self);
+        if let Some(moves) = search_moves {
+            root.unexplored_moves.retain(|(mv, _)| moves.contains(&unpack(*mv)));
+        }
+        let mut tree = Tree::new(root, self.hash_budget.map(|bytes| bytes - table_bytes));
         let start_time = std::time::Instant::now();
-        let timeout = std::time::Duration::from_secs(5);
-
-        while start_time.elapsed() < timeout {
+        let mut last_progress = start_time;
+        self.deadline = start_time.checked_add(timeout);
+
+        // a proven result won't change with more searching. a timeout of
+        // Duration::MAX searches until stopped
+        while start_time.elapsed() < timeout
+            && self.max_simulations.is_none_or(|max| self.current_stats.total_simulations < max)
+            && self.max_depth.is_none_or(|max| Self::pv_length(&tree, ROOT) < max)
+            && tree.node(ROOT).outcome.is_none()
+            && !self.stopped()
+        {
             self.current_stats.total_simulations += 1;
-            let eval = self.mcts_iteration(&mut root);
-            
-            // Update stats every 50 simulations
-            if self.current_stats.total_simulations % 50 == 0 {
-                self.current_stats.current_eval = eval;
-                
-                // Update top moves
-                let mut top_moves = Vec::new();
-                for (mv, child) in &root.children {
-                    let score = child.total_value / child.visits as f32;
-                    let move_str = format!("{}{}", 
-                        coordinate_to_string(mv.0),
-                        coordinate_to_string(mv.1)
-                    );
-                    top_moves.push((move_str, score, child.visits));
-                }
-                
-                // Sort by visits and take top 3
-                top_moves.sort_by(|a, b| b.2.cmp(&a.2));
-                top_moves.truncate(3);
-                self.current_stats.top_moves = top_moves;
-                
-                // Force UI refresh through crossterm
-                let _ = crossterm::execute!(
-                    std::io::stdout(),
-                    crossterm::cursor::Hide,
-                );
-                let backend = ratatui::backend::CrosstermBackend::new(std::io::stdout());
-                if let Ok(mut terminal) = ratatui::Terminal::new(backend) {
-                    let _ = terminal.draw(|f| {
-                        // This will be handled by the App's draw method
-                        f.render_widget(ratatui::widgets::Clear, f.area());
-                    });
-                }
+            let eval = self.mcts_iteration(&mut tree, ROOT, &mut board.clone(), 0);
+            if tree.over_budget() {
+                tree.recycle();
             }
+
+            if last_progress.elapsed() >= PROGRESS_INTERVAL {
+                self.current_stats.current_eval = to_centipawns(eval);
+                self.current_stats.elapsed = start_time.elapsed();
+                self.refresh_stats(&tree);
+                on_progress(&self.current_stats);
+                last_progress = std::time::Instant::now();
+            }
+        }
+        self.deadline = None;
+        self.current_stats.elapsed = start_time.elapsed();
+        if let Some(outcome) = tree.node(ROOT).outcome {
+            self.current_stats.current_eval = outcome;
         }
+        self.refresh_stats(&tree);
+        *self.table.get_mut() = None;
+        self.ordering = None;
+        session::searched(self.current_stats.total_simulations as u64);
 
         // Find best child and record statistics
-        if let Some((best_move, best_child)) = root.children.iter()
-            .max_by_key(|(_, child)| child.visits) {
-                
+        if let Some((best_move, best_child)) = tree.best_child(ROOT) {
             // Calculate confidence as visits ratio
-            let total_visits: u32 = root.children.iter()
-                .map(|(_, child)| child.visits)
-                .sum();
+            let total_visits: u32 = tree.children(ROOT).map(|(_, child)| child.visits).sum();
             self.current_stats.best_move_confidence = best_child.visits as f32 / total_visits as f32;
-            
-            // Record best line
-            self.current_stats.best_line = vec![
-                format!("{}{}", 
-                    coordinate_to_string(best_move.0),
-                    coordinate_to_string(best_move.1)
-                )
-            ];
-            
-            Some(*best_move)
+
+            Some(SearchResult {
+                best_move,
+                score: best_child.score(),
+                pv: Self::principal_variation(&tree, ROOT),
+                tree: Self::branches(&tree, ROOT, usize::MAX, 2),
+            })
         } else {
             None
         }
     }
 
-    fn mcts_iteration(&mut self, node: &mut MCTSNode) -> f32 {
+    // top moves and the best line so far, for the analytics display
+    fn refresh_stats(&mut self, tree: &Tree) {
+        self.current_stats.top_moves = Self::branches(tree, ROOT, self.multi_pv, 1)
+            .iter()
+            .map(PvLine::from_branch)
+            .collect();
+
+        self.current_stats.tree_size = (tree.nodes.len() - tree.free.len()) as u32;
+        self.current_stats.memory_bytes = tree.bytes;
+        self.current_stats.recycled = tree.recycled;
+        if let Some(table) = self.table.get_mut() {
+            self.current_stats.memory_bytes += table.bytes();
+            self.current_stats.table_probes = table.probes;
+            self.current_stats.table_hits = table.hits;
+            self.current_stats.table_fill = table.fill();
+        }
+        if let Some(ordering) = &self.ordering {
+            self.current_stats.cutoffs = ordering.cutoffs;
+            self.current_stats.first_move_cutoffs = ordering.first_move_cutoffs;
+        }
+
+        let pv = Self::principal_variation(tree, ROOT);
+        self.current_stats.best_line = pv.iter().map(|mv| coordinates(*mv)).collect();
+        self.current_stats.depth_reached = pv.len() as i32;
+    }
+
+    fn preferred_child(tree: &Tree, id: NodeId) -> Option<NodeId> {
+        tree.node(id)
+            .children
+            .iter()
+            .copied()
+            .max_by(|&a, &b| tree.node(a).preference().partial_cmp(&tree.node(b).preference()).unwrap())
+    }
+
+    // follow the preferred child down the tree
+    fn principal_variation(tree: &Tree, from: NodeId) -> Vec<MovePair> {
+        let mut pv = Vec::new();
+        let mut id = from;
+        while let Some(child) = Self::preferred_child(tree, id) {
+            pv.push(unpack(tree.node(child).mv));
+            id = child;
+        }
+        pv
+    }
+
+    // how long principal_variation would be, without collecting it
+    fn pv_length(tree: &Tree, from: NodeId) -> usize {
+        let mut length = 0;
+        let mut id = from;
+        while let Some(child) = Self::preferred_child(tree, id) {
+            length += 1;
+            id = child;
+        }
+        length
+    }
+
+    // the `width` preferred children, `depth` plies down
+    fn branches(tree: &Tree, id: NodeId, width: usize, depth: usize) -> Vec<Branch> {
+        let mut children = tree.node(id).children.clone();
+        children.sort_by(|&a, &b| tree.node(b).preference().partial_cmp(&tree.node(a).preference()).unwrap());
+        children
+            .into_iter()
+            .take(width)
+            .map(|child| {
+                let node = tree.node(child);
+                let mv = unpack(node.mv);
+                Branch {
+                    mv,
+                    visits: node.visits,
+                    value: node.score(),
+                    pv: std::iter::once(mv).chain(Self::principal_variation(tree, child)).collect(),
+                    replies: if depth > 1 { Self::branches(tree, child, TRACE_REPLIES, depth - 1) } else { Vec::new() },
+                }
+            })
+            .collect()
+    }
+
+    // one simulation through node `id`, `ply` below the root, with `board`
+    // the position there; the board is played on down the tree as the
+    // search descends. returns the value for the side to move there and
+    // adds the negation, the mover's value, to the node
+    fn mcts_iteration(&mut self, tree: &mut Tree, id: NodeId, board: &mut Board, ply: usize) -> f32 {
         self.current_stats.nodes_explored += 1;
-        if node.visits > 0 && !node.unexplored_moves.is_empty() {
-            let move_index = rand::thread_rng().gen_range(0..node.unexplored_moves.len());
-            let next_move = node.unexplored_moves.remove(move_index);
-            let mut new_board = node.board.clone();
-            
-            if new_board.move_piece(next_move.0, next_move.1) {
-                let mut child = MCTSNode::new(new_board, node.current_player.opposite(), self);
-                let value = -self.simulate(&mut child, self.simulation_depth);
+        let node = tree.node_mut(id);
+        if let Some(outcome) = node.outcome {
+            let value = to_value(outcome);
+            node.visits += 1;
+            node.total_value -= value;
+            return value;
+        }
+        if node.visits > 0 && node.can_widen() {
+            // the best-ranked move next, a random one among equals
+            let top = node.unexplored_moves[0].1;
+            let equals = node.unexplored_moves.iter().take_while(|(_, priority)| *priority == top).count();
+            let move_index = self.rng.get_mut().gen_range(0..equals);
+            let (next_move, priority) = node.unexplored_moves.remove(move_index);
+            let next_move = unpack(next_move);
+            let mover = node.current_player;
+            let mut new_board = board.clone();
+
+            if self.variant.make_move(&mut new_board, next_move.0, next_move.1) {
+                let mut child = MCTSNode::new(&new_board, mover.opposite(), ply + 1, self);
+                // a repeated position is a draw, nothing past it matters
+                if self.repeats(&new_board, child.current_player) {
+                    child.unexplored_moves.clear();
+                }
+                let mut plies = 0;
+                let child_value = match child.outcome {
+                    Some(outcome) => to_value(outcome),
+                    None => to_value(self.simulate(
+                        &new_board,
+                        child.current_player,
+                        self.simulation_depth,
+                        &mut plies,
+                    )),
+                };
+                child.prior = match child.outcome {
+                    Some(outcome) => -to_value(outcome),
+                    None => -to_value(self.evaluate_position(&new_board, child.current_player)),
+                };
+                self.current_stats.rollouts += 1;
+                self.current_stats.rollout_plies += plies as u64;
                 child.visits = 1;
-                child.total_value = value;
-                node.children.push((next_move, child));
+                child.total_value = -child_value;
+                tree.add_child(id, child, next_move, priority);
+                let node = tree.node_mut(id);
                 node.visits += 1;
-                node.total_value += value;
-                return value;
+                node.total_value += child_value;
+                tree.solve(id);
+                return -child_value;
             }
-            return self.mcts_iteration(node);
+            return self.mcts_iteration(tree, id, board, ply);
         }
 
         if node.children.is_empty() {
-            let value = self.evaluate_position(&node.board, node.current_player);
+            let value = to_value(self.evaluate_position(board, node.current_player));
+            let node = tree.node_mut(id);
             node.visits += 1;
-            node.total_value += value;
+            node.total_value -= value;
             return value;
         }
 
         let parent_visits = node.visits;
-        let (_, child) = node.children.iter_mut()
-            .max_by(|(_, a), (_, b)| {
-                a.uct_value(parent_visits)
-                    .partial_cmp(&b.uct_value(parent_visits))
+        let child = *tree
+            .node(id)
+            .children
+            .iter()
+            .max_by(|&&a, &&b| {
+                tree.node(a)
+                    .uct_value(parent_visits)
+                    .partial_cmp(&tree.node(b).uct_value(parent_visits))
                     .unwrap()
             })
             .unwrap();
+        let (from, to) = unpack(tree.node(child).mv);
+        let quiet = board.get_piece(to).is_none();
+        self.variant.make_move(board, from, to);
 
-        let value = -self.mcts_iteration(child);
+        let value = -self.mcts_iteration(tree, child, board, ply + 1);
+        let node = tree.node_mut(id);
         node.visits += 1;
-        node.total_value += value;
+        node.total_value -= value;
+        self.record_cutoff(tree, id, child, quiet, ply);
+        tree.solve(id);
         value
     }
 
-    fn simulate(&self, node: &mut MCTSNode, depth: i32) -> f32 {
-        if depth <= 0 || self.is_terminal(&node.board) {
-            return self.evaluate_position(&node.board, node.current_player);
+    // a move that's the most visited at its node has held up against
+    // everything else tried there, the nearest a tree search comes to a
+    // cutoff. a quiet one becomes a killer for its ply and goes up in the
+    // history table
+    fn record_cutoff(&mut self, tree: &Tree, id: NodeId, child: NodeId, quiet: bool, ply: usize) {
+        let Some(ordering) = self.ordering.as_mut() else {
+            return;
+        };
+        let node = tree.node(id);
+        let visits = tree.node(child).visits;
+        if node.children.len() < 2 || node.children.iter().any(|&other| tree.node(other).visits > visits) {
+            return;
+        }
+        ordering.cutoffs += 1;
+        if node.children[0] == child {
+            ordering.first_move_cutoffs += 1;
+        }
+        if quiet {
+            ordering.record(ply, node.current_player, unpack(tree.node(child).mv));
+        }
+    }
+
+    // quiet moves that have been best elsewhere in the search move up, and
+    // the list is sorted again
+    fn order_moves(&self, moves: &mut [(MovePair, f32)], board: &Board, color: Color, ply: usize) {
+        let Some(ordering) = &self.ordering else {
+            return;
+        };
+        for (mv, priority) in moves.iter_mut() {
+            if board.get_piece(mv.1).is_none() {
+                *priority += ordering.bonus(ply, color, *mv);
+            }
         }
+        moves.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+    }
 
-        let moves = self.generate_ranked_moves(&node.board, node.current_player);
-        if moves.is_empty() {
-            return self.evaluate_position(&node.board, node.current_player);
+    // plays `depth` moves out from `board` with `color` to move and scores
+    // the end for `color`. `plies` counts the moves the playout makes. a
+    // side in check when the moves run out gets to answer it first, a few
+    // times at most: a static eval doesn't know whether the check mates
+    fn simulate(&self, board: &Board, color: Color, depth: i32, plies: &mut u32) -> f32 {
+        let start = self.get_material_balance(board, Color::White);
+        self.playout(board, color, depth, plies, start, false)
+    }
+
+    // one ply of a playout. `start` is white's material where it began, and
+    // `swung` whether the last position was already past the cutoff from it:
+    // a swing that lasts through the reply isn't just half of an exchange,
+    // and the game is scored as won by whoever made it, as surely as
+    // anything short of a result can be
+    fn playout(&self, board: &Board, color: Color, depth: i32, plies: &mut u32, start: i32, swung: bool) -> f32 {
+        let extend = depth <= 0
+            && *plies < self.simulation_depth.max(0) as u32 + MAX_CHECK_EXTENSIONS
+            && board.is_in_check(color);
+        if (depth <= 0 && !extend)
+            || self.out_of_time()
+            || self.variant.terminal(board, color).is_some()
+            || self.repeats(board, color)
+        {
+            return self.evaluate_position(board, color);
+        }
+        let gained = self.get_material_balance(board, color) - if color == Color::White { start } else { -start };
+        let swing = self.rollout_cutoff.is_some_and(|cutoff| gained.abs() >= cutoff);
+        if swing && swung {
+            return to_centipawns(MAX_VALUE).copysign(gained as f32);
         }
 
-        let num_moves = moves.len().min(MAX_OPPONENT_MOVES);
-        let move_index = rand::thread_rng().gen_range(0..num_moves);
-        let (from, to) = moves[move_index];
+        let moves = self.scored_moves(board, color);
+        let Some((from, to)) = self.rollout_move(&moves) else {
+            return match self.variant.result(board, color) {
+                Some(result) => self.result_score(result, color),
+                None => self.evaluate_position(board, color),
+            };
+        };
 
-        let mut new_board = node.board.clone();
-        if new_board.move_piece(from, to) {
-            let mut child = MCTSNode::new(new_board, node.current_player.opposite(), self);
-            -self.simulate(&mut child, depth - 1)
+        let mut new_board = board.clone();
+        if self.variant.make_move(&mut new_board, from, to) {
+            *plies += 1;
+            -self.playout(&new_board, color.opposite(), depth - 1, plies, start, swing)
         } else {
-            self.evaluate_position(&node.board, node.current_player)
+            self.evaluate_position(board, color)
         }
     }
 
-    fn generate_ranked_moves(&self, board: &Board, color: Color) -> Vec<((usize, usize), (usize, usize))> {
+    // a playout out of time, or stopped, ends where it is, scored as it stands
+    fn out_of_time(&self) -> bool {
+        self.deadline.is_some_and(|deadline| std::time::Instant::now() >= deadline) || self.stopped()
+    }
+
+    // a softmax pick over the move priorities: captures and checks come up
+    // most, quiet moves still sometimes. at temperature 0 it's always the
+    // best-ranked move, random among equals
+    fn rollout_move(&self, moves: &[(MovePair, f32)]) -> Option<MovePair> {
+        let &(_, top) = moves.first()?;
+        let mut rng = self.rng.borrow_mut();
+        if self.rollout_temperature <= 0.0 {
+            let equals = moves.iter().take_while(|(_, priority)| *priority == top).count();
+            return Some(moves[rng.gen_range(0..equals)].0);
+        }
+        let weights: Vec<f32> = moves
+            .iter()
+            .map(|(_, priority)| ((priority - top) / self.rollout_temperature).exp())
+            .collect();
+        let mut pick = rng.gen::<f32>() * weights.iter().sum::<f32>();
+        for (&(mv, _), weight) in moves.iter().zip(&weights) {
+            if pick < *weight {
+                return Some(mv);
+            }
+            pick -= weight;
+        }
+        moves.last().map(|&(mv, _)| mv)
+    }
+
+    // legal moves with their priority, best first
+    fn scored_moves(&self, board: &Board, color: Color) -> Vec<(MovePair, f32)> {
         let mut moves = Vec::new();
         let analysis = self.analyze_board(board, color);
 
@@ -346,11 +1478,23 @@ impl RLEngine {
                         if let Some(possible_moves) = analysis.piece_mobility.get(&from) {
                             for &to in possible_moves {
                                 let mut board_copy = board.clone();
-                                if board_copy.move_piece(from, to) {
-                                    if !self.is_king_threatened(&board_copy, color) {
-                                        let score = self.evaluate_move_priority(board, from, to, &analysis);
-                                        moves.push((from, to, score));
-                                    }
+                                if !self.variant.make_move(&mut board_copy, from, to) {
+                                    continue;
+                                }
+                                // a move that wins on the spot is always worth a look,
+                                // otherwise the own king has to stay safe
+                                let wins = self
+                                    .variant
+                                    .terminal(&board_copy, color.opposite())
+                                    .and_then(|result| result.winner())
+                                    == Some(color);
+                                if wins
+                                    || ((board_copy.find_king(color).is_some()
+                                        || board.find_king(color).is_none())
+                                        && !self.is_king_threatened(&board_copy, color))
+                                {
+                                    let score = self.evaluate_move_priority(board, &board_copy, from, to, &analysis);
+                                    moves.push((from, to, score));
                                 }
                             }
                         }
@@ -361,29 +1505,147 @@ impl RLEngine {
 
         moves.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap());
         moves.truncate(MAX_OPPONENT_MOVES);
-        moves.into_iter().map(|(from, to, _)| (from, to)).collect()
+        moves.into_iter().map(|(from, to, score)| ((from, to), score)).collect()
     }
 
-    fn evaluate_move_priority(&self, board: &Board, from: (usize, usize), to: (usize, usize), analysis: &BoardAnalysis) -> f32 {
+    fn evaluate_move_priority(&self, board: &Board, after: &Board, from: (usize, usize), to: (usize, usize), analysis: &BoardAnalysis) -> f32 {
+        let weights = self.weights;
         let mut priority = 0.0;
 
+        // a capture that loses material once the recaptures are done goes
+        // behind the quiet moves
         if let Some(target) = board.get_piece(to) {
-            priority += self.piece_values[&target.piece_type] as f32;
+            let exchange = self.see(board, from, to);
+            let gain = if exchange < 0 { exchange } else { self.piece_values[&target.piece_type] };
+            priority += gain as f32 * weights.capture;
         }
 
         if analysis.threats.iter().any(|(_, target)| *target == from) {
-            priority += 50.0;
+            priority += weights.escape;
         }
 
-        if (to.0 >= 3 && to.0 <= 4) && (to.1 >= 3 && to.1 <= 4) {
-            priority += 10.0;
+        if (3..=4).contains(&to.0) && (3..=4).contains(&to.1) {
+            priority += weights.center_move;
+        }
+
+        if let Some(piece) = board.get_piece(from) {
+            // knights and bishops leaving the back rank
+            let back_rank = if piece.color == Color::White { 7 } else { 0 };
+            if from.0 == back_rank && matches!(piece.piece_type, PieceType::Knight | PieceType::Bishop) {
+                priority += weights.development;
+            }
+            if weights.check > 0.0 && after.is_in_check(piece.color.opposite()) {
+                priority += weights.check;
+            }
         }
 
         priority
     }
 
-    fn is_terminal(&self, _board: &Board) -> bool {
-        false
+    // moves per piece, worth more for the pieces that need room to be any
+    // good. `color`'s side minus the opponent's
+    fn mobility_balance(board: &Board, analysis: &BoardAnalysis, color: Color) -> f32 {
+        let mut balance = 0.0;
+        for (&pos, moves) in &analysis.piece_mobility {
+            let Some(piece) = board.get_piece(pos) else {
+                continue;
+            };
+            let weight = match piece.piece_type {
+                PieceType::Knight => 4.0,
+                PieceType::Bishop => 3.0,
+                PieceType::Rook => 2.0,
+                PieceType::Queen => 1.0,
+                PieceType::Pawn | PieceType::King => 0.0,
+            };
+            let value = moves.len() as f32 * weight;
+            if piece.color == color {
+                balance += value;
+            } else {
+                balance -= value;
+            }
+        }
+        balance
+    }
+
+    // rooks on open and half-open files, and knights and bishops on
+    // outposts: past the middle, backed by a pawn, and out of reach of the
+    // enemy's pawns
+    fn piece_activity(board: &Board, color: Color) -> f32 {
+        let is_pawn = |pos: (usize, usize), of: Color| {
+            board
+                .get_piece(pos)
+                .is_some_and(|p| p.piece_type == PieceType::Pawn && p.color == of)
+        };
+        let forward: isize = if color == Color::White { -1 } else { 1 };
+        let mut activity = 0.0;
+        for rank in 0..8 {
+            for file in 0..8 {
+                let Some(piece) = board.get_piece((rank, file)).filter(|p| p.color == color) else {
+                    continue;
+                };
+                match piece.piece_type {
+                    PieceType::Rook => {
+                        let own = (0..8).any(|r| is_pawn((r, file), color));
+                        let theirs = (0..8).any(|r| is_pawn((r, file), color.opposite()));
+                        activity += match (own, theirs) {
+                            (false, false) => ROOK_OPEN_FILE,
+                            (false, true) => ROOK_HALF_OPEN_FILE,
+                            _ => 0.0,
+                        };
+                    }
+                    PieceType::Knight | PieceType::Bishop => {
+                        // ranks 4 to 6 from this side
+                        let advanced = if color == Color::White { (2..=4).contains(&rank) } else { (3..=5).contains(&rank) };
+                        if !advanced {
+                            continue;
+                        }
+                        let behind = (rank as isize - forward) as usize;
+                        let supported = [file.wrapping_sub(1), file + 1]
+                            .into_iter()
+                            .any(|f| f < 8 && is_pawn((behind, f), color));
+                        // any enemy pawn on a neighbouring file still in front could chase it off
+                        let ahead = |r: usize| if color == Color::White { r < rank } else { r > rank };
+                        let attackable = (0..8).filter(|&r| ahead(r)).any(|r| {
+                            [file.wrapping_sub(1), file + 1]
+                                .into_iter()
+                                .any(|f| f < 8 && is_pawn((r, f), color.opposite()))
+                        });
+                        if supported && !attackable {
+                            activity += if piece.piece_type == PieceType::Knight { KNIGHT_OUTPOST } else { BISHOP_OUTPOST };
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        activity
+    }
+
+    // doubled and isolated pawns for `color`, one point each
+    fn pawn_weaknesses(board: &Board, color: Color) -> f32 {
+        let mut files = [0; 8];
+        for rank in 0..8 {
+            for (file, count) in files.iter_mut().enumerate() {
+                if let Some(piece) = board.get_piece((rank, file)) {
+                    if piece.piece_type == PieceType::Pawn && piece.color == color {
+                        *count += 1;
+                    }
+                }
+            }
+        }
+        let mut weaknesses = 0;
+        for file in 0..8 {
+            if files[file] == 0 {
+                continue;
+            }
+            weaknesses += files[file] - 1;
+            let left = file > 0 && files[file - 1] > 0;
+            let right = file < 7 && files[file + 1] > 0;
+            if !left && !right {
+                weaknesses += files[file];
+            }
+        }
+        weaknesses as f32
     }
 
     // Position value matrices for each piece type
@@ -452,6 +1714,34 @@ impl RLEngine {
         ]
     }
 
+    // passed or not, a pawn gets more valuable the closer it is to promoting
+    fn pawn_endgame_values() -> [[f32; 8]; 8] {
+        [
+            [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+            [8.0, 8.0, 8.0, 8.0, 8.0, 8.0, 8.0, 8.0],
+            [5.0, 5.0, 5.0, 5.0, 5.0, 5.0, 5.0, 5.0],
+            [3.0, 3.0, 3.0, 3.0, 3.0, 3.0, 3.0, 3.0],
+            [1.5, 1.5, 1.5, 1.5, 1.5, 1.5, 1.5, 1.5],
+            [0.5, 0.5, 0.5, 0.5, 0.5, 0.5, 0.5, 0.5],
+            [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+        ]
+    }
+
+    // with the queens off the king belongs in the middle of the board
+    fn king_endgame_values() -> [[f32; 8]; 8] {
+        [
+            [-5.0, -4.0, -3.0, -2.0, -2.0, -3.0, -4.0, -5.0],
+            [-3.0, -2.0, -1.0,  0.0,  0.0, -1.0, -2.0, -3.0],
+            [-3.0, -1.0,  2.0,  3.0,  3.0,  2.0, -1.0, -3.0],
+            [-3.0, -1.0,  3.0,  4.0,  4.0,  3.0, -1.0, -3.0],
+            [-3.0, -1.0,  3.0,  4.0,  4.0,  3.0, -1.0, -3.0],
+            [-3.0, -1.0,  2.0,  3.0,  3.0,  2.0, -1.0, -3.0],
+            [-3.0, -3.0,  0.0,  0.0,  0.0,  0.0, -3.0, -3.0],
+            [-5.0, -3.0, -3.0, -3.0, -3.0, -3.0, -3.0, -5.0],
+        ]
+    }
+
     fn king_position_values() -> [[f32; 8]; 8] {
         [
             [-3.0, -4.0, -4.0, -5.0, -5.0, -4.0, -4.0, -3.0],
@@ -478,47 +1768,72 @@ impl RLEngine {
     }
 
     fn get_piece_moves(&self, board: &Board, pos: (usize, usize)) -> Vec<(usize, usize)> {
-        let mut moves = Vec::new();
+        movement::destinations(board, pos)
+    }
+
+    fn evaluate_king_safety(&self, board: &Board, king_pos: (usize, usize), color: Color) -> f32 {
+        self.king_zone_safety(board, king_pos, color).score
+    }
+
+    // attackers on the king's zone (its square and the ones around it), the
+    // pawn shield in front of it and open files next to it. shield and files
+    // matter less as pieces come off, the attacks always count
+    fn king_zone_safety(&self, board: &Board, king_pos: (usize, usize), color: Color) -> KingSafety {
+        let in_zone = |(rank, file): (usize, usize)| rank.abs_diff(king_pos.0) <= 1 && file.abs_diff(king_pos.1) <= 1;
+        let forward: isize = if color == Color::White { -1 } else { 1 };
+        let mut safety = KingSafety::default();
+
         for rank in 0..8 {
             for file in 0..8 {
-                let to = (rank, file);
-                // Create a temporary board copy to test moves
-                let mut board_copy = board.clone();
-                if board_copy.move_piece(pos, to) {
-                    moves.push(to);
+                let Some(piece) = board.get_piece((rank, file)).filter(|p| p.color != color) else {
+                    continue;
+                };
+                let weight = match piece.piece_type {
+                    PieceType::Pawn => 1.0,
+                    PieceType::Knight | PieceType::Bishop => 2.0,
+                    PieceType::Rook => 3.0,
+                    PieceType::Queen => 5.0,
+                    PieceType::King => continue,
+                };
+                let hits = movement::attacks(board, (rank, file)).into_iter().filter(|&to| in_zone(to)).count();
+                if hits > 0 {
+                    safety.attackers += 1;
+                    safety.attack_weight += weight * hits as f32;
                 }
             }
         }
-        moves
-    }
 
-    fn evaluate_king_safety(&self, board: &Board, king_pos: (usize, usize), color: Color, analysis: &BoardAnalysis) -> f32 {
-        let mut safety = 0.0;
-        
-        // Check surrounding squares
-        for rank_offset in -1..=1 {
-            for file_offset in -1..=1 {
-                let rank = king_pos.0 as i32 + rank_offset;
-                let file = king_pos.1 as i32 + file_offset;
-                
-                if rank >= 0 && rank < 8 && file >= 0 && file < 8 {
-                    let pos = (rank as usize, file as usize);
-                    if let Some(piece) = board.get_piece(pos) {
-                        if piece.color == color {
-                            safety += 1.0; // Friendly piece protecting king
-                        }
-                    }
-                }
-            }
-        }
-        
-        // Penalize for enemy control of surrounding squares
-        for &(_threat_pos, target_pos) in &analysis.threats {
-            if target_pos == king_pos {
-                safety -= 2.0;
+        let own_pawn = |pos: (usize, usize)| {
+            board
+                .get_piece(pos)
+                .is_some_and(|p| p.piece_type == PieceType::Pawn && p.color == color)
+        };
+        let files = king_pos.1.saturating_sub(1)..=(king_pos.1 + 1).min(7);
+        for file in files {
+            let ahead = |steps: isize| {
+                let rank = king_pos.0 as isize + forward * steps;
+                (0..8).contains(&rank) && own_pawn((rank as usize, file))
+            };
+            safety.shield += if ahead(1) {
+                2.0
+            } else if ahead(2) {
+                1.0
+            } else {
+                -1.0
+            };
+            if !(0..8).any(|rank| own_pawn((rank, file))) {
+                let enemy_pawn = (0..8).any(|rank| {
+                    board
+                        .get_piece((rank, file))
+                        .is_some_and(|p| p.piece_type == PieceType::Pawn && p.color != color)
+                });
+                safety.open_files -= if enemy_pawn { 1.5 } else { 2.5 };
             }
         }
-        
+
+        let phase = Self::game_phase(board);
+        let penalty = safety.attack_weight * ATTACKER_SCALE[safety.attackers.min(ATTACKER_SCALE.len() - 1)];
+        safety.score = (safety.shield + safety.open_files) * phase - penalty;
         safety
     }
 
@@ -526,9 +1841,9 @@ impl RLEngine {
         let mut control = 0.0;
         
         // Center squares have higher weight
-        for rank in 3..=4 {
-            for file in 3..=4 {
-                if controlled_squares[rank][file] {
+        for row in &controlled_squares[3..=4] {
+            for &controlled in &row[3..=4] {
+                if controlled {
                     control += 1.0;
                 }
             }
@@ -590,7 +1905,7 @@ impl RLEngine {
 
         // Calculate king safety
         if let Some(king_pos) = king_pos {
-            analysis.king_safety = self.evaluate_king_safety(board, king_pos, color, &analysis);
+            analysis.king_safety = self.evaluate_king_safety(board, king_pos, color);
         }
 
         // Calculate center control
@@ -599,56 +1914,236 @@ impl RLEngine {
         analysis
     }
 
+    // `color`'s pieces, king aside, that the other side attacks and nothing
+    // of their own defends
+    pub fn hanging_pieces(&self, board: &Board, color: Color) -> Vec<(usize, usize)> {
+        let attacked = board.attacks_by(color.opposite());
+        let defended = board.attacks_by(color);
+        (0..8)
+            .flat_map(|rank| (0..8).map(move |file| (rank, file)))
+            .filter(|&(rank, file)| attacked[rank][file] && !defended[rank][file])
+            .filter(|&pos| board.get_piece(pos).is_some_and(|p| p.color == color && p.piece_type != PieceType::King))
+            .collect()
+    }
+
     fn is_king_threatened(&self, board: &Board, color: Color) -> bool {
-        if let Some(king_pos) = self.find_king(board, color) {
-            let opponent_analysis = self.analyze_board(board, color.opposite());
-            opponent_analysis.controlled_squares[king_pos.0][king_pos.1]
-        } else {
-            false
-        }
+        board.is_in_check(color)
     }
+}
 
-    fn find_escape_move(&self, board: &Board, color: Color, analysis: &BoardAnalysis) -> Option<((usize, usize), (usize, usize))> {
-        let king_pos = self.find_king(board, color)?;
-        let mut best_move = None;
-        let mut best_safety = f32::NEG_INFINITY;
 
-        // Try all king moves first
-        if let Some(moves) = analysis.piece_mobility.get(&king_pos) {
-            for &to in moves {
-                let mut board_copy = board.clone();
-                if board_copy.move_piece(king_pos, to) {
-                    let safety = self.evaluate_king_safety(&board_copy, to, color, analysis);
-                    if safety > best_safety {
-                        best_safety = safety;
-                        best_move = Some((king_pos, to));
-                    }
-                }
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn seeded_search(seed: u64) -> SearchResult {
+        let mut engine = RLEngine::with_seed(seed);
+        engine.set_max_simulations(Some(200));
+        engine
+            .search(&Board::new(), Color::White, None, Duration::from_secs(60), &mut |_| {})
+            .expect("the start position has moves")
+    }
+
+    #[test]
+    fn same_seed_picks_the_same_move() {
+        let first = seeded_search(7);
+        let second = seeded_search(7);
+        assert_eq!(first.best_move, second.best_move);
+        assert_eq!(first.pv, second.pv);
+        assert_eq!(first.score, second.score);
+    }
+
+    #[test]
+    fn forks_of_the_same_seed_agree() {
+        let mut a = RLEngine::with_seed(11);
+        let mut b = RLEngine::with_seed(11);
+        let mut fork_a = a.fork();
+        let mut fork_b = b.fork();
+        for engine in [&mut fork_a, &mut fork_b] {
+            engine.set_max_simulations(Some(100));
         }
+        let board = Board::new();
+        let run = |engine: &mut RLEngine| {
+            engine.search(&board, Color::White, None, Duration::from_secs(60), &mut |_| {}).map(|r| r.pv)
+        };
+        assert_eq!(run(&mut fork_a), run(&mut fork_b));
+    }
 
-        // If no safe king move, try blocking or capturing the threatening piece
-        if best_move.is_none() {
-            for &(threat_pos, target_pos) in &analysis.threats {
-                for (piece_pos, moves) in &analysis.piece_mobility {
-                    if *piece_pos != king_pos {
-                        for &to in moves {
-                            if to == threat_pos || to == target_pos {
-                                let mut board_copy = board.clone();
-                                if board_copy.move_piece(*piece_pos, to) {
-                                    let safety = self.evaluate_king_safety(&board_copy, king_pos, color, analysis);
-                                    if safety > best_safety {
-                                        best_safety = safety;
-                                        best_move = Some((*piece_pos, to));
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+    // Ra8 mates on the back rank; gxh3 wins the queen instead
+    #[test]
+    fn prefers_mate_over_material() {
+        let (board, color) = Board::from_fen("6k1/3p1ppp/8/8/8/7q/5PPP/R5K1 w - - 0 1").unwrap();
+        let mate = ((7, 0), (0, 0));
+        for seed in 0..3 {
+            let mut engine = RLEngine::with_seed(seed);
+            engine.set_max_simulations(Some(300));
+            let result = engine.search(&board, color, None, Duration::from_secs(60), &mut |_| {}).unwrap();
+            assert_eq!(result.best_move, mate, "seed {}", seed);
+            assert_eq!(mate_in(result.score), Some(1));
         }
+    }
+
+    // root branches are valued for white, who chooses between them, so the
+    // mate is the best of them and worth more than nothing
+    #[test]
+    fn values_are_from_the_movers_side() {
+        let (board, color) = Board::from_fen("6k1/3p1ppp/8/8/8/7q/5PPP/R5K1 w - - 0 1").unwrap();
+        let mut engine = RLEngine::with_seed(1);
+        engine.set_max_simulations(Some(300));
+        let result = engine.search(&board, color, None, Duration::from_secs(60), &mut |_| {}).unwrap();
+        let mate = result.tree.iter().find(|branch| branch.mv == ((7, 0), (0, 0))).unwrap();
+        assert!(mate.value > 0.0);
+        assert!(result.tree.iter().all(|branch| branch.value <= mate.value));
+    }
+
+    #[test]
+    fn mate_scores_count_moves() {
+        assert_eq!(from_parent(-WIN_SCORE), WIN_SCORE - 1.0);
+        assert_eq!(from_parent(WIN_SCORE - 1.0), -(WIN_SCORE - 2.0));
+        assert_eq!(format_score(WIN_SCORE - 1.0), "M1");
+        assert_eq!(format_score(-(WIN_SCORE - 2.0)), "-M1");
+        assert_eq!(format_score(WIN_SCORE - 5.0), "M3");
+        assert_eq!(format_score(35.0), "+0.35");
+        assert_eq!(format_score(white_score(WIN_SCORE - 1.0, Color::Black)), "-M1");
+        assert_eq!(format_score(white_score(-35.0, Color::Black)), "+0.35");
+        assert_eq!(mate_in(MATE_BOUND), None);
+    }
+
+    // a crowded position, slow to play out, still gets its move in on time
+    #[test]
+    fn keeps_to_its_time() {
+        let mut engine = RLEngine::with_seed(3);
+        let (board, color) =
+            Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w - - 0 1").unwrap();
+        let timeout = Duration::from_millis(50);
+        let started = std::time::Instant::now();
+        let result = engine.search(&board, color, None, timeout, &mut |_| {});
+        assert!(result.is_some());
+        assert!(started.elapsed() < timeout + Duration::from_millis(200));
+    }
+
+    // with all the time in the world, a depth limit is what ends the search
+    #[test]
+    fn stops_at_its_depth() {
+        let mut engine = RLEngine::with_seed(4);
+        engine.set_max_depth(Some(3));
+        let result = engine.search(&Board::new(), Color::White, None, Duration::MAX, &mut |_| {}).unwrap();
+        assert_eq!(result.pv.len(), 3);
+    }
+
+    // a tree far too small for the search keeps to its budget by recycling
+    // and still comes up with a move
+    #[test]
+    fn recycles_within_the_budget() {
+        let budget = 32 << 10;
+        let mut engine = RLEngine::with_seed(5);
+        engine.set_max_simulations(Some(1000));
+        engine.set_hash_budget(Some(budget));
+        let board = Board::new();
+        let result = engine.search(&board, Color::White, None, Duration::from_secs(60), &mut |_| {});
+        assert!(engine.current_stats.recycled > 0);
+        assert!(engine.current_stats.memory_bytes <= budget);
+        assert!(board.legal_moves(Color::White).find(result.unwrap().best_move).is_some());
+    }
+
+    // a quiet move that was best at a ply goes ahead of the other quiet
+    // moves there, but not ahead of a capture
+    #[test]
+    fn killers_move_up() {
+        let (board, color) = Board::from_fen("4k3/8/8/3p4/8/2N5/8/4K3 w - - 0 1").unwrap();
+        let mut engine = RLEngine::with_seed(6);
+        let killer = ((5, 2), (7, 1));
+        let mut ordering = MoveOrdering::default();
+        ordering.record(3, color, killer);
+        engine.ordering = Some(ordering);
+        let mut moves = engine.scored_moves(&board, color);
+        engine.order_moves(&mut moves, &board, color, 3);
+        let order: Vec<MovePair> = moves.iter().map(|(mv, _)| *mv).collect();
+        assert_eq!(order[0], ((5, 2), (3, 3)));
+        assert_eq!(order[1], killer);
+    }
+
+    // Rxd5 wins a pawn when nothing takes back, loses the rook for it when
+    // the c6 pawn does, and gets it back with the queen behind the rook
+    #[test]
+    fn exchanges_count_the_recaptures() {
+        let engine = RLEngine::new();
+        let take = ((4, 3), (3, 3));
+        let (board, _) = Board::from_fen("4k3/8/8/3p4/3R4/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(engine.see(&board, take.0, take.1), 100);
+        let (board, _) = Board::from_fen("4k3/8/2p5/3p4/3R4/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(engine.see(&board, take.0, take.1), -400);
+        let (board, _) = Board::from_fen("3rk3/8/8/3p4/3R4/3Q4/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(engine.see(&board, take.0, take.1), 100);
+    }
+
+    // a knight up and scored two pawns better than the count says: the
+    // knight's value goes up, and only once learning is on
+    #[test]
+    fn learns_piece_values() {
+        let (board, color) = Board::from_fen("4k3/8/8/8/8/8/8/1N2K3 w - - 0 1").unwrap();
+        let mut engine = RLEngine::new();
+        engine.set_piece_values(PieceValues::Kaufman);
+        assert_eq!(engine.piece_value(PieceType::Knight), 325);
+        engine.update_piece_values(&board, color, 525.0);
+        assert_eq!(engine.piece_value(PieceType::Knight), 325);
+        engine.set_learn_piece_values(true);
+        engine.update_piece_values(&board, color, 525.0);
+        assert_eq!(engine.piece_value(PieceType::Knight), 327);
+        assert_eq!(engine.piece_value(PieceType::Pawn), 100);
+    }
+
+    // a playout out of plies still answers a check before it's scored
+    #[test]
+    fn playouts_extend_on_check() {
+        let mut engine = RLEngine::with_seed(8);
+        engine.simulation_depth = 0;
+        let (board, color) = Board::from_fen("4k3/8/8/8/8/8/8/r3K3 w - - 0 1").unwrap();
+        let mut plies = 0;
+        engine.simulate(&board, color, 0, &mut plies);
+        assert_eq!(plies, 1);
+        let (board, color) = Board::from_fen("4k3/8/8/8/8/8/r7/4K3 w - - 0 1").unwrap();
+        let mut plies = 0;
+        engine.simulate(&board, color, 0, &mut plies);
+        assert_eq!(plies, 0);
+    }
+
+    // six pawns of material away from where the playout started, and still
+    // after the reply: it's over and lost for the side that's behind,
+    // unless the cutoff is off
+    #[test]
+    fn playouts_end_on_a_material_swing() {
+        let (board, color) = Board::from_fen("4k3/pppp4/8/8/8/8/PPPP4/4K3 w - - 0 1").unwrap();
+        let mut engine = RLEngine::with_seed(9);
+        engine.simulation_depth = 40;
+        let start = engine.get_material_balance(&board, Color::White) + 600;
+        let mut plies = 0;
+        let score = engine.playout(&board, color, 40, &mut plies, start, true);
+        assert_eq!(plies, 0);
+        assert_eq!(score, -to_centipawns(MAX_VALUE));
+        let score = engine.playout(&board, color.opposite(), 40, &mut plies, start, true);
+        assert_eq!(score, to_centipawns(MAX_VALUE));
+        engine.set_rollout_cutoff(None);
+        let mut plies = 0;
+        engine.playout(&board, color, 40, &mut plies, start, true);
+        assert!(plies > 0);
+    }
+
+    #[test]
+    fn packed_moves_round_trip() {
+        for mv in [((0, 0), (7, 7)), ((6, 4), (4, 4)), ((7, 6), (5, 5))] {
+            assert_eq!(unpack(pack(mv)), mv);
+        }
+    }
 
-        best_move
+    // the rook roller mates in two, and the search should prove it
+    #[test]
+    fn finds_mate_in_two() {
+        let (board, color) = Board::from_fen("7k/8/8/8/8/8/8/RR5K w - - 0 1").unwrap();
+        let mut engine = RLEngine::with_seed(0);
+        engine.set_max_simulations(Some(5000));
+        let result = engine.search(&board, color, None, Duration::from_secs(60), &mut |_| {}).unwrap();
+        assert_eq!(mate_in(result.score), Some(2));
     }
 }
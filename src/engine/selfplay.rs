@@ -0,0 +1,236 @@
+use crate::engine::curriculum::Scripted;
+use crate::engine::rl::{mate_in, white_score, MovePair, RLEngine};
+use crate::engine::strength::Limited;
+use crate::game::board::Board;
+use crate::game::movement::Move;
+use crate::game::piece::Color;
+use crate::game::record::GameRecord;
+use crate::game::result::{is_threefold, GameResult};
+use crate::game::variant::Variant;
+use crate::json_events::{self, Event};
+use crate::session;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// when to call an engine game early. the rules don't know every way a game
+// can end yet, so without these a self-play game can shuffle on forever
+#[derive(Clone, Copy, Debug)]
+pub struct Adjudication {
+    // a win once the referee's eval stays past this for `win_plies` plies
+    pub win_score: f32,
+    pub win_plies: u32,
+    // a draw once the eval stays within this for `draw_plies` plies,
+    // counted only after `draw_after` plies
+    pub draw_score: f32,
+    pub draw_plies: u32,
+    pub draw_after: usize,
+    // a draw once the game is this long
+    pub max_plies: usize,
+}
+
+impl Default for Adjudication {
+    fn default() -> Self {
+        Adjudication {
+            win_score: 1000.0,
+            win_plies: 6,
+            draw_score: 30.0,
+            draw_plies: 20,
+            draw_after: 80,
+            max_plies: 300,
+        }
+    }
+}
+
+// keeps the streaks between plies
+struct Adjudicator {
+    rules: Adjudication,
+    // plies in a row won for white (positive) or black (negative)
+    win_streak: i32,
+    flat_streak: u32,
+}
+
+impl Adjudicator {
+    fn new(rules: Adjudication) -> Self {
+        Adjudicator { rules, win_streak: 0, flat_streak: 0 }
+    }
+
+    // `eval` is from white's side, after ply number `ply`
+    fn check(&mut self, eval: f32, ply: usize) -> Option<GameResult> {
+        let rules = &self.rules;
+        self.win_streak = if eval >= rules.win_score {
+            self.win_streak.max(0) + 1
+        } else if eval <= -rules.win_score {
+            self.win_streak.min(0) - 1
+        } else {
+            0
+        };
+        if self.win_streak.unsigned_abs() >= rules.win_plies {
+            let winner = if self.win_streak > 0 { Color::White } else { Color::Black };
+            return Some(GameResult::Adjudicated { winner: Some(winner), reason: "adjudicated win" });
+        }
+
+        if ply >= rules.draw_after && eval.abs() <= rules.draw_score {
+            self.flat_streak += 1;
+        } else {
+            self.flat_streak = 0;
+        }
+        if self.flat_streak >= rules.draw_plies {
+            return Some(GameResult::Adjudicated { winner: None, reason: "adjudicated draw" });
+        }
+
+        (ply >= rules.max_plies).then_some(GameResult::Adjudicated { winner: None, reason: "move limit" })
+    }
+}
+
+// who makes one side's moves: an engine, which learns as it plays, or a
+// scripted opponent
+pub enum Player<'a> {
+    Engine(&'a mut RLEngine),
+    // an engine held to a rating, see engine::strength
+    Limited(&'a mut RLEngine, &'a mut Limited),
+    Scripted(&'a mut Scripted),
+}
+
+pub struct SelfPlayGame {
+    pub result: GameResult,
+    pub record: GameRecord,
+}
+
+// plays one game from the end of `opening`, each engine thinking
+// `think_time` a move. a plain engine
+// referees the adjudication so the players' personalities don't sway it,
+// with a fixed seed so it judges a seeded match the same way
+pub fn play_game<'a>(
+    mut white: Player<'a>,
+    mut black: Player<'a>,
+    variant: Arc<dyn Variant>,
+    opening: &[MovePair],
+    think_time: Duration,
+    rules: Adjudication,
+) -> SelfPlayGame {
+    let mut referee = RLEngine::with_seed(0);
+    referee.set_variant(variant.clone());
+    for player in [&mut white, &mut black] {
+        if let Player::Engine(engine) | Player::Limited(engine, _) = player {
+            engine.set_variant(variant.clone());
+        }
+    }
+
+    let mut adjudicator = Adjudicator::new(rules);
+    let mut board = variant.initial_board();
+    let mut record = GameRecord::new(board.clone(), Color::White);
+    for &(from, to) in opening {
+        let mv = Move::new(&board, from, to);
+        variant.make_move(&mut board, from, to);
+        record.push(mv, record.to_move(), board.clone());
+    }
+    json_events::emit(Event::GameStart {
+        variant: variant.name(),
+        opening: record.plies.iter().map(|ply| ply.mv.to_string()).collect(),
+    });
+
+    let result = loop {
+        let to_move = record.to_move();
+        if let Some(result) = variant.result(&board, to_move) {
+            break result;
+        }
+        if is_threefold(record.positions()) {
+            break GameResult::Repetition;
+        }
+
+        let player = if to_move == Color::White { &mut white } else { &mut black };
+        let started = Instant::now();
+        let mv = match player {
+            Player::Engine(engine) => engine_move(engine, None, variant.as_ref(), &board, &record, think_time),
+            Player::Limited(engine, limited) => {
+                engine_move(engine, Some(limited), variant.as_ref(), &board, &record, think_time)
+            }
+            Player::Scripted(scripted) => scripted.pick(variant.as_ref(), &board, to_move),
+        };
+        let Some(mv) = mv else {
+            break GameResult::Adjudicated { winner: Some(to_move.opposite()), reason: "no move found" };
+        };
+        variant.make_move(&mut board, mv.from, mv.to);
+        if let Player::Engine(engine) | Player::Limited(engine, _) = player {
+            let score = engine.evaluate_position(&board, to_move);
+            engine.update_position_values(&board, to_move, score);
+            engine.update_piece_values(&board, to_move, score);
+        }
+
+        let eval = referee.evaluate_position(&board, Color::White);
+        let ply = record.push(mv, to_move, board.clone());
+        ply.clock = Some(started.elapsed());
+        ply.eval = Some(eval);
+        let san = ply.san.clone();
+        json_events::emit(Event::Move {
+            ply: record.len(),
+            color: to_move.name(),
+            uci: mv.to_string(),
+            san,
+            eval,
+            clock_ms: started.elapsed().as_millis(),
+        });
+        if let Some(result) = adjudicator.check(eval, record.len()) {
+            break result;
+        }
+    };
+
+    json_events::emit(Event::GameEnd {
+        result: result.score(),
+        winner: result.winner().map(Color::name),
+        termination: result.termination(),
+        plies: record.len(),
+    });
+    session::engine_game();
+    SelfPlayGame { result, record }
+}
+
+// the same search the bot runs in the UI, fog of war included, and the
+// same choice among its moves when it's `limited`
+fn engine_move(
+    engine: &mut RLEngine,
+    limited: Option<&mut Limited>,
+    variant: &dyn Variant,
+    board: &Board,
+    record: &GameRecord,
+    think_time: Duration,
+) -> Option<Move> {
+    let color = record.to_move();
+    let legal = variant.moves(board, color);
+    let (view, search_moves) = if variant.hides_pieces() {
+        engine.set_history(Vec::new());
+        (variant.view(board, color), Some(legal.pairs()))
+    } else {
+        engine.set_history(record.with_sides());
+        (board.clone(), None)
+    };
+    engine
+        .search(&view, color, search_moves.as_deref(), think_time, &mut |_| {})
+        .inspect(|result| {
+            json_events::emit(Event::Eval {
+                color: color.name(),
+                score: white_score(result.score, color),
+                mate: mate_in(white_score(result.score, color)),
+                depth: result.pv.len(),
+                simulations: engine.current_stats.total_simulations,
+                pv: line_text(&view, &result.pv),
+            });
+        })
+        .and_then(|result| {
+            let chosen = limited.and_then(|limited| limited.pick(&result.tree)).map(|branch| branch.mv);
+            legal.find(chosen.unwrap_or(result.best_move))
+        })
+        .or_else(|| legal.first().copied())
+}
+
+// a searched line in coordinates, played out from `board` to know each move
+fn line_text(board: &Board, line: &[MovePair]) -> Vec<String> {
+    let mut board = board.clone();
+    line.iter()
+        .map(|&(from, to)| {
+            let mv = Move::new(&board, from, to);
+            board.move_piece(from, to);
+            mv.to_string()
+        })
+        .collect()
+}
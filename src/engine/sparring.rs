@@ -0,0 +1,45 @@
+use crate::engine::rl::{mate_in, Branch, MovePair};
+use rand::Rng;
+
+// the most a sparring move may give up on the best one, in centipawns.
+// past that it's a blunder, and a blunder teaches nothing
+const MAX_LOSS: f32 = 150.0;
+// how often the bot plays a lesser move against a player rated at or
+// below the first, and at or above the second; a straight line between
+const WEAKEST: (f32, f32) = (600.0, 0.4);
+const STRONGEST: (f32, f32) = (2000.0, 0.05);
+
+pub fn mistake_chance(target: f32) -> f32 {
+    let t = ((target - WEAKEST.0) / (STRONGEST.0 - WEAKEST.0)).clamp(0.0, 1.0);
+    WEAKEST.1 + (STRONGEST.1 - WEAKEST.1) * t
+}
+
+// now and then the second or third best move in place of the best, more
+// often the weaker `target` is. only moves that hold up reasonably well
+// qualify, and none that `hangs_mate`. None to play the best move
+pub fn pick<'a>(
+    tree: &'a [Branch],
+    target: f32,
+    rng: &mut impl Rng,
+    hangs_mate: impl Fn(MovePair) -> bool,
+) -> Option<&'a Branch> {
+    let best = tree.first()?;
+    // with a mate on the board either way, there's nothing to learn from
+    // missing it
+    if mate_in(best.value).is_some() || !rng.gen_bool(mistake_chance(target) as f64) {
+        return None;
+    }
+    let candidates: Vec<&Branch> = tree
+        .iter()
+        .skip(1)
+        .take(2)
+        .filter(|b| b.visits > 0 && mate_in(b.value).is_none() && best.value - b.value <= MAX_LOSS)
+        .filter(|b| !hangs_mate(b.mv))
+        .collect();
+    // the second best twice as often as the third
+    match candidates[..] {
+        [] => None,
+        [only] => Some(only),
+        [second, third, ..] => Some(if rng.gen_bool(2.0 / 3.0) { second } else { third }),
+    }
+}
@@ -0,0 +1,251 @@
+use crate::engine::curriculum::{Opponent, Scripted};
+use crate::engine::rl::{mate_in, Branch, RLEngine};
+use crate::engine::selfplay::{self, Adjudication, Player};
+use crate::game::openings;
+use crate::game::piece::Color;
+use crate::game::variant;
+use crate::utils::data_dir;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+// what the setup screen's slider and `elo` go between, and by
+pub const MIN_TARGET: u32 = 400;
+pub const MAX_TARGET: u32 = 2000;
+pub const TARGET_STEP: u32 = 100;
+
+// what each scripted opponent is taken to be rated. they're the fixed
+// points everything else is measured against, so only differences
+// between ratings on this scale mean much
+pub const REFERENCES: [(Opponent, f32); 4] = [
+    (Opponent::Random, 250.0),
+    (Opponent::Greedy, 600.0),
+    (Opponent::OnePly, 850.0),
+    (Opponent::AlphaBeta, 1200.0),
+];
+
+// the ladder, weakest first: simulations a move, how many centipawns a
+// move may give up on the best one, and a guess at the rating, which
+// `chessrl calibrate` replaces with a measured one
+const LADDER: [(u32, f32, f32); 8] = [
+    (25, 300.0, 500.0),
+    (50, 200.0, 700.0),
+    (100, 150.0, 900.0),
+    (200, 100.0, 1100.0),
+    (400, 60.0, 1300.0),
+    (1000, 30.0, 1500.0),
+    (3000, 10.0, 1700.0),
+    (10000, 0.0, 1900.0),
+];
+
+// searching slower than this, a move runs into the time cap before the
+// simulation cap; it keeps a slow machine from sitting on a weak move
+const MIN_SIMULATIONS_PER_SEC: f32 = 1000.0;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Rung {
+    pub simulations: u32,
+    pub max_loss: f32,
+    pub elo: f32,
+}
+
+// how the bot holds itself to a rating: a cap on its search, and moves
+// nearly as good as the best one played now and then in its place
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Limit {
+    pub simulations: u32,
+    pub think_time: Duration,
+    pub max_loss: f32,
+}
+
+impl Limit {
+    fn new(simulations: u32, max_loss: f32) -> Self {
+        let secs = (simulations as f32 / MIN_SIMULATIONS_PER_SEC).clamp(0.25, 5.0);
+        Limit { simulations, think_time: Duration::from_secs_f32(secs), max_loss }
+    }
+}
+
+// the ladder and the rating of each rung, kept in <data dir>/strength.json
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Calibration {
+    // when it was measured; 0 for the built-in guesses
+    pub unix_time: u64,
+    pub rungs: Vec<Rung>,
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Calibration {
+            unix_time: 0,
+            rungs: LADDER
+                .iter()
+                .map(|&(simulations, max_loss, elo)| Rung { simulations, max_loss, elo })
+                .collect(),
+        }
+    }
+}
+
+impl Calibration {
+    fn path() -> Option<PathBuf> {
+        data_dir().map(|dir| dir.join("strength.json"))
+    }
+
+    // the built-in guesses when nothing has been measured, or what was
+    // measured won't read
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|text| serde_json::from_str::<Calibration>(&text).ok())
+            .filter(|calibration| !calibration.rungs.is_empty())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<PathBuf> {
+        let path = Self::path().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = serde_json::to_string_pretty(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(&path, text)?;
+        Ok(path)
+    }
+
+    pub fn measured(&self) -> bool {
+        self.unix_time > 0
+    }
+
+    // the limit for a bot rated about `target`: the weakest rung that
+    // reaches it, eased toward the one below by how far short that falls.
+    // simulations go geometrically, the way strength does with them
+    pub fn limit(&self, target: f32) -> Limit {
+        let Some(idx) = self.rungs.iter().position(|rung| rung.elo >= target) else {
+            let top = self.rungs.last().copied().unwrap_or_else(|| Calibration::default().rungs[LADDER.len() - 1]);
+            return Limit::new(top.simulations, top.max_loss);
+        };
+        let upper = self.rungs[idx];
+        let Some(lower) = idx.checked_sub(1).map(|below| self.rungs[below]).filter(|lower| lower.elo < upper.elo) else {
+            return Limit::new(upper.simulations, upper.max_loss);
+        };
+        let t = (target - lower.elo) / (upper.elo - lower.elo);
+        let simulations = (lower.simulations as f32).ln() + t * ((upper.simulations as f32).ln() - (lower.simulations as f32).ln());
+        Limit::new(simulations.exp().round() as u32, lower.max_loss + t * (upper.max_loss - lower.max_loss))
+    }
+}
+
+// one of the root moves that give up no more than `limit` allows, the
+// better ones more likely. moves the search has seen lose to a mate don't
+// qualify, and with a mate on the board for the bot it plays it
+pub fn pick<'a>(tree: &'a [Branch], limit: &Limit, rng: &mut impl Rng) -> Option<&'a Branch> {
+    let best = tree.first()?;
+    if mate_in(best.value).is_some() {
+        return Some(best);
+    }
+    let candidates: Vec<&Branch> = tree
+        .iter()
+        .filter(|b| b.visits > 0 && mate_in(b.value).is_none_or(|moves| moves >= 0))
+        .filter(|b| best.value - b.value <= limit.max_loss)
+        .collect();
+    candidates
+        .choose_weighted(rng, |b| limit.max_loss + 1.0 - (best.value - b.value).max(0.0))
+        .ok()
+        .copied()
+        .or(Some(best))
+}
+
+// an engine held to a limit, for playing it outside the game screen
+pub struct Limited {
+    pub limit: Limit,
+    rng: StdRng,
+}
+
+impl Limited {
+    pub fn new(limit: Limit, seed: Option<u64>) -> Self {
+        Limited { limit, rng: seed.map_or_else(StdRng::from_entropy, StdRng::seed_from_u64) }
+    }
+
+    pub fn pick<'a>(&mut self, tree: &'a [Branch]) -> Option<&'a Branch> {
+        pick(tree, &self.limit, &mut self.rng)
+    }
+}
+
+// one game of a calibration run, as the runner reports it
+pub struct CalibrationGame {
+    pub rung: usize,
+    pub opponent: Opponent,
+    pub engine_color: Color,
+    // the engine's
+    pub score: f32,
+    pub termination: &'static str,
+}
+
+// plays every rung of the ladder against each reference opponent `games`
+// times, from book openings with the colors taken in turn, and rates each
+// rung by its performance. a stronger rung never comes out rated below a
+// weaker one
+pub fn calibrate(games: usize, seed: Option<u64>, on_game: &mut dyn FnMut(&CalibrationGame)) -> Calibration {
+    let variant = variant::by_name("standard").expect("standard is always there");
+    let suite = openings::suite();
+    let mut calibration = Calibration {
+        unix_time: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+        rungs: Vec::new(),
+    };
+    let mut played = 0u64;
+    for (rung, &(simulations, max_loss, _)) in LADDER.iter().enumerate() {
+        let limit = Limit::new(simulations, max_loss);
+        let mut results = Vec::new();
+        for &(opponent, rating) in &REFERENCES {
+            for game in 0..games {
+                let seed = seed.map(|seed| seed.wrapping_add(2 * played));
+                played += 1;
+                let mut engine = seed.map_or_else(RLEngine::new, RLEngine::with_seed);
+                engine.set_max_simulations(Some(limit.simulations));
+                let mut limited = Limited::new(limit, seed);
+                let mut scripted = Scripted::new(opponent, seed.map(|seed| seed + 1));
+                let engine_color = if game % 2 == 0 { Color::White } else { Color::Black };
+                let (white, black) = match engine_color {
+                    Color::White => (Player::Limited(&mut engine, &mut limited), Player::Scripted(&mut scripted)),
+                    Color::Black => (Player::Scripted(&mut scripted), Player::Limited(&mut engine, &mut limited)),
+                };
+                let (_, opening) = &suite[(game / 2) % suite.len()];
+                let result = selfplay::play_game(white, black, variant.clone(), opening, limit.think_time, Adjudication::default());
+                let score = match result.result.winner() {
+                    Some(winner) if winner == engine_color => 1.0,
+                    Some(_) => 0.0,
+                    None => 0.5,
+                };
+                on_game(&CalibrationGame { rung, opponent, engine_color, score, termination: result.result.termination() });
+                results.push((rating, score));
+            }
+        }
+        let floor = calibration.rungs.last().map_or(f32::MIN, |below: &Rung| below.elo);
+        calibration.rungs.push(Rung { simulations, max_loss, elo: performance(&results).max(floor) });
+    }
+    calibration
+}
+
+// the rating at which the expected score against these opponents matches
+// what was scored. a clean sweep either way lands 400 past the opponents
+fn performance(results: &[(f32, f32)]) -> f32 {
+    let scored: f32 = results.iter().map(|(_, score)| score).sum();
+    let (mut low, mut high) = (
+        results.iter().map(|(rating, _)| *rating).fold(f32::MAX, f32::min) - 400.0,
+        results.iter().map(|(rating, _)| *rating).fold(f32::MIN, f32::max) + 400.0,
+    );
+    for _ in 0..50 {
+        let mid = (low + high) / 2.0;
+        let expected: f32 = results.iter().map(|(rating, _)| 1.0 / (1.0 + 10f32.powf((rating - mid) / 400.0))).sum();
+        if expected < scored {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    (low + high) / 2.0
+}
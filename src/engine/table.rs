@@ -0,0 +1,57 @@
+use std::mem::size_of;
+
+// one key and one score
+const SLOT_BYTES: usize = size_of::<u64>() + size_of::<f32>();
+
+// static evals already worked out this search, by Zobrist hash, so a
+// position reached again by another line or playout isn't evaluated twice.
+// fixed in size from the start: a new position takes its slot from
+// whatever was there. keys and scores are kept apart, all zeros to start
+// with, so a new table costs nothing until it's used
+#[derive(Clone)]
+pub struct EvalTable {
+    keys: Vec<u64>,
+    scores: Vec<f32>,
+    filled: usize,
+    pub probes: u64,
+    pub hits: u64,
+}
+
+impl EvalTable {
+    pub fn new(bytes: usize) -> Self {
+        let slots = (bytes / SLOT_BYTES).max(1);
+        EvalTable { keys: vec![0; slots], scores: vec![0.0; slots], filled: 0, probes: 0, hits: 0 }
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.keys.len() * SLOT_BYTES
+    }
+
+    // how much of it is in use, 0 to 1
+    pub fn fill(&self) -> f32 {
+        self.filled as f32 / self.keys.len() as f32
+    }
+
+    // a key of 0 marks an empty slot, so the one position hashing to it
+    // never gets a hit
+    pub fn probe(&mut self, key: u64) -> Option<f32> {
+        self.probes += 1;
+        let slot = self.slot(key);
+        let hit = key != 0 && self.keys[slot] == key;
+        self.hits += hit as u64;
+        hit.then_some(self.scores[slot])
+    }
+
+    pub fn store(&mut self, key: u64, score: f32) {
+        let slot = self.slot(key);
+        if self.keys[slot] == 0 {
+            self.filled += 1;
+        }
+        self.keys[slot] = key;
+        self.scores[slot] = score;
+    }
+
+    fn slot(&self, key: u64) -> usize {
+        (key % self.keys.len() as u64) as usize
+    }
+}
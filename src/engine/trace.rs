@@ -0,0 +1,133 @@
+use crate::engine::rl::{format_score, Branch, MovePair, SearchResult, SimulationStats};
+use crate::game::board::Board;
+use crate::game::notation::san;
+use crate::game::piece::Color;
+use crate::game::variant::Variant;
+use crate::utils::{data_dir, pgn_date_time};
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+// root moves written out, most visited first
+const TRACE_MOVES: usize = 8;
+
+// a finished bot search, kept so it can be written out afterwards
+pub struct SearchTrace {
+    pub board: Board,
+    pub color: Color,
+    pub result: SearchResult,
+    pub stats: SimulationStats,
+    // the game up to the searched position, in SAN
+    pub moves: Vec<String>,
+}
+
+impl SearchTrace {
+    pub fn render(&self, variant: &dyn Variant) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "variant: {}", variant.title());
+        let _ = writeln!(out, "moves: {}", numbered(&self.moves));
+        let _ = writeln!(out, "to move: {:?}", self.color);
+        let _ = writeln!(out, "fen: {}", self.board.fen(self.color, self.moves.len() / 2 + 1));
+        out.push('\n');
+        out.push_str(&diagram(&self.board));
+        out.push('\n');
+
+        let stats = &self.stats;
+        let _ = writeln!(
+            out,
+            "simulations: {}  nodes: {}  depth: {}  confidence: {:.0}%",
+            stats.total_simulations,
+            stats.nodes_explored,
+            stats.depth_reached,
+            stats.best_move_confidence * 100.0
+        );
+        let _ = writeln!(
+            out,
+            "played: {} ({})",
+            san(&self.board, self.result.best_move),
+            format_score(self.result.score)
+        );
+        let _ = writeln!(out, "pv: {}", line(variant, &self.board, &self.result.pv));
+        out.push('\n');
+
+        let _ = writeln!(out, "most visited moves, valued in pawns for the side making them:");
+        let _ = writeln!(out, "{:<8} {:>7} {:>7}  line", "move", "visits", "value");
+        for branch in self.result.tree.iter().take(TRACE_MOVES) {
+            write_branch(&mut out, variant, &self.board, branch, 0);
+        }
+        out
+    }
+}
+
+fn write_branch(out: &mut String, variant: &dyn Variant, board: &Board, branch: &Branch, indent: usize) {
+    let name = format!("{}{}", "  ".repeat(indent), san(board, branch.mv));
+    let _ = writeln!(
+        out,
+        "{:<8} {:>7} {:>7}  {}",
+        name,
+        branch.visits,
+        format_score(branch.value),
+        line(variant, board, &branch.pv)
+    );
+    let mut after = board.clone();
+    variant.make_move(&mut after, branch.mv.0, branch.mv.1);
+    for reply in &branch.replies {
+        write_branch(out, variant, &after, reply, indent + 1);
+    }
+}
+
+// the moves in SAN, played out from `board`
+fn line(variant: &dyn Variant, board: &Board, moves: &[MovePair]) -> String {
+    let mut board = board.clone();
+    let mut sans = Vec::new();
+    for &(from, to) in moves {
+        sans.push(san(&board, (from, to)));
+        if !variant.make_move(&mut board, from, to) {
+            break;
+        }
+    }
+    sans.join(" ")
+}
+
+fn numbered(moves: &[String]) -> String {
+    if moves.is_empty() {
+        return "(start)".to_string();
+    }
+    moves
+        .chunks(2)
+        .enumerate()
+        .map(|(i, pair)| format!("{}. {}", i + 1, pair.join(" ")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// letters, white in capitals, rank 8 on top
+fn diagram(board: &Board) -> String {
+    let mut out = String::new();
+    for rank in 0..8 {
+        let _ = write!(out, "{} ", 8 - rank);
+        for file in 0..8 {
+            let c = board.get_piece((rank, file)).map_or('.', |piece| piece.letter());
+            let _ = write!(out, " {}", c);
+        }
+        out.push('\n');
+    }
+    out.push_str("   a b c d e f g h\n");
+    out
+}
+
+// writes the trace to its own file in the data directory and returns the path
+pub fn save(trace: &SearchTrace, variant: &dyn Variant) -> io::Result<PathBuf> {
+    let dir = data_dir()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?
+        .join("search");
+    fs::create_dir_all(&dir)?;
+    let unix_secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let (date, time) = pgn_date_time(unix_secs);
+    let path = dir.join(format!("{}-{}.txt", date.replace('.', ""), time.replace(':', "")));
+    fs::write(&path, trace.render(variant))?;
+    Ok(path)
+}
@@ -0,0 +1,234 @@
+use crate::engine::rl::MovePair;
+use crate::engine::worker;
+use crate::logging;
+use crate::utils::parse_coordinate;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+// [uci] in config.toml: an external engine such as stockfish, to play
+// against or to check the bot's evaluations with
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UciConfig {
+    // the engine's executable, found on PATH if it isn't a path
+    pub path: Option<String>,
+    pub args: Vec<String>,
+    // sent as `setoption name <key> value <value>`, e.g. "Skill Level" = "5"
+    pub options: BTreeMap<String, String>,
+    // seconds per move as an opponent; 0 thinks as long as the bot would
+    pub think_time: f32,
+    // its eval of every position beside the bot's in the analytics panel
+    pub second_opinion: bool,
+}
+
+impl UciConfig {
+    pub fn think_time(&self) -> Option<Duration> {
+        (self.think_time > 0.0).then(|| Duration::from_secs_f32(self.think_time))
+    }
+}
+
+// a score as UCI gives it, from the side to move's point of view
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UciScore {
+    Centipawns(i32),
+    // moves to mate, negative when it's the side to move getting mated
+    Mate(i32),
+}
+
+impl UciScore {
+    pub fn negate(self) -> UciScore {
+        match self {
+            UciScore::Centipawns(cp) => UciScore::Centipawns(-cp),
+            UciScore::Mate(moves) => UciScore::Mate(-moves),
+        }
+    }
+
+    pub fn text(self) -> String {
+        match self {
+            UciScore::Centipawns(cp) => format!("{:+.2}", cp as f32 / 100.0),
+            UciScore::Mate(moves) => format!("#{}", moves),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct UciInfo {
+    pub depth: u32,
+    pub score: Option<UciScore>,
+    // in coordinates, as the engine sent them
+    pub pv: Vec<String>,
+}
+
+pub enum UciReply {
+    Info(UciInfo),
+    // the search's move; None when the engine had none to play
+    BestMove(Option<MovePair>),
+    // the process went away
+    Exited(String),
+}
+
+// the engine's process. what it says comes back through `notify` from a
+// thread of its own, tagged with `id`, so nothing waits on it
+pub struct UciEngine {
+    pub id: u64,
+    child: Child,
+    stdin: ChildStdin,
+    // what it calls itself, once it has said; the file name until then
+    name: Arc<Mutex<String>>,
+    // the positions sent with `go`, oldest first. every go gets exactly one
+    // bestmove, stopped searches included, so replies are matched up in order
+    pending: VecDeque<u64>,
+}
+
+impl UciEngine {
+    pub fn start(config: &UciConfig, notify: impl Fn(u64, UciReply) + Send + 'static) -> io::Result<UciEngine> {
+        let path = config
+            .path
+            .as_deref()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no engine set; add path under [uci] in the config"))?;
+        let mut child = Command::new(path)
+            .args(&config.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| io::Error::new(e.kind(), format!("couldn't run {}: {}", path, e)))?;
+        let stdin = child.stdin.take().ok_or_else(|| io::Error::other("no pipe to the engine"))?;
+        let stdout = child.stdout.take().ok_or_else(|| io::Error::other("no pipe from the engine"))?;
+        let file_name = Path::new(path).file_name().map_or(path.to_string(), |name| name.to_string_lossy().into_owned());
+        let name = Arc::new(Mutex::new(file_name));
+        let id = worker::next_search_id();
+
+        let named = name.clone();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if let Some(engine_name) = line.strip_prefix("id name ") {
+                    if let Ok(mut name) = named.lock() {
+                        *name = engine_name.trim().to_string();
+                    }
+                    continue;
+                }
+                if let Some(reply) = parse(&line) {
+                    notify(id, reply);
+                }
+            }
+            notify(id, UciReply::Exited("the engine exited".to_string()));
+        });
+
+        let mut engine = UciEngine { id, child, stdin, name, pending: VecDeque::new() };
+        engine.send("uci")?;
+        for (option, value) in &config.options {
+            engine.send(&format!("setoption name {} value {}", option, value))?;
+        }
+        engine.send("isready")?;
+        engine.send("ucinewgame")?;
+        Ok(engine)
+    }
+
+    pub fn name(&self) -> String {
+        self.name.lock().map_or_else(|_| "engine".to_string(), |name| name.clone())
+    }
+
+    fn send(&mut self, command: &str) -> io::Result<()> {
+        logging::debug(&format!("uci> {}", command));
+        writeln!(self.stdin, "{}", command)?;
+        self.stdin.flush()
+    }
+
+    // searches `fen` for `time`, stopping whatever it was searching before.
+    // `key` comes back from answered with the move
+    pub fn go(&mut self, key: u64, fen: &str, time: Duration) -> io::Result<()> {
+        if !self.pending.is_empty() {
+            self.send("stop")?;
+        }
+        self.send(&format!("position fen {}", fen))?;
+        self.send(&format!("go movetime {}", time.as_millis().max(1)))?;
+        self.pending.push_back(key);
+        Ok(())
+    }
+
+    // has the engine answer its latest go now; false if it wasn't searching
+    pub fn stop(&mut self) -> io::Result<bool> {
+        if self.pending.is_empty() {
+            return Ok(false);
+        }
+        self.send("stop")?;
+        Ok(true)
+    }
+
+    pub fn is_searching(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    // the position of the latest go
+    pub fn asked(&self) -> Option<u64> {
+        self.pending.back().copied()
+    }
+
+    // whether the info coming in is about the latest go, not one stopped
+    pub fn on_latest(&self) -> bool {
+        self.pending.len() <= 1
+    }
+
+    // for a bestmove, the key of the go it answers
+    pub fn answered(&mut self) -> Option<u64> {
+        self.pending.pop_front()
+    }
+}
+
+impl Drop for UciEngine {
+    fn drop(&mut self) {
+        let _ = self.send("quit");
+        // an engine that doesn't listen is stopped the hard way
+        thread::sleep(Duration::from_millis(50));
+        if !matches!(self.child.try_wait(), Ok(Some(_))) {
+            let _ = self.child.kill();
+        }
+        let _ = self.child.wait();
+    }
+}
+
+fn parse(line: &str) -> Option<UciReply> {
+    let mut words = line.split_whitespace();
+    match words.next()? {
+        "bestmove" => Some(UciReply::BestMove(words.next().and_then(parse_move))),
+        "info" => {
+            let mut info = UciInfo::default();
+            while let Some(word) = words.next() {
+                match word {
+                    "depth" => info.depth = words.next()?.parse().ok()?,
+                    "score" => {
+                        let kind = words.next()?;
+                        let value = words.next()?.parse().ok()?;
+                        info.score = match kind {
+                            "cp" => Some(UciScore::Centipawns(value)),
+                            "mate" => Some(UciScore::Mate(value)),
+                            _ => None,
+                        };
+                    }
+                    // the rest of the line is the line
+                    "pv" => info.pv = words.by_ref().map(str::to_string).collect(),
+                    // so a string's words aren't read as fields
+                    "string" => return None,
+                    _ => {}
+                }
+            }
+            info.score.is_some().then_some(UciReply::Info(info))
+        }
+        _ => None,
+    }
+}
+
+// a move like e2e4, or e7e8q, whose promotion the rules don't have yet
+pub fn parse_move(text: &str) -> Option<MovePair> {
+    let from = parse_coordinate(text.get(0..2)?)?;
+    let to = parse_coordinate(text.get(2..4)?)?;
+    Some((from, to))
+}
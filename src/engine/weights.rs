@@ -0,0 +1,267 @@
+use crate::engine::difficulty::BotConfig;
+use crate::engine::personality::Personality;
+use crate::engine::rl::RLEngine;
+use crate::game::piece::PieceType;
+use crate::utils::{data_dir, expand_home};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const PIECES: [PieceType; 6] = [
+    PieceType::Pawn,
+    PieceType::Knight,
+    PieceType::Bishop,
+    PieceType::Rook,
+    PieceType::Queen,
+    PieceType::King,
+];
+
+type Table = [[f64; 8]; 8];
+
+// everything the engine evaluates with, as written by --export-weights and
+// read by --weights. piece values are centipawns, tables are pawns from
+// white's side. a file to load may leave any of it out
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WeightsFile {
+    pub personality: Personality,
+    pub piece_values: BTreeMap<String, i32>,
+    // what each evaluation term is multiplied by
+    pub terms: BTreeMap<String, f64>,
+    pub tables: BTreeMap<String, PhaseTables>,
+    // how far the above are from a fresh balanced engine; only what differs.
+    // ignored when loading
+    pub changes: Changes,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PhaseTables {
+    pub middle: Table,
+    pub end: Table,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Changes {
+    pub piece_values: BTreeMap<String, i32>,
+    pub terms: BTreeMap<String, f64>,
+    pub tables: BTreeMap<String, PhaseTables>,
+}
+
+impl WeightsFile {
+    pub fn from_engine(engine: &RLEngine) -> Self {
+        let defaults = RLEngine::new();
+        let mut changes = Changes::default();
+
+        let mut piece_values = BTreeMap::new();
+        for piece in PIECES {
+            let value = engine.piece_value(piece);
+            piece_values.insert(piece.name().to_string(), value);
+            let change = value - defaults.piece_value(piece);
+            if change != 0 {
+                changes.piece_values.insert(piece.name().to_string(), change);
+            }
+        }
+
+        let mut terms = BTreeMap::new();
+        let stock = Personality::Balanced.weights().named();
+        for ((name, value), (_, default)) in engine.eval_weights().named().into_iter().zip(stock) {
+            terms.insert(name.to_string(), round(value));
+            if value != default {
+                changes.terms.insert(name.to_string(), round(value - default));
+            }
+        }
+
+        let mut tables = BTreeMap::new();
+        for piece in PIECES {
+            let [middle, end] = [false, true].map(|endgame| engine.position_table(piece, endgame));
+            let [initial_middle, initial_end] =
+                [false, true].map(|endgame| RLEngine::initial_position_table(piece, endgame));
+            let change = PhaseTables {
+                middle: difference(&middle, &initial_middle),
+                end: difference(&end, &initial_end),
+            };
+            if change.middle.iter().chain(&change.end).flatten().any(|v| *v != 0.0) {
+                changes.tables.insert(piece.name().to_string(), change);
+            }
+            tables.insert(
+                piece.name().to_string(),
+                PhaseTables { middle: middle.map(|row| row.map(round)), end: end.map(|row| row.map(round)) },
+            );
+        }
+
+        WeightsFile {
+            personality: engine.personality(),
+            piece_values,
+            terms,
+            tables,
+            changes,
+        }
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        let parsed = if is_json(path) {
+            serde_json::from_str(&text).map_err(|e| e.to_string())
+        } else {
+            toml::from_str(&text).map_err(|e| e.to_string())
+        };
+        parsed.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e)))
+    }
+
+    // puts the file's values into `engine` over the built-in ones. the
+    // personality stays whatever the game picked, but terms in the file win
+    // over its weights. nothing changes if any name is unknown
+    pub fn apply(&self, engine: &mut RLEngine) -> io::Result<()> {
+        let mut piece_values = Vec::new();
+        for (name, value) in &self.piece_values {
+            piece_values.push((piece(name)?, *value));
+        }
+        let mut tables = Vec::new();
+        for (name, phases) in &self.tables {
+            tables.push((piece(name)?, phases));
+        }
+        let terms = term_list(&self.terms)?;
+
+        engine.reset_weights();
+        for (piece, value) in piece_values {
+            engine.set_piece_value(piece, value);
+        }
+        for (piece, phases) in tables {
+            engine.set_position_table(piece, false, phases.middle.map(|row| row.map(|v| v as f32)));
+            engine.set_position_table(piece, true, phases.end.map(|row| row.map(|v| v as f32)));
+        }
+        engine.set_term_overrides(terms);
+        Ok(())
+    }
+
+    // JSON for a .json path, TOML for anything else
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let text = if is_json(path) {
+            serde_json::to_string_pretty(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+        } else {
+            let body = toml::to_string(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            format!(
+                "# chessrl engine weights: piece values in centipawns, tables in pawns from\n\
+                 # white's side, rank 8 first. [changes] is the difference from a fresh engine\n\n{}\n",
+                body.lines().map(board_rows).collect::<Vec<_>>().join("\n")
+            )
+        };
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, text)
+    }
+}
+
+fn piece(name: &str) -> io::Result<PieceType> {
+    PIECES
+        .into_iter()
+        .find(|p| p.name() == name)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("unknown piece '{}'", name)))
+}
+
+fn term_list(terms: &BTreeMap<String, f64>) -> io::Result<Vec<(String, f32)>> {
+    let mut list = Vec::new();
+    let mut check = Personality::Balanced.weights();
+    for (name, value) in terms {
+        if !check.set(name, *value as f32) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("unknown term '{}'", name)));
+        }
+        list.push((name.clone(), *value as f32));
+    }
+    Ok(list)
+}
+
+// puts [bot]'s piece values and term weights into `engine` over whatever
+// it was loaded with: the named set first, then single pieces, and terms
+// on top of a file's. nothing changes if any name is unknown
+pub fn apply_config(engine: &mut RLEngine, bot: &BotConfig) -> io::Result<()> {
+    let mut piece_values = Vec::new();
+    for (name, value) in &bot.values {
+        piece_values.push((piece(name)?, *value));
+    }
+    let terms = term_list(&bot.terms)?;
+
+    if let Some(values) = bot.piece_values {
+        engine.set_piece_values(values);
+    }
+    for (piece, value) in piece_values {
+        engine.set_piece_value(piece, value);
+    }
+    if !terms.is_empty() {
+        let mut overrides = engine.term_overrides().to_vec();
+        overrides.retain(|(name, _)| !terms.iter().any(|(term, _)| term == name));
+        overrides.extend(terms);
+        engine.set_term_overrides(overrides);
+    }
+    engine.set_learn_piece_values(bot.learn_values);
+    Ok(())
+}
+
+// a table is written on one line as an array of ranks; this puts each
+// rank on its own line so the file reads like a board
+fn board_rows(line: &str) -> String {
+    if !line.contains(" = [[") {
+        return line.to_string();
+    }
+    line.replace("[[", "[\n    [").replace("], [", "],\n    [").replace("]]", "],\n]")
+}
+
+// where Settings looks for weights files to pick from
+pub fn weights_dir() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("weights"))
+}
+
+// the files in weights_dir, by name
+pub fn available() -> Vec<String> {
+    let Some(entries) = weights_dir().and_then(|dir| fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| name.ends_with(".toml") || name.ends_with(".json"))
+        .collect();
+    names.sort();
+    names
+}
+
+// a bare file name is looked up in weights_dir, anything else is a path
+pub fn resolve(name: &str) -> PathBuf {
+    if name.contains('/') || name.starts_with('~') {
+        return expand_home(name);
+    }
+    weights_dir().map_or_else(|| PathBuf::from(name), |dir| dir.join(name))
+}
+
+// loads `name` into `engine`, or the built-in values for None
+pub fn load_into(engine: &mut RLEngine, name: Option<&str>) -> io::Result<()> {
+    match name {
+        Some(name) => WeightsFile::load(&resolve(name))?.apply(engine),
+        None => {
+            engine.reset_weights();
+            Ok(())
+        }
+    }
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+// three decimals are plenty, and keep f32 noise like 0.8500000238 out of the file
+fn round(value: f32) -> f64 {
+    (value as f64 * 1000.0).round() / 1000.0
+}
+
+fn difference(learned: &[[f32; 8]; 8], initial: &[[f32; 8]; 8]) -> Table {
+    let mut table = [[0.0; 8]; 8];
+    for rank in 0..8 {
+        for file in 0..8 {
+            table[rank][file] = round(learned[rank][file] - initial[rank][file]);
+        }
+    }
+    table
+}
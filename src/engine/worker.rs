@@ -0,0 +1,186 @@
+use crate::engine::rl::{MovePair, PvLine, RLEngine, SearchResult, SimulationStats};
+use crate::game::{board::Board, piece::Color};
+use crate::game::movement::coordinates;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub enum SearchUpdate {
+    Progress(SimulationStats),
+    Finished(Option<SearchResult>, SimulationStats),
+}
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+// ids for searches run some other way, e.g. by a UCI engine, so they
+// can't be mistaken for one of these
+pub fn next_search_id() -> u64 {
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+// a search running on its own thread so the UI keeps drawing meanwhile.
+// updates go to `notify` along with the search's id
+pub struct SearchHandle {
+    pub id: u64,
+    pub started: Instant,
+    stop: Arc<AtomicBool>,
+}
+
+impl SearchHandle {
+    // the engine is a copy; searching doesn't change what it has learned.
+    // `search_moves` limits the root moves, see RLEngine::search. with more
+    // than one thread, each searches the whole position with its own copy
+    // and the trees are merged, see SearchResult::merge
+    pub fn spawn(
+        mut engine: RLEngine,
+        board: Board,
+        color: Color,
+        search_moves: Option<Vec<MovePair>>,
+        time: Duration,
+        threads: usize,
+        notify: impl Fn(u64, SearchUpdate) + Send + 'static,
+    ) -> Self {
+        let id = next_search_id();
+        let stop = Arc::new(AtomicBool::new(false));
+        engine.set_stop(Some(stop.clone()));
+        engine.set_hash_budget(engine.hash_budget().map(|bytes| bytes / threads.max(1)));
+        let helpers: Vec<RLEngine> = (1..threads).map(|_| engine.fork()).collect();
+        let multi_pv = engine.multi_pv();
+        thread::spawn(move || {
+            let search_moves = search_moves.as_deref();
+            let (result, stats) = thread::scope(|scope| {
+                let running: Vec<_> = helpers
+                    .into_iter()
+                    .map(|mut helper| {
+                        let board = &board;
+                        scope.spawn(move || {
+                            let result = helper.search(board, color, search_moves, time, &mut |_| {});
+                            (result, helper.current_stats)
+                        })
+                    })
+                    .collect();
+                // only the first search reports progress
+                let result = engine.search(&board, color, search_moves, time, &mut |stats| {
+                    notify(id, SearchUpdate::Progress(stats.clone()));
+                });
+                if running.is_empty() {
+                    return (result, engine.current_stats);
+                }
+
+                let mut stats = engine.current_stats;
+                let mut results: Vec<SearchResult> = result.into_iter().collect();
+                for handle in running {
+                    let Ok((result, helper_stats)) = handle.join() else { continue };
+                    stats.total_simulations += helper_stats.total_simulations;
+                    stats.nodes_explored += helper_stats.nodes_explored;
+                    stats.rollouts += helper_stats.rollouts;
+                    stats.rollout_plies += helper_stats.rollout_plies;
+                    stats.tree_size += helper_stats.tree_size;
+                    stats.memory_bytes += helper_stats.memory_bytes;
+                    stats.recycled += helper_stats.recycled;
+                    stats.table_probes += helper_stats.table_probes;
+                    stats.table_hits += helper_stats.table_hits;
+                    stats.table_fill = stats.table_fill.max(helper_stats.table_fill);
+                    stats.cutoffs += helper_stats.cutoffs;
+                    stats.first_move_cutoffs += helper_stats.first_move_cutoffs;
+                    stats.elapsed = stats.elapsed.max(helper_stats.elapsed);
+                    results.extend(result);
+                }
+                let result = SearchResult::merge(results);
+                if let Some(result) = &result {
+                    let total: u32 = result.tree.iter().map(|branch| branch.visits).sum();
+                    stats.best_move_confidence = result.tree[0].visits as f32 / total.max(1) as f32;
+                    stats.top_moves = result.tree.iter().take(multi_pv).map(PvLine::from_branch).collect();
+                    stats.best_line = result.pv.iter().map(|mv| coordinates(*mv)).collect();
+                    stats.depth_reached = result.pv.len() as i32;
+                }
+                (result, stats)
+            });
+            notify(id, SearchUpdate::Finished(result, stats));
+        });
+        SearchHandle {
+            id,
+            started: Instant::now(),
+            stop,
+        }
+    }
+
+    // ends the search early; it finishes as it would have, with the best
+    // move it had found
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+// nobody is waiting on a search whose handle is gone, and one searching
+// until stopped would otherwise never end
+impl Drop for SearchHandle {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+// how long a search goes on, in the words of UCI's go: `infinite`,
+// `movetime <ms>`, `nodes <n>` and `depth <n>`, any of them together.
+// with a limit on nodes or depth but no time it searches until the limit
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Go {
+    pub infinite: bool,
+    pub movetime: Option<Duration>,
+    pub nodes: Option<u32>,
+    pub depth: Option<usize>,
+}
+
+impl Go {
+    pub fn parse(words: &[&str]) -> Option<Go> {
+        let mut go = Go::default();
+        let mut words = words.iter();
+        while let Some(&word) = words.next() {
+            match word {
+                "infinite" => go.infinite = true,
+                "movetime" => go.movetime = Some(Duration::from_millis(words.next()?.parse().ok()?)),
+                "nodes" => go.nodes = Some(words.next()?.parse().ok()?),
+                "depth" => go.depth = Some(words.next()?.parse().ok()?),
+                _ => return None,
+            }
+        }
+        Some(go)
+    }
+
+    // the search's time, `default` when nothing says otherwise
+    pub fn time(&self, default: Duration) -> Duration {
+        match self.movetime {
+            Some(time) if !self.infinite => time,
+            None if !self.infinite && self.nodes.is_none() && self.depth.is_none() => default,
+            _ => Duration::MAX,
+        }
+    }
+
+    // the limits on `engine`, the nodes shared out among `threads`
+    pub fn limit(&self, engine: &mut RLEngine, threads: usize) {
+        engine.set_max_simulations(self.nodes.map(|nodes| (nodes / threads.max(1) as u32).max(1)));
+        engine.set_max_depth(self.depth);
+    }
+
+    pub fn text(&self) -> String {
+        let mut parts = Vec::new();
+        if self.infinite {
+            parts.push("until stopped".to_string());
+        }
+        if let Some(time) = self.movetime {
+            parts.push(format!("for {:.1}s", time.as_secs_f32()));
+        }
+        if let Some(nodes) = self.nodes {
+            parts.push(format!("to {} simulations", nodes));
+        }
+        if let Some(depth) = self.depth {
+            parts.push(format!("to depth {}", depth));
+        }
+        if parts.is_empty() {
+            parts.push("for the usual time".to_string());
+        }
+        parts.join(", ")
+    }
+}
+
@@ -1,23 +1,46 @@
+use crate::game::movement::{self, Move, MoveList};
 use crate::game::piece::{Color, Piece, PieceType};
 
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Board {
     squares: [[Option<Piece>; 8]; 8],
-    selected_square: Option<(usize, usize)>,
-    current_turn: Color,
+    // checks given by white and black, for three-check
+    checks: [u8; 2],
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Board::new()
+    }
 }
 
 impl Board {
     pub fn new() -> Self {
-        let mut board = Board {
-            squares: [[None; 8]; 8],
-            selected_square: None,
-            current_turn: Color::White,
-        };
+        let mut board = Board::empty();
         board.initialize_pieces();
         board
     }
 
+    pub fn empty() -> Self {
+        Board {
+            squares: [[None; 8]; 8],
+            checks: [0; 2],
+        }
+    }
+
+    pub fn put(&mut self, pos: (usize, usize), piece: Piece) {
+        self.squares[pos.0][pos.1] = Some(piece);
+    }
+
+    pub fn checks_given(&self, color: Color) -> u8 {
+        self.checks[color as usize]
+    }
+
+    pub fn add_check(&mut self, color: Color) {
+        self.checks[color as usize] += 1;
+    }
+
     fn initialize_pieces(&mut self) {
         // back rank pieces
         let back_rank = [
@@ -44,22 +67,112 @@ impl Board {
         }
     }
 
-    pub fn display(&self, _cursor_pos: (usize, usize), term_size: (u16, u16)) {
-        print!("\x1B[2J\x1B[1;1H");
+    pub fn get_piece(&self, pos: (usize, usize)) -> Option<&Piece> {
+        self.squares[pos.0][pos.1].as_ref()
+    }
 
-        // vertical padding to center the board
-        let board_height = 10; // 8 ranks + 2 border lines
-        let _v_padding = ((term_size.1 as i32 - board_height as i32) / 2).max(0) as u16;
-        let _h_padding = ((term_size.0 as i32 - 35) / 2).max(0) as u16; // 35 is the new width of the board
+    // same pieces on the same squares; who's to move is up to the caller
+    pub fn same_position(&self, other: &Board) -> bool {
+        self.squares == other.squares
+    }
 
-        // i got rid of this method kinda. i left it in case i need it later but i moved to the ratatui rendering
+    // no castling or en passant yet, so those fields are always "-"
+    pub fn fen(&self, to_move: Color, fullmove: usize) -> String {
+        let mut placement = String::new();
+        for (rank, row) in self.squares.iter().enumerate() {
+            let mut empty = 0;
+            for square in row {
+                match square {
+                    Some(piece) => {
+                        if empty > 0 {
+                            placement.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        placement.push(piece.letter());
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                placement.push_str(&empty.to_string());
+            }
+            if rank < 7 {
+                placement.push('/');
+            }
+        }
+        let side = if to_move == Color::White { 'w' } else { 'b' };
+        format!("{} {} - - 0 {}", placement, side, fullmove)
     }
 
-    pub fn get_piece(&self, pos: (usize, usize)) -> Option<&Piece> {
-        self.squares[pos.0][pos.1].as_ref()
+    // the placement and side to move of a FEN; the rest is ignored since
+    // the rules have no castling or en passant to restore
+    // each side needs exactly one king, as the standard rules do; see
+    // Variant::read_fen for the variants that differ
+    pub fn from_fen(fen: &str) -> Option<(Board, Color)> {
+        Self::from_fen_with_kings(fen, &[Color::White, Color::Black])
+    }
+
+    // the same, with a king needed only by the `kinged` sides. no side may
+    // have two
+    pub fn from_fen_with_kings(fen: &str, kinged: &[Color]) -> Option<(Board, Color)> {
+        let mut fields = fen.split_whitespace();
+        let placement = fields.next()?;
+        let to_move = match fields.next().unwrap_or("w") {
+            "w" => Color::White,
+            "b" => Color::Black,
+            _ => return None,
+        };
+        let rows: Vec<&str> = placement.split('/').collect();
+        if rows.len() != 8 {
+            return None;
+        }
+        let mut board = Board::empty();
+        for (rank, row) in rows.iter().enumerate() {
+            let mut file = 0;
+            for c in row.chars() {
+                if let Some(skip) = c.to_digit(10) {
+                    file += skip as usize;
+                } else {
+                    if file >= 8 {
+                        return None;
+                    }
+                    board.put((rank, file), Piece::from_letter(c)?);
+                    file += 1;
+                }
+            }
+            if file != 8 {
+                return None;
+            }
+        }
+        for color in [Color::White, Color::Black] {
+            let kings = (0..8)
+                .flat_map(|rank| (0..8).map(move |file| (rank, file)))
+                .filter(|&pos| board.get_piece(pos).is_some_and(|p| p.piece_type == PieceType::King && p.color == color))
+                .count();
+            if kings > 1 || (kings == 0 && kinged.contains(&color)) {
+                return None;
+            }
+        }
+        Some((board, to_move))
+    }
+
+    pub fn clear_square(&mut self, pos: (usize, usize)) {
+        self.squares[pos.0][pos.1] = None;
     }
 
     pub fn move_piece(&mut self, from: (usize, usize), to: (usize, usize)) -> bool {
+        if !self.can_move(from, to) {
+            return false;
+        }
+
+        let piece = self.squares[from.0][from.1];
+        self.squares[from.0][from.1] = None;
+        self.squares[to.0][to.1] = piece;
+        true
+    }
+
+    // whether the piece on `from` moves like that, ignoring checks
+    pub fn can_move(&self, from: (usize, usize), to: (usize, usize)) -> bool {
         // basic validation
         if from == to {
             return false;
@@ -79,23 +192,126 @@ impl Board {
         }
 
         // validate piece-specific movement
-        let valid = match piece.piece_type {
+        match piece.piece_type {
             PieceType::Pawn => self.validate_pawn_move(from, to, piece.color),
             PieceType::Rook => self.validate_rook_move(from, to),
             PieceType::Knight => self.validate_knight_move(from, to),
             PieceType::Bishop => self.validate_bishop_move(from, to),
             PieceType::Queen => self.validate_queen_move(from, to),
             PieceType::King => self.validate_king_move(from, to),
-        };
+        }
+    }
 
-        if !valid {
-            return false;
+    pub fn find_king(&self, color: Color) -> Option<(usize, usize)> {
+        (0..8)
+            .flat_map(|rank| (0..8).map(move |file| (rank, file)))
+            .find(|&pos| {
+                self.get_piece(pos)
+                    .is_some_and(|p| p.piece_type == PieceType::King && p.color == color)
+            })
+    }
+
+    pub fn is_in_check(&self, color: Color) -> bool {
+        self.find_king(color)
+            .is_some_and(|king| self.is_square_attacked(king, color.opposite()))
+    }
+
+    // whether any of `by`'s pieces attacks `square`, whatever stands on it
+    pub fn is_square_attacked(&self, square: (usize, usize), by: Color) -> bool {
+        !movement::attackers(self, square, by).is_empty()
+    }
+
+    // the squares of `by`'s pieces attacking `square`
+    pub fn attackers_of(&self, square: (usize, usize), by: Color) -> Vec<(usize, usize)> {
+        movement::attackers(self, square, by)
+    }
+
+    // the static exchange on `to` starting with the piece on `from`, with
+    // pieces worth `value`: what its side comes out with after the
+    // recaptures, negative when the capture loses material
+    pub fn see(&self, from: (usize, usize), to: (usize, usize), value: &dyn Fn(PieceType) -> i32) -> i32 {
+        movement::see(self, from, to, value)
+    }
+
+    // every square `color` attacks, its own pieces' squares included
+    pub fn attacks_by(&self, color: Color) -> [[bool; 8]; 8] {
+        let mut map = [[false; 8]; 8];
+        for rank in 0..8 {
+            for file in 0..8 {
+                if self.get_piece((rank, file)).is_some_and(|p| p.color == color) {
+                    for (r, f) in movement::attacks(self, (rank, file)) {
+                        map[r][f] = true;
+                    }
+                }
+            }
         }
+        map
+    }
 
-        //  else move the piece
-        self.squares[from.0][from.1] = None;
-        self.squares[to.0][to.1] = Some(piece);
-        true
+    // neither side could ever mate: bare kings, a single knight or bishop, or
+    // only bishops, all on squares of the same color
+    pub fn insufficient_material(&self) -> bool {
+        let mut knights = 0;
+        let mut bishop_squares = [false; 2];
+        for rank in 0..8 {
+            for file in 0..8 {
+                let Some(piece) = self.get_piece((rank, file)) else {
+                    continue;
+                };
+                match piece.piece_type {
+                    PieceType::King => {}
+                    PieceType::Knight => knights += 1,
+                    PieceType::Bishop => bishop_squares[(rank + file) % 2] = true,
+                    _ => return false,
+                }
+            }
+        }
+        let bishops_on_both = bishop_squares[0] && bishop_squares[1];
+        match knights {
+            0 => !bishops_on_both,
+            1 => !bishop_squares[0] && !bishop_squares[1],
+            _ => false,
+        }
+    }
+
+    // destinations for the piece on `from` that don't leave its own king in check
+    pub fn legal_destinations(&self, from: (usize, usize)) -> Vec<(usize, usize)> {
+        let Some(piece) = self.get_piece(from).copied() else {
+            return Vec::new();
+        };
+        self.destinations_evading(from, movement::check_evasions(self, piece.color).as_deref())
+    }
+
+    // every legal move for `color` as (from, to) pairs
+    pub fn legal_moves(&self, color: Color) -> MoveList {
+        let evasions = movement::check_evasions(self, color);
+        (0..8)
+            .flat_map(|rank| (0..8).map(move |file| (rank, file)))
+            .filter(|&pos| self.get_piece(pos).is_some_and(|p| p.color == color))
+            .flat_map(|from| {
+                self.destinations_evading(from, evasions.as_deref())
+                    .into_iter()
+                    .map(move |to| Move::new(self, from, to))
+            })
+            .collect()
+    }
+
+    // legal_destinations, with `evasions` from movement::check_evasions:
+    // in check, only the king and moves onto those squares are tried. a
+    // pinned piece or a king stepping along the checking line still has to
+    // pass the check test after the move
+    fn destinations_evading(&self, from: (usize, usize), evasions: Option<&[(usize, usize)]>) -> Vec<(usize, usize)> {
+        let Some(piece) = self.get_piece(from).copied() else {
+            return Vec::new();
+        };
+        movement::destinations(self, from)
+            .into_iter()
+            .filter(|to| piece.piece_type == PieceType::King || evasions.is_none_or(|squares| squares.contains(to)))
+            .filter(|&to| {
+                let mut after = self.clone();
+                after.move_piece(from, to) && !after.is_in_check(piece.color)
+            })
+            .collect()
     }
 
     fn validate_pawn_move(&self, from: (usize, usize), to: (usize, usize), color: Color) -> bool {
@@ -140,8 +356,8 @@ impl Board {
         }
 
         // check path is clear or return invalid move
-        let rank_step = (to.0 as i8 - from.0 as i8).signum() as i8;
-        let file_step = (to.1 as i8 - from.1 as i8).signum() as i8;
+        let rank_step = (to.0 as i8 - from.0 as i8).signum();
+        let file_step = (to.1 as i8 - from.1 as i8).signum();
 
         let mut current = (from.0 as i8 + rank_step, from.1 as i8 + file_step);
         let to = (to.0 as i8, to.1 as i8);
@@ -0,0 +1,182 @@
+use crate::game::board::Board;
+use crate::game::piece::{Color, Piece, PieceType};
+use crate::game::result::GameResult;
+use rand::Rng;
+
+// a basic endgame from a random start whose result is known: white always
+// has the extra material, and the player either converts it or, with the
+// lone king, holds the draw
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DrillKind {
+    QueenMate,
+    RookMate,
+    // the pawn side's king already on a key square, so it's a win
+    PawnWin,
+    // a rook pawn with the lone king in front of it, so it's a draw
+    PawnDraw,
+}
+
+impl DrillKind {
+    pub const ALL: [DrillKind; 4] = [DrillKind::QueenMate, DrillKind::RookMate, DrillKind::PawnWin, DrillKind::PawnDraw];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DrillKind::QueenMate => "kq",
+            DrillKind::RookMate => "kr",
+            DrillKind::PawnWin => "kp",
+            DrillKind::PawnDraw => "kp-defend",
+        }
+    }
+
+    pub fn by_name(name: &str) -> Option<DrillKind> {
+        DrillKind::ALL.iter().copied().find(|kind| kind.name() == name)
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            DrillKind::QueenMate => "KQ vs K",
+            DrillKind::RookMate => "KR vs K",
+            DrillKind::PawnWin => "KP vs K",
+            DrillKind::PawnDraw => "K vs KP",
+        }
+    }
+
+    pub fn player(self) -> Color {
+        match self {
+            DrillKind::PawnDraw => Color::Black,
+            _ => Color::White,
+        }
+    }
+
+    // the player's moves to get it done in, or to hold out for. the longest
+    // forced mates are 10 moves with the queen and 16 with the rook, so
+    // there's room for a few that aren't the quickest
+    pub fn budget(self) -> usize {
+        match self {
+            DrillKind::QueenMate => 15,
+            DrillKind::RookMate => 25,
+            DrillKind::PawnWin => 20,
+            DrillKind::PawnDraw => 20,
+        }
+    }
+
+    pub fn task(self) -> String {
+        match self {
+            DrillKind::QueenMate => format!("mate with the queen within {} moves", self.budget()),
+            DrillKind::RookMate => format!("mate with the rook within {} moves", self.budget()),
+            DrillKind::PawnWin => format!("get the pawn safely to the last rank within {} moves", self.budget()),
+            DrillKind::PawnDraw => format!("stop the pawn for {} moves", self.budget()),
+        }
+    }
+
+    // whether the game ended the way the drill wanted
+    pub fn passed(self, result: GameResult) -> bool {
+        match self {
+            DrillKind::PawnDraw => result.winner() != Some(Color::White),
+            _ => result.winner() == Some(Color::White),
+        }
+    }
+}
+
+// a legal start for `kind` with white to move
+pub fn setup(kind: DrillKind, rng: &mut impl Rng) -> Board {
+    loop {
+        let mut board = Board::empty();
+        let white_king = match kind {
+            DrillKind::QueenMate | DrillKind::RookMate => {
+                let piece = if kind == DrillKind::QueenMate { PieceType::Queen } else { PieceType::Rook };
+                let squares = distinct_squares(rng, 3);
+                board.put(squares[1], Piece::new(piece, Color::White));
+                board.put(squares[2], Piece::new(PieceType::King, Color::Black));
+                squares[0]
+            }
+            DrillKind::PawnWin => {
+                // a pawn from the b to the g file on its second to fourth rank,
+                // and the king on one of the three squares two ranks ahead
+                let pawn = (rng.gen_range(4..=6), rng.gen_range(1..=6));
+                let king = (pawn.0 - 2, pawn.1 + rng.gen_range(0..=2) - 1);
+                board.put(pawn, Piece::new(PieceType::Pawn, Color::White));
+                let black_king = random_square(rng);
+                if near(black_king, pawn) {
+                    continue;
+                }
+                board.put(black_king, Piece::new(PieceType::King, Color::Black));
+                king
+            }
+            DrillKind::PawnDraw => {
+                // the defending king on the promotion square or next to it,
+                // ahead of a rook pawn
+                let file = if rng.gen_bool(0.5) { 0 } else { 7 };
+                let pawn = (rng.gen_range(2..=6), file);
+                let inner = if file == 0 { 1 } else { 6 };
+                let black_king = (rng.gen_range(0..=1), if rng.gen_bool(0.5) { file } else { inner });
+                board.put(pawn, Piece::new(PieceType::Pawn, Color::White));
+                board.put(black_king, Piece::new(PieceType::King, Color::Black));
+                random_square(rng)
+            }
+        };
+        if board.get_piece(white_king).is_some() {
+            continue;
+        }
+        board.put(white_king, Piece::new(PieceType::King, Color::White));
+        let black_king = board.find_king(Color::Black).expect("every drill has both kings");
+        if !near(white_king, black_king) && !board.is_in_check(Color::Black) {
+            return board;
+        }
+    }
+}
+
+// ends a drill the rules haven't ended, the player's side having made
+// `moves` moves and `to_move` being next: a pawn through or stopped, or
+// the moves used up. heuristics rather than a tablebase, each one only
+// firing when the result is certain
+pub fn adjudicate(kind: DrillKind, board: &Board, to_move: Color, moves: usize) -> Option<GameResult> {
+    let pawn = find(board, PieceType::Pawn);
+    let kings = (board.find_king(Color::White)?, board.find_king(Color::Black)?);
+    if let (DrillKind::PawnWin | DrillKind::PawnDraw, Some(pawn)) = (kind, pawn) {
+        // no promotions yet, so a pawn the lone king can't take on the last
+        // rank counts as a queen
+        if pawn.0 == 0 && to_move == Color::Black && (!near(kings.1, pawn) || near(kings.0, pawn)) {
+            return Some(GameResult::Adjudicated { winner: Some(Color::White), reason: "promotion" });
+        }
+        // the lone king in front of the pawn with the other king behind it
+        if kind == DrillKind::PawnWin && pawn.0 > 0 && kings.1 == (pawn.0 - 1, pawn.1) && kings.0.0 > pawn.0 {
+            return Some(GameResult::Adjudicated { winner: None, reason: "a blockaded pawn" });
+        }
+        // the rule of the square: the pawn runs further than the king can chase it
+        if kind == DrillKind::PawnDraw && to_move == Color::White {
+            let steps = if pawn.0 == 6 { 5 } else { pawn.0 };
+            let chase = kings.1.0.max(kings.1.1.abs_diff(pawn.1));
+            if chase > steps {
+                return Some(GameResult::Adjudicated { winner: Some(Color::White), reason: "an unstoppable pawn" });
+            }
+        }
+    }
+    (moves >= kind.budget() && to_move != kind.player())
+        .then_some(GameResult::Adjudicated { winner: None, reason: "the drill's move budget" })
+}
+
+fn find(board: &Board, piece_type: PieceType) -> Option<(usize, usize)> {
+    (0..64)
+        .map(|idx| (idx / 8, idx % 8))
+        .find(|&pos| board.get_piece(pos).is_some_and(|piece| piece.piece_type == piece_type))
+}
+
+fn near(a: (usize, usize), b: (usize, usize)) -> bool {
+    a.0.abs_diff(b.0) <= 1 && a.1.abs_diff(b.1) <= 1
+}
+
+fn random_square(rng: &mut impl Rng) -> (usize, usize) {
+    (rng.gen_range(0..8), rng.gen_range(0..8))
+}
+
+fn distinct_squares(rng: &mut impl Rng, count: usize) -> Vec<(usize, usize)> {
+    let mut squares = Vec::new();
+    while squares.len() < count {
+        let square = random_square(rng);
+        if !squares.contains(&square) {
+            squares.push(square);
+        }
+    }
+    squares
+}
@@ -0,0 +1,87 @@
+use crate::game::board::Board;
+use crate::game::piece::Color;
+use std::time::Duration;
+
+// odds the bot gives (or takes) to even out a game. material odds take a
+// piece off the bot's side before the first move, time odds scale how long
+// it thinks
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum Handicap {
+    #[default]
+    None,
+    Pawn,
+    Knight,
+    Rook,
+    Queen,
+    HalfTime,
+    DoubleTime,
+}
+
+impl Handicap {
+    pub const ALL: [Handicap; 7] = [
+        Handicap::None,
+        Handicap::Pawn,
+        Handicap::Knight,
+        Handicap::Rook,
+        Handicap::Queen,
+        Handicap::HalfTime,
+        Handicap::DoubleTime,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Handicap::None => "none",
+            Handicap::Pawn => "pawn",
+            Handicap::Knight => "knight",
+            Handicap::Rook => "rook",
+            Handicap::Queen => "queen",
+            Handicap::HalfTime => "half-time",
+            Handicap::DoubleTime => "double-time",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Handicap> {
+        Handicap::ALL.iter().copied().find(|h| h.name() == name)
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Handicap::None => "Even game",
+            Handicap::Pawn => "Bot plays without its f-pawn",
+            Handicap::Knight => "Bot plays without its queen's knight",
+            Handicap::Rook => "Bot plays without its queen's rook",
+            Handicap::Queen => "Bot plays without its queen",
+            Handicap::HalfTime => "Bot thinks half as long",
+            Handicap::DoubleTime => "Bot thinks twice as long",
+        }
+    }
+
+    // the piece that comes off, as (rank, file) on black's side
+    fn removed_square(self) -> Option<(usize, usize)> {
+        match self {
+            Handicap::Pawn => Some((1, 5)),
+            Handicap::Knight => Some((0, 1)),
+            Handicap::Rook => Some((0, 0)),
+            Handicap::Queen => Some((0, 3)),
+            _ => None,
+        }
+    }
+
+    // takes the odds piece off `giver`'s side
+    pub fn apply(self, board: &mut Board, giver: Color) {
+        if let Some((rank, file)) = self.removed_square() {
+            let rank = if giver == Color::White { 7 - rank } else { rank };
+            board.clear_square((rank, file));
+        }
+    }
+
+    pub fn think_time(self, base: Duration) -> Duration {
+        match self {
+            Handicap::HalfTime => base / 2,
+            Handicap::DoubleTime => base * 2,
+            _ => base,
+        }
+    }
+}
@@ -1,4 +1,13 @@
 pub mod board;
+pub mod drill;
+pub mod handicap;
 pub mod piece;
 pub mod movement;
+pub mod notation;
+pub mod openings;
+pub mod position;
+pub mod record;
+pub mod result;
 pub mod validation;
+pub mod variant;
+pub mod zobrist;
@@ -1,10 +1,401 @@
+use crate::game::board::Board;
+use crate::game::notation;
+use crate::game::piece::{Color, Piece, PieceType};
+use crate::utils::coordinate_to_string;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+// a move with what it does to the board: the piece that goes, what it
+// takes, and the special kinds of move. the rules have no castling, en
+// passant or promotion yet, so those are never set for now
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Move {
     pub from: (usize, usize),
     pub to: (usize, usize),
+    pub piece: PieceType,
+    pub captured: Option<PieceType>,
+    pub promotion: Option<PieceType>,
+    pub castle: bool,
+    pub en_passant: bool,
 }
 
 impl Move {
-    pub fn new(from: (usize, usize), to: (usize, usize)) -> Self {
-        Move { from, to }
+    // filled in from the position before the move; `from` has to hold a piece
+    pub fn new(board: &Board, from: (usize, usize), to: (usize, usize)) -> Self {
+        Move {
+            from,
+            to,
+            piece: board.get_piece(from).map_or(PieceType::Pawn, |p| p.piece_type),
+            captured: board.get_piece(to).map(|p| p.piece_type),
+            promotion: None,
+            castle: false,
+            en_passant: false,
+        }
+    }
+
+    pub fn pair(&self) -> ((usize, usize), (usize, usize)) {
+        (self.from, self.to)
+    }
+
+    pub fn is_capture(&self) -> bool {
+        self.captured.is_some() || self.en_passant
+    }
+
+    // standard algebraic notation, from the position before the move
+    pub fn san(&self, board: &Board) -> String {
+        notation::san(board, self.pair())
+    }
+}
+
+// a bare from-to pair the way Move displays it, for the engine's and the
+// UI's moves that don't say what they do to the board
+pub fn coordinates(pair: ((usize, usize), (usize, usize))) -> String {
+    format!("{}{}", coordinate_to_string(pair.0), coordinate_to_string(pair.1))
+}
+
+// long algebraic, the way UCI writes moves: "e2e4", "e7e8q"
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", coordinates(self.pair()))?;
+        if let Some(piece) = self.promotion {
+            write!(f, "{}", Piece::new(piece, Color::Black).letter())?;
+        }
+        Ok(())
+    }
+}
+
+// enough for any real position; set-up ones with a crowd of queens spill
+// over onto the heap
+const MOVE_LIST_CAPACITY: usize = 256;
+
+const NO_MOVE: Move = Move {
+    from: (0, 0),
+    to: (0, 0),
+    piece: PieceType::Pawn,
+    captured: None,
+    promotion: None,
+    castle: false,
+    en_passant: false,
+};
+
+// the moves in a position, kept on the stack while there's room
+#[derive(Clone)]
+pub struct MoveList {
+    inline: [Move; MOVE_LIST_CAPACITY],
+    len: usize,
+    spilled: Option<Vec<Move>>,
+}
+
+impl MoveList {
+    pub fn new() -> Self {
+        MoveList { inline: [NO_MOVE; MOVE_LIST_CAPACITY], len: 0, spilled: None }
+    }
+
+    pub fn push(&mut self, mv: Move) {
+        if let Some(moves) = &mut self.spilled {
+            moves.push(mv);
+        } else if self.len < MOVE_LIST_CAPACITY {
+            self.inline[self.len] = mv;
+            self.len += 1;
+        } else {
+            let mut moves = self.inline.to_vec();
+            moves.push(mv);
+            self.spilled = Some(moves);
+        }
+    }
+
+    // the move from `from` to `to`, if it's here
+    pub fn find(&self, (from, to): ((usize, usize), (usize, usize))) -> Option<Move> {
+        self.iter().find(|mv| mv.from == from && mv.to == to).copied()
+    }
+
+    pub fn pairs(&self) -> Vec<((usize, usize), (usize, usize))> {
+        self.iter().map(Move::pair).collect()
+    }
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        MoveList::new()
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [Move];
+
+    fn deref(&self) -> &[Move] {
+        match &self.spilled {
+            Some(moves) => moves,
+            None => &self.inline[..self.len],
+        }
+    }
+}
+
+impl DerefMut for MoveList {
+    fn deref_mut(&mut self) -> &mut [Move] {
+        match &mut self.spilled {
+            Some(moves) => moves,
+            None => &mut self.inline[..self.len],
+        }
+    }
+}
+
+impl FromIterator<Move> for MoveList {
+    fn from_iter<I: IntoIterator<Item = Move>>(iter: I) -> Self {
+        let mut list = MoveList::new();
+        for mv in iter {
+            list.push(mv);
+        }
+        list
+    }
+}
+
+impl IntoIterator for MoveList {
+    type Item = Move;
+    type IntoIter = MoveListIter;
+
+    fn into_iter(self) -> MoveListIter {
+        MoveListIter { list: self, next: 0 }
+    }
+}
+
+pub struct MoveListIter {
+    list: MoveList,
+    next: usize,
+}
+
+impl Iterator for MoveListIter {
+    type Item = Move;
+
+    fn next(&mut self) -> Option<Move> {
+        let mv = self.list.get(self.next).copied()?;
+        self.next += 1;
+        Some(mv)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.list.len() - self.next;
+        (left, Some(left))
+    }
+}
+
+impl ExactSizeIterator for MoveListIter {}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Move;
+    type IntoIter = std::slice::Iter<'a, Move>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl fmt::Debug for MoveList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+const KNIGHT_STEPS: [(i8, i8); 8] = [(-2, -1), (-2, 1), (-1, -2), (-1, 2), (1, -2), (1, 2), (2, -1), (2, 1)];
+const KING_STEPS: [(i8, i8); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+const ROOK_RAYS: [(i8, i8); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+const BISHOP_RAYS: [(i8, i8); 4] = [(-1, -1), (-1, 1), (1, -1), (1, 1)];
+
+fn offset(pos: (usize, usize), (dr, df): (i8, i8)) -> Option<(usize, usize)> {
+    let rank = pos.0 as i8 + dr;
+    let file = pos.1 as i8 + df;
+    ((0..8).contains(&rank) && (0..8).contains(&file)).then_some((rank as usize, file as usize))
+}
+
+// every square the piece on `from` can move to, ignoring checks. the same
+// moves Board::can_move accepts, found by walking the piece's steps and rays
+// instead of trying all 64 squares
+pub fn destinations(board: &Board, from: (usize, usize)) -> Vec<(usize, usize)> {
+    let Some(piece) = board.get_piece(from).copied() else {
+        return Vec::new();
+    };
+    let mut moves = Vec::new();
+    let open_or_enemy = |to: (usize, usize)| board.get_piece(to).is_none_or(|p| p.color != piece.color);
+
+    match piece.piece_type {
+        PieceType::Pawn => {
+            let (direction, start_rank) = if piece.color == Color::White { (-1, 6) } else { (1, 1) };
+            if let Some(one) = offset(from, (direction, 0)).filter(|&to| board.get_piece(to).is_none()) {
+                moves.push(one);
+                if from.0 == start_rank {
+                    if let Some(two) = offset(from, (2 * direction, 0)).filter(|&to| board.get_piece(to).is_none()) {
+                        moves.push(two);
+                    }
+                }
+            }
+            for side in [-1, 1] {
+                if let Some(to) = offset(from, (direction, side)) {
+                    if board.get_piece(to).is_some_and(|p| p.color != piece.color) {
+                        moves.push(to);
+                    }
+                }
+            }
+        }
+        PieceType::Knight | PieceType::King => {
+            let steps = if piece.piece_type == PieceType::Knight { &KNIGHT_STEPS } else { &KING_STEPS };
+            moves.extend(steps.iter().filter_map(|&step| offset(from, step)).filter(|&to| open_or_enemy(to)));
+        }
+        PieceType::Rook | PieceType::Bishop | PieceType::Queen => {
+            let rays: &[(i8, i8)] = match piece.piece_type {
+                PieceType::Rook => &ROOK_RAYS,
+                PieceType::Bishop => &BISHOP_RAYS,
+                // the queen's rays point the same ways as the king's steps
+                _ => &KING_STEPS,
+            };
+            for &ray in rays {
+                let mut current = from;
+                while let Some(to) = offset(current, ray) {
+                    match board.get_piece(to) {
+                        None => moves.push(to),
+                        Some(p) => {
+                            if p.color != piece.color {
+                                moves.push(to);
+                            }
+                            break;
+                        }
+                    }
+                    current = to;
+                }
+            }
+        }
+    }
+    moves
+}
+
+// every square the piece on `from` attacks: where it could capture if an
+// enemy stood there. unlike its moves this counts squares its own pieces
+// stand on, which it defends, and a pawn's diagonals whether or not
+// anything is there
+pub fn attacks(board: &Board, from: (usize, usize)) -> Vec<(usize, usize)> {
+    let Some(piece) = board.get_piece(from).copied() else {
+        return Vec::new();
+    };
+    let steps: &[(i8, i8)] = match piece.piece_type {
+        PieceType::Pawn => {
+            let direction = if piece.color == Color::White { -1 } else { 1 };
+            return [-1, 1].into_iter().filter_map(|side| offset(from, (direction, side))).collect();
+        }
+        PieceType::Knight => return KNIGHT_STEPS.iter().filter_map(|&step| offset(from, step)).collect(),
+        PieceType::King => return KING_STEPS.iter().filter_map(|&step| offset(from, step)).collect(),
+        PieceType::Rook => &ROOK_RAYS,
+        PieceType::Bishop => &BISHOP_RAYS,
+        PieceType::Queen => &KING_STEPS,
+    };
+    let mut squares = Vec::new();
+    for &ray in steps {
+        let mut current = from;
+        while let Some(to) = offset(current, ray) {
+            squares.push(to);
+            if board.get_piece(to).is_some() {
+                break;
+            }
+            current = to;
+        }
+    }
+    squares
+}
+
+// the squares of `by`'s pieces attacking `square`, found by looking out
+// from the square along each piece's pattern rather than from every piece
+pub fn attackers(board: &Board, square: (usize, usize), by: Color) -> Vec<(usize, usize)> {
+    let is = |pos: (usize, usize), kinds: &[PieceType]| {
+        board.get_piece(pos).is_some_and(|p| p.color == by && kinds.contains(&p.piece_type))
+    };
+    // a pawn attacks toward the far side, so look back the way it came
+    let behind = if by == Color::White { 1 } else { -1 };
+    let mut found: Vec<(usize, usize)> = [-1, 1]
+        .into_iter()
+        .filter_map(|side| offset(square, (behind, side)))
+        .filter(|&pos| is(pos, &[PieceType::Pawn]))
+        .collect();
+    found.extend(KNIGHT_STEPS.iter().filter_map(|&step| offset(square, step)).filter(|&pos| is(pos, &[PieceType::Knight])));
+    found.extend(KING_STEPS.iter().filter_map(|&step| offset(square, step)).filter(|&pos| is(pos, &[PieceType::King])));
+    for &ray in &KING_STEPS {
+        let slider = if ray.0 == 0 || ray.1 == 0 { PieceType::Rook } else { PieceType::Bishop };
+        let mut current = square;
+        while let Some(pos) = offset(current, ray) {
+            if board.get_piece(pos).is_some() {
+                if is(pos, &[slider, PieceType::Queen]) {
+                    found.push(pos);
+                }
+                break;
+            }
+            current = pos;
+        }
+    }
+    found
+}
+
+// where `color`'s pieces other than the king can go to get it out of
+// check: onto the checking piece, or between it and the king when it
+// checks from a distance. None when it isn't in check, and nowhere when two
+// pieces give check, which only a king move answers
+pub fn check_evasions(board: &Board, color: Color) -> Option<Vec<(usize, usize)>> {
+    let king = board.find_king(color)?;
+    let checkers = attackers(board, king, color.opposite());
+    match checkers[..] {
+        [] => None,
+        [checker] => {
+            let mut squares = vec![checker];
+            let slides = board
+                .get_piece(checker)
+                .is_some_and(|p| matches!(p.piece_type, PieceType::Rook | PieceType::Bishop | PieceType::Queen));
+            if slides {
+                let step = (
+                    (checker.0 as i8 - king.0 as i8).signum(),
+                    (checker.1 as i8 - king.1 as i8).signum(),
+                );
+                let mut current = king;
+                while let Some(square) = offset(current, step).filter(|&square| square != checker) {
+                    squares.push(square);
+                    current = square;
+                }
+            }
+            Some(squares)
+        }
+        _ => Some(Vec::new()),
+    }
+}
+
+// what taking on `to` with the piece on `from` wins in the end, once both
+// sides have made every recapture worth making, least valuable attacker
+// first. each side may stop whenever going on would lose more. pieces
+// behind the ones that take join in as the way opens; pins are ignored
+pub fn see(board: &Board, from: (usize, usize), to: (usize, usize), value: &dyn Fn(PieceType) -> i32) -> i32 {
+    let Some(piece) = board.get_piece(from).copied() else {
+        return 0;
+    };
+    let mut board = board.clone();
+    let mut gains = vec![board.get_piece(to).map_or(0, |p| value(p.piece_type))];
+    let mut on_square = piece.piece_type;
+    let mut side = piece.color;
+    board.clear_square(from);
+    board.put(to, piece);
+    loop {
+        side = side.opposite();
+        let Some(attacker) = attackers(&board, to, side)
+            .into_iter()
+            .min_by_key(|&pos| board.get_piece(pos).map_or(0, |p| value(p.piece_type)))
+        else {
+            break;
+        };
+        let taker = *board.get_piece(attacker).expect("an attacker stands there");
+        gains.push(value(on_square) - gains[gains.len() - 1]);
+        on_square = taker.piece_type;
+        board.clear_square(attacker);
+        board.put(to, taker);
+    }
+    // back from the last capture: each side takes only if it comes out ahead
+    while gains.len() > 1 {
+        let last = gains.pop().unwrap();
+        let previous = gains.last_mut().unwrap();
+        *previous = -(-*previous).max(last);
     }
+    gains[0]
 }
@@ -0,0 +1,77 @@
+use crate::game::board::Board;
+use crate::game::movement::Move;
+use crate::game::piece::PieceType;
+use crate::utils::coordinate_to_string;
+
+type Square = (usize, usize);
+
+fn letter(piece_type: PieceType) -> &'static str {
+    match piece_type {
+        PieceType::King => "K",
+        PieceType::Queen => "Q",
+        PieceType::Rook => "R",
+        PieceType::Bishop => "B",
+        PieceType::Knight => "N",
+        PieceType::Pawn => "",
+    }
+}
+
+// standard algebraic notation for a legal move, e.g. "Nbd2", "exd5", "Qh5+"
+pub fn san(board: &Board, mv: (Square, Square)) -> String {
+    let mut text = san_body(board, mv);
+    let Some(piece) = board.get_piece(mv.0).copied() else {
+        return text;
+    };
+    let mut after = board.clone();
+    after.move_piece(mv.0, mv.1);
+    let them = piece.color.opposite();
+    if after.is_in_check(them) {
+        text.push(if after.legal_moves(them).is_empty() { '#' } else { '+' });
+    }
+    text
+}
+
+// everything but the check marker, which is the expensive part to work out
+fn san_body(board: &Board, (from, to): (Square, Square)) -> String {
+    let Some(piece) = board.get_piece(from).copied() else {
+        return String::new();
+    };
+    let capture = board.get_piece(to).is_some();
+    let from_text = coordinate_to_string(from);
+    let (file, rank) = from_text.split_at(1);
+
+    let mut text = letter(piece.piece_type).to_string();
+    if piece.piece_type == PieceType::Pawn {
+        if capture {
+            text.push_str(file);
+        }
+    } else {
+        // other pieces of the same kind that could also land on `to`
+        let rivals: Vec<Square> = board
+            .legal_moves(piece.color)
+            .iter()
+            .filter(|mv| mv.to == to && mv.from != from && mv.piece == piece.piece_type)
+            .map(|mv| mv.from)
+            .collect();
+        if !rivals.is_empty() {
+            if rivals.iter().all(|r| r.1 != from.1) {
+                text.push_str(file);
+            } else if rivals.iter().all(|r| r.0 != from.0) {
+                text.push_str(rank);
+            } else {
+                text.push_str(&from_text);
+            }
+        }
+    }
+    if capture {
+        text.push('x');
+    }
+    text.push_str(&coordinate_to_string(to));
+    text
+}
+
+// finds which of `moves` the text describes
+pub fn parse_san(board: &Board, moves: &[Move], text: &str) -> Option<Move> {
+    let wanted = text.trim_end_matches(['+', '#', '!', '?']);
+    moves.iter().copied().find(|mv| san_body(board, mv.pair()) == wanted)
+}
@@ -0,0 +1,89 @@
+use crate::game::variant::Square;
+use crate::utils::parse_coordinate;
+
+// a small opening book, just enough to put a name on a game. lines are
+// coordinate moves from the start position; the longest matching one wins
+const OPENINGS: &[(&str, &str)] = &[
+    ("e2e4", "King's Pawn Opening"),
+    ("e2e4 e7e5", "Open Game"),
+    ("e2e4 e7e5 g1f3", "King's Knight Opening"),
+    ("e2e4 e7e5 g1f3 b8c6", "King's Knight Opening: Normal Variation"),
+    ("e2e4 e7e5 g1f3 b8c6 f1b5", "Ruy Lopez"),
+    ("e2e4 e7e5 g1f3 b8c6 f1c4", "Italian Game"),
+    ("e2e4 e7e5 g1f3 b8c6 f1c4 f8c5", "Giuoco Piano"),
+    ("e2e4 e7e5 g1f3 b8c6 f1c4 g8f6", "Two Knights Defense"),
+    ("e2e4 e7e5 g1f3 b8c6 d2d4", "Scotch Game"),
+    ("e2e4 e7e5 g1f3 b8c6 b1c3", "Three Knights Game"),
+    ("e2e4 e7e5 g1f3 b8c6 b1c3 g8f6", "Four Knights Game"),
+    ("e2e4 e7e5 g1f3 g8f6", "Petrov's Defense"),
+    ("e2e4 e7e5 g1f3 d7d6", "Philidor Defense"),
+    ("e2e4 e7e5 f2f4", "King's Gambit"),
+    ("e2e4 e7e5 b1c3", "Vienna Game"),
+    ("e2e4 e7e5 f1c4", "Bishop's Opening"),
+    ("e2e4 e7e5 d1h5", "Wayward Queen Attack"),
+    ("e2e4 c7c5", "Sicilian Defense"),
+    ("e2e4 c7c5 g1f3 d7d6", "Sicilian Defense: Modern Variations"),
+    ("e2e4 c7c5 g1f3 b8c6", "Sicilian Defense: Old Sicilian"),
+    ("e2e4 c7c5 b1c3", "Sicilian Defense: Closed"),
+    ("e2e4 c7c5 c2c3", "Sicilian Defense: Alapin Variation"),
+    ("e2e4 e7e6", "French Defense"),
+    ("e2e4 e7e6 d2d4 d7d5", "French Defense: Normal Variation"),
+    ("e2e4 c7c6", "Caro-Kann Defense"),
+    ("e2e4 d7d5", "Scandinavian Defense"),
+    ("e2e4 g8f6", "Alekhine's Defense"),
+    ("e2e4 d7d6", "Pirc Defense"),
+    ("e2e4 g7g6", "Modern Defense"),
+    ("e2e4 b8c6", "Nimzowitsch Defense"),
+    ("d2d4", "Queen's Pawn Opening"),
+    ("d2d4 d7d5", "Closed Game"),
+    ("d2d4 d7d5 c2c4", "Queen's Gambit"),
+    ("d2d4 d7d5 c2c4 e7e6", "Queen's Gambit Declined"),
+    ("d2d4 d7d5 c2c4 c7c6", "Slav Defense"),
+    ("d2d4 d7d5 c2c4 d5c4", "Queen's Gambit Accepted"),
+    ("d2d4 d7d5 c1f4", "London System"),
+    ("d2d4 d7d5 g1f3 g8f6 c1f4", "London System"),
+    ("d2d4 g8f6", "Indian Defense"),
+    ("d2d4 g8f6 c2c4 e7e6", "Indian Defense: Normal Variation"),
+    ("d2d4 g8f6 c2c4 e7e6 b1c3 f8b4", "Nimzo-Indian Defense"),
+    ("d2d4 g8f6 c2c4 e7e6 g1f3 b7b6", "Queen's Indian Defense"),
+    ("d2d4 g8f6 c2c4 g7g6", "King's Indian Defense"),
+    ("d2d4 g8f6 c2c4 g7g6 b1c3 d7d5", "Grünfeld Defense"),
+    ("d2d4 g8f6 c2c4 c7c5", "Benoni Defense"),
+    ("d2d4 f7f5", "Dutch Defense"),
+    ("c2c4", "English Opening"),
+    ("c2c4 e7e5", "English Opening: King's English"),
+    ("g1f3", "Zukertort Opening"),
+    ("g1f3 d7d5 g2g3", "King's Indian Attack"),
+    ("f2f4", "Bird's Opening"),
+    ("b2b3", "Nimzo-Larsen Attack"),
+    ("g2g3", "Hungarian Opening"),
+    ("b1c3", "Van 't Kruijs Opening"),
+];
+
+// the book's deepest lines, as starting points for engine games: every
+// line of four plies or more that no other line goes on from
+pub fn suite() -> Vec<(&'static str, Vec<(Square, Square)>)> {
+    OPENINGS
+        .iter()
+        .filter(|(line, _)| line.split(' ').count() >= 4)
+        .filter(|(line, _)| !OPENINGS.iter().any(|(other, _)| other.len() > line.len() && other.starts_with(&format!("{} ", line))))
+        .map(|(line, name)| {
+            let moves = line
+                .split(' ')
+                .filter_map(|mv| Some((parse_coordinate(mv.get(..2)?)?, parse_coordinate(mv.get(2..)?)?)))
+                .collect();
+            (*name, moves)
+        })
+        .collect()
+}
+
+pub fn opening_name(moves: &[String]) -> Option<&'static str> {
+    OPENINGS
+        .iter()
+        .filter(|(line, _)| {
+            let line: Vec<&str> = line.split(' ').collect();
+            line.len() <= moves.len() && line.iter().zip(moves).all(|(a, b)| a == b)
+        })
+        .max_by_key(|(line, _)| line.len())
+        .map(|(_, name)| *name)
+}
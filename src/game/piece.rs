@@ -1,4 +1,5 @@
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PieceType {
     King,
     Queen,
@@ -8,7 +9,21 @@ pub enum PieceType {
     Pawn,
 }
 
+impl PieceType {
+    pub fn name(self) -> &'static str {
+        match self {
+            PieceType::King => "king",
+            PieceType::Queen => "queen",
+            PieceType::Rook => "rook",
+            PieceType::Bishop => "bishop",
+            PieceType::Knight => "knight",
+            PieceType::Pawn => "pawn",
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Color {
     White,
     Black,
@@ -21,9 +36,17 @@ impl Color {
             Color::Black => Color::White,
         }
     }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Color::White => "white",
+            Color::Black => "black",
+        }
+    }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Piece {
     pub piece_type: PieceType,
     pub color: Color,
@@ -34,7 +57,7 @@ impl Piece {
         Piece { piece_type, color }
     }
 
-    pub fn to_char(&self) -> char {
+    pub fn to_char(self) -> char {
         let symbol = match self.piece_type {
             PieceType::King => '♔',
             PieceType::Queen => '♕',
@@ -50,4 +73,35 @@ impl Piece {
             char::from_u32(symbol as u32 + 6).unwrap_or(symbol)
         }
     }
+
+    // the FEN letter, capitals for white
+    pub fn letter(self) -> char {
+        let letter = match self.piece_type {
+            PieceType::King => 'k',
+            PieceType::Queen => 'q',
+            PieceType::Rook => 'r',
+            PieceType::Bishop => 'b',
+            PieceType::Knight => 'n',
+            PieceType::Pawn => 'p',
+        };
+        if self.color == Color::White {
+            letter.to_ascii_uppercase()
+        } else {
+            letter
+        }
+    }
+
+    pub fn from_letter(letter: char) -> Option<Piece> {
+        let piece_type = match letter.to_ascii_lowercase() {
+            'k' => PieceType::King,
+            'q' => PieceType::Queen,
+            'r' => PieceType::Rook,
+            'b' => PieceType::Bishop,
+            'n' => PieceType::Knight,
+            'p' => PieceType::Pawn,
+            _ => return None,
+        };
+        let color = if letter.is_ascii_uppercase() { Color::White } else { Color::Black };
+        Some(Piece::new(piece_type, color))
+    }
 }
@@ -0,0 +1,284 @@
+use crate::game::board::Board;
+use crate::game::movement::Move;
+use crate::game::notation::parse_san;
+use crate::game::piece::Color;
+use crate::game::result::GameResult;
+use crate::game::variant::{Standard, Variant};
+use crate::game::zobrist;
+use crate::utils::parse_coordinate;
+use std::sync::Arc;
+
+// a game under one variant's rules: the board, whose turn it is and every
+// position so far. this is the way in for programs using chessrl's rules
+// without its engine or screens
+#[derive(Clone)]
+pub struct Position {
+    variant: Arc<dyn Variant>,
+    board: Board,
+    to_move: Color,
+    fullmove: usize,
+    // hashes of every position so far, this one last, for repetitions
+    hashes: Vec<u64>,
+}
+
+impl Position {
+    // the standard starting position
+    pub fn new() -> Self {
+        Position::with_variant(Arc::new(Standard))
+    }
+
+    // the variant's starting position
+    pub fn with_variant(variant: Arc<dyn Variant>) -> Self {
+        let board = variant.initial_board();
+        Position::from_board(variant, board, Color::White, 1)
+    }
+
+    // a standard game from a FEN. castling and en passant aren't in the
+    // rules, so those fields are ignored
+    pub fn from_fen(fen: &str) -> Option<Self> {
+        let (board, to_move) = Board::from_fen(fen)?;
+        let fullmove = fen.split_whitespace().nth(5).and_then(|n| n.parse().ok()).unwrap_or(1);
+        Some(Position::from_board(Arc::new(Standard), board, to_move, fullmove))
+    }
+
+    // a game under way from every position it's been through, the first
+    // one's mover given, so repetitions count from the start. None if
+    // there are no positions at all
+    pub fn from_positions(variant: Arc<dyn Variant>, positions: &[Board], first_to_move: Color) -> Option<Self> {
+        let board = positions.last()?.clone();
+        let mut to_move = first_to_move;
+        let mut fullmove = 1;
+        let mut hashes = Vec::with_capacity(positions.len());
+        for (idx, board) in positions.iter().enumerate() {
+            if idx > 0 {
+                if to_move == Color::Black {
+                    fullmove += 1;
+                }
+                to_move = to_move.opposite();
+            }
+            hashes.push(zobrist::hash(board, to_move));
+        }
+        Some(Position { variant, board, to_move, fullmove, hashes })
+    }
+
+    fn from_board(variant: Arc<dyn Variant>, board: Board, to_move: Color, fullmove: usize) -> Self {
+        let hashes = vec![zobrist::hash(&board, to_move)];
+        Position { variant, board, to_move, fullmove, hashes }
+    }
+
+    pub fn variant(&self) -> &dyn Variant {
+        self.variant.as_ref()
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn to_move(&self) -> Color {
+        self.to_move
+    }
+
+    // every move the side to move may play
+    pub fn legal_moves(&self) -> impl Iterator<Item = Move> {
+        self.variant.moves(&self.board, self.to_move).into_iter()
+    }
+
+    // a move written in coordinates ("e2e4") or SAN ("Nf3"), if it's legal here
+    pub fn parse_move(&self, text: &str) -> Option<Move> {
+        let moves = self.variant.moves(&self.board, self.to_move);
+        let text = text.trim();
+        let coordinates = text.get(..2).and_then(parse_coordinate).zip(text.get(2..4).and_then(parse_coordinate));
+        match coordinates {
+            Some(pair) if text.len() == 4 => moves.find(pair),
+            _ => parse_san(&self.board, &moves, text),
+        }
+    }
+
+    // plays `mv` if it's legal, false if it isn't
+    pub fn make_move(&mut self, mv: Move) -> bool {
+        if self.game_result().is_some() || self.variant.moves(&self.board, self.to_move).find(mv.pair()).is_none() {
+            return false;
+        }
+        if !self.variant.make_move(&mut self.board, mv.from, mv.to) {
+            return false;
+        }
+        if self.to_move == Color::Black {
+            self.fullmove += 1;
+        }
+        self.to_move = self.to_move.opposite();
+        self.hashes.push(zobrist::hash(&self.board, self.to_move));
+        true
+    }
+
+    // how the game ended, if it has, repetitions included
+    pub fn game_result(&self) -> Option<GameResult> {
+        if let Some(result) = self.variant.result(&self.board, self.to_move) {
+            return Some(result);
+        }
+        let hash = self.zobrist_hash();
+        (self.hashes.iter().filter(|&&seen| seen == hash).count() >= 3).then_some(GameResult::Repetition)
+    }
+
+    // the same for the same placement, side to move and checks given
+    pub fn zobrist_hash(&self) -> u64 {
+        self.hashes[self.hashes.len() - 1]
+    }
+
+    pub fn fen(&self) -> String {
+        self.board.fen(self.to_move, self.fullmove)
+    }
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Position::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::variant;
+
+    // leaves `depth` plies on, counting the last ply's moves without playing them
+    fn perft(position: &Position, depth: u32) -> u64 {
+        if depth == 1 {
+            return position.legal_moves().count() as u64;
+        }
+        position
+            .legal_moves()
+            .map(|mv| {
+                let mut next = position.clone();
+                assert!(next.make_move(mv), "{} refused in {}", mv, position.fen());
+                perft(&next, depth - 1)
+            })
+            .sum()
+    }
+
+    // the well-known counts, to depths short of castling, en passant and
+    // promotion, which the rules don't have yet
+    #[test]
+    fn perft_from_the_start() {
+        let position = Position::new();
+        for (depth, nodes) in [(1, 20), (2, 400), (3, 8902), (4, 197_281)] {
+            assert_eq!(perft(&position, depth), nodes, "depth {}", depth);
+        }
+    }
+
+    #[test]
+    fn perft_rook_and_pawn_endgame() {
+        let position = Position::from_fen("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1").unwrap();
+        assert_eq!(perft(&position, 1), 14);
+        assert_eq!(perft(&position, 2), 191);
+    }
+
+    // checks from a distance, from a knight and a pawn, and a double
+    // check: only evasions count, and the counts hold further down
+    #[test]
+    fn perft_in_check() {
+        for (fen, counts) in [
+            ("r3k3/8/8/8/4q3/8/3N1N2/4K2R w - - 0 1", [4, 110, 2020]),
+            ("4k3/8/8/8/1b6/8/8/RN2K1N1 w - - 0 1", [6, 81, 1395]),
+            ("rnbqkbnr/ppppp1pp/8/5p1Q/4P3/8/PPPP1PPP/RNB1KBNR b - - 0 1", [1, 39, 794]),
+            ("8/8/4k3/8/8/8/P1n5/4K3 w - - 0 1", [5, 70, 504]),
+            ("8/8/8/3k4/4P3/8/8/4KR2 b - - 0 1", [8, 110, 638]),
+            ("4k3/8/8/8/8/5n2/8/r3K3 w - - 0 1", [2, 54, 239]),
+        ] {
+            let position = Position::from_fen(fen).unwrap();
+            for (depth, nodes) in (1..).zip(counts) {
+                assert_eq!(perft(&position, depth), nodes, "{} depth {}", fen, depth);
+            }
+        }
+    }
+
+    // no kings, a missing king and two kings on one side aren't positions,
+    // but the horde has none by the rules
+    #[test]
+    fn fen_needs_its_kings() {
+        assert!(Position::from_fen("8/8/8/8/8/8/8/8 w - - 0 1").is_none());
+        assert!(Position::from_fen("4k3/8/8/8/8/8/8/8 w - - 0 1").is_none());
+        assert!(Position::from_fen("4k3/8/8/8/8/8/8/3KK3 w - - 0 1").is_none());
+        let horde = "rnbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w - - 0 1";
+        assert!(Board::from_fen(horde).is_none());
+        assert!(variant::by_name("horde").unwrap().read_fen(horde).is_some());
+    }
+
+    #[test]
+    fn fen_survives_a_game() {
+        let mut position = Position::new();
+        for text in ["e4", "e5", "Nf3", "Nc6", "Bb5", "a6", "Bxc6", "dxc6"] {
+            let mv = position.parse_move(text).unwrap();
+            assert!(position.make_move(mv));
+            let reread = Position::from_fen(&position.fen()).unwrap();
+            assert_eq!(reread.fen(), position.fen());
+            assert_eq!(reread.zobrist_hash(), position.zobrist_hash());
+        }
+        assert_eq!(position.fen(), "r1bqkbnr/1pp2ppp/p1p5/4p3/4P3/5N2/PPPP1PPP/RNBQK2R w - - 0 5");
+    }
+
+    #[test]
+    fn transpositions_hash_alike() {
+        let play = |moves: &[&str]| {
+            let mut position = Position::new();
+            for text in moves {
+                let mv = position.parse_move(text).unwrap();
+                assert!(position.make_move(mv));
+            }
+            position
+        };
+        let a = play(&["Nf3", "Nf6", "Nc3"]);
+        let b = play(&["g1f3", "g8f6", "b1c3"]);
+        let c = play(&["Nc3", "Nf6", "Nf3"]);
+        assert_eq!(a.zobrist_hash(), b.zobrist_hash());
+        assert_eq!(a.zobrist_hash(), c.zobrist_hash());
+        assert_ne!(a.zobrist_hash(), play(&["Nf3", "Nf6"]).zobrist_hash());
+        assert_ne!(Position::new().zobrist_hash(), Position::from_fen(&Position::new().fen().replace(" w ", " b ")).unwrap().zobrist_hash());
+    }
+
+    #[test]
+    fn repetition_and_illegal_moves() {
+        let mut position = Position::new();
+        assert!(position.parse_move("e2e5").is_none());
+        let far = Move::new(position.board(), (6, 4), (3, 4));
+        assert!(!position.make_move(far));
+        for _ in 0..2 {
+            for text in ["Nf3", "Nf6", "Ng1", "Ng8"] {
+                let mv = position.parse_move(text).unwrap();
+                assert!(position.make_move(mv));
+            }
+        }
+        assert_eq!(position.game_result(), Some(GameResult::Repetition));
+        assert!(position.legal_moves().next().is_some());
+        let first = position.legal_moves().next().unwrap();
+        assert!(!position.make_move(first), "moves after the game is over");
+    }
+
+    #[test]
+    fn rebuilt_from_its_positions() {
+        let mut position = Position::new();
+        let mut boards = vec![position.board().clone()];
+        for text in ["Nf3", "Nf6", "Ng1", "Ng8", "Nf3", "Nf6", "Ng1"] {
+            let mv = position.parse_move(text).unwrap();
+            assert!(position.make_move(mv));
+            boards.push(position.board().clone());
+        }
+        let rebuilt = Position::from_positions(Arc::new(Standard), &boards, Color::White).unwrap();
+        assert_eq!(rebuilt.fen(), position.fen());
+        assert_eq!(rebuilt.zobrist_hash(), position.zobrist_hash());
+        let mut rebuilt = rebuilt;
+        assert!(rebuilt.make_move(rebuilt.parse_move("Ng8").unwrap()));
+        assert_eq!(rebuilt.game_result(), Some(GameResult::Repetition));
+        assert!(Position::from_positions(Arc::new(Standard), &[], Color::White).is_none());
+    }
+
+    #[test]
+    fn variants_play_by_their_own_rules() {
+        let mut position = Position::with_variant(variant::by_name("atomic").unwrap());
+        for text in ["e4", "d5", "exd5"] {
+            let mv = position.parse_move(text).unwrap();
+            assert!(position.make_move(mv));
+        }
+        // the capture blew up both pawns
+        assert!(position.board().get_piece((3, 3)).is_none());
+    }
+}
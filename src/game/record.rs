@@ -0,0 +1,148 @@
+use crate::game::board::Board;
+use crate::game::movement::Move;
+use crate::game::piece::Color;
+use std::time::Duration;
+
+// one move of a game and what's known about it
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ply {
+    pub mv: Move,
+    pub color: Color,
+    pub san: String,
+    // the position after the move
+    pub fen: String,
+    // how long the mover took over it
+    pub clock: Option<Duration>,
+    // the engine's view after the move, in centipawns from white's side
+    pub eval: Option<f32>,
+    pub comment: Option<String>,
+}
+
+// a game as it's played or read back: where it started, every position
+// since and the moves between them. the game screen, saved games, replay,
+// analysis and self-play all keep their games in one
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameRecord {
+    first_to_move: Color,
+    // one more than there are plies, starting position first
+    positions: Vec<Board>,
+    pub plies: Vec<Ply>,
+}
+
+impl GameRecord {
+    pub fn new(start: Board, first_to_move: Color) -> Self {
+        GameRecord { first_to_move, positions: vec![start], plies: Vec::new() }
+    }
+
+    pub fn first_to_move(&self) -> Color {
+        self.first_to_move
+    }
+
+    pub fn start(&self) -> &Board {
+        &self.positions[0]
+    }
+
+    // `positions()[i]` is the board `plies[i]` was played in
+    pub fn positions(&self) -> &[Board] {
+        &self.positions
+    }
+
+    pub fn len(&self) -> usize {
+        self.plies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.plies.is_empty()
+    }
+
+    pub fn last_move(&self) -> Option<Move> {
+        self.plies.last().map(|ply| ply.mv)
+    }
+
+    // whose turn it is now. read-back games don't always alternate, so this
+    // goes by who made the last move
+    pub fn to_move(&self) -> Color {
+        self.plies.last().map_or(self.first_to_move, |ply| ply.color.opposite())
+    }
+
+    // adds `mv`, which `color` played to reach `after`
+    pub fn push(&mut self, mv: Move, color: Color, after: Board) -> &mut Ply {
+        let before = &self.positions[self.positions.len() - 1];
+        let san = mv.san(before);
+        let black_first = (self.first_to_move == Color::Black) as usize;
+        let fen = after.fen(color.opposite(), (self.plies.len() + 1 + black_first) / 2 + 1);
+        self.positions.push(after);
+        self.plies.push(Ply { mv, color, san, fen, clock: None, eval: None, comment: None });
+        self.plies.last_mut().expect("just pushed")
+    }
+
+    // back to the first `plies` moves
+    pub fn truncate(&mut self, plies: usize) {
+        self.plies.truncate(plies);
+        self.positions.truncate(plies + 1);
+    }
+
+    // the latest position's FEN
+    pub fn fen(&self) -> String {
+        self.fen_at(self.plies.len())
+    }
+
+    // the FEN of `positions()[position]`
+    pub fn fen_at(&self, position: usize) -> String {
+        match position.checked_sub(1) {
+            Some(ply) => self.plies[ply].fen.clone(),
+            None => self.start().fen(self.first_to_move, 1),
+        }
+    }
+
+    pub fn sans(&self) -> Vec<String> {
+        self.plies.iter().map(|ply| ply.san.clone()).collect()
+    }
+
+    pub fn comments(&self) -> Vec<Option<String>> {
+        self.plies.iter().map(|ply| ply.comment.clone()).collect()
+    }
+
+    // the moves in coordinates, e.g. "e2e4"
+    pub fn uci(&self) -> Vec<String> {
+        self.plies.iter().map(|ply| ply.mv.to_string()).collect()
+    }
+
+    // every position so far and who was to move in it, as the engine
+    // takes its history
+    pub fn with_sides(&self) -> Vec<(Board, Color)> {
+        let sides = self.plies.iter().map(|ply| ply.color).chain([self.to_move()]);
+        self.positions.iter().cloned().zip(sides).collect()
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_json() {
+        let mut board = Board::new();
+        let mut record = GameRecord::new(board.clone(), Color::White);
+        for (from, to) in [((6, 4), (4, 4)), ((1, 3), (3, 3)), ((4, 4), (3, 3))] {
+            let mv = Move::new(&board, from, to);
+            board.move_piece(from, to);
+            let ply = record.push(mv, record.to_move(), board.clone());
+            ply.clock = Some(Duration::from_millis(1500));
+            ply.eval = Some(35.0);
+        }
+        record.plies[2].comment = Some("takes back the center".to_string());
+
+        let json = serde_json::to_string(&record).unwrap();
+        let back: GameRecord = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.sans(), ["e4", "d5", "exd5"]);
+        assert_eq!(back.plies[2].mv.captured, Some(crate::game::piece::PieceType::Pawn));
+        assert_eq!(back.plies[2].fen, record.plies[2].fen);
+        assert_eq!(back.plies[0].clock, Some(Duration::from_millis(1500)));
+        assert_eq!(back.comments(), record.comments());
+        assert!(back.positions()[3].same_position(&board));
+        assert_eq!(back.to_move(), Color::Black);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::game::board::Board;
+use crate::game::piece::Color;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GameResult {
+    Checkmate { winner: Color },
+    Stalemate,
+    // the same position with the same side to move for the third time
+    Repetition,
+    // neither side has the pieces left to mate
+    InsufficientMaterial,
+    // fog of war lets a king walk into capture
+    KingCaptured { winner: Color },
+    Resignation { winner: Color },
+    DrawAgreed,
+    // called by the match runner before the rules could end the game
+    Adjudicated { winner: Option<Color>, reason: &'static str },
+    // a win condition of the variant being played, e.g. "king of the hill"
+    VariantWin { winner: Color, reason: &'static str },
+}
+
+impl GameResult {
+    pub fn winner(self) -> Option<Color> {
+        match self {
+            GameResult::Checkmate { winner }
+            | GameResult::KingCaptured { winner }
+            | GameResult::Resignation { winner }
+            | GameResult::VariantWin { winner, .. } => Some(winner),
+            GameResult::Adjudicated { winner, .. } => winner,
+            GameResult::Stalemate
+            | GameResult::Repetition
+            | GameResult::InsufficientMaterial
+            | GameResult::DrawAgreed => None,
+        }
+    }
+
+    // the PGN result token
+    pub fn score(self) -> &'static str {
+        match self.winner() {
+            Some(Color::White) => "1-0",
+            Some(Color::Black) => "0-1",
+            None => "1/2-1/2",
+        }
+    }
+
+    pub fn termination(self) -> &'static str {
+        match self {
+            GameResult::Checkmate { .. } => "checkmate",
+            GameResult::Stalemate => "stalemate",
+            GameResult::Repetition => "threefold repetition",
+            GameResult::InsufficientMaterial => "insufficient material",
+            GameResult::KingCaptured { .. } => "king captured",
+            GameResult::Resignation { .. } => "resignation",
+            GameResult::DrawAgreed => "agreement",
+            GameResult::VariantWin { reason, .. } | GameResult::Adjudicated { reason, .. } => reason,
+        }
+    }
+}
+
+// whether the last of `positions` has come up three times with the same side
+// to move. positions alternate sides, starting with the first
+pub fn is_threefold(positions: &[Board]) -> bool {
+    let Some(last) = positions.last() else {
+        return false;
+    };
+    let parity = (positions.len() - 1) % 2;
+    positions
+        .iter()
+        .enumerate()
+        .filter(|(ply, board)| ply % 2 == parity && board.same_position(last))
+        .count()
+        >= 3
+}
+
+// checkmate or stalemate, for when `to_move` has no moves left
+pub fn out_of_moves(board: &Board, to_move: Color) -> GameResult {
+    if board.is_in_check(to_move) {
+        GameResult::Checkmate { winner: to_move.opposite() }
+    } else {
+        GameResult::Stalemate
+    }
+}
@@ -1,7 +1,153 @@
 use crate::game::board::Board;
-use crate::game::movement::Move;
+use crate::game::piece::{Color, Piece, PieceType};
 
-pub fn is_valid_move(_board: &Board, _mv: &Move) -> bool {
-    // lowkey forgot this was here. look in board.rs for piece validation
-    true
+type Square = (usize, usize);
+
+// why a move was turned down, for the UI to put into words
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RejectionReason {
+    NoPiece { square: Square },
+    NotYourPiece { piece: Piece },
+    SameSquare,
+    OwnPieceThere { piece: Piece, square: Square },
+    // the piece doesn't move in that direction or that far
+    WrongShape { piece: PieceType },
+    // something stands between the squares
+    Blocked { piece: PieceType, blocker: Piece, square: Square },
+    // a pawn can't take what's straight ahead of it
+    PawnBlocked { blocker: Piece, square: Square },
+    PawnCapturesOnly,
+    PawnDoubleStep,
+    // the king is in check before the move and still is after it
+    StillInCheck { attacker: Piece, square: Square },
+    LeavesKingInCheck { attacker: Piece, square: Square },
+    // something only the variant's rules forbid
+    VariantRule,
+}
+
+// whether `color` may play `from` to `to` under the standard rules, and why
+// not if it may not. the engine sticks to Board::can_move, which is cheaper
+pub fn validate_move(board: &Board, from: Square, to: Square, color: Color) -> Result<(), RejectionReason> {
+    let Some(&piece) = board.get_piece(from) else {
+        return Err(RejectionReason::NoPiece { square: from });
+    };
+    if piece.color != color {
+        return Err(RejectionReason::NotYourPiece { piece });
+    }
+    if from == to {
+        return Err(RejectionReason::SameSquare);
+    }
+    if let Some(&there) = board.get_piece(to).filter(|p| p.color == color) {
+        return Err(RejectionReason::OwnPieceThere { piece: there, square: to });
+    }
+    shape(board, piece, from, to)?;
+
+    let mut after = board.clone();
+    after.move_piece(from, to);
+    if let Some((attacker, square)) = checker(&after, color) {
+        if board.is_in_check(color) && piece.piece_type != PieceType::King {
+            return Err(RejectionReason::StillInCheck { attacker, square });
+        }
+        return Err(RejectionReason::LeavesKingInCheck { attacker, square });
+    }
+    Ok(())
+}
+
+// how the piece moves, and what's in its way
+fn shape(board: &Board, piece: Piece, from: Square, to: Square) -> Result<(), RejectionReason> {
+    let ranks = to.0 as i8 - from.0 as i8;
+    let files = to.1 as i8 - from.1 as i8;
+    let wrong = Err(RejectionReason::WrongShape { piece: piece.piece_type });
+    let straight = ranks == 0 || files == 0;
+    let diagonal = ranks.abs() == files.abs();
+    match piece.piece_type {
+        PieceType::Knight if ranks.abs() * files.abs() != 2 => wrong,
+        PieceType::King if ranks.abs() > 1 || files.abs() > 1 => wrong,
+        PieceType::Rook if !straight => wrong,
+        PieceType::Bishop if !diagonal => wrong,
+        PieceType::Queen if !straight && !diagonal => wrong,
+        PieceType::Rook | PieceType::Bishop | PieceType::Queen => match first_between(board, from, to) {
+            Some((blocker, square)) => Err(RejectionReason::Blocked { piece: piece.piece_type, blocker, square }),
+            None => Ok(()),
+        },
+        PieceType::Pawn => pawn_shape(board, piece.color, from, to, ranks, files),
+        PieceType::Knight | PieceType::King => Ok(()),
+    }
+}
+
+fn pawn_shape(board: &Board, color: Color, from: Square, to: Square, ranks: i8, files: i8) -> Result<(), RejectionReason> {
+    let (direction, start_rank) = if color == Color::White { (-1, 6) } else { (1, 1) };
+    if files.abs() == 1 && ranks == direction {
+        return match board.get_piece(to) {
+            Some(_) => Ok(()),
+            None => Err(RejectionReason::PawnCapturesOnly),
+        };
+    }
+    if files != 0 || (ranks != direction && ranks != 2 * direction) {
+        return Err(RejectionReason::WrongShape { piece: PieceType::Pawn });
+    }
+    if ranks == 2 * direction {
+        if from.0 != start_rank {
+            return Err(RejectionReason::PawnDoubleStep);
+        }
+        if let Some((blocker, square)) = first_between(board, from, to) {
+            return Err(RejectionReason::Blocked { piece: PieceType::Pawn, blocker, square });
+        }
+    }
+    match board.get_piece(to) {
+        Some(&blocker) => Err(RejectionReason::PawnBlocked { blocker, square: to }),
+        None => Ok(()),
+    }
+}
+
+// the nearest piece strictly between two squares on a line
+fn first_between(board: &Board, from: Square, to: Square) -> Option<(Piece, Square)> {
+    let rank_step = (to.0 as i8 - from.0 as i8).signum();
+    let file_step = (to.1 as i8 - from.1 as i8).signum();
+    let mut current = (from.0 as i8 + rank_step, from.1 as i8 + file_step);
+    while (current.0 as usize, current.1 as usize) != to {
+        let square = (current.0 as usize, current.1 as usize);
+        if let Some(&piece) = board.get_piece(square) {
+            return Some((piece, square));
+        }
+        current = (current.0 + rank_step, current.1 + file_step);
+    }
+    None
+}
+
+// an enemy piece giving `color` check, if there is one
+fn checker(board: &Board, color: Color) -> Option<(Piece, Square)> {
+    let king = board.find_king(color)?;
+    let square = *board.attackers_of(king, color.opposite()).first()?;
+    board.get_piece(square).map(|&piece| (piece, square))
+}
+
+// why a set-up position can't be played from, if it can't. `to_move` is the
+// side whose turn it is
+pub fn position_problem(board: &Board, to_move: Color) -> Option<String> {
+    for color in [Color::White, Color::Black] {
+        let kings = squares()
+            .filter(|&pos| board.get_piece(pos) == Some(&Piece::new(PieceType::King, color)))
+            .count();
+        if kings != 1 {
+            return Some(format!("{:?} needs exactly one king, not {}", color, kings));
+        }
+    }
+    let back_rank_pawn = squares()
+        .filter(|&(rank, _)| rank == 0 || rank == 7)
+        .any(|pos| board.get_piece(pos).is_some_and(|p| p.piece_type == PieceType::Pawn));
+    if back_rank_pawn {
+        return Some("Pawns can't stand on the first or last rank".to_string());
+    }
+    if board.is_in_check(to_move.opposite()) {
+        return Some(format!("{:?} is in check but it's {:?}'s move", to_move.opposite(), to_move));
+    }
+    if board.legal_moves(to_move).is_empty() {
+        return Some(format!("{:?} has no legal moves", to_move));
+    }
+    None
+}
+
+fn squares() -> impl Iterator<Item = (usize, usize)> {
+    (0..8).flat_map(|rank| (0..8).map(move |file| (rank, file)))
 }
@@ -0,0 +1,84 @@
+use super::{squares, Square, Variant};
+use crate::game::board::Board;
+use crate::game::movement;
+use crate::game::piece::{Color, PieceType};
+use crate::game::result::GameResult;
+
+// every capture explodes: the capturing piece and all non-pawns next to the
+// captured square leave the board. blowing up the enemy king wins
+pub struct Atomic;
+
+fn neighbours((rank, file): Square) -> impl Iterator<Item = Square> {
+    squares().filter(move |&(r, f)| {
+        (r, f) != (rank, file) && r.abs_diff(rank) <= 1 && f.abs_diff(file) <= 1
+    })
+}
+
+impl Variant for Atomic {
+    fn name(&self) -> &'static str {
+        "atomic"
+    }
+
+    fn title(&self) -> &'static str {
+        "Atomic"
+    }
+
+    fn make_move(&self, board: &mut Board, from: Square, to: Square) -> bool {
+        let Some(piece) = board.get_piece(from).copied() else {
+            return false;
+        };
+        let capture = board.get_piece(to).is_some();
+        // a king would go up with its own explosion
+        if capture && piece.piece_type == PieceType::King {
+            return false;
+        }
+        if !board.move_piece(from, to) {
+            return false;
+        }
+        if capture {
+            board.clear_square(to);
+            for pos in neighbours(to) {
+                if board.get_piece(pos).is_some_and(|p| p.piece_type != PieceType::Pawn) {
+                    board.clear_square(pos);
+                }
+            }
+        }
+        true
+    }
+
+    // no blowing up your own king, and no leaving it in check unless the
+    // move blows up the other one
+    fn destinations(&self, board: &Board, from: Square) -> Vec<Square> {
+        let Some(color) = board.get_piece(from).map(|p| p.color) else {
+            return Vec::new();
+        };
+        movement::destinations(board, from)
+            .into_iter()
+            .filter(|&to| {
+                let mut after = board.clone();
+                self.make_move(&mut after, from, to)
+                    && after.find_king(color).is_some()
+                    && (after.find_king(color.opposite()).is_none() || !after.is_in_check(color))
+            })
+            .collect()
+    }
+
+    fn terminal(&self, board: &Board, to_move: Color) -> Option<GameResult> {
+        board.find_king(to_move).is_none().then_some(GameResult::VariantWin {
+            winner: to_move.opposite(),
+            reason: "explosion",
+        })
+    }
+
+    // pieces crowding a king are what blows it up
+    fn evaluate(&self, board: &Board, color: Color) -> f32 {
+        let crowding = |side: Color| {
+            board.find_king(side).map_or(0, |king| {
+                neighbours(king)
+                    .filter(|&pos| board.get_piece(pos).is_some_and(|p| p.color == side))
+                    .count()
+            }) as f32
+        };
+        (crowding(color.opposite()) - crowding(color)) * 15.0
+    }
+}
@@ -0,0 +1,73 @@
+use super::{squares, Square, Variant};
+use crate::game::board::Board;
+use crate::game::movement;
+use crate::game::piece::Color;
+use crate::game::result::GameResult;
+
+// you only see your own pieces and the squares they could move to;
+// there's no check, the game ends when a king is captured
+pub struct FogOfWar;
+
+impl Variant for FogOfWar {
+    fn name(&self) -> &'static str {
+        "fog"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["fog-of-war", "dark"]
+    }
+
+    fn title(&self) -> &'static str {
+        "Fog of War"
+    }
+
+    fn hides_pieces(&self) -> bool {
+        true
+    }
+
+    fn destinations(&self, board: &Board, from: Square) -> Vec<Square> {
+        movement::destinations(board, from)
+    }
+
+    // the bot searches its own view, which usually lacks the enemy king,
+    // so a missing king can't be what ends a line there
+    fn terminal(&self, _board: &Board, _to_move: Color) -> Option<GameResult> {
+        None
+    }
+
+    fn result(&self, board: &Board, to_move: Color) -> Option<GameResult> {
+        if board.find_king(to_move).is_none() {
+            Some(GameResult::KingCaptured { winner: to_move.opposite() })
+        } else if self.moves(board, to_move).is_empty() {
+            Some(GameResult::Stalemate)
+        } else {
+            None
+        }
+    }
+
+    // enemy pieces `color` can't see are left off
+    fn view(&self, board: &Board, color: Color) -> Board {
+        let mut view = board.clone();
+        let visible = visible_squares(board, color);
+        for pos in squares().filter(|&(r, f)| !visible[r][f]) {
+            view.clear_square(pos);
+        }
+        view
+    }
+}
+
+// squares holding `color`'s pieces or reachable by one of them
+pub fn visible_squares(board: &Board, color: Color) -> [[bool; 8]; 8] {
+    let mut visible = [[false; 8]; 8];
+    for mv in &FogOfWar.moves(board, color) {
+        visible[mv.to.0][mv.to.1] = true;
+        visible[mv.from.0][mv.from.1] = true;
+    }
+    // pieces with no moves at all are still yours to see
+    for (rank, file) in squares() {
+        if board.get_piece((rank, file)).is_some_and(|p| p.color == color) {
+            visible[rank][file] = true;
+        }
+    }
+    visible
+}
@@ -0,0 +1,100 @@
+use super::{squares, Square, Variant};
+use crate::game::board::Board;
+use crate::game::piece::{Color, Piece, PieceType};
+use crate::game::result::GameResult;
+
+// white has 36 pawns and no king against the normal black army. black wins
+// by taking every pawn, white by mating the black king
+pub struct Horde;
+
+fn horde_pawns(board: &Board) -> usize {
+    squares()
+        .filter(|&pos| board.get_piece(pos).is_some_and(|p| p.color == Color::White))
+        .count()
+}
+
+// pawns on the first rank may also step two squares
+fn is_first_rank_double_step(board: &Board, from: Square, to: Square) -> bool {
+    board
+        .get_piece(from)
+        .is_some_and(|p| p.piece_type == PieceType::Pawn && p.color == Color::White)
+        && from.0 == 7
+        && to == (5, from.1)
+        && board.get_piece((6, from.1)).is_none()
+        && board.get_piece(to).is_none()
+}
+
+impl Variant for Horde {
+    fn name(&self) -> &'static str {
+        "horde"
+    }
+
+    fn title(&self) -> &'static str {
+        "Horde"
+    }
+
+    fn initial_board(&self) -> Board {
+        let mut board = Board::new();
+        for (rank, file) in squares().filter(|&(rank, _)| rank >= 3) {
+            board.clear_square((rank, file));
+            if rank >= 4 || [1, 2, 5, 6].contains(&file) {
+                board.put((rank, file), Piece::new(PieceType::Pawn, Color::White));
+            }
+        }
+        board
+    }
+
+    // the horde has no king to need
+    fn read_fen(&self, fen: &str) -> Option<(Board, Color)> {
+        Board::from_fen_with_kings(fen, &[Color::Black])
+    }
+
+    fn make_move(&self, board: &mut Board, from: Square, to: Square) -> bool {
+        if is_first_rank_double_step(board, from, to) {
+            let pawn = *board.get_piece(from).unwrap();
+            board.clear_square(from);
+            board.put(to, pawn);
+            return true;
+        }
+        board.move_piece(from, to)
+    }
+
+    fn destinations(&self, board: &Board, from: Square) -> Vec<Square> {
+        let mut destinations = board.legal_destinations(from);
+        if is_first_rank_double_step(board, from, (5, from.1)) {
+            destinations.push((5, from.1));
+        }
+        destinations
+    }
+
+    fn terminal(&self, board: &Board, _to_move: Color) -> Option<GameResult> {
+        if horde_pawns(board) == 0 {
+            return Some(GameResult::VariantWin {
+                winner: Color::Black,
+                reason: "horde destroyed",
+            });
+        }
+        board
+            .find_king(Color::Black)
+            .is_none()
+            .then_some(GameResult::KingCaptured { winner: Color::White })
+    }
+
+    // the horde wants to get its pawns up the board
+    fn evaluate(&self, board: &Board, color: Color) -> f32 {
+        let advance: usize = squares()
+            .filter(|&pos| board.get_piece(pos).is_some_and(|p| p.color == Color::White))
+            .map(|(rank, _)| 7 - rank)
+            .sum();
+        let score = advance as f32 * 2.0;
+        if color == Color::White {
+            score
+        } else {
+            -score
+        }
+    }
+
+    fn status(&self, board: &Board) -> Vec<String> {
+        vec![format!("Horde pawns left: {}", horde_pawns(board))]
+    }
+}
@@ -0,0 +1,56 @@
+use super::Variant;
+use crate::game::board::Board;
+use crate::game::piece::Color;
+use crate::game::result::GameResult;
+
+// d4, e4, d5 and e5; a king that reaches one of them wins
+const HILL: [(usize, usize); 4] = [(3, 3), (3, 4), (4, 3), (4, 4)];
+
+pub struct KingOfTheHill;
+
+fn distance_to_hill(king: (usize, usize)) -> usize {
+    HILL.iter()
+        .map(|&(r, f)| r.abs_diff(king.0).max(f.abs_diff(king.1)))
+        .min()
+        .unwrap_or(0)
+}
+
+impl Variant for KingOfTheHill {
+    fn name(&self) -> &'static str {
+        "koth"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["king-of-the-hill", "hill"]
+    }
+
+    fn title(&self) -> &'static str {
+        "King of the Hill"
+    }
+
+    fn terminal(&self, board: &Board, to_move: Color) -> Option<GameResult> {
+        for color in [to_move.opposite(), to_move] {
+            match board.find_king(color) {
+                None => return Some(GameResult::KingCaptured { winner: color.opposite() }),
+                Some(king) if HILL.contains(&king) => {
+                    return Some(GameResult::VariantWin {
+                        winner: color,
+                        reason: "king of the hill",
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        None
+    }
+
+    // a king closer to the centre is closer to winning
+    fn evaluate(&self, board: &Board, color: Color) -> f32 {
+        let closeness = |side: Color| {
+            board
+                .find_king(side)
+                .map_or(0.0, |king| (3 - distance_to_hill(king).min(3)) as f32)
+        };
+        (closeness(color) - closeness(color.opposite())) * 40.0
+    }
+}
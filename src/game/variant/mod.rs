@@ -0,0 +1,145 @@
+mod atomic;
+mod fog;
+mod horde;
+mod koth;
+mod standard;
+mod three_check;
+
+pub use atomic::Atomic;
+pub use fog::{visible_squares, FogOfWar};
+pub use horde::Horde;
+pub use koth::KingOfTheHill;
+pub use standard::Standard;
+pub use three_check::ThreeCheck;
+
+use crate::game::board::Board;
+use crate::game::movement::{Move, MoveList};
+use crate::game::piece::Color;
+use crate::game::result::{out_of_moves, GameResult};
+use crate::game::validation::{validate_move, RejectionReason};
+use std::sync::Arc;
+
+pub type Square = (usize, usize);
+
+// the rules a game is played under. everything defaults to standard chess,
+// so a variant only overrides what it changes
+pub trait Variant: Send + Sync {
+    // what `new <name>` takes
+    fn name(&self) -> &'static str;
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    // for display and the PGN Variant tag
+    fn title(&self) -> &'static str;
+
+    fn initial_board(&self) -> Board {
+        Board::new()
+    }
+
+    // a FEN's position under these rules
+    fn read_fen(&self, fen: &str) -> Option<(Board, Color)> {
+        Board::from_fen(fen)
+    }
+
+    // whether results count toward the player's rating
+    fn rated(&self) -> bool {
+        false
+    }
+
+    // whether players only see part of the board
+    fn hides_pieces(&self) -> bool {
+        false
+    }
+
+    // plays the move along with any side effects; false if the piece
+    // can't move like that
+    fn make_move(&self, board: &mut Board, from: Square, to: Square) -> bool {
+        board.move_piece(from, to)
+    }
+
+    // where the piece on `from` may go
+    fn destinations(&self, board: &Board, from: Square) -> Vec<Square> {
+        board.legal_destinations(from)
+    }
+
+    // whether `color` may play the move, and if not, why. the variant's own
+    // destinations decide; the standard rules only explain the refusal
+    fn check_move(&self, board: &Board, from: Square, to: Square, color: Color) -> Result<(), RejectionReason> {
+        let own = board.get_piece(from).is_some_and(|p| p.color == color);
+        if own && self.destinations(board, from).contains(&to) {
+            return Ok(());
+        }
+        validate_move(board, from, to, color).and(Err(RejectionReason::VariantRule))
+    }
+
+    // every move `color` may play
+    fn moves(&self, board: &Board, color: Color) -> MoveList {
+        squares()
+            .filter(|&from| board.get_piece(from).is_some_and(|p| p.color == color))
+            .flat_map(|from| self.destinations(board, from).into_iter().map(move |to| Move::new(board, from, to)))
+            .collect()
+    }
+
+    // win conditions that are cheap enough to check inside the engine's
+    // search; running out of moves is left to `result`
+    fn terminal(&self, board: &Board, to_move: Color) -> Option<GameResult> {
+        board
+            .find_king(to_move)
+            .is_none()
+            .then_some(GameResult::KingCaptured { winner: to_move.opposite() })
+    }
+
+    // whether the game is over with `to_move` next to play
+    fn result(&self, board: &Board, to_move: Color) -> Option<GameResult> {
+        if let Some(result) = self.terminal(board, to_move) {
+            return Some(result);
+        }
+        if self.moves(board, to_move).is_empty() {
+            Some(out_of_moves(board, to_move))
+        } else {
+            None
+        }
+    }
+
+    // added to the engine's evaluation, from `color`'s side
+    fn evaluate(&self, _board: &Board, _color: Color) -> f32 {
+        0.0
+    }
+
+    // variant-specific state worth showing, like check counts
+    fn status(&self, _board: &Board) -> Vec<String> {
+        Vec::new()
+    }
+
+    // the board as `color` knows it
+    fn view(&self, board: &Board, _color: Color) -> Board {
+        board.clone()
+    }
+}
+
+pub fn all() -> Vec<Arc<dyn Variant>> {
+    vec![
+        Arc::new(Standard),
+        Arc::new(FogOfWar),
+        Arc::new(Atomic),
+        Arc::new(KingOfTheHill),
+        Arc::new(Horde),
+        Arc::new(ThreeCheck),
+    ]
+}
+
+pub fn by_name(name: &str) -> Option<Arc<dyn Variant>> {
+    all()
+        .into_iter()
+        .find(|v| v.name() == name || v.aliases().contains(&name))
+}
+
+pub fn by_title(title: &str) -> Option<Arc<dyn Variant>> {
+    all().into_iter().find(|v| v.title() == title)
+}
+
+pub fn squares() -> impl Iterator<Item = Square> {
+    (0..8).flat_map(|rank| (0..8).map(move |file| (rank, file)))
+}
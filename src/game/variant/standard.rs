@@ -0,0 +1,29 @@
+use super::Variant;
+use crate::game::board::Board;
+use crate::game::piece::Color;
+use crate::game::result::GameResult;
+
+pub struct Standard;
+
+impl Variant for Standard {
+    fn name(&self) -> &'static str {
+        "standard"
+    }
+
+    fn title(&self) -> &'static str {
+        "Standard"
+    }
+
+    fn rated(&self) -> bool {
+        true
+    }
+
+    // the other variants can still be won with a lone minor piece or bare
+    // kings, so dead positions are only a draw here
+    fn terminal(&self, board: &Board, to_move: Color) -> Option<GameResult> {
+        if board.find_king(to_move).is_none() {
+            return Some(GameResult::KingCaptured { winner: to_move.opposite() });
+        }
+        board.insufficient_material().then_some(GameResult::InsufficientMaterial)
+    }
+}
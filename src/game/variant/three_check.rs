@@ -0,0 +1,68 @@
+use super::{Square, Variant};
+use crate::game::board::Board;
+use crate::game::piece::Color;
+use crate::game::result::GameResult;
+
+const CHECKS_TO_WIN: u8 = 3;
+
+// standard chess, but the third check given wins
+pub struct ThreeCheck;
+
+impl Variant for ThreeCheck {
+    fn name(&self) -> &'static str {
+        "3check"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["three-check", "threecheck"]
+    }
+
+    fn title(&self) -> &'static str {
+        "Three-check"
+    }
+
+    fn make_move(&self, board: &mut Board, from: Square, to: Square) -> bool {
+        let Some(color) = board.get_piece(from).map(|p| p.color) else {
+            return false;
+        };
+        if !board.move_piece(from, to) {
+            return false;
+        }
+        if board.is_in_check(color.opposite()) {
+            board.add_check(color);
+        }
+        true
+    }
+
+    fn terminal(&self, board: &Board, to_move: Color) -> Option<GameResult> {
+        for color in [to_move.opposite(), to_move] {
+            if board.checks_given(color) >= CHECKS_TO_WIN {
+                return Some(GameResult::VariantWin {
+                    winner: color,
+                    reason: "three checks",
+                });
+            }
+            if board.find_king(color).is_none() {
+                return Some(GameResult::KingCaptured { winner: color.opposite() });
+            }
+        }
+        None
+    }
+
+    // each check is a third of the way to winning
+    fn evaluate(&self, board: &Board, color: Color) -> f32 {
+        let given = board.checks_given(color) as f32;
+        let taken = board.checks_given(color.opposite()) as f32;
+        (given - taken) * 150.0
+    }
+
+    fn status(&self, board: &Board) -> Vec<String> {
+        vec![format!(
+            "Checks: White {}/{}  Black {}/{}",
+            board.checks_given(Color::White),
+            CHECKS_TO_WIN,
+            board.checks_given(Color::Black),
+            CHECKS_TO_WIN
+        )]
+    }
+}
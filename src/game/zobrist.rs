@@ -0,0 +1,78 @@
+use crate::game::board::Board;
+use crate::game::piece::{Color, PieceType};
+
+// a key for every piece on every square, plus the side to move and the
+// checks given for three-check, made at compile time so a hash means the
+// same thing in every build
+const PIECE_KEYS: [[u64; 64]; 12] = piece_keys();
+const BLACK_TO_MOVE: u64 = splitmix(0x5eed_0000_0000_0001);
+const CHECK_KEYS: [[u64; 4]; 2] = check_keys();
+
+const fn splitmix(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+const fn piece_keys() -> [[u64; 64]; 12] {
+    let mut keys = [[0; 64]; 12];
+    let mut piece = 0;
+    while piece < 12 {
+        let mut square = 0;
+        while square < 64 {
+            keys[piece][square] = splitmix((piece * 64 + square) as u64);
+            square += 1;
+        }
+        piece += 1;
+    }
+    keys
+}
+
+const fn check_keys() -> [[u64; 4]; 2] {
+    let mut keys = [[0; 4]; 2];
+    let mut color = 0;
+    while color < 2 {
+        let mut checks = 0;
+        while checks < 4 {
+            keys[color][checks] = splitmix(0xc4ec_0000_0000_0000 + (color * 4 + checks) as u64);
+            checks += 1;
+        }
+        color += 1;
+    }
+    keys
+}
+
+fn piece_index(piece_type: PieceType, color: Color) -> usize {
+    let kind = match piece_type {
+        PieceType::Pawn => 0,
+        PieceType::Knight => 1,
+        PieceType::Bishop => 2,
+        PieceType::Rook => 3,
+        PieceType::Queen => 4,
+        PieceType::King => 5,
+    };
+    if color == Color::White { kind } else { kind + 6 }
+}
+
+// the same for the same placement, side to move and checks given
+pub fn hash(board: &Board, to_move: Color) -> u64 {
+    let mut hash = 0;
+    for rank in 0..8 {
+        for file in 0..8 {
+            if let Some(piece) = board.get_piece((rank, file)) {
+                hash ^= PIECE_KEYS[piece_index(piece.piece_type, piece.color)][rank * 8 + file];
+            }
+        }
+    }
+    if to_move == Color::Black {
+        hash ^= BLACK_TO_MOVE;
+    }
+    for (side, color) in [Color::White, Color::Black].into_iter().enumerate() {
+        let checks = board.checks_given(color) as usize;
+        if checks > 0 {
+            hash ^= CHECK_KEYS[side][checks.min(3)];
+        }
+    }
+    hash
+}
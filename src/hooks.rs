@@ -0,0 +1,184 @@
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum HookEvent {
+    YourMove,
+    // every move, yours or the bot's, that isn't a capture or a check
+    MovePlayed,
+    Capture,
+    Check,
+    GameOver,
+    ImportFinished,
+}
+
+impl HookEvent {
+    pub const ALL: [HookEvent; 6] = [
+        HookEvent::YourMove,
+        HookEvent::MovePlayed,
+        HookEvent::Capture,
+        HookEvent::Check,
+        HookEvent::GameOver,
+        HookEvent::ImportFinished,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HookEvent::YourMove => "your_move",
+            HookEvent::MovePlayed => "move_played",
+            HookEvent::Capture => "capture",
+            HookEvent::Check => "check",
+            HookEvent::GameOver => "game_over",
+            HookEvent::ImportFinished => "import_finished",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            HookEvent::YourMove => "The bot has moved and it's your turn",
+            HookEvent::MovePlayed => "Any other move, by either side",
+            HookEvent::Capture => "A piece is taken",
+            HookEvent::Check => "A king is put in check",
+            HookEvent::GameOver => "The game ends",
+            HookEvent::ImportFinished => "An import from chess.com is done",
+        }
+    }
+
+    // the ones worth a desktop notification: things you may have looked away
+    // from, not every move
+    fn desktop(self) -> bool {
+        matches!(self, HookEvent::YourMove | HookEvent::GameOver | HookEvent::ImportFinished)
+    }
+}
+
+// [hooks] in config.toml, e.g.
+//   desktop = true
+//   bell = ["check", "game_over"]
+//   your_move = "paplay ~/ding.ogg"
+// commands run through `sh -c` with CHESSRL_EVENT and CHESSRL_MESSAGE set
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Hooks {
+    pub desktop: bool,
+    // events that ring the terminal bell, by name
+    pub bell: Vec<String>,
+    pub your_move: Option<String>,
+    pub move_played: Option<String>,
+    pub capture: Option<String>,
+    pub check: Option<String>,
+    pub game_over: Option<String>,
+    pub import_finished: Option<String>,
+}
+
+// something that hears about events. a new integration is one more of these
+// and a line in Hooks::notifiers
+trait Notifier {
+    fn notify(&self, event: HookEvent, message: &str);
+}
+
+struct Bell;
+
+impl Notifier for Bell {
+    fn notify(&self, _event: HookEvent, _message: &str) {
+        let mut out = std::io::stdout();
+        let _ = out.write_all(b"\x07");
+        let _ = out.flush();
+    }
+}
+
+struct ShellCommand<'a>(&'a str);
+
+impl Notifier for ShellCommand<'_> {
+    fn notify(&self, event: HookEvent, message: &str) {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c")
+            .arg(self.0)
+            .env("CHESSRL_EVENT", event.name())
+            .env("CHESSRL_MESSAGE", message);
+        spawn_detached(cmd);
+    }
+}
+
+struct Desktop;
+
+impl Notifier for Desktop {
+    fn notify(&self, _event: HookEvent, message: &str) {
+        spawn_detached(desktop_notification(message));
+    }
+}
+
+impl Hooks {
+    fn command_for(&self, event: HookEvent) -> Option<&str> {
+        match event {
+            HookEvent::YourMove => self.your_move.as_deref(),
+            HookEvent::MovePlayed => self.move_played.as_deref(),
+            HookEvent::Capture => self.capture.as_deref(),
+            HookEvent::Check => self.check.as_deref(),
+            HookEvent::GameOver => self.game_over.as_deref(),
+            HookEvent::ImportFinished => self.import_finished.as_deref(),
+        }
+    }
+
+    pub fn rings(&self, event: HookEvent) -> bool {
+        self.bell.iter().any(|name| name == event.name())
+    }
+
+    // turns the bell on or off for `event`, returning whether it's now on
+    pub fn toggle_bell(&mut self, event: HookEvent) -> bool {
+        if self.rings(event) {
+            self.bell.retain(|name| name != event.name());
+            false
+        } else {
+            self.bell.push(event.name().to_string());
+            true
+        }
+    }
+
+    // whatever is set up to hear about `event`
+    fn notifiers(&self, event: HookEvent) -> Vec<Box<dyn Notifier + '_>> {
+        let mut notifiers: Vec<Box<dyn Notifier + '_>> = Vec::new();
+        if self.rings(event) {
+            notifiers.push(Box::new(Bell));
+        }
+        if let Some(command) = self.command_for(event) {
+            notifiers.push(Box::new(ShellCommand(command)));
+        }
+        if self.desktop && event.desktop() {
+            notifiers.push(Box::new(Desktop));
+        }
+        notifiers
+    }
+
+    // fire and forget; a broken hook must never take the game down with it
+    pub fn fire(&self, event: HookEvent, message: &str) {
+        for notifier in self.notifiers(event) {
+            notifier.notify(event, message);
+        }
+    }
+}
+
+fn desktop_notification(message: &str) -> Command {
+    if cfg!(target_os = "macos") {
+        let mut cmd = Command::new("osascript");
+        cmd.arg("-e").arg(format!(
+            "display notification {:?} with title \"chessrl\"",
+            message
+        ));
+        cmd
+    } else {
+        let mut cmd = Command::new("notify-send");
+        cmd.arg("chessrl").arg(message);
+        cmd
+    }
+}
+
+fn spawn_detached(mut cmd: Command) {
+    // the hook's output would scribble over the TUI
+    cmd.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null());
+    if let Ok(mut child) = cmd.spawn() {
+        std::thread::spawn(move || {
+            let _ = child.wait();
+        });
+    }
+}
@@ -0,0 +1,69 @@
+use serde::Serialize;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+// set once nothing reads stdout any more. the reports stay on stderr
+static CLOSED: AtomicBool = AtomicBool::new(false);
+
+// what the headless commands report with --json-events, one JSON object a
+// line on stdout, told apart by their "event" field. scores are
+// centipawns, moves both in coordinates and SAN
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event<'a> {
+    GameStart {
+        variant: &'a str,
+        // plies played from a book opening before the players took over
+        opening: Vec<String>,
+    },
+    // a search finished. the score and mate are from white's side, like
+    // the move events' evals, whichever side searched
+    Eval {
+        color: &'a str,
+        score: f32,
+        mate: Option<i32>,
+        depth: usize,
+        simulations: u32,
+        pv: Vec<String>,
+    },
+    Move {
+        ply: usize,
+        color: &'a str,
+        uci: String,
+        san: String,
+        // the referee's eval after the move, from white's side
+        eval: f32,
+        clock_ms: u128,
+    },
+    GameEnd {
+        result: &'a str,
+        winner: Option<&'a str>,
+        termination: &'a str,
+        plies: usize,
+    },
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn emit(event: Event) {
+    if !enabled() || CLOSED.load(Ordering::Relaxed) {
+        return;
+    }
+    let Ok(line) = serde_json::to_string(&event) else {
+        return;
+    };
+    // whatever read the stream has gone, like `head -1` once it has its
+    // line: the events stop and the games play on
+    if let Err(e) = writeln!(io::stdout().lock(), "{}", line) {
+        if e.kind() == io::ErrorKind::BrokenPipe {
+            CLOSED.store(true, Ordering::Relaxed);
+        }
+    }
+}
@@ -0,0 +1,4 @@
+// the rules of the game, for programs that want them without the engine or
+// the screens: game::position::Position plays a game through
+pub mod game;
+pub mod utils;
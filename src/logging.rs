@@ -0,0 +1,115 @@
+use crate::utils::{data_dir, pgn_date_time};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Mutex;
+
+// the log starts over once it grows past this, keeping one old copy
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Off,
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+}
+
+impl Level {
+    fn label(self) -> &'static str {
+        match self {
+            Level::Off => "OFF",
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+        }
+    }
+}
+
+// [log] in config.toml, e.g.
+//   level = "debug"
+// everything goes to chessrl.log in the data directory, since the TUI owns
+// the terminal
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogConfig {
+    pub level: Level,
+    // write a report with the game so far when chessrl crashes
+    pub crash_reports: bool,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        LogConfig { level: Level::default(), crash_reports: true }
+    }
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Off as u8);
+static FILE: Mutex<Option<File>> = Mutex::new(None);
+
+pub fn path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("chessrl.log"))
+}
+
+// opens the log file; until this runs, and if it fails, nothing is logged
+pub fn init(level: Level) {
+    if level == Level::Off {
+        return;
+    }
+    let Some(path) = path() else { return };
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    if fs::metadata(&path).is_ok_and(|meta| meta.len() > MAX_LOG_BYTES) {
+        let _ = fs::rename(&path, path.with_extension("log.old"));
+    }
+    let Ok(file) = OpenOptions::new().create(true).append(true).open(&path) else {
+        return;
+    };
+    if let Ok(mut slot) = FILE.lock() {
+        *slot = Some(file);
+        LEVEL.store(level as u8, Ordering::Relaxed);
+    }
+}
+
+pub fn enabled(level: Level) -> bool {
+    level != Level::Off && level as u8 <= LEVEL.load(Ordering::Relaxed)
+}
+
+pub fn log(level: Level, message: &str) {
+    if !enabled(level) {
+        return;
+    }
+    let unix_secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let (date, time) = pgn_date_time(unix_secs);
+    // a failed write has nowhere better to be reported
+    if let Ok(mut slot) = FILE.lock() {
+        if let Some(file) = slot.as_mut() {
+            let _ = writeln!(file, "{} {} {:<5} {}", date, time, level.label(), message);
+        }
+    }
+}
+
+pub fn error(message: &str) {
+    log(Level::Error, message);
+}
+
+pub fn warn(message: &str) {
+    log(Level::Warn, message);
+}
+
+pub fn info(message: &str) {
+    log(Level::Info, message);
+}
+
+pub fn debug(message: &str) {
+    log(Level::Debug, message);
+}
@@ -1,50 +1,142 @@
+mod archive;
+mod cli;
+mod config;
 mod engine;
-mod game;
+mod hooks;
+mod json_events;
+mod logging;
+mod profile;
+mod session;
 mod ui;
-mod utils;
 
-use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
+use chessrl::{game, utils};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io::{self, Result};
+use std::path::PathBuf;
+use std::time::Instant;
 
-use ui::app::{App, GameState};
+use config::Config;
+use ui::app::{App, GameState, MENU_ITEMS};
+use ui::events::{AppEvent, EventLoop};
+use ui::follow::FeedSource;
+use ui::i18n::Language;
+use ui::keymap::Action;
+use ui::panels::Focus;
+use ui::terminal::{self as term, TerminalGuard};
 
 fn main() -> Result<()> {
-    // terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let accessible = cli::take_accessible(&mut args);
+    let json_events = cli::take_json_events(&mut args);
+    let Startup { seed, config, export, weights, follow, serve } = match startup(&mut args) {
+        Ok(startup) => startup,
+        Err(err) => {
+            eprintln!("chessrl: {}", err);
+            std::process::exit(1);
+        }
+    };
+    logging::init(config.log.level);
+    session::start();
+    if json_events && args.is_empty() {
+        eprintln!("chessrl: --json-events works with match, train, arena and calibrate only");
+        std::process::exit(1);
+    }
+    if !args.is_empty() {
+        if follow.is_some() || serve.is_some() {
+            eprintln!("chessrl: --follow and --serve work with the game only");
+            std::process::exit(1);
+        }
+        if json_events {
+            json_events::enable();
+        }
+        if let Err(err) = cli::run(&args, &config, seed, export.as_deref(), weights.as_deref()) {
+            eprintln!("chessrl: {}", err);
+            std::process::exit(1);
+        }
+        // the commands print their own reports; the log gets the summary
+        session::summary();
+        return Ok(());
+    }
+
+    // terminal, given back when the guard drops, even on a panic
+    term::install_panic_hook(config.log.crash_reports);
+    let guard = TerminalGuard::enter()?;
+    let backend = CrosstermBackend::new(io::stdout());
     let mut terminal = Terminal::new(backend)?;
 
+    ui::i18n::set_language(Language::resolve(&config.ui.language));
+
     // make the app, then run it
-    let mut app = App::new();
-    let res = run_app(&mut terminal, &mut app);
-
-    // restore the terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    let events = EventLoop::new();
+    let mut app = App::new(config, seed, events.sender());
+    // the flags are for this run only; the config keeps its own settings
+    app.accessible |= accessible;
+    if weights.is_some() {
+        app.use_weights_for_run(weights);
+    }
+    if let Some(port) = serve {
+        app.start_relay(port);
+    }
+    if let Some(source) = follow {
+        app.start_follow(FeedSource::parse(&source));
+    } else {
+        app.offer_resume();
+    }
+    let res = run_app(&mut terminal, &mut app, &events);
+    drop(guard);
 
     if let Err(err) = res {
         println!("{err:?}");
     }
+    if let Some(path) = export {
+        if let Err(err) = cli::export_weights(&app.rl_engine, &path) {
+            eprintln!("chessrl: couldn't write the weights: {}", err);
+        }
+    }
+    for line in session::summary() {
+        println!("{}", line);
+    }
 
     Ok(())
 }
 
+struct Startup {
+    seed: Option<u64>,
+    config: Config,
+    export: Option<PathBuf>,
+    weights: Option<String>,
+    follow: Option<String>,
+    serve: Option<u16>,
+}
+
+// the options that apply to every mode, and the config they point at. a
+// broken config file is only fatal when it was asked for by name
+fn startup(args: &mut Vec<String>) -> Result<Startup> {
+    let seed = cli::take_seed(args)?;
+    let export = cli::take_export_weights(args)?;
+    let config = match cli::take_config(args)? {
+        Some(path) => {
+            Config::use_path(path);
+            Config::try_load()?
+        }
+        None => Config::load(),
+    };
+    // weights files are found under the data directory the config may move
+    config.paths.apply();
+    let weights = cli::take_weights(args)?;
+    let follow = cli::take_follow(args)?;
+    let serve = cli::take_serve(args)?;
+    Ok(Startup { seed, config, export, weights, follow, serve })
+}
+
 fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
+    events: &EventLoop,
 ) -> io::Result<()> {
+    let mut last_tick = Instant::now();
     loop {
         terminal.draw(|f| app.draw(f))?;
 
@@ -52,54 +144,263 @@ fn run_app<B: ratatui::backend::Backend>(
             return Ok(());
         }
 
-        if let Event::Key(key) = event::read()? {
-            match key.code {
-                KeyCode::Char('q') => {
-                    app.should_quit = true;
-                }
-                KeyCode::Esc => match app.game_state {
-                    GameState::Playing | GameState::About => {
-                        app.game_state = GameState::Menu;
-                        app.command_buffer.clear(); // get rid of any artifacts from previous screen when there is pending commands
-                    }
-                    GameState::Menu => {
-                        app.should_quit = true;
-                    }
-                },
-                KeyCode::Up => match app.game_state {
-                    GameState::Menu => app.menu_index = app.menu_index.saturating_sub(1),
-                    _ => {}
-                },
-                KeyCode::Down => match app.game_state {
-                    GameState::Menu => app.menu_index = (app.menu_index + 1).min(1),
-                    _ => {}
-                },
-                KeyCode::Enter => match app.game_state {
-                    GameState::Menu => {
-                        app.game_state = match app.menu_index {
-                            0 => GameState::Playing,
-                            1 => GameState::About,
-                            _ => GameState::Menu,
-                        };
-                    }
-                    GameState::Playing => {
-                        if let Some(msg) = app.handle_command() {
-                            println!("{}", msg);
-                        }
-                    }
-                    GameState::About => app.game_state = GameState::Menu,
-                },
-                KeyCode::Char(c) => {
-                    if let GameState::Playing = app.game_state {
-                        app.command_buffer.push(c);
-                    }
-                }
-                KeyCode::Backspace => {
-                    if let GameState::Playing = app.game_state {
-                        app.command_buffer.pop();
-                    }
+        // wait for something to happen, but never past the next tick
+        match events.next(app.time_until_tick(last_tick.elapsed())) {
+            AppEvent::Tick => {}
+            event => dispatch(app, event),
+        }
+        if app.time_until_tick(last_tick.elapsed()).is_zero() {
+            dispatch(app, AppEvent::Tick);
+            last_tick = Instant::now();
+        }
+    }
+}
+
+fn dispatch(app: &mut App, event: AppEvent) {
+    match event {
+        AppEvent::Key(key) => handle_key(app, key),
+        AppEvent::Mouse(mouse) => handle_mouse(app, mouse),
+        AppEvent::Tick => app.on_tick(),
+        AppEvent::EngineProgress { search, stats } => app.on_search_progress(search, stats),
+        AppEvent::EngineMove { search, result, stats } => app.on_search_finished(search, result, stats),
+        AppEvent::Feed(update) => app.on_feed(update),
+        AppEvent::DailyPuzzle(fetched) => app.on_daily_puzzle(fetched),
+        AppEvent::Uci { engine, reply } => app.on_uci(engine, reply),
+        AppEvent::Peer(event) => app.on_peer(event),
+        // the screen is redrawn after every event anyway
+        AppEvent::Resize => {}
+    }
+}
+
+// the wheel scrolls the move history, and a click on a move jumps to it
+fn handle_mouse(app: &mut App, mouse: MouseEvent) {
+    if app.dialog.is_some() || app.palette.is_some() {
+        return;
+    }
+    if let Some(help) = &mut app.help {
+        match mouse.kind {
+            MouseEventKind::ScrollUp => help.scroll_by(-1),
+            MouseEventKind::ScrollDown => help.scroll_by(1),
+            _ => {}
+        }
+        return;
+    }
+    match app.game_state {
+        GameState::Playing => match mouse.kind {
+            MouseEventKind::ScrollUp => app.scroll_history(true),
+            MouseEventKind::ScrollDown => app.scroll_history(false),
+            MouseEventKind::Down(MouseButton::Left) => app.click_history(mouse.column, mouse.row),
+            _ => {}
+        },
+        GameState::Replay => {
+            if let MouseEventKind::Down(MouseButton::Left) = mouse.kind {
+                app.click_replay(mouse.column, mouse.row);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_key(app: &mut App, key: KeyEvent) {
+    if key.kind != KeyEventKind::Press {
+        return;
+    }
+
+    if app.dialog.is_some() {
+        app.handle_dialog_key(key);
+        return;
+    }
+
+    if app.help.is_some() {
+        app.handle_help_key(key);
+        return;
+    }
+
+    if app.palette.is_some() {
+        app.handle_palette_key(key);
+        return;
+    }
+
+    if let GameState::Settings = app.game_state {
+        if app.capturing_key || key.code != KeyCode::Esc {
+            app.handle_settings_key(key);
+            return;
+        }
+    }
+
+    if let GameState::History = app.game_state {
+        if key.code != KeyCode::Esc && app.config.keys.action_for(&key, true).is_none() {
+            app.handle_history_key(key);
+            return;
+        }
+    }
+
+    if let GameState::Setup = app.game_state {
+        if key.code != KeyCode::Esc && app.config.keys.action_for(&key, true).is_none() {
+            app.handle_setup_key(key);
+            return;
+        }
+    }
+
+    if let GameState::Editor = app.game_state {
+        if key.code != KeyCode::Esc && app.config.keys.action_for(&key, true).is_none() {
+            app.handle_editor_key(key);
+            return;
+        }
+    }
+
+    if let GameState::Training = app.game_state {
+        if key.code != KeyCode::Esc && app.config.keys.action_for(&key, true).is_none() {
+            app.handle_training_key(key);
+            return;
+        }
+    }
+
+    if let GameState::Themes = app.game_state {
+        if key.code != KeyCode::Esc {
+            app.handle_themes_key(key);
+            return;
+        }
+    }
+
+    if let GameState::Heatmap = app.game_state {
+        if key.code != KeyCode::Esc && app.config.keys.action_for(&key, true).is_none() {
+            app.handle_heatmap_key(key);
+            return;
+        }
+    }
+
+    if let GameState::Arena = app.game_state {
+        if key.code != KeyCode::Esc && app.config.keys.action_for(&key, true).is_none() {
+            app.handle_arena_key(key);
+            return;
+        }
+    }
+
+    if let GameState::Replay = app.game_state {
+        if key.code != KeyCode::Esc && app.config.keys.action_for(&key, true).is_none() {
+            app.handle_replay_key(key);
+            return;
+        }
+    }
+
+    if let GameState::Watch = app.game_state {
+        if key.code != KeyCode::Esc && app.config.keys.action_for(&key, true).is_none() {
+            app.handle_watch_key(key);
+            return;
+        }
+    }
+
+    if let GameState::Puzzle = app.game_state {
+        if key.code != KeyCode::Esc && app.config.keys.action_for(&key, true).is_none() {
+            app.handle_puzzle_key(key);
+            return;
+        }
+    }
+
+    if let GameState::Follow = app.game_state {
+        if key.code != KeyCode::Esc && app.config.keys.action_for(&key, true).is_none() {
+            app.handle_follow_key(key);
+            return;
+        }
+    }
+
+    // while playing, plain letters always go to the command box
+    let typing = matches!(app.game_state, GameState::Playing);
+    // the help and palette keys work on an empty command line even when
+    // they're characters
+    if typing && app.command_input.is_empty() {
+        if let Some(action) = [Action::Help, Action::Palette].into_iter().find(|&action| app.config.keys.binding(action).matches(&key)) {
+            app.perform(action);
+            return;
+        }
+    }
+    if typing && key.modifiers.is_empty() && app.handle_focus_key(key) {
+        return;
+    }
+    // tab completes what's typed in the command box, and otherwise moves
+    // the focus on. ctrl+tab is the next tab's key
+    if typing && key.code == KeyCode::Tab && key.modifiers.is_empty() {
+        if app.focused() == Focus::Command && !app.command_input.is_empty() {
+            app.complete_command();
+        } else {
+            app.cycle_focus(false);
+        }
+        return;
+    }
+    if typing && key.code == KeyCode::BackTab {
+        app.cycle_focus(true);
+        return;
+    }
+    app.reset_completion();
+
+    if let Some(action) = app.config.keys.action_for(&key, typing) {
+        app.perform(action);
+        return;
+    }
+
+    match key.code {
+        KeyCode::Char('q') if !typing => app.request_quit(),
+        KeyCode::Esc => match app.game_state {
+            GameState::Replay => app.game_state = app.replay_return,
+            GameState::Heatmap | GameState::Arena => app.game_state = GameState::Playing,
+            GameState::Watch => app.close_watch(),
+            GameState::Setup
+            | GameState::Editor
+            | GameState::Training
+            | GameState::Playing
+            | GameState::History
+            | GameState::Profile
+            | GameState::Themes
+            | GameState::Settings
+            | GameState::About
+            | GameState::Follow
+            | GameState::Puzzle => {
+                app.game_state = GameState::Menu;
+                app.command_input.clear(); // get rid of any artifacts from previous screen when there is pending commands
+                app.status.clear();
+            }
+            GameState::Menu => app.request_quit(),
+        },
+        KeyCode::Up if !typing => {
+            if let GameState::Menu = app.game_state {
+                app.menu_index = app.menu_index.saturating_sub(1)
+            }
+        }
+        KeyCode::Down if !typing => {
+            if let GameState::Menu = app.game_state {
+                app.menu_index = (app.menu_index + 1).min(MENU_ITEMS.len() - 1)
+            }
+        }
+        KeyCode::Enter => match app.game_state {
+            GameState::Menu => app.open_menu_item(app.menu_index),
+            GameState::Playing => {
+                if let Some(msg) = app.handle_command() {
+                    app.report(msg);
                 }
-                _ => {}
+            }
+            GameState::About | GameState::Profile => app.game_state = GameState::Menu,
+            GameState::Setup
+            | GameState::Editor
+            | GameState::Training
+            | GameState::History
+            | GameState::Themes
+            | GameState::Settings
+            | GameState::Replay
+            | GameState::Heatmap
+            | GameState::Arena
+            | GameState::Follow
+            | GameState::Puzzle
+            | GameState::Watch => {}
+        },
+        // space on an empty command line has the bot move now
+        KeyCode::Char(' ') if typing && key.modifiers.is_empty() && app.command_input.is_empty() && app.can_move_now() => {
+            let message = app.move_now();
+            app.report(message);
+        }
+        _ => {
+            if let GameState::Playing = app.game_state {
+                app.command_input.handle_key(key);
             }
         }
     }
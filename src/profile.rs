@@ -0,0 +1,149 @@
+use crate::engine::difficulty::Difficulty;
+use crate::engine::opponent::OpponentModel;
+use crate::game::handicap::Handicap;
+use crate::utils::data_dir;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+const START_RATING: f32 = 1200.0;
+// how far one game can move the rating
+const K_FACTOR: f32 = 32.0;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RatedGame {
+    pub date: String,
+    pub level: Difficulty,
+    // 1 for a win, 0.5 for a draw, 0 for a loss
+    pub score: f32,
+    pub rating_after: f32,
+    // the odds the game was played at. only even games move the rating;
+    // the rest measure what their odds are worth
+    #[serde(default)]
+    pub handicap: Handicap,
+}
+
+// the player's results against the bot, kept in <data dir>/profile.json
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub rating: f32,
+    pub games: Vec<RatedGame>,
+    // built up from the same games
+    pub model: OpponentModel,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Profile {
+            rating: START_RATING,
+            games: Vec::new(),
+            model: OpponentModel::default(),
+        }
+    }
+}
+
+impl Profile {
+    pub fn path() -> Option<PathBuf> {
+        data_dir().map(|dir| dir.join("profile.json"))
+    }
+
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = serde_json::to_string_pretty(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, text)
+    }
+
+    // standard Elo update against the level's nominal rating; returns the change
+    pub fn record(&mut self, date: String, level: Difficulty, score: f32) -> f32 {
+        let expected = 1.0 / (1.0 + 10f32.powf((level.rating() - self.rating) / 400.0));
+        let change = K_FACTOR * (score - expected);
+        self.rating += change;
+        self.games.push(RatedGame {
+            date,
+            level,
+            score,
+            rating_after: self.rating,
+            handicap: Handicap::None,
+        });
+        change
+    }
+
+    // a game at odds, kept apart from the rating
+    pub fn record_odds(&mut self, date: String, level: Difficulty, handicap: Handicap, score: f32) {
+        self.games.push(RatedGame {
+            date,
+            level,
+            score,
+            rating_after: self.rating,
+            handicap,
+        });
+    }
+
+    // the games that moved the rating
+    pub fn rated_games(&self) -> impl Iterator<Item = &RatedGame> {
+        self.games.iter().filter(|g| g.handicap == Handicap::None)
+    }
+
+    // how many rating points `handicap` has been worth to the player, and
+    // over how many games: their performance at those odds against what
+    // their rating expected. the score gets half a point either way so a
+    // first win isn't worth infinitely much
+    pub fn odds_gap(&self, handicap: Handicap) -> Option<(f32, usize)> {
+        let games: Vec<&RatedGame> = self.games.iter().filter(|g| g.handicap == handicap).collect();
+        if handicap == Handicap::None || games.is_empty() {
+            return None;
+        }
+        let count = games.len() as f32;
+        let score = (games.iter().map(|g| g.score).sum::<f32>() + 0.5) / (count + 1.0);
+        let above = games.iter().map(|g| g.level.rating() - g.rating_after).sum::<f32>() / count;
+        Some((above + 400.0 * (score / (1.0 - score)).log10(), games.len()))
+    }
+
+    // (wins, draws, losses) against one level, in even games
+    pub fn record_against(&self, level: Difficulty) -> (usize, usize, usize) {
+        self.rated_games()
+            .filter(|g| g.level == level)
+            .fold((0, 0, 0), |(w, d, l), g| match g.score {
+                s if s >= 1.0 => (w + 1, d, l),
+                s if s > 0.0 => (w, d + 1, l),
+                _ => (w, d, l + 1),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a game at odds leaves the rating alone and shows up in what the
+    // odds are worth instead
+    #[test]
+    fn odds_games_stay_out_of_the_rating() {
+        let mut profile = Profile::default();
+        profile.record_odds("2026.01.01".to_string(), Difficulty::Medium, Handicap::Queen, 1.0);
+        profile.record_odds("2026.01.02".to_string(), Difficulty::Medium, Handicap::Queen, 1.0);
+        assert_eq!(profile.rating, START_RATING);
+        assert_eq!(profile.rated_games().count(), 0);
+        assert_eq!(profile.record_against(Difficulty::Medium), (0, 0, 0));
+        let (gap, games) = profile.odds_gap(Handicap::Queen).unwrap();
+        assert_eq!(games, 2);
+        // 2.5 of 3, smoothed, is 280 points of performance over a level 100 below the rating
+        assert!((gap - (-100.0 + 400.0 * 5f32.log10())).abs() < 0.01);
+        assert!(profile.odds_gap(Handicap::Rook).is_none());
+        profile.record("2026.01.03".to_string(), Difficulty::Medium, 0.0);
+        assert!(profile.rating < START_RATING);
+        assert_eq!(profile.rated_games().count(), 1);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::logging;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// what this run of chessrl got up to, for the summary printed on the way
+// out. the game, the engine and the headless commands all report here
+struct Stats {
+    started: Option<Instant>,
+    won: u32,
+    drawn: u32,
+    lost: u32,
+    // games the engine played itself: matches, training, the arena
+    engine_games: u32,
+    searches: u64,
+    simulations: u64,
+    learning_updates: u64,
+    // the clocks of every move made in the game screen
+    time_at_board: Duration,
+}
+
+static STATS: Mutex<Stats> = Mutex::new(Stats {
+    started: None,
+    won: 0,
+    drawn: 0,
+    lost: 0,
+    engine_games: 0,
+    searches: 0,
+    simulations: 0,
+    learning_updates: 0,
+    time_at_board: Duration::ZERO,
+});
+
+fn update(change: impl FnOnce(&mut Stats)) {
+    if let Ok(mut stats) = STATS.lock() {
+        change(&mut stats);
+    }
+}
+
+// the session's clock starts here
+pub fn start() {
+    update(|stats| stats.started = Some(Instant::now()));
+}
+
+// a game in the game screen ended: Some(true) if the player won, None for a draw
+pub fn game_over(won: Option<bool>) {
+    update(|stats| match won {
+        Some(true) => stats.won += 1,
+        Some(false) => stats.lost += 1,
+        None => stats.drawn += 1,
+    });
+}
+
+pub fn engine_game() {
+    update(|stats| stats.engine_games += 1);
+}
+
+pub fn searched(simulations: u64) {
+    update(|stats| {
+        stats.searches += 1;
+        stats.simulations += simulations;
+    });
+}
+
+pub fn learned() {
+    update(|stats| stats.learning_updates += 1);
+}
+
+pub fn moved(clock: Duration) {
+    update(|stats| stats.time_at_board += clock);
+}
+
+// the summary, one line each, and in the log as well
+pub fn summary() -> Vec<String> {
+    let Ok(stats) = STATS.lock() else {
+        return Vec::new();
+    };
+    let elapsed = stats.started.map_or(Duration::ZERO, |started| started.elapsed());
+    let games = stats.won + stats.drawn + stats.lost;
+    let mut lines = vec![format!("Session: {}", format_duration(elapsed))];
+    if games > 0 {
+        lines.push(format!(
+            "Games: {} ({} won, {} drawn, {} lost), {} at the board",
+            games,
+            stats.won,
+            stats.drawn,
+            stats.lost,
+            format_duration(stats.time_at_board)
+        ));
+    } else if stats.time_at_board > Duration::ZERO {
+        lines.push(format!("Games: none finished, {} at the board", format_duration(stats.time_at_board)));
+    }
+    if stats.engine_games > 0 {
+        lines.push(format!("Engine games: {}", stats.engine_games));
+    }
+    if stats.searches > 0 {
+        lines.push(format!("Engine: {} simulations over {} searches", stats.simulations, stats.searches));
+    }
+    if stats.learning_updates > 0 {
+        lines.push(format!("Learning: {} updates", stats.learning_updates));
+    }
+    logging::info(&format!("session over: {}", lines.join("; ")));
+    lines
+}
+
+// "1h 02m", "4m 10s" or "12s"
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..60 => format!("{}s", secs),
+        60..3600 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
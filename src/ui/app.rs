@@ -1,555 +1,6009 @@
-fn parse_coordinate(coord: &str) -> Option<(usize, usize)> {
-    if coord.len() != 2 {
-        return None;
-    }
-
-    let file = coord.chars().nth(0)?.to_ascii_lowercase();
-    let rank = coord.chars().nth(1)?.to_digit(10)?;
-
-    if !('a'..='h').contains(&file) || !(1..=8).contains(&rank) {
-        return None;
-    }
-
-    let file_idx = (file as u8 - b'a') as usize;
-    let rank_idx = 8 - rank as usize;
-
-    Some((rank_idx, file_idx))
-}
-
-fn coordinate_to_string(pos: (usize, usize)) -> String {
-    let file = (b'a' + pos.1 as u8) as char;
-    let rank = 8 - pos.0;
-    format!("{}{}", file, rank)
-}
-use crate::engine::rl::RLEngine;
+use crate::archive::games::{self, NewGame, SavedGame};
+use crate::archive::lichess::{self, Puzzle};
+use crate::config::Config;
+use crate::engine::analysis::Judgement;
+use crate::engine::arena::{self, Tournament};
+use crate::engine::curriculum::{Opponent, Scripted};
+use crate::engine::difficulty::{Difficulty, MAX_MULTI_PV};
+use crate::engine::material::PieceValues;
+use crate::engine::opponent::{position_key, OPENING_PLIES};
+use crate::engine::personality::Personality;
+use crate::engine::sparring;
+use crate::engine::strength::{self, Calibration, Limit, MAX_TARGET, MIN_TARGET, TARGET_STEP};
+use crate::engine::rl::{format_score, mate_in, white_score, MovePair, MAX_POSITION_VALUE, RLEngine, SearchResult, SimulationStats};
+use crate::engine::trace::{self, SearchTrace};
+use crate::engine::uci::{UciEngine, UciInfo, UciReply};
+use crate::engine::weights;
+use crate::engine::worker::{Go, SearchHandle, SearchUpdate};
 use crate::game::board::Board;
-use crate::game::piece::Color as PieceColor;
-use crossterm::event::KeyCode;
+use crate::game::drill::{self, DrillKind};
+use crate::game::handicap::Handicap;
+use crate::game::movement::{coordinates, Move};
+use crate::game::notation::san;
+use crate::game::openings::opening_name;
+use crate::game::piece::{Color as PieceColor, Piece, PieceType};
+use crate::game::position::Position;
+use crate::game::record::GameRecord;
+use crate::game::result::{is_threefold, GameResult};
+use crate::game::validation::RejectionReason;
+use crate::game::variant::{self, visible_squares, Standard, Variant};
+use crate::hooks::HookEvent;
+use crate::logging::{self, Level};
+use crate::profile::Profile;
+use crate::session;
+use crate::ui::board::{BoardView, BoardWidget};
+use crate::ui::chat::{self, Chat};
+use crate::ui::clipboard::Clipboard;
+use crate::ui::commands;
+use crate::ui::diagram::{self, Diagram, DiagramFormat};
+use crate::ui::dialog::{Dialog, Purpose, Reply};
+use crate::ui::editor::Editor;
+use crate::ui::events::AppEvent;
+use crate::ui::follow::{spawn_feed, FeedSource, FeedUpdate, Follow};
+use crate::ui::heatmap::{heat_color, Heatmap, PIECES};
+use crate::ui::help::Help;
+use crate::ui::i18n::{fill, sentence, tr, Text};
+use crate::ui::input::TextInput;
+use crate::ui::keymap::{Action, KeyBinding};
+use crate::ui::movelist;
+use crate::ui::narration::{board_listing, Narrator};
+use crate::ui::network::{self, PeerEvent};
+use crate::ui::palette::{Entry, Palette, PaletteKey, Target};
+use crate::ui::panels::{Focus, GameAreas};
+use crate::ui::players::{
+    side, Clock, EngineController, HumanController, MoveDecision, NetworkPeer, OpponentKind, PlayerController,
+    ScriptedController, UciController,
+};
+use crate::ui::puzzle::PuzzleScreen;
+use crate::ui::relay::Relay;
+use crate::ui::replay::Replay;
+use crate::ui::setup::{self, SetupRow, Side};
+use crate::ui::speedrun::{self, Attempt, Run};
+use crate::ui::spoken::{self, Outcome};
+use crate::ui::tabs::{self, TabKind};
+use crate::ui::terminal;
+use crate::ui::theme::{PieceSet, Theme, PIECE_SETS, THEMES};
+use crate::ui::training::CoordinateQuiz;
+use crate::ui::watch::Watch;
+use std::net::TcpStream;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use crate::utils::{coordinate_to_string, format_bytes, parse_coordinate, pgn_date_time};
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::seq::SliceRandom;
+use rand::Rng;
 use ratatui::{
-    layout::{Constraint, Direction as LayoutDirection, Layout},
+    layout::{Constraint, Direction as LayoutDirection, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    symbols,
+    widgets::{
+        Axis, Block, Borders, Chart, Clear, Dataset, GraphType, Paragraph, Scrollbar, ScrollbarOrientation,
+        ScrollbarState, Wrap,
+    },
     Frame,
 };
 
+#[derive(Clone, Copy, PartialEq)]
 pub enum GameState {
     Menu,
+    Setup,
+    Editor,
+    Training,
     Playing,
+    Replay,
+    Heatmap,
+    Arena,
+    History,
+    Profile,
+    Themes,
+    Settings,
     About,
+    Follow,
+    Puzzle,
+    Watch,
+}
+
+pub const MENU_ITEMS: [Text; 9] = [
+    Text::MenuPlay,
+    Text::MenuEditor,
+    Text::MenuTraining,
+    Text::MenuDailyPuzzle,
+    Text::MenuHistory,
+    Text::MenuProfile,
+    Text::MenuThemes,
+    Text::MenuSettings,
+    Text::MenuAbout,
+];
+
+// the event loop ticks at least this often, keys or not, so the engine's
+// progress, the thinking spinner and anything else on a timer keep moving
+pub const TICK_RATE: std::time::Duration = std::time::Duration::from_millis(100);
+// moves of each multi-PV line shown after its first
+const PV_MOVES: usize = 6;
+// the game screen's tabs, each with an engine of its own
+const MAX_TABS: usize = 9;
+// lines of the move history a page key scrolls
+const HISTORY_PAGE: isize = 5;
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+// words the command box accepts besides plain moves
+// the bot resigns once its eval has been this bad for this many of its moves in a row
+const RESIGN_SCORE: f32 = -1500.0;
+const RESIGN_MOVES: u32 = 4;
+// evals wobble by this much from move to move, so a draw has to beat the
+// bot's eval by more than that before it takes one
+const DRAW_MARGIN: f32 = 150.0;
+
+// contempt steps and limits on the settings screen, in centipawns
+const CONTEMPT_STEP: i32 = 10;
+const CONTEMPT_RANGE: std::ops::RangeInclusive<i32> = -100..=200;
+// the key bindings, then contempt, multipv, weights and piece values, then a
+// bell for each event
+const ENGINE_ROWS: usize = 4;
+const SETTINGS_ROWS: usize = Action::ALL.len() + ENGINE_ROWS + HookEvent::ALL.len();
+
+// everything needed to take a move back
+struct Snapshot {
+    board: Board,
+    history_len: usize,
+    plies: usize,
+    current_turn: PieceColor,
+    position_score: f32,
+}
+
+// a takeback in a network game waiting on an answer, with the number of
+// plies the game would go back to
+#[derive(Clone, Copy, PartialEq)]
+enum Takeback {
+    Asked(usize),
+    Offered(usize),
+}
+
+// a game in a tab that isn't on screen. the one that is lives in App's
+// own fields, and switching tabs swaps the two, see App::exchange
+struct Game {
+    kind: TabKind,
+    analysis: Option<SearchHandle>,
+    analysis_go: Go,
+    board: Board,
+    cursor_pos: (usize, usize),
+    selected_piece: Option<(usize, usize)>,
+    move_history: Vec<String>,
+    history_scroll: Option<usize>,
+    rl_engine: RLEngine,
+    current_turn: PieceColor,
+    bot_color: PieceColor,
+    last_position_score: f32,
+    current_position_score: f32,
+    flipped: bool,
+    show_threats: bool,
+    engine_notes: Vec<String>,
+    undo_stack: Vec<Snapshot>,
+    record: GameRecord,
+    turn_started: Instant,
+    bot_expected: Vec<(usize, f32)>,
+    player_moves: Vec<(usize, Option<Judgement>)>,
+    players: [Box<dyn PlayerController>; 2],
+    opponent: OpponentKind,
+    engine_choice: Option<(MovePair, f32)>,
+    opponent_info: Option<UciInfo>,
+    second_opinion: Option<UciEngine>,
+    second_opinion_info: Option<UciInfo>,
+    second_opinion_error: Option<String>,
+    last_search: Option<SearchTrace>,
+    result: Option<GameResult>,
+    variant: Arc<dyn Variant>,
+    level: Difficulty,
+    target_elo: Option<u32>,
+    personality: Personality,
+    loaded_weights: Option<String>,
+    loaded_values: Option<PieceValues>,
+    bot_losing_streak: u32,
+    draw_offer_ply: Option<usize>,
+    spoken_choices: Option<Vec<Move>>,
+    premove: Option<MovePair>,
+    switched: bool,
+    handicap: Handicap,
+    start_fen: Option<String>,
+    drill: Option<DrillKind>,
+    speedrun: Option<Attempt>,
+    blindfold: bool,
+    narrator: Narrator,
+}
+
+impl Game {
+    fn title(&self) -> String {
+        let thinking = self.result.is_none() && !self.players[side(self.current_turn)].is_human();
+        let opponent = match self.opponent {
+            OpponentKind::Engine => "Bot".to_string(),
+            _ => self.players[side(self.bot_color)].name(),
+        };
+        tabs::title(self.kind, &opponent, self.result, thinking)
+    }
+
+    fn owns_search(&self, id: u64) -> bool {
+        self.players.iter().any(|player| player.owns_search(id))
+            || self.analysis.as_ref().is_some_and(|search| search.id == id)
+            || self.second_opinion.as_ref().is_some_and(|engine| engine.id == id)
+    }
 }
 
 pub struct App {
     pub game_state: GameState,
+    // the game screen's tabs; the one on screen is None here, its game
+    // being the fields below
+    tabs: Vec<Option<Game>>,
+    tab: usize,
+    kind: TabKind,
+    // the engine looking at the position on an analysis board, and for how
+    // long it looks at each
+    analysis: Option<SearchHandle>,
+    analysis_go: Go,
     pub board: Board,
     pub cursor_pos: (usize, usize),
     pub selected_piece: Option<(usize, usize)>,
     pub should_quit: bool,
     pub menu_index: usize,
-    pub command_buffer: String,
+    pub command_input: TextInput,
     pub move_history: Vec<String>,
-    pub history_scroll: usize,
+    // the history's top line once scrolled up, None to follow the newest
+    pub history_scroll: Option<usize>,
+    // the game screen's panel the keys go to, and how far the analytics
+    // panel is scrolled
+    pub focus: Focus,
+    analytics_scroll: usize,
+    // a question over the screen that takes the keys until it's answered
+    pub dialog: Option<Dialog>,
+    pub help: Option<Help>,
+    pub palette: Option<Palette>,
     pub rl_engine: RLEngine,
     pub current_turn: PieceColor,
     pub bot_color: PieceColor,
     pub last_position_score: f32,
     pub current_position_score: f32,
+    pub config: Config,
+    pub status: String,
+    pub flipped: bool,
+    // the hanging pieces overlay on the game board
+    pub show_threats: bool,
+    pub settings_index: usize,
+    pub capturing_key: bool,
+    // what the theme gallery has highlighted, before it's applied
+    pub gallery_theme: usize,
+    pub gallery_pieces: usize,
+    pub engine_notes: Vec<String>,
+    undo_stack: Vec<Snapshot>,
+    completion: Option<Completion>,
+    // the game so far
+    record: GameRecord,
+    // when the side to move got the move, for the record's clock
+    turn_started: Instant,
+    // what the bot's search expected after each of its moves, by position
+    // index, and the player's moves judged against that
+    bot_expected: Vec<(usize, f32)>,
+    player_moves: Vec<(usize, Option<Judgement>)>,
+    pub replay: Option<Replay>,
+    // the learned piece-square tables, from `heatmap`
+    pub heatmap: Heatmap,
+    // saved tournaments, newest first, and the one the arena screen shows
+    tournaments: Vec<Tournament>,
+    tournament_index: usize,
+    // where esc leaves the replay viewer for
+    pub replay_return: GameState,
+    // white's controller, then black's; the bot's side is whoever
+    // `opponent` says, the other one the player at the keyboard
+    players: [Box<dyn PlayerController>; 2],
+    pub opponent: OpponentKind,
+    // the move the bot's search settled on and what it expects after it,
+    // until the move is played
+    engine_choice: Option<(MovePair, f32)>,
+    // a UCI opponent's latest on its search, for the thinking panel
+    opponent_info: Option<UciInfo>,
+    // the [uci] engine looking over the game beside the bot, its latest on
+    // the position on the board from the bot's side, and why it isn't
+    // running if it couldn't be started
+    second_opinion: Option<UciEngine>,
+    second_opinion_info: Option<UciInfo>,
+    second_opinion_error: Option<String>,
+    // where the search thread reports back to the main loop
+    events: Sender<AppEvent>,
+    // the bot's last finished search, for `dump search`
+    last_search: Option<SearchTrace>,
+    pub result: Option<GameResult>,
+    pub variant: Arc<dyn Variant>,
+    // fixed when the game starts so a rated result can't be picked afterwards
+    pub level: Difficulty,
+    // a rating the bot holds itself to over the level, fixed the same way
+    pub target_elo: Option<u32>,
+    // what each step of the target Elo ladder is rated, see engine::strength
+    calibration: Calibration,
+    pub personality: Personality,
+    // the weights file the bot's engine was loaded from, None for built-in
+    loaded_weights: Option<String>,
+    // the named piece values put in over them, None for the weights' own
+    loaded_values: Option<PieceValues>,
+    // a --weights file, used over the config's for this run
+    weights_override: Option<String>,
+    // the bot's moves in a row spent below RESIGN_SCORE
+    bot_losing_streak: u32,
+    // the ply of the last draw offer, one per move
+    draw_offer_ply: Option<usize>,
+    // the moves a move in words could have meant, while the player is asked
+    // which
+    spoken_choices: Option<Vec<Move>>,
+    // the player's move entered while the other side was thinking, played
+    // as soon as it's their turn if it's legal then
+    premove: Option<MovePair>,
+    // whether the player swapped sides with the bot once moves were played;
+    // a game like that isn't theirs to rate
+    switched: bool,
+    pub profile: Profile,
+    // odds for this game, picked on the setup screen
+    pub handicap: Handicap,
+    setup_index: usize,
+    // the setup screen's row with the cursor, and the side picked there
+    setup_row: usize,
+    setup_side: Side,
+    pub editor: Editor,
+    pub quiz: CoordinateQuiz,
+    // the game board shows no pieces until the game is over
+    pub blindfold: bool,
+    // plain text for screen readers: no box drawing, and the game told as
+    // sentences in the narration panel
+    pub accessible: bool,
+    pub narrator: Narrator,
+    // the FEN a game set up in the editor started from; such games are unrated
+    start_fen: Option<String>,
+    // the endgame being practiced, which ends the game once it's decided
+    drill: Option<DrillKind>,
+    // the race to mate the random mover, timed and put on the leaderboard
+    speedrun: Option<Attempt>,
+    // the history screen
+    saved_games: Vec<SavedGame>,
+    history_index: usize,
+    pub history_filter: TextInput,
+    // a game followed live with --follow
+    pub follow: Option<Follow>,
+    clipboard: Clipboard,
+    // the daily puzzle, once it's been fetched or read from the cache
+    pub puzzle: Option<PuzzleScreen>,
+    fetching_puzzle: bool,
+    // an engine game watched with `watch`
+    pub watch: Option<Watch>,
+    // the other player in a network game, and whether it was ours to host
+    peer: Option<TcpStream>,
+    hosting: bool,
+    takeback: Option<Takeback>,
+    chat: Chat,
+    // the command box sends what's typed to the other player
+    chatting: bool,
+    // spectators watching with --serve, and what they were last sent
+    relay: Option<Relay>,
+    relayed: Option<String>,
+}
+
+// Tab cycles through `candidates`, each one replacing the last typed word
+struct Completion {
+    stem: String,
+    candidates: Vec<String>,
+    index: usize,
 }
 
 impl App {
-    pub fn new() -> Self {
+    // a seed makes every bot search reproducible
+    pub fn new(config: Config, seed: Option<u64>, events: Sender<AppEvent>) -> Self {
+        let mut rl_engine = seed.map_or_else(RLEngine::new, RLEngine::with_seed);
+        let loaded_weights = match weights::load_into(&mut rl_engine, config.bot.weights.as_deref()) {
+            Ok(()) => config.bot.weights.clone(),
+            Err(e) => {
+                logging::warn(&format!("couldn't load the bot's weights: {}", e));
+                None
+            }
+        };
+        if let Err(e) = config.configure(&mut rl_engine) {
+            logging::warn(&format!("couldn't use the bot's piece values from the config: {}", e));
+        }
+        let loaded_values = config.bot.piece_values;
+        let accessible = config.accessibility.enabled;
+        let narrator = Narrator::new(&config.accessibility);
         Self {
             game_state: GameState::Menu,
+            tabs: vec![None],
+            tab: 0,
+            kind: TabKind::Game,
+            analysis: None,
+            analysis_go: Go::default(),
             board: Board::new(),
             cursor_pos: (0, 0),
             selected_piece: None,
             should_quit: false,
             menu_index: 0,
-            command_buffer: String::new(),
+            command_input: TextInput::new(),
             move_history: Vec::new(),
-            history_scroll: 0,
-            rl_engine: RLEngine::new(),
+            history_scroll: None,
+            focus: Focus::default(),
+            analytics_scroll: 0,
+            dialog: None,
+            help: None,
+            palette: None,
+            rl_engine,
             current_turn: PieceColor::White,
             bot_color: PieceColor::Black,
             last_position_score: 0.0,
             current_position_score: 0.0,
+            level: config.bot.level,
+            target_elo: config.bot.target_elo,
+            calibration: Calibration::load(),
+            personality: config.bot.personality,
+            loaded_weights,
+            loaded_values,
+            weights_override: None,
+            bot_losing_streak: 0,
+            draw_offer_ply: None,
+            switched: false,
+            spoken_choices: None,
+            premove: None,
+            config,
+            status: String::new(),
+            flipped: false,
+            show_threats: false,
+            settings_index: 0,
+            capturing_key: false,
+            gallery_theme: 0,
+            gallery_pieces: 0,
+            engine_notes: Vec::new(),
+            undo_stack: Vec::new(),
+            completion: None,
+            record: GameRecord::new(Board::new(), PieceColor::White),
+            turn_started: Instant::now(),
+            bot_expected: Vec::new(),
+            player_moves: Vec::new(),
+            replay: None,
+            heatmap: Heatmap::new(),
+            tournaments: Vec::new(),
+            tournament_index: 0,
+            replay_return: GameState::Playing,
+            players: [Box::new(HumanController::default()), Box::new(HumanController::default())],
+            opponent: OpponentKind::Engine,
+            engine_choice: None,
+            opponent_info: None,
+            second_opinion: None,
+            second_opinion_info: None,
+            second_opinion_error: None,
+            events,
+            last_search: None,
+            result: None,
+            variant: Arc::new(Standard),
+            profile: Profile::load(),
+            handicap: Handicap::None,
+            setup_index: 0,
+            setup_row: 0,
+            setup_side: Side::White,
+            editor: Editor::new(Board::new(), PieceColor::White),
+            quiz: CoordinateQuiz::new(),
+            blindfold: false,
+            accessible,
+            narrator,
+            start_fen: None,
+            drill: None,
+            speedrun: None,
+            saved_games: Vec::new(),
+            history_index: 0,
+            history_filter: TextInput::new(),
+            follow: None,
+            clipboard: Clipboard::new(),
+            puzzle: None,
+            fetching_puzzle: false,
+            watch: None,
+            peer: None,
+            hosting: false,
+            takeback: None,
+            chat: Chat::default(),
+            chatting: false,
+            relay: None,
+            relayed: None,
         }
     }
 
-    pub fn make_bot_move(&mut self) -> Option<String> {
-        if self.current_turn == self.bot_color {
-            if let Some((from, to)) = self.rl_engine.get_best_move(&self.board, self.bot_color) {
-                let piece = self.board.get_piece(from).cloned();
-                if let Some(piece) = piece {
-                    if self.board.move_piece(from, to) {
-                        let move_str = format!(
-                            "{} {} → {}",
-                            piece.to_char(),
-                            coordinate_to_string(from),
-                            coordinate_to_string(to)
-                        );
-                        self.move_history.push(move_str.clone());
-
-                        // Update RL engine based on position evaluation
-                        self.last_position_score = self.current_position_score;
-                        self.current_position_score = self
-                            .rl_engine
-                            .evaluate_position(&self.board, self.bot_color);
-                        self.rl_engine.update_position_values(
-                            &self.board,
-                            self.bot_color,
-                            self.current_position_score,
-                        );
+    // the game so far as the controllers see it
+    fn position(&self) -> Position {
+        Position::from_positions(self.variant.clone(), self.record.positions(), self.record.first_to_move())
+            .expect("a game record starts from a position")
+    }
 
-                        // Switch turns
-                        self.current_turn = PieceColor::White;
-                        return Some("Bot moved successfully".to_string());
-                    }
-                }
+    // whoever `opponent` says plays the bot's side. with the connection to
+    // a network game gone, or a UCI engine that won't start, that's the
+    // engine again
+    fn opponent_controller(&mut self) -> Box<dyn PlayerController> {
+        match self.opponent {
+            OpponentKind::Scripted(opponent) => return Box::new(ScriptedController::new(Scripted::new(opponent, None))),
+            // it would see through the fog, and knows no other rules anyway
+            OpponentKind::Uci if self.variant.name() != "standard" => {
+                self.move_history.push(tr(Text::UciStandardOnly).to_string());
             }
-            Some("Bot failed to move".to_string())
-        } else {
-            None
+            OpponentKind::Uci => match self.start_uci() {
+                Ok(engine) => return Box::new(UciController::new(engine, self.config.uci.think_time())),
+                Err(e) => {
+                    logging::warn(&format!("couldn't start the UCI engine: {}", e));
+                    self.move_history.push(fill(Text::UciWontStart, &[&e]));
+                }
+            },
+            OpponentKind::Network => match self.peer.as_ref().map(TcpStream::try_clone) {
+                Some(Ok(stream)) => return Box::new(NetworkPeer::new(stream, self.bot_color)),
+                Some(Err(e)) => logging::warn(&format!("couldn't use the connection to the other player: {}", e)),
+                None => {}
+            },
+            OpponentKind::Engine => {}
         }
+        self.opponent = OpponentKind::Engine;
+        let limit = self.strength_limit();
+        let mut engine = self.rl_engine.fork();
+        engine.set_max_simulations(limit.map(|limit| limit.simulations));
+        // the cap is per search thread, so a held-back bot gets just one
+        let threads = if limit.is_some() { 1 } else { self.config.engine.threads() };
+        Box::new(EngineController::new("The bot".to_string(), engine, threads, self.events.clone()))
     }
 
-    pub fn handle_command(&mut self) -> Option<String> {
-        let cmd = self.command_buffer.trim().to_lowercase();
-        let parts: Vec<&str> = cmd.split_whitespace().collect();
+    // how the bot is held back for a target Elo, if it is
+    fn strength_limit(&self) -> Option<Limit> {
+        self.target_elo.map(|elo| self.calibration.limit(elo as f32))
+    }
 
-        if parts.len() == 2 {
-            let from = parse_coordinate(parts[0]);
-            let to = parse_coordinate(parts[1]);
+    // the level, or the rating the bot's aiming for
+    fn level_text(&self) -> String {
+        match self.target_elo {
+            Some(elo) => format!("~{}", elo),
+            None => self.level.name().to_string(),
+        }
+    }
 
-            match (from, to) {
-                (Some(from_pos), Some(to_pos)) => {
-                    if let Some(piece) = self.board.get_piece(from_pos).cloned() {
-                        if self.board.move_piece(from_pos, to_pos) {
-                            let move_str = format!(
-                                "{} {} → {}",
-                                piece.to_char(),
-                                coordinate_to_string(from_pos),
-                                coordinate_to_string(to_pos)
-                            );
-                            self.move_history.push(move_str.clone());
-                            self.command_buffer.clear();
-                            // Switch turns after successful move
-                            self.current_turn = self.bot_color;
-                            let result = Some("Move successful".to_string());
-
-                            // Trigger bot move if it's their turn
-                            if let Some(bot_msg) = self.make_bot_move() {
-                                self.move_history.push(format!("Bot: {}", bot_msg));
-                            }
-
-                            self.command_buffer.clear();
-                            return result;
-                        } else {
-                            return Some("Invalid move".to_string());
-                        }
-                    } else {
-                        return Some("No piece at selected position".to_string());
-                    }
-                }
-                _ => return Some("Invalid coordinate format. Use a1-h8".to_string()),
-            }
-        } else {
-            Some("Invalid command. Use: <from> <to> (e.g. 'e2 e4')".to_string())
+    // how the move history names the bot's side
+    fn opponent_label(&self) -> String {
+        match self.opponent {
+            OpponentKind::Engine => "Bot".to_string(),
+            _ => self.players[side(self.bot_color)].name(),
         }
     }
 
-    pub fn select_piece(&mut self) {
-        let pos = self.cursor_pos;
-        if let Some(_piece) = self.board.get_piece(pos) {
-            if self.selected_piece.is_none() {
-                // Select piece
-                self.selected_piece = Some(pos);
-            } else if self.selected_piece == Some(pos) {
-                // Deselect piece
-                self.selected_piece = None;
-            } else {
-                // Try to move selected piece to new position
-                if let Some(from) = self.selected_piece {
-                    if self.board.move_piece(from, pos) {
-                        self.selected_piece = None;
+    // asks the side to move for its move until one of them has to wait: the
+    // player for a command, the engine for its search, the other end of a
+    // network game for a message. returns what came of the first move, or
+    // of the game ending
+    fn drive_players(&mut self) -> Option<String> {
+        let mut message = None;
+        while self.result.is_none() {
+            let color = self.current_turn;
+            let player = side(color);
+            let human = self.players[player].is_human();
+            if let Some(premove) = self.premove.filter(|_| human) {
+                self.premove = None;
+                match self.variant.check_move(&self.board, premove.0, premove.1, color) {
+                    Ok(()) => {
+                        self.players[player].offer(premove);
+                        self.status = fill(Text::PremovePlayed, &[&coordinates(premove)]);
+                    }
+                    Err(reason) => {
+                        let msg = fill(
+                            Text::PremoveDropped,
+                            &[&coordinates(premove), &rejection_text(reason, self.variant.title())],
+                        );
+                        self.move_history.push(msg.clone());
+                        self.report(msg);
                     }
                 }
             }
+            if !human {
+                // the bot's view of a fogged board never matches a real one
+                let history = if self.variant.hides_pieces() {
+                    Vec::new()
+                } else {
+                    self.record.with_sides()
+                };
+                self.rl_engine.set_history(history);
+                self.players[player].refresh_engine(&mut self.rl_engine);
+            }
+            let clock = self.clock();
+            let position = self.position();
+            let msg = match self.players[player].next_move(&position, &clock) {
+                MoveDecision::Pending => break,
+                MoveDecision::Resign => {
+                    let name = self.players[player].name();
+                    self.move_history.push(format!("{} resigns", name));
+                    self.end_game(GameResult::Resignation { winner: color.opposite() })
+                }
+                MoveDecision::Play(mv) => {
+                    let plies = self.record.len();
+                    let msg = self.play_move(color, mv);
+                    if self.record.len() == plies {
+                        // asking again would only get the same move
+                        logging::error(&format!("{} failed to move, it gave {:?}: {}", self.players[player].name(), mv, msg));
+                        message.get_or_insert(msg);
+                        break;
+                    }
+                    msg
+                }
+            };
+            if !human {
+                self.move_history.push(format!("{}: {}", self.opponent_label(), msg));
+            }
+            message.get_or_insert(msg);
         }
+        message
     }
 
-    pub fn draw(&self, frame: &mut Frame) {
-        match self.game_state {
-            GameState::Menu => self.draw_menu(frame),
-            GameState::Playing => self.draw_game(frame),
-            GameState::About => self.draw_about(frame),
+    // plays `color`'s move on the board and in the record, and tells both
+    // players about it
+    fn play_move(&mut self, color: PieceColor, (from, to): MovePair) -> String {
+        if let Err(reason) = self.variant.check_move(&self.board, from, to, color) {
+            return rejection_text(reason, self.variant.title());
         }
-    }
-
-    fn draw_menu(&self, frame: &mut Frame) {
-        let area = frame.area();
+        if self.board.get_piece(from).is_none() {
+            return tr(Text::NoPieceSelected).to_string();
+        }
+        let human = self.players[side(color)].is_human();
+        let snapshot = self.snapshot();
+        let mv = Move::new(&self.board, from, to);
+        if !self.variant.make_move(&mut self.board, from, to) {
+            return tr(Text::InvalidMove).to_string();
+        }
+        // either side of a network game may ask to take back a move
+        if human || self.opponent == OpponentKind::Network {
+            self.undo_stack.push(snapshot);
+        }
+        if human {
+            self.engine_notes.clear();
+        }
+        self.record_ply(mv);
+        let label = self.opponent_label();
 
-        let layout = Layout::default()
-            .direction(LayoutDirection::Vertical)
-            .constraints([
-                Constraint::Percentage(20),
-                Constraint::Length(8), // Title height
-                Constraint::Length(3), // Spacing
-                Constraint::Length(4), // Menu items
-                Constraint::Min(0),
-            ])
-            .split(area);
+        let choice = if !human && self.opponent == OpponentKind::Engine {
+            // Update RL engine based on position evaluation
+            self.last_position_score = self.current_position_score;
+            self.current_position_score = self.rl_engine.evaluate_position(&self.board, self.bot_color);
+            self.rl_engine
+                .update_position_values(&self.board, self.bot_color, self.current_position_score);
+            self.rl_engine
+                .update_piece_values(&self.board, self.bot_color, self.current_position_score);
+            self.engine_choice.take().filter(|(chosen, _)| *chosen == (from, to))
+        } else {
+            None
+        };
 
-        // Title
-        let title = vec![
-            Line::from("██████╗██╗  ██╗███████╗███████╗███████╗"),
-            Line::from("██╔════╝██║  ██║██╔════╝██╔════╝██╔════╝"),
-            Line::from("██║     ███████║█████╗  ███████╗███████╗"),
-            Line::from("██║     ██╔══██║██╔══╝  ╚════██║╚════██║"),
-            Line::from("╚██████╗██║  ██║███████╗███████║███████║"),
-            Line::from("╚═════╝╚═╝  ╚═╝╚══════╝╚══════╝╚══════╝"),
-        ];
+        let position = self.position();
+        for player in &mut self.players {
+            player.observe((from, to), &position);
+        }
 
-        let title_block = Paragraph::new(title)
-            .style(Style::default().fg(Color::Yellow))
-            .alignment(ratatui::layout::Alignment::Center)
-            .block(Block::default().borders(Borders::NONE));
+        // Switch turns
+        self.current_turn = color.opposite();
+        self.ask_second_opinion();
+        self.analyze_position();
+        if let Some(msg) = self.check_game_over(color.opposite()) {
+            return msg;
+        }
+        if let Some(msg) = self.check_drill() {
+            return msg;
+        }
+        if let Some((_, value)) = choice {
+            self.bot_expected.push((self.record.len(), value));
+            if let Some(ply) = self.record.plies.last_mut() {
+                ply.eval = Some(white_score(value, self.bot_color));
+            }
+        }
+        if human {
+            return tr(Text::MoveSuccessful).to_string();
+        }
+        let played = if self.variant.hides_pieces() {
+            tr(Text::YourMove).to_string()
+        } else {
+            fill(Text::YourMoveAfter, &[&label.to_lowercase(), &mv])
+        };
+        self.config.hooks.fire(HookEvent::YourMove, &played);
+        fill(Text::MovedSuccessfully, &[&label])
+    }
 
-        // Menu items
-        let menu_items = vec![
-            Line::from(vec![
-                Span::styled("     ► ", Style::default().fg(Color::Reset)),
-                Span::styled(
-                    "PLAY",
-                    Style::default()
-                        .fg(if self.menu_index == 0 {
-                            Color::Green
-                        } else {
-                            Color::White
-                        })
-                        .add_modifier(if self.menu_index == 0 {
-                            Modifier::BOLD
-                        } else {
-                            Modifier::empty()
-                        }),
-                ),
-            ]),
-            Line::from(vec![
-                Span::styled("     ► ", Style::default().fg(Color::White)),
-                Span::styled(
-                    "ABOUT",
-                    Style::default()
-                        .fg(if self.menu_index == 1 {
-                            Color::Green
-                        } else {
-                            Color::White
-                        })
-                        .add_modifier(if self.menu_index == 1 {
-                            Modifier::BOLD
-                        } else {
-                            Modifier::empty()
-                        }),
-                ),
-            ]),
-        ];
+    fn bot_think_time(&self) -> std::time::Duration {
+        let think_time = match self.strength_limit() {
+            Some(limit) => limit.think_time,
+            None => self.config.engine.think_time(self.level),
+        };
+        self.handicap.think_time(think_time)
+    }
 
-        let menu = Paragraph::new(menu_items)
-            .alignment(ratatui::layout::Alignment::Left)
-            .block(Block::default().borders(Borders::NONE));
+    fn clock(&self) -> Clock {
+        Clock {
+            think_time: self.bot_think_time(),
+            elapsed: self.turn_started.elapsed(),
+            overhead: self.config.engine.move_overhead(),
+        }
+    }
 
-        let instructions = Paragraph::new("Use ↑↓ arrows to select and ENTER to confirm")
-            .style(Style::default().fg(Color::DarkGray))
-            .alignment(ratatui::layout::Alignment::Center);
+    // whether the game is waiting on the bot's side
+    pub fn is_thinking(&self) -> bool {
+        self.result.is_none() && !self.players[side(self.current_turn)].is_human()
+    }
 
-        frame.render_widget(title_block, layout[1]);
-        frame.render_widget(menu, layout[3]);
-        frame.render_widget(instructions, layout[4]);
+    fn waiting_text(&self) -> String {
+        match self.opponent {
+            OpponentKind::Engine => tr(Text::WaitForEngine).to_string(),
+            _ => tr(Text::WaitForPlayer).to_string(),
+        }
     }
 
-    fn draw_game(&self, frame: &mut Frame) {
-        let area = frame.area();
+    pub fn handle_command(&mut self) -> Option<String> {
+        let input = self.command_input.submit();
+        if self.chatting {
+            return Some(self.send_chat(&input));
+        }
+        let cmd = input.trim().to_lowercase();
+        let parts: Vec<&str> = cmd.split_whitespace().collect();
 
-        // Main horizontal split between game+analytics and history
-        let main_layout = Layout::default()
-            .direction(LayoutDirection::Horizontal)
-            .constraints([
-                Constraint::Ratio(3, 4), // Game board + analytics
-                Constraint::Ratio(1, 4), // History + command
-            ])
-            .split(area);
+        // anything that isn't an answer drops the question
+        if let Some(choices) = self.spoken_choices.take() {
+            if let Some(outcome) = spoken::answer(&choices, &cmd) {
+                return self.spoken_outcome(outcome);
+            }
+        }
 
-        // Vertical split for board and analytics
-        let left_layout = Layout::default()
-            .direction(LayoutDirection::Vertical)
-            .constraints([
-                Constraint::Length(25), // Board height
-                Constraint::Length(40), // Analytics height - increased from 15 to 40
-            ])
-            .split(main_layout[0]);
+        if parts.first() == Some(&"why-not") {
+            return Some(self.why_not(&parts[1..]));
+        }
 
-        // Vertical split for history and command input
-        let right_layout = Layout::default()
-            .direction(LayoutDirection::Vertical)
-            .constraints([
-                Constraint::Min(3),    // History takes most space
-                Constraint::Length(3), // Command input height
-            ])
-            .split(main_layout[1]);
+        if parts.first() == Some(&"replay") {
+            return Some(match parts.get(1) {
+                Some(number) => self.replay_move(number),
+                None => self.start_replay(),
+            });
+        }
 
-        let board_area = left_layout[0];
+        if parts.first() == Some(&"diagram") {
+            return Some(self.export_diagram(parts.get(1).copied()));
+        }
 
-        // Create the board content
-        let mut board_content = vec![];
+        if parts.first() == Some(&"heatmap") {
+            return Some(self.open_heatmap(parts.get(1).copied()));
+        }
 
-        // Add column labels
-        board_content.push(Line::from(vec![Span::raw(
-            "     a    b    c    d    e    f    g    h",
-        )]));
+        if parts.first() == Some(&"arena") {
+            self.tournaments = arena::load_all();
+            self.tournament_index = 0;
+            self.game_state = GameState::Arena;
+            return Some(String::new());
+        }
 
-        // Add top border with vertical grid markers
-        board_content.push(Line::from(Span::styled(
-            "   ┌────┬────┬────┬────┬────┬────┬────┬────┐",
-            Style::default().fg(Color::LightGreen),
-        )));
+        if parts.first() == Some(&"watch") {
+            return Some(self.start_watch(&parts[1..]));
+        }
 
-        // Add board rows
-        for rank in 0..8 {
-            let mut row = vec![
-                Span::styled(format!("{}  ", 8 - rank), Style::default().fg(Color::Green)),
-                Span::styled("│ ", Style::default().fg(Color::Green)),
-            ];
-            for file in 0..8 {
-                let _is_dark = (rank + file) % 2 == 1;
-                let piece = self.board.get_piece((rank, file));
-                let piece_char = piece.map_or(" ".to_string(), |p| p.to_char().to_string());
+        if parts.first() == Some(&"level") {
+            return Some(self.set_level(parts.get(1).copied()));
+        }
 
-                let piece_color = if let Some(piece) = self.board.get_piece((rank, file)) {
-                    if piece.color == crate::game::piece::Color::White {
-                        Color::White
-                    } else {
-                        Color::Yellow
-                    }
-                } else {
-                    Color::DarkGray
-                };
+        if parts.first() == Some(&"drill") {
+            return Some(self.start_drill(parts.get(1).copied()));
+        }
 
-                let style = Style::default().fg(piece_color);
+        if parts.first() == Some(&"go") {
+            return Some(self.go(&parts[1..]));
+        }
 
-                if (rank, file) == self.cursor_pos {
-                    row.push(Span::styled(format!(" {}   ", piece_char), style));
-                } else if Some((rank, file)) == self.selected_piece {
-                    row.push(Span::styled(format!(" {}   ", piece_char), style));
-                } else {
-                    row.push(Span::styled(format!(" {}   ", piece_char), style));
-                }
-            }
-            row.push(Span::styled(" │", Style::default().fg(Color::Green)));
-            board_content.push(Line::from(row));
+        if cmd == "stop" {
+            return Some(self.move_now());
+        }
 
-            // horizontal grid line after each row except the last
-            if rank < 7 {
-                let mut grid_line = vec![
-                    Span::styled("   ", Style::default()),
-                    Span::styled("├────", Style::default().fg(Color::LightGreen)),
-                ];
-                for _ in 0..7 {
-                    grid_line.push(Span::styled(
-                        "┼────",
-                        Style::default().fg(Color::LightGreen),
-                    ));
-                }
-                grid_line.push(Span::styled("┤", Style::default().fg(Color::LightGreen)));
-                board_content.push(Line::from(grid_line));
-            }
+        if parts.first() == Some(&"speedrun") {
+            return Some(self.start_speedrun(parts.get(1).copied()));
+        }
 
-            // bottom border with vertical grid markers
-            if rank == 7 {
-                board_content.push(Line::from(Span::styled(
-                    "   └────┴────┴────┴────┴────┴────┴────┴────┘",
-                    Style::default().fg(Color::LightGreen),
-                )));
-            }
+        if parts.first() == Some(&"tab") {
+            return Some(self.tab_command(&parts[1..]));
         }
 
-        let board = Paragraph::new(board_content)
-            .block(Block::default().borders(Borders::NONE))
-            .style(Style::default().fg(Color::White));
+        if parts == ["switch"] || parts == ["switch", "sides"] {
+            return Some(self.switch_sides());
+        }
 
-        frame.render_widget(board, board_area);
+        if parts.first() == Some(&"elo") {
+            return Some(self.set_target_elo(parts.get(1).copied()));
+        }
 
-        // analytics section
-        let mut analytics_text = vec![
-            Line::from(vec![
-                Span::raw("Position Evaluation"),
-                Span::raw(" ".repeat(30)),
-                Span::raw("Engine Analysis"),
-            ]),
-            Line::from("─".repeat(60)),
-            Line::from(vec![
-                Span::raw("Thinking depth: "),
-                Span::styled(
-                    format!("{}", self.rl_engine.current_stats.depth_reached),
-                    Style::default().fg(Color::Blue),
-                ),
-            ]),
-            Line::from(vec![
-                Span::raw("Total positions: "),
-                Span::styled(
-                    format!("{}", self.rl_engine.current_stats.total_simulations),
-                    Style::default().fg(Color::Yellow),
-                ),
-            ]),
-            Line::from(vec![
-                Span::raw("Current Evaluation: "),
-                Span::styled(
-                    format!("{:.2}", self.rl_engine.current_stats.current_eval),
-                    Style::default().fg(Color::Green),
-                ),
-            ]),
-            Line::from(""),
-            Line::from("Top Moves Considered:"),
-        ];
+        if parts.first() == Some(&"dump") {
+            return Some(match parts.get(1) {
+                None | Some(&"search") => self.dump_search(),
+                Some(_) => tr(Text::DumpUsage).to_string(),
+            });
+        }
 
-        // add top moves
-        for (idx, (mv, score, visits)) in self.rl_engine.current_stats.top_moves.iter().enumerate()
-        {
-            analytics_text.push(Line::from(vec![
-                Span::raw(format!("{}. ", idx + 1)),
-                Span::styled(mv.clone(), Style::default().fg(Color::Yellow)),
-                Span::raw(" ("),
-                Span::styled(format!("{:.2}", score), Style::default().fg(Color::Blue)),
-                Span::raw(format!(", {} visits)", visits)),
-            ]));
+        if parts.first() == Some(&"blindfold") {
+            self.blindfold = !self.blindfold;
+            return Some(if self.blindfold {
+                tr(Text::BlindfoldOn).to_string()
+            } else {
+                tr(Text::BlindfoldOff).to_string()
+            });
         }
 
-        analytics_text.push(Line::from(""));
-        analytics_text.push(Line::from(vec![
-            Span::raw("Current Position Score: "),
-            Span::styled(
-                format!("{:.2}", self.current_position_score),
-                Style::default().fg(Color::Green),
-            ),
-        ]));
-        analytics_text.push(Line::from(""));
-        analytics_text.push(Line::from(vec![
-            Span::raw("Material Balance: "),
-            Span::styled(
-                format!(
-                    "{}",
-                    self.rl_engine
-                        .get_material_balance(&self.board, self.bot_color)
-                ),
-                Style::default().fg(Color::Blue),
-            ),
-        ]));
-        analytics_text.push(Line::from(vec![
-            Span::raw("King Safety: "),
-            Span::styled(
-                format!(
-                    "{:.2}",
-                    self.rl_engine.get_king_safety(&self.board, self.bot_color)
-                ),
-                Style::default().fg(Color::Magenta),
-            ),
-        ]));
-        analytics_text.push(Line::from(vec![
-            Span::raw("Center Control: "),
-            Span::styled(
-                format!(
-                    "{:.2}",
-                    self.rl_engine
-                        .get_center_control(&self.board, self.bot_color)
-                ),
-                Style::default().fg(Color::Cyan),
-            ),
-        ]));
+        if parts.first() == Some(&"board") {
+            return Some(self.read_board());
+        }
 
-        let analytics = Paragraph::new(analytics_text)
-            .block(Block::default().borders(Borders::ALL).title("Analytics"))
-            .style(Style::default().fg(Color::White));
+        if parts.first() == Some(&"accessible") {
+            return Some(self.toggle_accessible());
+        }
 
-        frame.render_widget(analytics, left_layout[1]);
+        if parts.first() == Some(&"personality") {
+            return Some(self.set_personality(parts.get(1).copied()));
+        }
 
-        // Move history on right side
-        let visible_history: Vec<&str> = self
-            .move_history
-            .iter()
-            .skip(self.history_scroll)
-            .map(|s| s.as_str())
-            .collect();
+        if parts.first() == Some(&"opponent") {
+            return Some(self.set_opponent(parts.get(1).copied()));
+        }
 
-        let history = Paragraph::new(visible_history.join("\n"))
-            .block(Block::default().borders(Borders::ALL).title("Move History"))
-            .style(Style::default().fg(Color::White))
-            .scroll((self.history_scroll as u16, 0));
+        if parts.first() == Some(&"host") {
+            return Some(self.host(parts.get(1).copied()));
+        }
 
-        frame.render_widget(history, right_layout[0]);
+        if parts.first() == Some(&"join") {
+            return Some(match parts.get(1) {
+                Some(address) => self.join(address),
+                None => tr(Text::JoinUsage).to_string(),
+            });
+        }
 
-        // command input at bottom
-        let input = Paragraph::new(format!(">> {}", self.command_buffer))
-            .block(Block::default().borders(Borders::ALL).title("Command"))
-            .style(Style::default().fg(Color::Yellow));
+        if parts[..] == ["takeback"] {
+            return Some(self.ask_takeback());
+        }
 
-        frame.render_widget(input, right_layout[1]);
-    }
+        if matches!(parts[..], ["accept"] | ["decline"]) {
+            return Some(self.answer_takeback(parts[0] == "accept"));
+        }
 
-    pub fn scroll_history(&mut self, up: bool) {
-        if up {
-            if self.history_scroll > 0 {
-                self.history_scroll -= 1;
+        if parts.first() == Some(&"resign") || parts.first() == Some(&"draw") || parts[..] == ["offer", "draw"] {
+            if let Some(result) = self.result {
+                return Some(fill(Text::GameOver, &[&result.termination()]));
             }
-        } else {
-            if self.history_scroll < self.move_history.len().saturating_sub(1) {
-                self.history_scroll += 1;
+            if self.kind == TabKind::Analysis {
+                return Some(tr(Text::NobodyToResignTo).to_string());
+            }
+            // no waiting on the other side to resign
+            if parts.first() == Some(&"resign") {
+                self.dialog = Some(Dialog::confirm(tr(Text::ResignTitle), tr(Text::ResignQuestion).to_string(), Purpose::Resign));
+                return None;
             }
+            if self.is_thinking() {
+                return Some(self.waiting_text());
+            }
+            return Some(match self.opponent {
+                OpponentKind::Engine => self.offer_draw(),
+                OpponentKind::Scripted(_) | OpponentKind::Uci => {
+                    fill(Text::PlaysOn, &[&self.players[side(self.bot_color)].name()])
+                }
+                OpponentKind::Network => tr(Text::NoNetworkDraws).to_string(),
+            });
         }
-    }
 
-    pub fn handle_key_event(&mut self, key_event: crossterm::event::KeyEvent) {
-        match key_event.code {
-            KeyCode::Up => self.scroll_history(true),
-            KeyCode::Down => self.scroll_history(false),
-            KeyCode::PageUp => {
-                for _ in 0..5 {
-                    self.scroll_history(true);
-                }
+        if parts.first() == Some(&"new") && self.opponent == OpponentKind::Network && !self.hosting {
+            return Some(tr(Text::HostStartsGames).to_string());
+        }
+
+        if parts.first() == Some(&"new") {
+            let name = parts.get(1).map(|name| name.to_string());
+            if self.game_in_progress() {
+                let message = tr(Text::NewGameQuestion).to_string();
+                self.dialog = Some(Dialog::confirm(tr(Text::NewGameTitle), message, Purpose::NewGame(name)));
+                return None;
             }
-            KeyCode::PageDown => {
-                for _ in 0..5 {
-                    self.scroll_history(false);
-                }
+            return Some(self.start_new_game(name.as_deref()));
+        }
+
+        if let Some(spoken) = spoken::parse(&cmd) {
+            if let Some(refusal) = self.move_refusal() {
+                return Some(refusal);
             }
-            _ => {}
+            // under fog of war, only what the player can see
+            let human = self.mover();
+            let board = self.variant.view(&self.board, human);
+            let moves = self.variant.moves(&board, human);
+            return self.spoken_outcome(spoken::resolve(&spoken, &moves));
         }
-    }
 
-    fn draw_about(&self, frame: &mut Frame) {
+        if parts.len() == 2 {
+            if let Some(refusal) = self.move_refusal() {
+                return Some(refusal);
+            }
+            let from = parse_coordinate(parts[0]);
+            let to = parse_coordinate(parts[1]);
+
+            match (from, to) {
+                (Some(from_pos), Some(to_pos)) => self.offer_move((from_pos, to_pos)),
+                _ => Some(tr(Text::InvalidCoordinates).to_string()),
+            }
+        } else {
+            Some(tr(Text::InvalidCommand).to_string())
+        }
+    }
+
+    fn start_new_game(&mut self, name: Option<&str>) -> String {
+        if let Some(name) = name {
+            match variant::by_name(name) {
+                Some(variant) => self.variant = variant,
+                None => {
+                    let names: Vec<&str> = variant::all().iter().map(|v| v.name()).collect();
+                    return fill(Text::UnknownVariant, &[&names.join(", ")]);
+                }
+            }
+        }
+        self.new_game();
+        self.send_new_game();
+        self.drive_players();
+        fill(Text::NewGameStarted, &[&self.variant.title()])
+    }
+
+    // a game against someone with moves played and no result yet, which
+    // quitting or starting over would throw away
+    pub fn game_in_progress(&self) -> bool {
+        self.kind == TabKind::Game && self.result.is_none() && !self.record.is_empty()
+    }
+
+    // quits, once it's been confirmed if there's a game on
+    pub fn request_quit(&mut self) {
+        if self.game_in_progress() {
+            let message = tr(Text::QuitQuestion).to_string();
+            self.dialog = Some(Dialog::confirm(tr(Text::QuitTitle), message, Purpose::Quit));
+        } else {
+            self.should_quit = true;
+        }
+    }
+
+    pub fn handle_dialog_key(&mut self, key: KeyEvent) {
+        let Some(dialog) = &mut self.dialog else {
+            return;
+        };
+        match dialog.handle_key(key) {
+            Reply::Pending => {}
+            Reply::Dismissed => self.dialog = None,
+            Reply::Entered(text) => {
+                if let Some(Dialog { purpose: Purpose::Command(name), .. }) = self.dialog.take() {
+                    self.command_input.set_value(&format!("{} {}", name, text.trim()));
+                    if let Some(message) = self.handle_command() {
+                        self.report(message);
+                    }
+                }
+            }
+            Reply::Chose(choice) => {
+                if let Some(dialog) = self.dialog.take() {
+                    // every dialog with choices so far is a yes or no
+                    self.answered(dialog.purpose, choice == 0);
+                }
+            }
+        }
+    }
+
+    pub fn handle_help_key(&mut self, key: KeyEvent) {
+        if self.help.as_mut().is_some_and(|help| !help.handle_key(key)) {
+            self.help = None;
+        }
+    }
+
+    // the keymap's actions, the main menu's screens and, in a game (not
+    // while chatting), the text commands
+    fn open_palette(&mut self) {
+        let mut entries: Vec<Entry> = Action::ALL
+            .iter()
+            .filter(|&&action| action != Action::Palette)
+            .map(|&action| Entry {
+                name: action.name().to_string(),
+                description: format!("{} ({})", action.description(), self.config.keys.binding(action)),
+                target: Target::Key(action),
+            })
+            .collect();
+        if self.game_state == GameState::Playing && !self.chatting {
+            entries.extend(commands::COMMANDS.iter().map(|command| Entry {
+                name: command.name.to_string(),
+                description: tr(command.description).to_string(),
+                target: Target::Command { name: command.name, needs_args: command.usage.contains('<') },
+            }));
+        }
+        entries.extend(MENU_ITEMS.iter().enumerate().map(|(idx, item)| Entry {
+            name: tr(*item).to_lowercase(),
+            description: tr(Text::GoToScreen).to_string(),
+            target: Target::Screen(idx),
+        }));
+        self.palette = Some(Palette::new(entries));
+    }
+
+    pub fn handle_palette_key(&mut self, key: KeyEvent) {
+        let Some(palette) = &mut self.palette else {
+            return;
+        };
+        match palette.handle_key(key) {
+            PaletteKey::Pending => {}
+            PaletteKey::Closed => self.palette = None,
+            PaletteKey::Run(target) => {
+                self.palette = None;
+                self.run_target(target);
+            }
+        }
+    }
+
+    fn run_target(&mut self, target: Target) {
+        match target {
+            Target::Key(action) => self.perform(action),
+            Target::Command { name, needs_args: true } => {
+                let usage = commands::COMMANDS.iter().find(|command| command.name == name).map_or("", |command| command.usage);
+                self.dialog = Some(Dialog::prompt(name, usage.to_string(), Purpose::Command(name)));
+            }
+            Target::Command { name, needs_args: false } => {
+                self.command_input.set_value(name);
+                if let Some(message) = self.handle_command() {
+                    self.report(message);
+                }
+            }
+            Target::Screen(idx) => self.open_menu_item(idx),
+        }
+    }
+
+    pub fn open_menu_item(&mut self, idx: usize) {
+        match idx {
+            0 => self.open_setup(),
+            1 => self.open_editor(),
+            2 => self.open_training(),
+            3 => self.open_puzzle(),
+            4 => self.open_history(),
+            5 => self.open_profile(),
+            6 => self.open_theme_gallery(),
+            7 => self.game_state = GameState::Settings,
+            8 => self.game_state = GameState::About,
+            _ => {}
+        }
+    }
+
+    fn answered(&mut self, purpose: Purpose, yes: bool) {
+        if !yes {
+            // a game not resumed isn't offered again
+            if purpose == Purpose::Resume {
+                games::clear_autosave();
+            }
+            return;
+        }
+        match purpose {
+            Purpose::Quit => self.should_quit = true,
+            Purpose::Resign => {
+                self.move_history.push(tr(Text::YouResign).to_string());
+                let message = self.end_game(GameResult::Resignation { winner: self.bot_color });
+                self.report(message);
+            }
+            // the game given up on isn't offered for resuming either
+            Purpose::NewGame(name) => {
+                games::clear_autosave();
+                let message = self.start_new_game(name.as_deref());
+                self.report(message);
+            }
+            Purpose::StartFromSetup => {
+                games::clear_autosave();
+                self.start_from_setup();
+            }
+            Purpose::Resume => {
+                let message = self.resume_game();
+                self.report(message);
+            }
+            // answered with what was typed, in handle_dialog_key
+            Purpose::Command(_) => {}
+        }
+    }
+
+    // asks on startup whether to carry on with a game left unfinished
+    pub fn offer_resume(&mut self) {
+        let Some(saved) = games::load_autosave() else {
+            return;
+        };
+        let message = fill(
+            Text::ResumeQuestion,
+            &[&saved.variant().title().to_lowercase(), &saved.date, &saved.moves.len().div_ceil(2)],
+        );
+        self.dialog = Some(Dialog::confirm(tr(Text::ResumeTitle), message, Purpose::Resume));
+    }
+
+    // the autosaved game set up again against the bot, from its last position
+    fn resume_game(&mut self) -> String {
+        let Some(saved) = games::load_autosave() else {
+            return tr(Text::UnfinishedGameGone).to_string();
+        };
+        let record = saved.record();
+        self.variant = saved.variant();
+        self.handicap = Handicap::from_name(&saved.handicap).unwrap_or(Handicap::None);
+        self.bot_color = saved.human_color().unwrap_or(PieceColor::White).opposite();
+        self.opponent = OpponentKind::Engine;
+        self.kind = TabKind::Game;
+        self.new_game();
+        self.board = record.positions()[record.len()].clone();
+        self.current_turn = record.to_move();
+        self.start_fen = (!saved.fen.is_empty()).then(|| saved.fen.clone());
+        self.record = record;
+        self.move_history.push(fill(Text::ResumedFromMove, &[&(self.record.len() / 2 + 1)]));
+        self.update_crash_context();
+        self.analyze_position();
+        self.game_state = GameState::Playing;
+        self.drive_players();
+        fill(Text::ResumedGame, &[&saved.date])
+    }
+
+    // rewrites the autosave after every move and takeback. only games
+    // against the bot, the one opponent that's sure to be there to resume
+    // them with
+    fn autosave(&self) {
+        let plain = self.drill.is_none() && self.speedrun.is_none();
+        if self.kind != TabKind::Game || self.opponent != OpponentKind::Engine || !plain || self.record.is_empty() {
+            return;
+        }
+        let unix_time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        if let Err(e) = games::autosave(&self.saved_game(unix_time, "*", "unterminated")) {
+            logging::warn(&format!("couldn't autosave the game: {}", e));
+        }
+    }
+
+    // why the player can't move right now, if they can't. while the other
+    // side thinks, a move is kept as a premove
+    fn move_refusal(&self) -> Option<String> {
+        self.result
+            .map(|result| fill(Text::GameOverPlayAgain, &[&result.termination(), &self.config.keys.binding(Action::NewGame)]))
+    }
+
+    // the side the player moves: theirs, or on an analysis board whichever
+    // is to move
+    fn mover(&self) -> PieceColor {
+        match self.kind {
+            TabKind::Game => self.bot_color.opposite(),
+            TabKind::Analysis => self.current_turn,
+        }
+    }
+
+    fn offer_move(&mut self, (from, to): MovePair) -> Option<String> {
+        if self.is_thinking() {
+            return Some(self.set_premove((from, to)));
+        }
+        let human = self.mover();
+        if let Err(reason) = self.variant.check_move(&self.board, from, to, human) {
+            return Some(rejection_text(reason, self.variant.title()));
+        }
+        self.players[side(human)].offer((from, to));
+        self.drive_players()
+    }
+
+    // checked for now only as far as the piece being the player's; whether
+    // it's legal waits for the other side's move
+    fn set_premove(&mut self, (from, to): MovePair) -> String {
+        let human = self.mover();
+        if !self.board.get_piece(from).is_some_and(|piece| piece.color == human) {
+            return fill(Text::NoPieceOfYoursOn, &[&coordinate_to_string(from)]);
+        }
+        self.premove = Some((from, to));
+        let undo = self.config.keys.binding(Action::Undo);
+        fill(Text::PremoveSet, &[&coordinates((from, to)), &undo])
+    }
+
+    // a move in words played, or asked about when it could be more than one
+    fn spoken_outcome(&mut self, outcome: Outcome) -> Option<String> {
+        match outcome {
+            Outcome::Play(mv) => self.offer_move(mv),
+            Outcome::Ask(question, choices) => {
+                self.spoken_choices = Some(choices);
+                Some(question)
+            }
+            Outcome::Refuse(reason) => Some(reason),
+        }
+    }
+
+    // writes the bot's last search tree to a file, to see why it played what it did
+    fn dump_search(&self) -> String {
+        let Some(trace) = &self.last_search else {
+            return tr(Text::NoSearchYet).to_string();
+        };
+        if self.variant.hides_pieces() && self.result.is_none() {
+            return tr(Text::SearchHidden).to_string();
+        }
+        match trace::save(trace, self.variant.as_ref()) {
+            Ok(path) => fill(Text::SearchWritten, &[&path.display()]),
+            Err(e) => fill(Text::SearchNotWritten, &[&e]),
+        }
+    }
+
+    // writes the position to an SVG picture or a text diagram
+    fn export_diagram(&self, format: Option<&str>) -> String {
+        let Some(format) = format.map_or(Some(DiagramFormat::Svg), DiagramFormat::by_name) else {
+            return tr(Text::DiagramUsage).to_string();
+        };
+        if (self.variant.hides_pieces() || self.blindfold) && self.result.is_none() {
+            return tr(Text::BoardHidden).to_string();
+        }
+        let diagram = Diagram {
+            board: &self.board,
+            to_move: self.record.to_move(),
+            fen: self.record.fen(),
+            last_move: self.record.last_move(),
+            flipped: self.flipped,
+        };
+        match diagram::save(&diagram, format) {
+            Ok(path) => fill(Text::DiagramWritten, &[&path.display()]),
+            Err(e) => fill(Text::DiagramNotWritten, &[&e]),
+        }
+    }
+
+    // searches the given move on its own, then the whole position, and
+    // explains the difference in the engine notes
+    fn why_not(&mut self, args: &[&str]) -> String {
+        let Some(mv) = parse_move_text(args) else {
+            return tr(Text::WhyNotUsage).to_string();
+        };
+        let name = coordinates(mv);
+        let color = self.mover();
+        let time = self.config.engine.analysis_time();
+
+        let board = self.variant.view(&self.board, color);
+        let Some(theirs) = self.rl_engine.search(&board, color, Some(&[mv]), time, &mut |_| {}) else {
+            return fill(Text::NotLegalHere, &[&name]);
+        };
+        let Some(best) = self.rl_engine.search(&board, color, None, time, &mut |_| {}) else {
+            return tr(Text::NothingToCompare).to_string();
+        };
+
+        self.engine_notes = vec![fill(Text::WhyNot, &[&name])];
+        if best.best_move == mv {
+            self.engine_notes
+                .push(fill(Text::EnginesChoiceToo, &[&name, &format_score(white_score(best.score, color))]));
+        } else {
+            let delta = best.score - theirs.score;
+            self.engine_notes.push(fill(
+                Text::EnginePrefersOver,
+                &[
+                    &coordinates(best.best_move),
+                    &format_score(white_score(best.score, color)),
+                    &name,
+                    &format_score(white_score(theirs.score, color)),
+                ],
+            ));
+            // with a mate on either side the scores above say it all
+            let mates = mate_in(best.score).is_some() || mate_in(theirs.score).is_some();
+            if !mates {
+                if delta > 0.0 {
+                    self.engine_notes.push(fill(Text::CostsPawns, &[&name, &format!("{:.2}", delta / 100.0)]));
+                } else {
+                    self.engine_notes.push(fill(Text::HoldsUp, &[&name]));
+                }
+            }
+        }
+        if let Some(reply) = theirs.pv.get(1) {
+            self.engine_notes.push(fill(Text::MainReply, &[&coordinates(*reply)]));
+        }
+        let line: Vec<String> = theirs.pv.iter().map(|m| coordinates(*m)).collect();
+        self.engine_notes.push(fill(Text::Line, &[&line.join(" ")]));
+
+        fill(Text::Compared, &[&name, &coordinates(best.best_move)])
+    }
+
+    // false when something typed can't be a square, e.g. "z9"
+    pub fn command_looks_valid(&self) -> bool {
+        let text = self.command_input.value().to_lowercase();
+        let mut words = text.split_whitespace().peekable();
+        if words.peek().is_some_and(|first| commands::could_be_command(first)) {
+            words.next();
+        }
+        words.all(|word| match word.len() {
+            _ if !word.is_ascii() => false,
+            _ if spoken::could_be_word(word) => true,
+            1 | 2 => is_square_prefix(word),
+            3 | 4 => is_square_prefix(&word[..2]) && is_square_prefix(&word[2..]),
+            _ => false,
+        })
+    }
+
+    // completes the origin square from the human's movable pieces, or the
+    // destination from the legal moves of the piece on the typed origin;
+    // pressing Tab again cycles through the candidates
+    pub fn complete_command(&mut self) {
+        if self.chatting {
+            return;
+        }
+        if let Some(completion) = &mut self.completion {
+            completion.index = (completion.index + 1) % completion.candidates.len();
+            let text = format!("{}{}", completion.stem, completion.candidates[completion.index]);
+            self.command_input.set_value(&text);
+            return;
+        }
+
+        let text = self.command_input.value().to_lowercase();
+        let ends_with_space = text.ends_with(' ');
+        let mut words: Vec<&str> = text.split_whitespace().collect();
+        let prefix = if ends_with_space { "" } else { words.pop().unwrap_or("") };
+        let command_word = words.first().is_some_and(|w| commands::is_command(w));
+        let move_words = &words[command_word as usize..];
+
+        let human = self.bot_color.opposite();
+        let candidates: Vec<(usize, usize)> = match move_words {
+            [] => (0..8)
+                .flat_map(|rank| (0..8).map(move |file| (rank, file)))
+                .filter(|&pos| {
+                    self.board.get_piece(pos).is_some_and(|p| p.color == human)
+                        && !self.variant.destinations(&self.board, pos).is_empty()
+                })
+                .collect(),
+            [origin] => match parse_coordinate(origin) {
+                Some(from) if self.board.get_piece(from).is_some_and(|p| p.color == human) => {
+                    self.variant.destinations(&self.board, from)
+                }
+                _ => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
+
+        let candidates: Vec<String> = candidates
+            .into_iter()
+            .map(coordinate_to_string)
+            .filter(|square| square.starts_with(prefix))
+            .collect();
+        if candidates.is_empty() {
+            self.status = tr(Text::NoCompletions).to_string();
+            return;
+        }
+
+        let mut stem = words.join(" ");
+        if !stem.is_empty() {
+            stem.push(' ');
+        }
+        self.command_input.set_value(&format!("{}{}", stem, candidates[0]));
+        if candidates.len() > 1 {
+            self.status = fill(Text::Completions, &[&candidates.len(), &candidates.join(" ")]);
+            self.completion = Some(Completion { stem, candidates, index: 0 });
+        }
+    }
+
+    pub fn reset_completion(&mut self) {
+        self.completion = None;
+    }
+
+    // the bot's own eval of the position, or None when it can't see it all
+    fn bot_eval(&self) -> Option<f32> {
+        (!self.variant.hides_pieces()).then(|| self.rl_engine.evaluate_position(&self.board, self.bot_color))
+    }
+
+    // counts the bot's moves spent lost; a few in a row and it gives up
+    fn bot_should_resign(&mut self) -> bool {
+        match self.bot_eval() {
+            Some(eval) if eval < RESIGN_SCORE => self.bot_losing_streak += 1,
+            _ => self.bot_losing_streak = 0,
+        }
+        self.bot_losing_streak >= RESIGN_MOVES
+    }
+
+    // the bot takes a draw when it thinks it's doing clearly worse than what
+    // a draw is worth to it with its contempt
+    fn offer_draw(&mut self) -> String {
+        let ply = self.record.len();
+        if self.draw_offer_ply == Some(ply) {
+            return tr(Text::DrawAlreadyOffered).to_string();
+        }
+        self.draw_offer_ply = Some(ply);
+        match self.bot_eval() {
+            Some(eval) if eval < -(self.config.bot.contempt as f32) - DRAW_MARGIN => {
+                self.move_history.push(tr(Text::DrawAccepted).to_string());
+                self.end_game(GameResult::DrawAgreed)
+            }
+            Some(_) => tr(Text::DrawDeclined).to_string(),
+            None => tr(Text::DrawDeclinedInFog).to_string(),
+        }
+    }
+
+    // sets up a random endgame of `name`'s kind, or of any kind, for the
+    // player to win or hold against the bot
+    fn start_drill(&mut self, name: Option<&str>) -> String {
+        let kind = match name {
+            Some(name) => match DrillKind::by_name(name) {
+                Some(kind) => kind,
+                None => {
+                    let names: Vec<&str> = DrillKind::ALL.iter().map(|kind| kind.name()).collect();
+                    return fill(Text::UnknownDrill, &[&names.join(", ")]);
+                }
+            },
+            None => *DrillKind::ALL.choose(&mut rand::thread_rng()).expect("there are drills"),
+        };
+        if self.opponent == OpponentKind::Network {
+            return tr(Text::NoNetworkDrills).to_string();
+        }
+        self.variant = Arc::new(Standard);
+        self.handicap = Handicap::None;
+        self.kind = TabKind::Game;
+        self.bot_color = kind.player().opposite();
+        self.new_game();
+        self.board = drill::setup(kind, &mut rand::thread_rng());
+        self.record = GameRecord::new(self.board.clone(), PieceColor::White);
+        self.current_turn = PieceColor::White;
+        self.start_fen = Some(self.record.fen());
+        self.drill = Some(kind);
+        self.flipped = kind.player() == PieceColor::Black;
+        self.update_crash_context();
+        logging::info(&format!("{} drill from {}", kind.title(), self.record.fen()));
+        self.ask_second_opinion();
+        self.game_state = GameState::Playing;
+        let task = fill(Text::DrillTask, &[&kind.title(), &kind.task()]);
+        self.move_history.push(task.clone());
+        self.drive_players();
+        task
+    }
+
+    // the player's moves so far in the drill
+    fn drill_moves(&self, kind: DrillKind) -> usize {
+        self.record.plies.iter().filter(|ply| ply.color == kind.player()).count()
+    }
+
+    // ends a drill the rules left going once its result is certain
+    fn check_drill(&mut self) -> Option<String> {
+        let kind = self.drill?;
+        let result = drill::adjudicate(kind, &self.board, self.current_turn, self.drill_moves(kind))?;
+        Some(self.end_game(result))
+    }
+
+    fn drill_verdict(&self, kind: DrillKind, result: GameResult) -> String {
+        let moves = self.drill_moves(kind);
+        if kind.passed(result) {
+            fill(Text::DrillPassed, &[&kind.title(), &moves])
+        } else {
+            fill(Text::DrillFailed, &[&kind.title(), &moves, &kind.name()])
+        }
+    }
+
+    // a race to mate the random mover, playing white, from `seed` or a new
+    // one; `speedrun scores` lists the best runs instead
+    fn start_speedrun(&mut self, seed: Option<&str>) -> String {
+        if seed == Some("scores") {
+            let runs = speedrun::leaderboard();
+            if runs.is_empty() {
+                return tr(Text::NoSpeedruns).to_string();
+            }
+            for (place, run) in runs.iter().enumerate() {
+                self.move_history.push(format!("{}. {}", place + 1, run.text()));
+            }
+            return fill(Text::BestSpeedrun, &[&runs[0].text()]);
+        }
+        let seed = match seed.map(str::parse) {
+            Some(Ok(seed)) => seed,
+            Some(Err(_)) => return tr(Text::SpeedrunUsage).to_string(),
+            None => rand::thread_rng().gen_range(0..1_000_000),
+        };
+        if self.opponent == OpponentKind::Network {
+            return tr(Text::NoNetworkSpeedruns).to_string();
+        }
+        let previous = self.speedrun.map_or(self.opponent, |attempt| attempt.previous);
+        self.variant = Arc::new(Standard);
+        self.handicap = Handicap::None;
+        self.kind = TabKind::Game;
+        self.bot_color = PieceColor::Black;
+        self.flipped = false;
+        self.opponent = OpponentKind::Scripted(Opponent::Random);
+        self.new_game();
+        self.speedrun = Some(Attempt { seed, previous });
+        // seeded, the same moves get the same replies
+        self.players[side(self.bot_color)] = Box::new(ScriptedController::new(Scripted::new(Opponent::Random, Some(seed))));
+        logging::info(&format!("speedrun on seed {}", seed));
+        self.game_state = GameState::Playing;
+        let task = fill(Text::SpeedrunTask, &[&seed]);
+        self.move_history.push(task.clone());
+        task
+    }
+
+    // the player's moves so far and the time they've spent on them, the
+    // move they're thinking over included
+    fn speedrun_clock(&self) -> (usize, Duration) {
+        let human = self.bot_color.opposite();
+        let mine = self.record.plies.iter().filter(|ply| ply.color == human);
+        let mut time: Duration = mine.clone().filter_map(|ply| ply.clock).sum();
+        if self.result.is_none() && self.current_turn == human {
+            time += self.turn_started.elapsed();
+        }
+        (mine.count(), time)
+    }
+
+    fn speedrun_verdict(&self, attempt: Attempt, result: GameResult) -> String {
+        let human = self.bot_color.opposite();
+        if result != (GameResult::Checkmate { winner: human }) {
+            return fill(Text::SpeedrunFailed, &[&attempt.seed]);
+        }
+        let (moves, time) = self.speedrun_clock();
+        let run = Run::new(attempt.seed, moves, time);
+        let text = run.text();
+        match speedrun::record(run) {
+            Ok(Some(place)) => fill(Text::SpeedrunPlace, &[&text, &place]),
+            Ok(None) => fill(Text::SpeedrunDone, &[&text]),
+            Err(e) => {
+                logging::warn(&format!("couldn't save the speedrun: {}", e));
+                fill(Text::SpeedrunNotSaved, &[&text, &e])
+            }
+        }
+    }
+
+    // ends and saves the game when `to_move` has no way to continue
+    fn check_game_over(&mut self, to_move: PieceColor) -> Option<String> {
+        let result = self
+            .variant
+            .result(&self.board, to_move)
+            .or_else(|| is_threefold(self.record.positions()).then_some(GameResult::Repetition))?;
+        Some(self.end_game(result))
+    }
+
+    // the position on screen, for copying. a hidden board stays hidden
+    fn shown_fen(&self) -> Option<String> {
+        match self.game_state {
+            GameState::Playing if (self.variant.hides_pieces() || self.blindfold) && self.result.is_none() => None,
+            GameState::Playing => Some(self.record.fen()),
+            GameState::Replay => self.replay.as_ref().map(Replay::fen),
+            GameState::Follow => self.follow.as_ref().map(Follow::fen),
+            GameState::Editor => Some(self.editor.fen()),
+            _ => None,
+        }
+    }
+
+    // the game on screen, for copying
+    fn shown_pgn(&self) -> Option<String> {
+        match self.game_state {
+            GameState::Playing if self.variant.hides_pieces() && self.result.is_none() => None,
+            GameState::Playing => Some(self.game_pgn()),
+            GameState::Replay => self.replay.as_ref().map(Replay::movetext),
+            GameState::Follow => self.follow.as_ref().map(Follow::pgn),
+            _ => None,
+        }
+    }
+
+    // the game being played, as it would be saved now
+    fn game_pgn(&self) -> String {
+        let unix_time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let (result, termination) = match self.result {
+            Some(result) => (result.score(), result.termination()),
+            None => ("*", "unterminated"),
+        };
+        games::to_pgn(&self.saved_game(unix_time, result, termination))
+    }
+
+    fn copy(&mut self, text: String, what: &str) -> String {
+        match self.clipboard.copy(&text) {
+            Ok(to) => fill(Text::CopiedTo, &[&what, &to]),
+            Err(e) => e,
+        }
+    }
+
+    // a FEN opens in the editor to look over before playing it; a PGN, the
+    // last game in it if there are several, opens in the replay viewer
+    fn paste(&mut self) {
+        let text = match self.clipboard.paste() {
+            Ok(text) => text,
+            Err(e) => {
+                self.status = e;
+                return;
+            }
+        };
+        if let Some((board, to_move)) = Board::from_fen(text.trim()) {
+            self.editor = Editor::new(board, to_move);
+            self.game_state = GameState::Editor;
+            self.status = tr(Text::PositionPasted).to_string();
+            return;
+        }
+        let game = games::split_games(&text).last().map(|pgn| games::parse(pgn));
+        let Some(game) = game.filter(|game| !game.moves.is_empty()) else {
+            self.status = tr(Text::NothingToPaste).to_string();
+            return;
+        };
+        let record = game.record();
+        if record.is_empty() {
+            self.status = fill(Text::PastedMoveUnreadable, &[&game.moves[0]]);
+            return;
+        }
+        if record.len() < game.moves.len() {
+            self.status = fill(Text::OnlyFirstMovesRead, &[&record.len()]);
+        } else {
+            self.status.clear();
+        }
+        self.replay = Some(Replay::new(&record));
+        if self.game_state != GameState::Replay {
+            self.replay_return = self.game_state;
+        }
+        self.game_state = GameState::Replay;
+    }
+
+    // an edited position isn't any opening, whatever its moves look like
+    fn opening(&self) -> &'static str {
+        match self.start_fen {
+            Some(_) => "Unknown",
+            None => opening_name(&self.record.uci()).unwrap_or("Unknown"),
+        }
+    }
+
+    // the game so far with what its PGN tags need
+    fn saved_game<'a>(&'a self, unix_time: u64, result: &'a str, termination: &'a str) -> NewGame<'a> {
+        NewGame {
+            unix_time,
+            human: self.bot_color.opposite(),
+            bot_time_secs: self.bot_think_time().as_secs_f32(),
+            bot_level: self.level_text(),
+            result,
+            termination,
+            opening: self.opening(),
+            variant: self.variant.title(),
+            handicap: self.handicap.name(),
+            fen: self.start_fen.as_deref(),
+            record: &self.record,
+        }
+    }
+
+    // records the result, updates the rating and saves the game as PGN
+    fn end_game(&mut self, result: GameResult) -> String {
+        self.result = Some(result);
+        for player in &mut self.players {
+            player.game_over(result);
+        }
+        // an analysis board's games are nobody's to win, save or rate
+        if self.kind == TabKind::Analysis {
+            let headline = match result.winner() {
+                Some(color) => sentence(Text::SideWinsBy, &[&tr(Text::Side(color)), &result.termination()]),
+                None => fill(Text::DrawBy, &[&result.termination()]),
+            };
+            self.move_history.push(format!("{} {}", result.score(), headline));
+            self.narrate(&fill(Text::GameOverNarrated, &[&headline]));
+            return headline;
+        }
+
+        if self.opponent == OpponentKind::Engine {
+            games::clear_autosave();
+        }
+        let human = self.bot_color.opposite();
+        let headline = match result.winner() {
+            Some(color) if color == human => fill(Text::YouWinBy, &[&result.termination()]),
+            Some(_) => fill(Text::PlayerWinsBy, &[&self.players[side(self.bot_color)].name(), &result.termination()]),
+            None => fill(Text::DrawBy, &[&result.termination()]),
+        };
+        self.move_history.push(format!("{} {}", result.score(), headline));
+        session::game_over(result.winner().map(|color| color == human));
+        logging::info(&format!("game over: {} {}", result.score(), headline));
+        self.config.hooks.fire(HookEvent::GameOver, &headline);
+        self.narrate(&fill(Text::GameOverNarrated, &[&headline]));
+
+        let unix_time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let opening = self.opening();
+
+        let mut headline = headline;
+        if let Some(kind) = self.drill {
+            let verdict = self.drill_verdict(kind, result);
+            self.move_history.push(verdict.clone());
+            headline = format!("{}. {}", headline, verdict);
+        }
+        if let Some(attempt) = self.speedrun {
+            let verdict = self.speedrun_verdict(attempt, result);
+            self.move_history.push(verdict.clone());
+            headline = format!("{}. {}", headline, verdict);
+        }
+
+        // only standard games against the engine are kept: even ones count
+        // toward the rating, ones at odds toward what the odds are worth
+        if self.variant.rated()
+            && self.start_fen.is_none()
+            && self.opponent == OpponentKind::Engine
+            && self.level.rated()
+            && self.target_elo.is_none()
+            && !self.switched
+        {
+            let score = match result.winner() {
+                Some(color) if color == human => 1.0,
+                Some(_) => 0.0,
+                None => 0.5,
+            };
+            let (date, _) = pgn_date_time(unix_time);
+            let rating = if self.handicap == Handicap::None {
+                let change = self.profile.record(date, self.level, score);
+                fill(Text::RatingChange, &[&format!("{:.0}", self.profile.rating), &format!("{:+.0}", change)])
+            } else {
+                self.profile.record_odds(date, self.level, self.handicap, score);
+                let (gap, games) = self.profile.odds_gap(self.handicap).unwrap_or_default();
+                fill(Text::OddsChange, &[&self.handicap.name(), &format!("{:+.0}", gap), &games])
+            };
+            self.move_history.push(fill(Text::YourRating, &[&rating]));
+            for &(idx, judgement) in &self.player_moves {
+                let key = (idx < OPENING_PLIES).then(|| position_key(&self.record.positions()[idx], human));
+                self.profile.model.observe(key, judgement);
+            }
+            self.profile.model.record_opening(opening);
+            if let Err(e) = self.profile.save() {
+                logging::warn(&format!("couldn't save the profile: {}", e));
+                headline = fill(Text::RatingNotSaved, &[&headline, &rating, &e]);
+            }
+        }
+
+        match games::save(&self.saved_game(unix_time, result.score(), result.termination())) {
+            Ok(_) => fill(Text::GameSaved, &[&headline]),
+            Err(e) => {
+                logging::warn(&format!("couldn't save the game: {}", e));
+                fill(Text::GameNotSaved, &[&headline, &e])
+            }
+        }
+    }
+
+    fn set_level(&mut self, name: Option<&str>) -> String {
+        let Some(name) = name else {
+            let names: Vec<&str> = Difficulty::ALL.iter().map(|d| d.name()).collect();
+            return fill(Text::PlayingAtLevel, &[&self.level.name(), &names.join(", ")]);
+        };
+        let Some(level) = Difficulty::from_name(name) else {
+            return fill(Text::UnknownLevel, &[&name]);
+        };
+        self.config.bot.level = level;
+        let when = if self.record.is_empty() {
+            self.level = level;
+            tr(Text::Now)
+        } else {
+            tr(Text::FromNextGame)
+        };
+        let done = fill(Text::BotLevel, &[&level.name(), &when]);
+        match self.config.save() {
+            Ok(()) => done,
+            Err(e) => fill(Text::ButNotSaved, &[&done, &e]),
+        }
+    }
+
+    fn set_target_elo(&mut self, arg: Option<&str>) -> String {
+        let target = match arg {
+            None => {
+                return match self.target_elo {
+                    Some(elo) => fill(Text::PlaysAtElo, &[&elo]),
+                    None => fill(Text::PlaysAtLevel, &[&self.level.name(), &MIN_TARGET, &MAX_TARGET]),
+                }
+            }
+            Some("off") => None,
+            Some(text) => match text.parse::<u32>() {
+                Ok(elo) if (MIN_TARGET..=MAX_TARGET).contains(&elo) => Some(elo),
+                _ => return fill(Text::TargetEloRange, &[&MIN_TARGET, &MAX_TARGET]),
+            },
+        };
+        self.config.bot.target_elo = target;
+        let when = if self.record.is_empty() {
+            self.target_elo = target;
+            // the bot's search cap goes in with its controller; if it's
+            // already thinking, it starts over under the new one
+            if self.opponent == OpponentKind::Engine {
+                self.players[side(self.bot_color)] = self.opponent_controller();
+                self.drive_players();
+            }
+            tr(Text::Now)
+        } else {
+            tr(Text::FromNextGame)
+        };
+        let done = match target {
+            Some(elo) => fill(Text::BotAtAbout, &[&elo, &when]),
+            None => fill(Text::BotLevel, &[&self.level.name(), &when]),
+        };
+        match self.config.save() {
+            Ok(()) => done,
+            Err(e) => fill(Text::ButNotSaved, &[&done, &e]),
+        }
+    }
+
+    // the bot takes over the player's side and the player the bot's, from
+    // the position as it stands. if that leaves the bot to move, it does
+    fn switch_sides(&mut self) -> String {
+        if self.opponent == OpponentKind::Network {
+            return tr(Text::NoNetworkSwitch).to_string();
+        }
+        if self.kind == TabKind::Analysis {
+            return tr(Text::AnalysisBothSides).to_string();
+        }
+        if self.result.is_some() {
+            return tr(Text::TheGameIsOver).to_string();
+        }
+        self.bot_color = self.bot_color.opposite();
+        self.players.swap(0, 1);
+        // a search under way was for the side the bot just left
+        if self.opponent == OpponentKind::Engine {
+            self.players[side(self.bot_color)] = self.opponent_controller();
+        }
+        // your side stays at the bottom
+        self.flipped = !self.flipped;
+        // what was taken or expected belongs to the other side now
+        self.premove = None;
+        self.undo_stack.clear();
+        self.bot_expected.clear();
+        self.engine_choice = None;
+        self.bot_losing_streak = 0;
+        self.current_position_score = self.rl_engine.evaluate_position(&self.board, self.bot_color);
+        self.last_position_score = self.current_position_score;
+        self.turn_started = Instant::now();
+        self.switched |= !self.record.is_empty();
+
+        let human = self.bot_color.opposite();
+        logging::info(&format!("sides switched at ply {}, bot plays {}", self.record.len(), self.bot_color.name()));
+        let msg = fill(
+            if self.switched { Text::SidesSwitchedUnrated } else { Text::SidesSwitched },
+            &[&tr(Text::Side(human)), &tr(Text::Side(self.bot_color))],
+        );
+        self.narrate(&msg);
+        self.drive_players();
+        msg
+    }
+
+    // `tab`: lists the tabs; `tab new`, `tab analysis`, `tab close` and
+    // `tab <n>` open, close and go to one
+    fn tab_command(&mut self, args: &[&str]) -> String {
+        match args {
+            [] => {
+                let titles: Vec<String> = self
+                    .tab_titles()
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, title)| format!("{} {}", idx + 1, title))
+                    .collect();
+                fill(Text::TabList, &[&titles.join(", ")])
+            }
+            ["new"] => self.open_tab(TabKind::Game),
+            ["analysis"] => self.open_tab(TabKind::Analysis),
+            ["close"] => self.close_tab(),
+            [n] => match n.parse::<usize>() {
+                Ok(n) if (1..=self.tabs.len()).contains(&n) => self.go_to_tab(n - 1),
+                _ => fill(Text::NoSuchTab, &[n]),
+            },
+            _ => tr(Text::TabUsage).to_string(),
+        }
+    }
+
+    fn tab_titles(&self) -> Vec<String> {
+        self.tabs
+            .iter()
+            .map(|game| match game {
+                Some(game) => game.title(),
+                None => tabs::title(self.kind, &self.opponent_label(), self.result, self.is_thinking()),
+            })
+            .collect()
+    }
+
+    // the other player in a network game only knows about the one game
+    fn tab_refusal(&self) -> Option<String> {
+        self.peer.is_some().then(|| tr(Text::NoNetworkTabs).to_string())
+    }
+
+    // a game set up like the one on screen, or an analysis board starting
+    // from its position
+    pub fn open_tab(&mut self, kind: TabKind) -> String {
+        if let Some(refusal) = self.tab_refusal() {
+            return refusal;
+        }
+        if self.tabs.len() >= MAX_TABS {
+            return fill(Text::TooManyTabs, &[&MAX_TABS]);
+        }
+        // an analysis board shows both sides, so no fog
+        let from = (kind == TabKind::Analysis && !self.variant.hides_pieces() && !self.record.is_empty())
+            .then(|| (self.record.clone(), self.board.clone(), self.current_turn, self.start_fen.clone()));
+        let mut game = Game {
+            kind,
+            analysis: None,
+            analysis_go: Go::default(),
+            board: Board::new(),
+            cursor_pos: (0, 0),
+            selected_piece: None,
+            move_history: Vec::new(),
+            history_scroll: None,
+            rl_engine: self.rl_engine.fork(),
+            current_turn: PieceColor::White,
+            bot_color: self.bot_color,
+            last_position_score: 0.0,
+            current_position_score: 0.0,
+            flipped: self.flipped,
+            show_threats: false,
+            engine_notes: Vec::new(),
+            undo_stack: Vec::new(),
+            record: GameRecord::new(Board::new(), PieceColor::White),
+            turn_started: Instant::now(),
+            bot_expected: Vec::new(),
+            player_moves: Vec::new(),
+            players: [Box::new(HumanController::default()), Box::new(HumanController::default())],
+            opponent: self.opponent,
+            engine_choice: None,
+            opponent_info: None,
+            second_opinion: None,
+            second_opinion_info: None,
+            second_opinion_error: None,
+            last_search: None,
+            result: None,
+            variant: if self.variant.hides_pieces() && kind == TabKind::Analysis {
+                Arc::new(Standard)
+            } else {
+                self.variant.clone()
+            },
+            level: self.level,
+            target_elo: self.target_elo,
+            personality: self.personality,
+            loaded_weights: self.loaded_weights.clone(),
+            loaded_values: self.loaded_values,
+            bot_losing_streak: 0,
+            draw_offer_ply: None,
+            spoken_choices: None,
+            premove: None,
+            switched: false,
+            handicap: if kind == TabKind::Analysis { Handicap::None } else { self.handicap },
+            start_fen: None,
+            drill: None,
+            speedrun: None,
+            blindfold: false,
+            narrator: Narrator::new(&self.config.accessibility),
+        };
+        self.exchange(&mut game);
+        self.tabs[self.tab] = Some(game);
+        self.tabs.push(None);
+        self.tab = self.tabs.len() - 1;
+        self.new_game();
+        if let Some((record, board, to_move, start_fen)) = from {
+            self.record = record;
+            self.board = board;
+            self.current_turn = to_move;
+            self.start_fen = start_fen;
+            self.move_history.push(fill(Text::AnalysisFromMove, &[&(self.record.len() / 2 + 1)]));
+        }
+        self.analyze_position();
+        self.drive_players();
+        match kind {
+            TabKind::Game => fill(Text::NewGameTab, &[&(self.tab + 1), &self.opponent.description()]),
+            TabKind::Analysis => fill(Text::AnalysisTab, &[&(self.tab + 1)]),
+        }
+    }
+
+    fn go_to_tab(&mut self, idx: usize) -> String {
+        if let Some(refusal) = self.tab_refusal() {
+            return refusal;
+        }
+        self.switch_tab(idx);
+        fill(Text::TabTitle, &[&(idx + 1), &self.tab_titles()[idx]])
+    }
+
+    pub fn next_tab(&mut self) -> String {
+        if self.tabs.len() == 1 {
+            let key = self.config.keys.binding(Action::NewTab);
+            return fill(Text::OneTab, &[&key]);
+        }
+        self.go_to_tab((self.tab + 1) % self.tabs.len())
+    }
+
+    // leaves the game in it unfinished and unsaved
+    fn close_tab(&mut self) -> String {
+        if let Some(refusal) = self.tab_refusal() {
+            return refusal;
+        }
+        if self.tabs.len() == 1 {
+            return tr(Text::OnlyTab).to_string();
+        }
+        let closing = self.tab;
+        self.switch_tab(if closing + 1 < self.tabs.len() { closing + 1 } else { closing - 1 });
+        self.tabs.remove(closing);
+        if self.tab > closing {
+            self.tab -= 1;
+        }
+        fill(Text::TabClosed, &[&(closing + 1)])
+    }
+
+    fn switch_tab(&mut self, idx: usize) {
+        let Some(mut game) = self.tabs.get_mut(idx).and_then(Option::take) else {
+            return;
+        };
+        self.exchange(&mut game);
+        self.tabs[self.tab] = Some(game);
+        self.tab = idx;
+        self.completion = None;
+    }
+
+    // the parked tab an engine's search or UCI engine belongs to
+    fn tab_of_search(&self, id: u64) -> Option<usize> {
+        self.tabs.iter().position(|game| game.as_ref().is_some_and(|game| game.owns_search(id)))
+    }
+
+    // runs `f` with tab `idx` on screen for a moment, for an engine
+    // reporting back to a game that isn't. what it says goes to that game's
+    // history, not the status line
+    fn in_tab<R>(&mut self, idx: usize, f: impl FnOnce(&mut App) -> R) -> R {
+        let Some(mut game) = self.tabs[idx].take() else {
+            return f(self);
+        };
+        let status = std::mem::take(&mut self.status);
+        self.exchange(&mut game);
+        let result = f(self);
+        self.exchange(&mut game);
+        self.tabs[idx] = Some(game);
+        self.status = status;
+        result
+    }
+
+    // swaps the game on screen with `game`
+    fn exchange(&mut self, game: &mut Game) {
+        use std::mem::swap;
+        swap(&mut self.kind, &mut game.kind);
+        swap(&mut self.analysis, &mut game.analysis);
+        swap(&mut self.analysis_go, &mut game.analysis_go);
+        swap(&mut self.board, &mut game.board);
+        swap(&mut self.cursor_pos, &mut game.cursor_pos);
+        swap(&mut self.selected_piece, &mut game.selected_piece);
+        swap(&mut self.move_history, &mut game.move_history);
+        swap(&mut self.history_scroll, &mut game.history_scroll);
+        swap(&mut self.rl_engine, &mut game.rl_engine);
+        swap(&mut self.current_turn, &mut game.current_turn);
+        swap(&mut self.bot_color, &mut game.bot_color);
+        swap(&mut self.last_position_score, &mut game.last_position_score);
+        swap(&mut self.current_position_score, &mut game.current_position_score);
+        swap(&mut self.flipped, &mut game.flipped);
+        swap(&mut self.show_threats, &mut game.show_threats);
+        swap(&mut self.engine_notes, &mut game.engine_notes);
+        swap(&mut self.undo_stack, &mut game.undo_stack);
+        swap(&mut self.record, &mut game.record);
+        swap(&mut self.turn_started, &mut game.turn_started);
+        swap(&mut self.bot_expected, &mut game.bot_expected);
+        swap(&mut self.player_moves, &mut game.player_moves);
+        swap(&mut self.players, &mut game.players);
+        swap(&mut self.opponent, &mut game.opponent);
+        swap(&mut self.engine_choice, &mut game.engine_choice);
+        swap(&mut self.opponent_info, &mut game.opponent_info);
+        swap(&mut self.second_opinion, &mut game.second_opinion);
+        swap(&mut self.second_opinion_info, &mut game.second_opinion_info);
+        swap(&mut self.second_opinion_error, &mut game.second_opinion_error);
+        swap(&mut self.last_search, &mut game.last_search);
+        swap(&mut self.result, &mut game.result);
+        swap(&mut self.variant, &mut game.variant);
+        swap(&mut self.level, &mut game.level);
+        swap(&mut self.target_elo, &mut game.target_elo);
+        swap(&mut self.personality, &mut game.personality);
+        swap(&mut self.loaded_weights, &mut game.loaded_weights);
+        swap(&mut self.loaded_values, &mut game.loaded_values);
+        swap(&mut self.bot_losing_streak, &mut game.bot_losing_streak);
+        swap(&mut self.draw_offer_ply, &mut game.draw_offer_ply);
+        swap(&mut self.spoken_choices, &mut game.spoken_choices);
+        swap(&mut self.premove, &mut game.premove);
+        swap(&mut self.switched, &mut game.switched);
+        swap(&mut self.handicap, &mut game.handicap);
+        swap(&mut self.start_fen, &mut game.start_fen);
+        swap(&mut self.drill, &mut game.drill);
+        swap(&mut self.speedrun, &mut game.speedrun);
+        swap(&mut self.blindfold, &mut game.blindfold);
+        swap(&mut self.narrator, &mut game.narrator);
+    }
+
+    // has the engine look at the position on an analysis board. a search
+    // still running on the one before is left to finish and be ignored
+    fn analyze_position(&mut self) {
+        self.analysis = None;
+        if self.kind != TabKind::Analysis || self.variant.result(&self.board, self.current_turn).is_some() {
+            return;
+        }
+        let mut engine = self.rl_engine.fork();
+        engine.set_history(self.record.with_sides());
+        let threads = self.config.engine.threads();
+        self.analysis_go.limit(&mut engine, threads);
+        let events = self.events.clone();
+        self.analysis = Some(SearchHandle::spawn(
+            engine,
+            self.board.clone(),
+            self.current_turn,
+            None,
+            self.analysis_go.time(self.config.engine.analysis_time()),
+            threads,
+            move |search, update| {
+                let _ = events.send(match update {
+                    SearchUpdate::Progress(stats) => AppEvent::EngineProgress { search, stats },
+                    SearchUpdate::Finished(result, stats) => AppEvent::EngineMove { search, result, stats },
+                });
+            },
+        ));
+    }
+
+    // how the analysis board searches from now on, UCI style: `go infinite`
+    // looks until stopped, `go depth 8` until the line is that deep
+    fn go(&mut self, args: &[&str]) -> String {
+        if self.kind != TabKind::Analysis {
+            return tr(Text::GoOnAnalysisOnly).to_string();
+        }
+        let Some(go) = Go::parse(args) else {
+            return tr(Text::GoUsage).to_string();
+        };
+        self.analysis_go = go;
+        self.analyze_position();
+        if self.analysis.is_none() {
+            return tr(Text::NothingToSearch).to_string();
+        }
+        fill(Text::AnalysingEach, &[&go.text()])
+    }
+
+    // whether there's a search that `move_now` would cut short
+    pub fn can_move_now(&self) -> bool {
+        self.analysis.is_some() || (self.is_thinking() && self.opponent != OpponentKind::Network)
+    }
+
+    // the bot plays the best move it has found so far, or the analysis
+    // stops where it is
+    pub fn move_now(&mut self) -> String {
+        if self.is_thinking() {
+            return if self.players[side(self.current_turn)].move_now() {
+                tr(Text::MovingNow).to_string()
+            } else {
+                self.waiting_text()
+            };
+        }
+        match &self.analysis {
+            Some(search) => {
+                search.stop();
+                tr(Text::AnalysisStopped).to_string()
+            }
+            None => tr(Text::NothingSearching).to_string(),
+        }
+    }
+
+    fn is_analysis_search(&self, id: u64) -> bool {
+        self.analysis.as_ref().is_some_and(|search| search.id == id)
+    }
+
+    fn set_personality(&mut self, name: Option<&str>) -> String {
+        let Some(name) = name else {
+            let names: Vec<&str> = Personality::ALL.iter().map(|p| p.name()).collect();
+            return fill(Text::BotPersonality, &[&self.personality.name(), &names.join(", ")]);
+        };
+        let Some(personality) = Personality::from_name(name) else {
+            return fill(Text::UnknownPersonality, &[&name]);
+        };
+        self.config.bot.personality = personality;
+        let when = if self.record.is_empty() {
+            self.personality = personality;
+            self.rl_engine.set_personality(personality);
+            tr(Text::Now)
+        } else {
+            tr(Text::FromNextGame)
+        };
+        let done = fill(Text::BotPlays, &[&personality.name(), &when]);
+        match self.config.save() {
+            Ok(()) => done,
+            Err(e) => fill(Text::ButNotSaved, &[&done, &e]),
+        }
+    }
+
+    // who plays the bot's side. a game under way keeps its opponent, so a
+    // rated game can't be finished off against the random mover
+    fn set_opponent(&mut self, name: Option<&str>) -> String {
+        let Some(name) = name else {
+            let names: Vec<&str> = OpponentKind::CHOICES.iter().map(|o| o.name()).collect();
+            return fill(Text::PlayingOpponents, &[&self.opponent.description(), &names.join(", ")]);
+        };
+        let Some(opponent) = OpponentKind::from_name(name) else {
+            return fill(Text::UnknownOpponent, &[&name]);
+        };
+        if self.opponent == OpponentKind::Network && self.peer.is_some() {
+            return tr(Text::QuitToPlayOthers).to_string();
+        }
+        if !self.record.is_empty() && self.result.is_none() {
+            return tr(Text::FinishFirst).to_string();
+        }
+        self.opponent = opponent;
+        if !self.record.is_empty() {
+            return fill(Text::PlayingFromNextGame, &[&opponent.description()]);
+        }
+        self.players[side(self.bot_color)] = self.opponent_controller();
+        self.drive_players();
+        // a UCI engine that won't start leaves the bot playing
+        fill(Text::PlayingNow, &[&self.opponent.description()])
+    }
+
+    // waits for someone to `join` from another chessrl; on_peer starts the
+    // game when they do. the host plays white
+    fn host(&mut self, port: Option<&str>) -> String {
+        if self.peer.is_some() {
+            return tr(Text::AlreadyConnected).to_string();
+        }
+        let port = match port.map(str::parse) {
+            None => network::DEFAULT_PORT,
+            Some(Ok(port)) => port,
+            Some(Err(_)) => return tr(Text::HostUsage).to_string(),
+        };
+        match network::host(port, self.events.clone()) {
+            Ok(()) => {
+                logging::info(&format!("hosting a network game on port {}", port));
+                fill(Text::WaitingToJoin, &[&port])
+            }
+            Err(e) => fill(Text::CouldntHost, &[&port, &e]),
+        }
+    }
+
+    fn join(&mut self, address: &str) -> String {
+        if self.peer.is_some() {
+            return tr(Text::AlreadyConnected).to_string();
+        }
+        network::join(address, self.events.clone());
+        fill(Text::Connecting, &[&address])
+    }
+
+    fn send_peer(&self, message: &str) -> Result<(), String> {
+        let peer = self.peer.as_ref().filter(|_| self.opponent == OpponentKind::Network);
+        let peer = peer.ok_or_else(|| tr(Text::NotConnected).to_string())?;
+        network::send(peer, message).map_err(|e| fill(Text::CouldntReach, &[&e]))
+    }
+
+    fn toggle_chat(&mut self) -> String {
+        if !self.chatting && self.opponent != OpponentKind::Network {
+            return tr(Text::ChatNetworkOnly).to_string();
+        }
+        self.chatting = !self.chatting;
+        self.command_input.clear();
+        let key = self.config.keys.binding(Action::ToggleChat);
+        if self.chatting {
+            fill(Text::ChatOn, &[&key])
+        } else {
+            fill(Text::ChatOff, &[&key])
+        }
+    }
+
+    fn send_chat(&mut self, text: &str) -> String {
+        let text = chat::clean(text);
+        if text.is_empty() {
+            return String::new();
+        }
+        if let Err(e) = self.send_peer(&format!("chat {}", text)) {
+            return e;
+        }
+        self.chat.add("You", &text);
+        self.file_chat();
+        String::new()
+    }
+
+    // puts what's been said since the last move on it, as a PGN comment
+    fn file_chat(&mut self) {
+        let Some(ply) = self.record.plies.last_mut() else {
+            return;
+        };
+        if let Some(said) = self.chat.take_unfiled() {
+            ply.comment = Some(match ply.comment.take() {
+                Some(comment) => format!("{} | {}", comment, said),
+                None => said,
+            });
+        }
+    }
+
+    // asks the other player of a network game to go back to before our
+    // last move, which is one ply back on their turn and two on ours
+    fn ask_takeback(&mut self) -> String {
+        if let Some(result) = self.result {
+            return fill(Text::GameOver, &[&result.termination()]);
+        }
+        match self.takeback {
+            Some(Takeback::Asked(_)) => return tr(Text::TakebackPending).to_string(),
+            Some(Takeback::Offered(_)) => return tr(Text::TakebackAskedFirst).to_string(),
+            None => {}
+        }
+        let back = if self.current_turn == self.bot_color { 1 } else { 2 };
+        let Some(plies) = self.record.len().checked_sub(back) else {
+            return tr(Text::NotMovedYet).to_string();
+        };
+        if let Err(e) = self.send_peer(&format!("takeback {}", plies)) {
+            return e;
+        }
+        self.takeback = Some(Takeback::Asked(plies));
+        tr(Text::TakebackAsked).to_string()
+    }
+
+    fn answer_takeback(&mut self, accept: bool) -> String {
+        let Some(Takeback::Offered(plies)) = self.takeback else {
+            return tr(Text::NoTakebackAsked).to_string();
+        };
+        self.takeback = None;
+        if let Some(result) = self.result {
+            return fill(Text::GameOver, &[&result.termination()]);
+        }
+        if !accept {
+            return match self.send_peer("takeback decline") {
+                Ok(()) => tr(Text::TakebackDeclined).to_string(),
+                Err(e) => e,
+            };
+        }
+        if let Err(e) = self.send_peer(&format!("takeback accept {}", plies)) {
+            return e;
+        }
+        if !self.take_back_to(plies) {
+            return tr(Text::TakebackUnknownPosition).to_string();
+        }
+        self.drive_players().unwrap_or_else(|| tr(Text::MoveTakenBack).to_string())
+    }
+
+    // the other player takes the bot's side of a game the host just started
+    fn send_new_game(&self) {
+        let Some(peer) = self.peer.as_ref().filter(|_| self.opponent == OpponentKind::Network && self.hosting) else {
+            return;
+        };
+        let message = format!("new {} {}", self.bot_color.name(), self.variant.name());
+        if let Err(e) = network::send(peer, &message) {
+            logging::warn(&format!("couldn't start the game on the other side: {}", e));
+        }
+    }
+
+    pub fn on_peer(&mut self, event: PeerEvent) {
+        match event {
+            PeerEvent::Connected { stream, hosting } => {
+                if let Err(e) = network::listen(&stream, self.events.clone()) {
+                    self.status = fill(Text::CouldntTalk, &[&e]);
+                    return;
+                }
+                self.peer = Some(stream);
+                self.hosting = hosting;
+                self.chat.clear();
+                if !hosting {
+                    self.status = tr(Text::WaitingForHost).to_string();
+                    return;
+                }
+                self.opponent = OpponentKind::Network;
+                self.bot_color = PieceColor::Black;
+                self.handicap = Handicap::None;
+                self.kind = TabKind::Game;
+                self.new_game();
+                self.send_new_game();
+                self.game_state = GameState::Playing;
+                self.report(tr(Text::SomeoneJoined).to_string());
+            }
+            PeerEvent::Start { color, variant } => {
+                if self.peer.is_none() || self.hosting {
+                    return;
+                }
+                let Some(variant) = variant::by_name(&variant) else {
+                    logging::warn(&format!("the host started a game of {}, which isn't a variant here", variant));
+                    self.status = fill(Text::HostsVariantMissing, &[&variant]);
+                    return;
+                };
+                self.variant = variant;
+                self.opponent = OpponentKind::Network;
+                self.bot_color = color.opposite();
+                self.handicap = Handicap::None;
+                self.kind = TabKind::Game;
+                self.new_game();
+                self.flipped = color == PieceColor::Black;
+                self.game_state = GameState::Playing;
+                self.report(fill(Text::NetworkGameStarted, &[&self.variant.title(), &tr(Text::Side(color))]));
+                self.drive_players();
+            }
+            PeerEvent::Move(mv) => {
+                if self.opponent == OpponentKind::Network && self.result.is_none() {
+                    self.players[side(self.bot_color)].offer(mv);
+                    self.drive_players();
+                }
+            }
+            PeerEvent::Resigned => {
+                if self.opponent == OpponentKind::Network && self.result.is_none() {
+                    self.move_history.push(tr(Text::OpponentResigns).to_string());
+                    let message = self.end_game(GameResult::Resignation { winner: self.bot_color.opposite() });
+                    self.report(message);
+                }
+            }
+            PeerEvent::TakebackAsked(plies) => {
+                if self.opponent != OpponentKind::Network {
+                    return;
+                }
+                if self.result.is_some() || self.takeback.is_some() || plies >= self.record.len() {
+                    let _ = self.send_peer("takeback decline");
+                    return;
+                }
+                self.takeback = Some(Takeback::Offered(plies));
+                self.move_history.push(tr(Text::OpponentAsksTakeback).to_string());
+                self.report(tr(Text::TakebackOffered).to_string());
+            }
+            PeerEvent::TakebackAccepted(plies) => {
+                if self.takeback != Some(Takeback::Asked(plies)) || self.result.is_some() {
+                    return;
+                }
+                let message = if self.take_back_to(plies) {
+                    self.drive_players().unwrap_or_else(|| tr(Text::TakebackAgreed).to_string())
+                } else {
+                    logging::warn(&format!("the other player took back to ply {}, which this game never reached", plies));
+                    tr(Text::TakebackMissingMove).to_string()
+                };
+                self.report(message);
+            }
+            PeerEvent::Chat(text) => {
+                if self.opponent == OpponentKind::Network {
+                    self.chat.add("Opponent", &text);
+                    self.file_chat();
+                    self.narrate(&fill(Text::OpponentSays, &[&text]));
+                }
+            }
+            PeerEvent::TakebackDeclined => {
+                if let Some(Takeback::Asked(_)) = self.takeback {
+                    self.takeback = None;
+                    self.report(tr(Text::TakebackRefused).to_string());
+                }
+            }
+            PeerEvent::Closed(reason) => {
+                self.takeback = None;
+                self.chatting = false;
+                logging::info(&format!("network game closed: {}", reason));
+                let playing = self.opponent == OpponentKind::Network && self.peer.is_some() && self.result.is_none();
+                self.peer = None;
+                self.hosting = false;
+                if playing {
+                    self.move_history.push(fill(Text::ConnectionLost, &[&reason]));
+                    let result = GameResult::Adjudicated { winner: Some(self.bot_color.opposite()), reason: "opponent left" };
+                    let message = self.end_game(result);
+                    self.report(message);
+                } else {
+                    self.status = fill(Text::NotConnectedReason, &[&reason]);
+                }
+                self.opponent = OpponentKind::Engine;
+            }
+        }
+    }
+
+    // the engine [uci] in the config names, reporting back through the
+    // events channel
+    fn start_uci(&self) -> std::io::Result<UciEngine> {
+        let events = self.events.clone();
+        UciEngine::start(&self.config.uci, move |engine, reply| {
+            let _ = events.send(AppEvent::Uci { engine, reply });
+        })
+    }
+
+    // has the [uci] engine look at the position on the board too, when
+    // the config asks for it. the fog would be no secret to it
+    fn ask_second_opinion(&mut self) {
+        self.second_opinion_info = None;
+        if !self.config.uci.second_opinion || self.variant.name() != "standard" || self.second_opinion_error.is_some() {
+            return;
+        }
+        if self.second_opinion.is_none() {
+            match self.start_uci() {
+                Ok(engine) => self.second_opinion = Some(engine),
+                Err(e) => {
+                    logging::warn(&format!("couldn't start the UCI engine for a second opinion: {}", e));
+                    self.second_opinion_error = Some(e.to_string());
+                    return;
+                }
+            }
+        }
+        let position = self.position();
+        let time = self.config.engine.analysis_time();
+        if let Some(engine) = &mut self.second_opinion {
+            if let Err(e) = engine.go(position.zobrist_hash(), &position.fen(), time) {
+                self.second_opinion_error = Some(e.to_string());
+                self.second_opinion = None;
+            }
+        }
+    }
+
+    pub fn on_uci(&mut self, engine: u64, reply: UciReply) {
+        if let Some(idx) = self.tab_of_search(engine) {
+            return self.in_tab(idx, |app| app.on_uci(engine, reply));
+        }
+        if let Some(second_opinion) = self.second_opinion.as_mut().filter(|e| e.id == engine) {
+            match reply {
+                UciReply::Info(mut info) if second_opinion.on_latest() => {
+                    // it speaks for the side to move, and is shown for white
+                    if self.current_turn == PieceColor::Black {
+                        info.score = info.score.map(|score| score.negate());
+                    }
+                    self.second_opinion_info = Some(info);
+                }
+                UciReply::Info(_) => {}
+                UciReply::BestMove(_) => {
+                    second_opinion.answered();
+                }
+                UciReply::Exited(reason) => {
+                    logging::warn(&format!("the second opinion's engine stopped: {}", reason));
+                    self.second_opinion = None;
+                    self.second_opinion_error = Some(reason);
+                }
+            }
+            return;
+        }
+        let Some(player) = self.players.iter().position(|player| player.owns_search(engine)) else {
+            return;
+        };
+        match reply {
+            // it's searching for the bot's side, and is shown for white
+            UciReply::Info(mut info) => {
+                if self.bot_color == PieceColor::Black {
+                    info.score = info.score.map(|score| score.negate());
+                }
+                self.opponent_info = Some(info);
+            }
+            UciReply::BestMove(best) => {
+                self.players[player].search_finished(best);
+                self.drive_players();
+            }
+            UciReply::Exited(reason) => {
+                let name = self.players[player].name();
+                logging::warn(&format!("{} stopped: {}", name, reason));
+                if self.result.is_none() {
+                    self.move_history.push(fill(Text::EngineStopped, &[&name, &reason]));
+                    let result = GameResult::Adjudicated { winner: Some(self.bot_color.opposite()), reason: "opponent left" };
+                    let message = self.end_game(result);
+                    self.report(message);
+                }
+            }
+        }
+    }
+
+    // the next or previous file in the weights folder, built-in counting as
+    // one; takes effect now between games, otherwise from the next one
+    fn step_weights(&mut self, step: i32) -> String {
+        let mut choices: Vec<Option<String>> = vec![None];
+        choices.extend(weights::available().into_iter().map(Some));
+        let current = self.wanted_weights();
+        if !choices.contains(&current) {
+            choices.push(current.clone());
+        }
+        if choices.len() == 1 {
+            let dir = weights::weights_dir().map_or(tr(Text::TheWeightsFolder).to_string(), |d| d.display().to_string());
+            return fill(Text::NoWeightsFiles, &[&dir]);
+        }
+        let idx = choices.iter().position(|c| *c == current).unwrap_or(0);
+        let next = (idx as i32 + step).rem_euclid(choices.len() as i32) as usize;
+        self.config.bot.weights = choices[next].clone();
+        self.weights_override = None;
+        let name = self.config.bot.weights.clone().unwrap_or_else(|| tr(Text::BuiltIn).to_string());
+        if !self.record.is_empty() && self.result.is_none() {
+            return fill(Text::WeightsFromNextGame, &[&name]);
+        }
+        match self.sync_weights() {
+            Some(problem) => problem,
+            None => fill(Text::WeightsNow, &[&name]),
+        }
+    }
+
+    // plays this run with `name` whatever the config says; the file was
+    // checked when the flag was read
+    pub fn use_weights_for_run(&mut self, name: Option<String>) {
+        self.weights_override = name;
+        self.sync_weights();
+    }
+
+    fn wanted_weights(&self) -> Option<String> {
+        self.weights_override.clone().or_else(|| self.config.bot.weights.clone())
+    }
+
+    // the next or previous named set of piece values, the weights' own
+    // counting as one; like the weights, from the next game once one is on
+    fn step_piece_values(&mut self, step: i32) -> String {
+        let mut choices = vec![None];
+        choices.extend(PieceValues::ALL.map(Some));
+        let idx = choices.iter().position(|c| *c == self.config.bot.piece_values).unwrap_or(0);
+        let next = (idx as i32 + step).rem_euclid(choices.len() as i32) as usize;
+        self.config.bot.piece_values = choices[next];
+        let name = self.config.bot.piece_values.map_or(tr(Text::WeightsOwn), PieceValues::name);
+        if !self.record.is_empty() && self.result.is_none() {
+            return fill(Text::ValuesFromNextGame, &[&name]);
+        }
+        match self.sync_weights() {
+            Some(problem) => problem,
+            None => fill(Text::ValuesNow, &[&name]),
+        }
+    }
+
+    // loads the weights the bot should play with, and the config's piece
+    // values over them, if it doesn't have them already. a file that won't
+    // load leaves the engine as it was
+    fn sync_weights(&mut self) -> Option<String> {
+        let wanted = self.wanted_weights();
+        let values = self.config.bot.piece_values;
+        if wanted == self.loaded_weights && values == self.loaded_values {
+            return None;
+        }
+        let loaded = weights::load_into(&mut self.rl_engine, wanted.as_deref())
+            .and_then(|()| weights::apply_config(&mut self.rl_engine, &self.config.bot));
+        match loaded {
+            Ok(()) => {
+                self.loaded_weights = wanted;
+                self.loaded_values = values;
+                None
+            }
+            Err(e) => {
+                logging::warn(&format!("couldn't load the bot's weights: {}", e));
+                Some(fill(Text::WeightsNotLoaded, &[&e]))
+            }
+        }
+    }
+
+    // back to the starting position; the engine keeps what it has learned,
+    // unless other weights were picked for it since
+    pub fn new_game(&mut self) {
+        self.level = self.config.bot.level;
+        self.target_elo = self.config.bot.target_elo;
+        self.personality = self.config.bot.personality;
+        self.rl_engine.set_personality(self.personality);
+        self.bot_losing_streak = 0;
+        self.draw_offer_ply = None;
+        self.switched = false;
+        self.spoken_choices = None;
+        self.premove = None;
+        self.takeback = None;
+        self.last_search = None;
+        self.engine_choice = None;
+        self.board = self.variant.initial_board();
+        self.handicap.apply(&mut self.board, self.bot_color);
+        self.rl_engine.set_variant(self.variant.clone());
+        self.current_turn = PieceColor::White;
+        self.start_fen = None;
+        self.drill = None;
+        if let Some(attempt) = self.speedrun.take() {
+            self.opponent = attempt.previous;
+        }
+        self.move_history.clear();
+        if let Some(problem) = self.sync_weights() {
+            self.move_history.push(problem);
+        }
+        self.history_scroll = None;
+        self.undo_stack.clear();
+        self.engine_notes.clear();
+        self.record = GameRecord::new(self.board.clone(), PieceColor::White);
+        self.turn_started = Instant::now();
+        self.bot_expected.clear();
+        self.player_moves.clear();
+        self.result = None;
+        self.last_position_score = 0.0;
+        self.current_position_score = 0.0;
+        self.rl_engine.current_stats = Default::default();
+        self.players[side(self.bot_color.opposite())] = Box::new(HumanController::default());
+        self.players[side(self.bot_color)] = match self.kind {
+            TabKind::Game => self.opponent_controller(),
+            TabKind::Analysis => Box::new(HumanController::default()),
+        };
+        self.opponent_info = None;
+        self.analysis = None;
+        self.ask_second_opinion();
+        self.update_crash_context();
+        logging::info(&format!(
+            "new {} game against {} at level {}, bot plays {}, odds {}",
+            self.variant.title(),
+            self.opponent.description(),
+            self.level_text(),
+            self.personality.name(),
+            self.handicap.name()
+        ));
+        self.narrate(&fill(
+            Text::NewGameNarrated,
+            &[&self.variant.title(), &tr(Text::Side(self.bot_color.opposite())), &tr(Text::Side(self.bot_color))],
+        ));
+    }
+
+    pub fn narrate(&mut self, line: &str) {
+        self.narrator.say(line);
+    }
+
+    // a status line that's also worth hearing
+    pub fn report(&mut self, message: String) {
+        self.narrate(&message);
+        self.status = message;
+    }
+
+    // the board for the narration panel, as far as the human may see it
+    fn read_board(&mut self) -> String {
+        if self.blindfold && self.result.is_none() {
+            return tr(Text::BlindfoldIsOn).to_string();
+        }
+        let human = self.bot_color.opposite();
+        let board = if self.result.is_some() { self.board.clone() } else { self.variant.view(&self.board, human) };
+        for line in board_listing(&board) {
+            self.narrate(&line);
+        }
+        let who = tr(if self.current_turn == human { Text::YouLower } else { Text::TheBotLower });
+        sentence(Text::SideToMove, &[&tr(Text::Side(self.current_turn)), &who])
+    }
+
+    fn toggle_accessible(&mut self) -> String {
+        self.accessible = !self.accessible;
+        self.config.accessibility.enabled = self.accessible;
+        let done = tr(if self.accessible { Text::AccessibleOn } else { Text::AccessibleOff }).to_string();
+        match self.config.save() {
+            Ok(()) => done,
+            Err(e) => fill(Text::ButNotSaved, &[&done, &e]),
+        }
+    }
+
+    fn record_ply(&mut self, mv: Move) {
+        let color = self.record.to_move();
+        let clock = self.turn_started.elapsed();
+        self.record.push(mv, color, self.board.clone()).clock = Some(clock);
+        self.file_chat();
+        session::moved(clock);
+        self.turn_started = Instant::now();
+        self.update_crash_context();
+        self.autosave();
+        self.announce_move(mv);
+    }
+
+    // tells the hooks and the narration about the move just recorded: a
+    // check, a capture or just a move, whichever comes first. the bot's moves
+    // under fog are only ever a move
+    fn announce_move(&mut self, mv: Move) {
+        let before = &self.record.positions()[self.record.len() - 1];
+        let Some(mover) = before.get_piece(mv.from).map(|p| p.color) else {
+            return;
+        };
+        let hidden = self.variant.hides_pieces() && mover == self.bot_color;
+        let event = if hidden {
+            HookEvent::MovePlayed
+        } else if !self.variant.hides_pieces() && self.board.is_in_check(mover.opposite()) {
+            HookEvent::Check
+        } else if mv.is_capture() {
+            HookEvent::Capture
+        } else {
+            HookEvent::MovePlayed
+        };
+        let message = match (mover == self.bot_color, hidden) {
+            (true, true) => tr(Text::BotMoved).to_string(),
+            (true, false) => fill(Text::BotPlayed, &[&mv.san(before)]),
+            (false, true) => tr(Text::YouMoved).to_string(),
+            (false, false) => fill(Text::YouPlayed, &[&mv.san(before)]),
+        };
+        self.config.hooks.fire(event, &message);
+
+        let spoken = if hidden {
+            sentence(Text::MovedInFog, &[&tr(Text::Side(mover))])
+        } else {
+            let mut spoken = sentence(
+                Text::SpokenMove,
+                &[
+                    &tr(Text::SidePiece(mover, mv.piece)),
+                    &coordinate_to_string(mv.from),
+                    &coordinate_to_string(mv.to),
+                ],
+            );
+            if let Some(taken) = mv.captured {
+                spoken.push_str(&fill(Text::Takes, &[&tr(Text::Piece(taken))]));
+            }
+            if event == HookEvent::Check {
+                spoken.push_str(tr(Text::CheckSpoken));
+            }
+            spoken
+        };
+        self.narrate(&spoken);
+    }
+
+    // the position and moves, for a crash report if one is ever needed
+    fn update_crash_context(&self) {
+        let record = &self.record;
+        let moves = games::format_movetext(&record.sans(), &[], "*", record.first_to_move());
+        terminal::set_crash_context(format!(
+            "variant: {}\nodds: {}\nstart: {}\nfen: {}\nmoves: {}",
+            self.variant.title(),
+            self.handicap.name(),
+            self.start_fen.as_deref().unwrap_or("standard"),
+            record.fen(),
+            moves
+        ));
+    }
+
+    fn start_replay(&mut self) -> String {
+        if self.record.is_empty() {
+            return tr(Text::NothingToReplay).to_string();
+        }
+        self.replay = Some(Replay::new(&self.record));
+        self.replay_return = GameState::Playing;
+        self.game_state = GameState::Replay;
+        String::new()
+    }
+
+    // `piece` picks the table to start on, by name
+    fn open_heatmap(&mut self, piece: Option<&str>) -> String {
+        if let Some(name) = piece {
+            match PIECES.iter().position(|p| p.name() == name.trim_end_matches('s')) {
+                Some(idx) => self.heatmap.piece = idx,
+                None => return fill(Text::UnknownPiece, &[&name]),
+            }
+        }
+        self.game_state = GameState::Heatmap;
+        String::new()
+    }
+
+    // ←→ pick the piece, ↑↓ the phase, c shows learned values or changes
+    pub fn handle_heatmap_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Right => self.heatmap.next_piece(true),
+            KeyCode::Left => self.heatmap.next_piece(false),
+            KeyCode::Up | KeyCode::Down => self.heatmap.endgame = !self.heatmap.endgame,
+            KeyCode::Char('c') => self.heatmap.changes = !self.heatmap.changes,
+            _ => {}
+        }
+    }
+
+    // ←→ step through the saved tournaments
+    pub fn handle_arena_key(&mut self, key: KeyEvent) {
+        let count = self.tournaments.len().max(1);
+        match key.code {
+            KeyCode::Right => self.tournament_index = (self.tournament_index + 1) % count,
+            KeyCode::Left => self.tournament_index = (self.tournament_index + count - 1) % count,
+            _ => {}
+        }
+    }
+
+    // PLAY goes back to a game in progress, otherwise to the setup screen
+    pub fn open_setup(&mut self) {
+        if !self.record.is_empty() && self.result.is_none() {
+            self.game_state = GameState::Playing;
+            return;
+        }
+        self.setup_index = Handicap::ALL.iter().position(|&h| h == self.handicap).unwrap_or(0);
+        // the side last played, unless it's left to chance
+        if self.setup_side != Side::Random {
+            self.setup_side = match self.bot_color {
+                PieceColor::White => Side::Black,
+                PieceColor::Black => Side::White,
+            };
+        }
+        self.status.clear();
+        self.game_state = GameState::Setup;
+    }
+
+    // up/down pick a row, left/right change it, enter starts the game
+    pub fn handle_setup_key(&mut self, key: KeyEvent) {
+        let step = match key.code {
+            KeyCode::Up => {
+                self.setup_row = self.setup_row.saturating_sub(1);
+                return;
+            }
+            KeyCode::Down => {
+                self.setup_row = (self.setup_row + 1).min(SetupRow::ALL.len() - 1);
+                return;
+            }
+            KeyCode::Left | KeyCode::Char('-') => -1,
+            KeyCode::Right | KeyCode::Char('+') | KeyCode::Char('=') => 1,
+            KeyCode::Enter if self.opponent == OpponentKind::Network && !self.hosting => {
+                self.status = tr(Text::HostStartsGames).to_string();
+                return;
+            }
+            KeyCode::Enter if self.game_in_progress() => {
+                self.dialog = Some(Dialog::confirm(
+                    tr(Text::NewGameTitle),
+                    tr(Text::SetupGameQuestion).to_string(),
+                    Purpose::StartFromSetup,
+                ));
+                return;
+            }
+            KeyCode::Enter => {
+                self.start_from_setup();
+                return;
+            }
+            _ => return,
+        };
+        self.change_setup(SetupRow::ALL[self.setup_row], step);
+    }
+
+    fn change_setup(&mut self, row: SetupRow, step: isize) {
+        match row {
+            SetupRow::Side => {
+                let idx = Side::ALL.iter().position(|&side| side == self.setup_side).unwrap_or(0);
+                self.setup_side = Side::ALL[setup::stepped(idx, Side::ALL.len(), step)];
+            }
+            SetupRow::Variant => {
+                let variants = variant::all();
+                let idx = variants.iter().position(|v| v.name() == self.variant.name()).unwrap_or(0);
+                self.variant = variants[setup::stepped(idx, variants.len(), step)].clone();
+            }
+            SetupRow::Level => {
+                let idx = Difficulty::ALL.iter().position(|&level| level == self.config.bot.level).unwrap_or(0);
+                self.config.bot.level = Difficulty::ALL[setup::stepped(idx, Difficulty::ALL.len(), step)];
+            }
+            // the strength slider: off the top end is the level again
+            SetupRow::Strength if step > 0 => {
+                self.config.bot.target_elo = self.config.bot.target_elo.and_then(|elo| {
+                    let up = elo + TARGET_STEP;
+                    (up <= MAX_TARGET).then_some(up)
+                })
+            }
+            SetupRow::Strength => {
+                self.config.bot.target_elo = Some(
+                    self.config.bot.target_elo.map_or(MAX_TARGET, |elo| elo.saturating_sub(TARGET_STEP).max(MIN_TARGET)),
+                )
+            }
+            SetupRow::TimePerMove => {
+                let level = self.config.bot.level;
+                let idx = setup::think_step(self.config.engine.think_time(level).as_secs_f32());
+                let secs = setup::THINK_STEPS[setup::stepped(idx, setup::THINK_STEPS.len(), step)];
+                self.config.engine.set_think_time(level, secs);
+            }
+            SetupRow::Odds => self.setup_index = setup::stepped(self.setup_index, Handicap::ALL.len(), step),
+            SetupRow::Personality => {
+                let idx = Personality::ALL.iter().position(|&p| p == self.config.bot.personality).unwrap_or(0);
+                self.config.bot.personality = Personality::ALL[setup::stepped(idx, Personality::ALL.len(), step)];
+            }
+            // only a handful, so it goes round
+            SetupRow::Opponent => {
+                let choices = OpponentKind::CHOICES;
+                let idx = choices.iter().position(|&o| o == self.opponent).unwrap_or(0);
+                self.opponent = choices[(idx as isize + step).rem_euclid(choices.len() as isize) as usize];
+            }
+        }
+    }
+
+    fn start_from_setup(&mut self) {
+        self.handicap = Handicap::ALL[self.setup_index];
+        let human = self.setup_side.color();
+        self.bot_color = human.opposite();
+        self.flipped = human == PieceColor::Black;
+        self.kind = TabKind::Game;
+        self.new_game();
+        self.send_new_game();
+        self.drive_players();
+        self.status = match self.handicap {
+            Handicap::None if self.target_elo.is_some() && self.opponent == OpponentKind::Engine => {
+                fill(Text::TargetEloGame, &[&self.target_elo.unwrap_or_default()])
+            }
+            Handicap::None if self.level == Difficulty::Sparring => {
+                fill(Text::SparringGame, &[&format!("{:.0}", self.profile.rating)])
+            }
+            Handicap::None => String::new(),
+            odds => fill(Text::Unrated, &[&odds.description()]),
+        };
+        if let Err(e) = self.config.save() {
+            self.status = fill(Text::SettingsNotSaved, &[&e]);
+        }
+        self.game_state = GameState::Playing;
+    }
+
+    // the editor starts from the game on the board so a position can be
+    // taken from it and changed
+    pub fn open_editor(&mut self) {
+        self.editor = Editor::new(self.board.clone(), self.current_turn);
+        self.status.clear();
+        self.game_state = GameState::Editor;
+    }
+
+    // today's puzzle from the cache if it's there, otherwise fetched in the
+    // background; on_daily_puzzle takes it from there
+    pub fn open_puzzle(&mut self) {
+        self.status.clear();
+        self.game_state = GameState::Puzzle;
+        if let Some(puzzle) = lichess::cached().filter(|puzzle| puzzle.date == lichess::today()) {
+            self.show_puzzle(puzzle, None);
+            return;
+        }
+        if self.fetching_puzzle {
+            return;
+        }
+        self.fetching_puzzle = true;
+        let events = self.events.clone();
+        std::thread::spawn(move || {
+            let _ = events.send(AppEvent::DailyPuzzle(lichess::fetch_daily().map_err(|e| e.to_string())));
+        });
+    }
+
+    // with no answer from lichess, the last puzzle kept will do
+    pub fn on_daily_puzzle(&mut self, fetched: Result<Puzzle, String>) {
+        self.fetching_puzzle = false;
+        match fetched {
+            Ok(puzzle) => self.show_puzzle(puzzle, None),
+            Err(e) => {
+                logging::warn(&format!("couldn't fetch the daily puzzle: {}", e));
+                match lichess::cached() {
+                    Some(puzzle) => {
+                        let note = fill(Text::OfflinePuzzle, &[&puzzle.date]);
+                        self.show_puzzle(puzzle, Some(note));
+                    }
+                    None => {
+                        self.status = tr(Text::LichessUnreachable).to_string();
+                    }
+                }
+            }
+        }
+    }
+
+    fn show_puzzle(&mut self, puzzle: Puzzle, note: Option<String>) {
+        // a puzzle already under way stays as it is
+        if self.puzzle.as_ref().is_some_and(|current| current.puzzle.id == puzzle.id) {
+            return;
+        }
+        match PuzzleScreen::new(puzzle, note) {
+            Ok(screen) => {
+                self.flipped = screen.solver == PieceColor::Black;
+                self.puzzle = Some(screen);
+            }
+            Err(e) => self.status = e,
+        }
+    }
+
+    // moves and the words `hint` and `show` go in the box
+    pub fn handle_puzzle_key(&mut self, key: KeyEvent) {
+        let Some(screen) = &mut self.puzzle else {
+            return;
+        };
+        if key.code != KeyCode::Enter {
+            screen.input.handle_key(key);
+            return;
+        }
+        let text = screen.input.submit();
+        match text.trim().to_lowercase().as_str() {
+            "" => {}
+            "hint" => screen.hint(),
+            "show" | "solution" => screen.reveal(),
+            _ => screen.attempt(&text),
+        }
+    }
+
+    pub fn open_training(&mut self) {
+        self.quiz = CoordinateQuiz::new();
+        self.status.clear();
+        self.game_state = GameState::Training;
+    }
+
+    // a file letter and a rank digit name the square, backspace takes one back
+    pub fn handle_training_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char(c) => self.quiz.type_char(c),
+            KeyCode::Backspace => self.quiz.backspace(),
+            _ => {}
+        }
+    }
+
+    // arrows move, a FEN letter places a piece (capitals for white),
+    // space/x/delete empties the square, tab switches the side to move
+    pub fn handle_editor_key(&mut self, key: KeyEvent) {
+        // up is toward black's side unless the board is flipped
+        let up = if self.flipped { 1 } else { -1 };
+        match key.code {
+            KeyCode::Up => self.editor.move_cursor(up, 0),
+            KeyCode::Down => self.editor.move_cursor(-up, 0),
+            KeyCode::Left => self.editor.move_cursor(0, up),
+            KeyCode::Right => self.editor.move_cursor(0, -up),
+            KeyCode::Char(' ' | 'x') | KeyCode::Delete | KeyCode::Backspace => self.editor.remove(),
+            KeyCode::Tab => self.editor.toggle_side(),
+            KeyCode::Char('0') => self.editor.clear(),
+            KeyCode::Char('1') => self.editor.reset(),
+            KeyCode::Char('a') => self.status = self.analyze_editor(),
+            KeyCode::Enter => self.status = self.play_from_editor(),
+            KeyCode::Char(c) => {
+                if !self.editor.place(c) {
+                    self.status = fill(Text::NotAPiece, &[&c]);
+                    return;
+                }
+            }
+            _ => return,
+        }
+        if !matches!(key.code, KeyCode::Char('a') | KeyCode::Enter) {
+            self.status.clear();
+        }
+    }
+
+    // a standard game against the bot from the edited position. it's
+    // unrated, and if black is to move the bot starts
+    fn play_from_editor(&mut self) -> String {
+        if let Some(problem) = self.editor.problem() {
+            return problem;
+        }
+        if self.opponent == OpponentKind::Network {
+            return tr(Text::NoNetworkEdits).to_string();
+        }
+        self.variant = Arc::new(Standard);
+        self.handicap = Handicap::None;
+        self.kind = TabKind::Game;
+        self.new_game();
+        self.board = self.editor.board.clone();
+        self.record = GameRecord::new(self.board.clone(), self.editor.to_move);
+        self.current_turn = self.editor.to_move;
+        self.start_fen = Some(self.editor.fen());
+        self.update_crash_context();
+        logging::info(&format!("game set up from {}", self.editor.fen()));
+        self.ask_second_opinion();
+        self.game_state = GameState::Playing;
+        self.drive_players();
+        tr(Text::PlayingFromPosition).to_string()
+    }
+
+    // searches the edited position for the side to move and lists its best
+    // moves beside the board
+    fn analyze_editor(&mut self) -> String {
+        if let Some(problem) = self.editor.problem() {
+            return problem;
+        }
+        let board = self.editor.board.clone();
+        let color = self.editor.to_move;
+        self.rl_engine.set_variant(Arc::new(Standard));
+        self.rl_engine.set_history(Vec::new());
+        let time = self.config.engine.analysis_time();
+        let result = self.rl_engine.search(&board, color, None, time, &mut |_| {});
+        self.rl_engine.set_variant(self.variant.clone());
+        let Some(result) = result else {
+            return tr(Text::EngineFoundNoMoves).to_string();
+        };
+        self.editor.notes = analysis_notes(&board, color, &result);
+        fill(Text::EnginePrefers, &[&san(&board, result.best_move)])
+    }
+
+    pub fn open_profile(&mut self) {
+        self.profile = Profile::load();
+        self.game_state = GameState::Profile;
+    }
+
+    pub fn open_history(&mut self) {
+        self.saved_games = games::load_all();
+        self.history_index = 0;
+        self.history_filter.clear();
+        self.status.clear();
+        self.game_state = GameState::History;
+    }
+
+    // one line per game, which is also what the search box matches against
+    fn history_row(game: &SavedGame) -> String {
+        let side = tr(Text::Side(game.human_color().unwrap_or(PieceColor::Black)));
+        // games saved before variants existed have no tag
+        let variant = match game.variant.as_str() {
+            "" | "Standard" => String::new(),
+            other => format!("{}: ", other),
+        };
+        let bot = if game.bot_level.is_empty() {
+            format!("{}s", game.bot_time)
+        } else {
+            game.bot_level.clone()
+        };
+        let odds = match game.handicap.as_str() {
+            "" | "none" => String::new(),
+            other => fill(Text::OddsOf, &[&other]),
+        };
+        fill(
+            Text::HistoryRow,
+            &[
+                &format!("{:<10}  {:<7}  {:<4}", game.date, game.result, game.outcome()),
+                &format!("{:<5}", side),
+                &format!("{:>3}", game.moves.len().div_ceil(2)),
+                &format!("{:<6}", bot),
+                &format!("{}{}{} ({})", odds, variant, game.opening, game.termination),
+            ],
+        )
+    }
+
+    // indexes into saved_games that match the search text
+    fn filtered_games(&self) -> Vec<usize> {
+        let needle = self.history_filter.value().to_lowercase();
+        self.saved_games
+            .iter()
+            .enumerate()
+            .filter(|(_, game)| Self::history_row(game).to_lowercase().contains(needle.trim()))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    // typing searches, up/down pick a game, enter opens it in the replay viewer
+    pub fn handle_history_key(&mut self, key: KeyEvent) {
+        let count = self.filtered_games().len();
+        match key.code {
+            KeyCode::Up => self.history_index = self.history_index.saturating_sub(1),
+            KeyCode::Down => self.history_index = (self.history_index + 1).min(count.saturating_sub(1)),
+            KeyCode::Enter => {
+                let Some(&idx) = self.filtered_games().get(self.history_index) else {
+                    return;
+                };
+                let record = self.saved_games[idx].record();
+                if record.len() < self.saved_games[idx].moves.len() {
+                    self.status = fill(Text::OnlyFirstMovesRead, &[&record.len()]);
+                }
+                self.replay = Some(Replay::new(&record));
+                self.replay_return = GameState::History;
+                self.game_state = GameState::Replay;
+            }
+            _ => {
+                if self.history_filter.handle_key(key) {
+                    self.history_index = 0;
+                }
+            }
+        }
+    }
+
+    pub fn handle_replay_key(&mut self, key: KeyEvent) {
+        let Some(replay) = &mut self.replay else {
+            return;
+        };
+        match key.code {
+            KeyCode::Right => replay.forward(),
+            KeyCode::Left => replay.back(),
+            KeyCode::Home => replay.jump_to_start(),
+            KeyCode::End => replay.jump_to_end(),
+            KeyCode::Char(' ') => replay.toggle_play(),
+            KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Up => replay.faster(),
+            KeyCode::Char('-') | KeyCode::Down => replay.slower(),
+            _ => {}
+        }
+    }
+
+    // watches `source` for a game and shows it as it's played
+    pub fn start_follow(&mut self, source: FeedSource) {
+        self.follow = Some(Follow::new(&source));
+        spawn_feed(source, self.events.clone());
+        self.game_state = GameState::Follow;
+    }
+
+    pub fn on_feed(&mut self, update: FeedUpdate) {
+        let Some(follow) = &mut self.follow else {
+            return;
+        };
+        if follow.update(update) && follow.evaluate {
+            self.evaluate_follow();
+        }
+    }
+
+    // has the engine look at the followed game's latest position. a search
+    // still running on an older one is left to finish and be ignored
+    fn evaluate_follow(&mut self) {
+        let Some(follow) = &mut self.follow else {
+            return;
+        };
+        follow.eval = None;
+        let record = &follow.record;
+        let board = record.positions()[record.len()].clone();
+        let color = record.to_move();
+        if follow.game.variant().result(&board, color).is_some() {
+            follow.search = None;
+            return;
+        }
+        let mut engine = self.rl_engine.fork();
+        engine.set_variant(follow.game.variant());
+        engine.set_history(record.with_sides());
+        let events = self.events.clone();
+        follow.search = Some(SearchHandle::spawn(
+            engine,
+            board,
+            color,
+            None,
+            self.config.engine.analysis_time(),
+            self.config.engine.threads(),
+            move |search, update| {
+                let _ = events.send(match update {
+                    SearchUpdate::Progress(stats) => AppEvent::EngineProgress { search, stats },
+                    SearchUpdate::Finished(result, stats) => AppEvent::EngineMove { search, result, stats },
+                });
+            },
+        ));
+    }
+
+    fn is_follow_search(&self, id: u64) -> bool {
+        self.follow.as_ref().and_then(|follow| follow.search.as_ref()).is_some_and(|search| search.id == id)
+    }
+
+    pub fn handle_follow_key(&mut self, key: KeyEvent) {
+        let Some(follow) = &mut self.follow else {
+            return;
+        };
+        match key.code {
+            KeyCode::Left => follow.back(),
+            KeyCode::Right => follow.forward(),
+            KeyCode::End => follow.live(),
+            KeyCode::Char('e') => {
+                follow.evaluate = !follow.evaluate;
+                if follow.evaluate {
+                    self.evaluate_follow();
+                } else {
+                    follow.search = None;
+                    follow.eval = None;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // the engine against itself from the variant's starting position, one
+    // style a side
+    fn start_watch(&mut self, styles: &[&str]) -> String {
+        let mut personalities = [Personality::Balanced; 2];
+        for (personality, name) in personalities.iter_mut().zip(styles) {
+            match Personality::from_name(name) {
+                Some(style) => *personality = style,
+                None => {
+                    let names: Vec<&str> = Personality::ALL.iter().map(|p| p.name()).collect();
+                    return fill(Text::UnknownStyle, &[&names.join(", ")]);
+                }
+            }
+        }
+        let [white, black] = personalities.map(|personality| Box::new(self.watch_engine(personality)));
+        let mut watch = Watch::new(Position::with_variant(self.variant.clone()), white, black);
+        watch.overhead = self.config.engine.move_overhead();
+        watch.drive();
+        self.watch = Some(watch);
+        self.game_state = GameState::Watch;
+        String::new()
+    }
+
+    fn watch_engine(&mut self, personality: Personality) -> EngineController {
+        let mut engine = self.rl_engine.fork();
+        engine.set_personality(personality);
+        engine.set_variant(self.variant.clone());
+        let name = fill(Text::EngineNamed, &[&personality.name()]);
+        EngineController::new(name, engine, self.config.engine.threads(), self.events.clone())
+    }
+
+    // a watched game ends with its screen; its searches are ignored once they finish
+    pub fn close_watch(&mut self) {
+        self.watch = None;
+        self.game_state = GameState::Playing;
+    }
+
+    fn is_watch_search(&self, id: u64) -> bool {
+        self.watch.as_ref().is_some_and(|watch| watch.owns_search(id))
+    }
+
+    // with nothing typed, space pauses, → plays one move and +/- change the
+    // engines' think time; otherwise keys go to the box, which takes moves
+    // and `white` or `black` to take a side over or hand it back
+    pub fn handle_watch_key(&mut self, key: KeyEvent) {
+        let Some(watch) = &mut self.watch else {
+            return;
+        };
+        if watch.input.is_empty() {
+            match key.code {
+                KeyCode::Char(' ') => watch.toggle_pause(),
+                KeyCode::Right => watch.step(),
+                KeyCode::Char('+') => watch.faster(),
+                KeyCode::Char('-') => watch.slower(),
+                _ => {
+                    watch.input.handle_key(key);
+                }
+            }
+            watch.drive();
+            return;
+        }
+        if key.code != KeyCode::Enter {
+            watch.input.handle_key(key);
+            return;
+        }
+        let text = watch.input.submit();
+        let side = match text.trim().to_lowercase().as_str() {
+            "white" => Some(PieceColor::White),
+            "black" => Some(PieceColor::Black),
+            _ => None,
+        };
+        match side {
+            Some(color) if watch.player(color).is_human() => {
+                let controller = self.watch_engine(self.config.bot.personality);
+                let watch = self.watch.as_mut().expect("watch checked above");
+                watch.message = sentence(Text::BackToEngine, &[&tr(Text::Side(color))]);
+                watch.replace(color, Box::new(controller));
+            }
+            Some(color) => {
+                watch.message = fill(Text::YouPlaySide, &[&tr(Text::Side(color))]);
+                watch.replace(color, Box::new(HumanController::default()));
+            }
+            None => watch.human_move(&text),
+        }
+        if let Some(watch) = &mut self.watch {
+            watch.drive();
+        }
+    }
+
+    // how long the event loop may wait for a key before calling on_tick,
+    // `since_last` after the last tick. auto-play in the replay viewer can
+    // want its step sooner
+    pub fn time_until_tick(&self, since_last: std::time::Duration) -> std::time::Duration {
+        let due = TICK_RATE.saturating_sub(since_last);
+        match (self.game_state, &self.replay) {
+            (GameState::Replay, Some(replay)) => replay.time_until_step().map_or(due, |step| step.min(due)),
+            _ => due,
+        }
+    }
+
+    pub fn on_tick(&mut self) {
+        if let (GameState::Replay, Some(replay)) = (&self.game_state, &mut self.replay) {
+            replay.on_tick();
+        }
+        self.relay_game();
+    }
+
+    // lets spectators watch the game on screen, see relay.rs
+    pub fn start_relay(&mut self, port: u16) {
+        match Relay::start(port) {
+            Ok(relay) => {
+                logging::info(&format!("serving spectators on port {}", port));
+                self.relay = Some(relay);
+            }
+            Err(e) => {
+                logging::warn(&format!("couldn't serve spectators on port {}: {}", port, e));
+                self.status = fill(Text::CouldntRelay, &[&port, &e]);
+            }
+        }
+    }
+
+    // sends spectators the game on screen whenever it changes. under fog
+    // they see no more than the player until it's over
+    fn relay_game(&mut self) {
+        let Some(relay) = &self.relay else {
+            return;
+        };
+        if self.variant.hides_pieces() && self.result.is_none() {
+            return;
+        }
+        let fen = self.record.fen();
+        let seen = format!("{} {} {:?}", self.record.len(), fen, self.result.map(|result| result.score()));
+        if self.relayed.as_ref() == Some(&seen) {
+            return;
+        }
+        relay.publish(&fen, &self.game_pgn());
+        self.relayed = Some(seen);
+    }
+
+    // updates from a search that's since been dropped are ignored
+    fn is_current_search(&self, id: u64) -> bool {
+        self.players.iter().any(|player| player.owns_search(id))
+    }
+
+    pub fn on_search_progress(&mut self, id: u64, stats: SimulationStats) {
+        if let Some(idx) = self.tab_of_search(id) {
+            return self.in_tab(idx, |app| app.on_search_progress(id, stats));
+        }
+        if self.is_watch_search(id) {
+            if let Some(watch) = &mut self.watch {
+                watch.stats = Some(stats);
+            }
+            return;
+        }
+        if self.is_current_search(id) || self.is_follow_search(id) || self.is_analysis_search(id) {
+            self.rl_engine.current_stats = stats;
+        }
+    }
+
+    pub fn on_search_finished(&mut self, id: u64, result: Option<SearchResult>, stats: SimulationStats) {
+        if let Some(idx) = self.tab_of_search(id) {
+            return self.in_tab(idx, |app| app.on_search_finished(id, result, stats));
+        }
+        if self.is_analysis_search(id) {
+            self.analysis = None;
+            self.rl_engine.current_stats = stats;
+            if let Some(result) = result {
+                self.engine_notes = analysis_notes(&self.board, self.current_turn, &result);
+            }
+            return;
+        }
+        if self.is_watch_search(id) {
+            if let Some(watch) = &mut self.watch {
+                watch.stats = Some(stats);
+                watch.search_finished(id, result);
+                watch.drive();
+            }
+            return;
+        }
+        if self.is_follow_search(id) {
+            if let Some(follow) = &mut self.follow {
+                follow.search = None;
+                if let Some(result) = result {
+                    follow.evaluated(result);
+                }
+            }
+            return;
+        }
+        if !self.is_current_search(id) || self.result.is_some() {
+            return;
+        }
+        self.rl_engine.current_stats = stats.clone();
+        let choice = result.as_ref().map(|r| {
+            self.judge_player_move(r.score);
+            self.choose_move(r)
+        });
+        if let Some(result) = result {
+            self.keep_search(result, stats);
+        }
+        let best = if self.bot_should_resign() {
+            None
+        } else {
+            // searching a partial board can leave nothing that's playable for real
+            let best = choice.map(|(mv, _)| mv).or_else(|| {
+                if !self.variant.hides_pieces() {
+                    return None;
+                }
+                self.variant
+                    .moves(&self.board, self.bot_color)
+                    .choose(&mut rand::thread_rng())
+                    .map(|mv| mv.pair())
+            });
+            if best.is_none() {
+                logging::error("bot failed to move, its search gave nothing");
+                self.move_history.push(tr(Text::BotFailedToMove).to_string());
+                return;
+            }
+            best
+        };
+        self.engine_choice = choice;
+        if let Some(player) = self.players.iter_mut().find(|player| player.owns_search(id)) {
+            player.search_finished(best);
+        }
+        self.drive_players();
+    }
+
+    // the player's last move cost them whatever the bot's outlook improved
+    // by since its own move. mates say nothing about how well they played
+    fn judge_player_move(&mut self, score: f32) {
+        let Some(moved_from) = self.record.len().checked_sub(1) else {
+            return;
+        };
+        let Some(&(_, expected)) = self.bot_expected.iter().find(|(idx, _)| *idx == moved_from) else {
+            return;
+        };
+        if mate_in(expected).is_some() || mate_in(score).is_some() {
+            return;
+        }
+        self.player_moves.push((moved_from, Judgement::from_loss(score - expected)));
+    }
+
+    // the search's move, unless one nearly as good leads somewhere the
+    // player has often gone wrong before
+    fn choose_move(&self, result: &SearchResult) -> (MovePair, f32) {
+        if let Some(limit) = self.strength_limit() {
+            let branch = strength::pick(&result.tree, &limit, &mut rand::thread_rng());
+            if let Some(branch) = branch.filter(|branch| branch.mv != result.best_move) {
+                logging::debug(&format!(
+                    "at ~{}: {} ({}) over {} ({})",
+                    self.target_elo.unwrap_or_default(),
+                    coordinates(branch.mv),
+                    format_score(branch.value),
+                    coordinates(result.best_move),
+                    format_score(result.score)
+                ));
+                return (branch.mv, branch.value);
+            }
+            return (result.best_move, result.score);
+        }
+        if self.level == Difficulty::Sparring {
+            if let Some(branch) = sparring::pick(&result.tree, self.profile.rating, &mut rand::thread_rng(), |mv| {
+                self.hangs_mate(mv)
+            }) {
+                logging::debug(&format!(
+                    "sparring: {} ({}) over {} ({})",
+                    coordinates(branch.mv),
+                    format_score(branch.value),
+                    coordinates(result.best_move),
+                    format_score(result.score)
+                ));
+                return (branch.mv, branch.value);
+            }
+        }
+        let best = (result.best_move, result.score);
+        if self.variant.hides_pieces() || self.start_fen.is_some() || self.record.len() >= OPENING_PLIES {
+            return best;
+        }
+        let human = self.bot_color.opposite();
+        let steered = self.profile.model.steer(&result.tree, |(from, to)| {
+            let mut board = self.board.clone();
+            self.variant.make_move(&mut board, from, to);
+            position_key(&board, human)
+        });
+        match steered {
+            Some((mv, value)) if mv != result.best_move => {
+                logging::debug(&format!(
+                    "steering to {} ({}) over {} ({}), where the player has gone wrong before",
+                    coordinates(mv),
+                    format_score(value),
+                    coordinates(result.best_move),
+                    format_score(result.score)
+                ));
+                (mv, value)
+            }
+            _ => best,
+        }
+    }
+
+    // whether the bot's `mv` leaves the player a move that ends the game
+    fn hangs_mate(&self, (from, to): MovePair) -> bool {
+        let human = self.bot_color.opposite();
+        let mut board = self.board.clone();
+        if !self.variant.make_move(&mut board, from, to) {
+            return false;
+        }
+        self.variant.moves(&board, human).iter().any(|reply| {
+            let mut after = board.clone();
+            self.variant.make_move(&mut after, reply.from, reply.to)
+                && self.variant.result(&after, self.bot_color).is_some_and(|result| result.winner() == Some(human))
+        })
+    }
+
+    fn keep_search(&mut self, result: SearchResult, stats: SimulationStats) {
+        if logging::enabled(Level::Debug) {
+            let pv: Vec<String> = result.pv.iter().map(|mv| coordinates(*mv)).collect();
+            logging::debug(&format!(
+                "bot search: {} ({}) after {} simulations in {} nodes, pv {}",
+                coordinates(result.best_move),
+                format_score(result.score),
+                stats.total_simulations,
+                stats.nodes_explored,
+                pv.join(" ")
+            ));
+        }
+        self.last_search = Some(SearchTrace {
+            board: self.variant.view(&self.board, self.bot_color),
+            color: self.bot_color,
+            result,
+            stats,
+            moves: self.record.sans(),
+        });
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            board: self.board.clone(),
+            history_len: self.move_history.len(),
+            plies: self.record.len(),
+            current_turn: self.current_turn,
+            position_score: self.current_position_score,
+        }
+    }
+
+    // rewinds to just before the human's last move, bot reply included
+    pub fn undo(&mut self) -> String {
+        if self.speedrun.is_some() && self.result.is_none() {
+            return tr(Text::NoSpeedrunTakebacks).to_string();
+        }
+        match self.undo_stack.pop() {
+            Some(snapshot) => {
+                self.restore(snapshot);
+                tr(Text::MoveTakenBack).to_string()
+            }
+            None => tr(Text::NothingToUndo).to_string(),
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.board = snapshot.board;
+        self.move_history.truncate(snapshot.history_len);
+        self.record.truncate(snapshot.plies);
+        self.bot_expected.retain(|(idx, _)| *idx <= snapshot.plies);
+        self.player_moves.retain(|(idx, _)| *idx < snapshot.plies);
+        self.current_turn = snapshot.current_turn;
+        self.current_position_score = snapshot.position_score;
+        self.result = None;
+        self.history_scroll = None;
+        self.autosave();
+        self.analyze_position();
+    }
+
+    // rewinds a network game to `plies` plies in, which both sides agreed
+    // on. false if the game never went through that position
+    fn take_back_to(&mut self, plies: usize) -> bool {
+        if plies >= self.record.len() || !self.undo_stack.iter().any(|snapshot| snapshot.plies == plies) {
+            return false;
+        }
+        let said: Vec<String> = self.record.plies[plies..].iter().filter_map(|ply| ply.comment.clone()).collect();
+        if !said.is_empty() {
+            self.chat.refile(said.join(" | "));
+        }
+        while let Some(snapshot) = self.undo_stack.pop() {
+            if snapshot.plies == plies {
+                self.restore(snapshot);
+                break;
+            }
+        }
+        self.file_chat();
+        self.premove = None;
+        self.takeback = None;
+        // a move the other player sent before agreeing is for a position gone now
+        self.players[side(self.bot_color.opposite())] = Box::new(HumanController::default());
+        self.players[side(self.bot_color)] = self.opponent_controller();
+        self.turn_started = Instant::now();
+        self.move_history.push(fill(Text::TakenBackToMove, &[&(plies / 2 + 1)]));
+        self.ask_second_opinion();
+        true
+    }
+
+    // shows, hides or resizes a panel of the game screen, and keeps the
+    // layout for next time
+    fn adjust_panels(&mut self, action: Action) -> String {
+        let panels = &mut self.config.layout;
+        let message = match action {
+            Action::ToggleAnalytics => {
+                panels.analytics = !panels.analytics;
+                tr(if panels.analytics { Text::AnalyticsShown } else { Text::AnalyticsHidden }).to_string()
+            }
+            Action::ToggleHistory => {
+                panels.history = !panels.history;
+                tr(if panels.history { Text::HistoryShown } else { Text::HistoryHidden }).to_string()
+            }
+            Action::BoardWider | Action::BoardNarrower => {
+                let steps = if action == Action::BoardWider { 1 } else { -1 };
+                if !panels.history {
+                    return tr(Text::BoardFullWidth).to_string();
+                }
+                if !panels.widen_board(steps) {
+                    return tr(if steps > 0 { Text::BoardWidest } else { Text::BoardNarrowest }).to_string();
+                }
+                fill(Text::BoardWidth, &[&panels.board_width])
+            }
+            Action::AnalyticsTaller | Action::AnalyticsShorter => {
+                let steps = if action == Action::AnalyticsTaller { 1 } else { -1 };
+                if !panels.analytics {
+                    return tr(Text::AnalyticsPanelHidden).to_string();
+                }
+                if !panels.grow_analytics(steps) {
+                    return tr(if steps > 0 { Text::AnalyticsTallest } else { Text::AnalyticsShortest }).to_string();
+                }
+                fill(Text::AnalyticsHeight, &[&panels.analytics_height])
+            }
+            _ => return String::new(),
+        };
+        match self.config.save() {
+            Ok(()) => message,
+            Err(e) => fill(Text::LayoutNotSaved, &[&message, &e]),
+        }
+    }
+
+    pub fn flip(&mut self) {
+        self.flipped = !self.flipped;
+    }
+
+    pub fn hint(&mut self) -> String {
+        let color = self.mover();
+        let board = self.variant.view(&self.board, color);
+        let time = self.config.engine.analysis_time();
+        match self.rl_engine.search(&board, color, None, time, &mut |_| {}).map(|r| r.best_move) {
+            Some((from, to)) => fill(Text::Hint, &[&coordinate_to_string(from), &coordinate_to_string(to)]),
+            None => tr(Text::NoHint).to_string(),
+        }
+    }
+
+    pub fn perform(&mut self, action: Action) {
+        match action {
+            Action::Quit => self.request_quit(),
+            Action::Menu => match self.game_state {
+                GameState::Menu => self.request_quit(),
+                GameState::Replay => self.game_state = self.replay_return,
+                GameState::Heatmap | GameState::Arena => self.game_state = GameState::Playing,
+                GameState::Watch => self.close_watch(),
+                _ => {
+                    self.game_state = GameState::Menu;
+                    self.command_input.clear(); // get rid of any artifacts from previous screen when there is pending commands
+                    self.status.clear();
+                }
+            },
+            Action::Undo => {
+                if let GameState::Playing = self.game_state {
+                    let message = if let Some(premove) = self.premove.take() {
+                        fill(Text::PremoveCancelled, &[&coordinates(premove)])
+                    } else if self.opponent == OpponentKind::Network {
+                        self.ask_takeback()
+                    } else if self.is_thinking() {
+                        self.waiting_text()
+                    } else {
+                        self.undo()
+                    };
+                    self.report(message);
+                }
+            }
+            Action::Flip => {
+                if let GameState::Playing | GameState::Replay | GameState::Editor | GameState::Training | GameState::Puzzle = self.game_state {
+                    self.flip();
+                }
+            }
+            Action::Hint => {
+                if let GameState::Playing = self.game_state {
+                    let message = self.hint();
+                    self.report(message);
+                }
+            }
+            Action::CopyFen => {
+                if let Some(fen) = self.shown_fen() {
+                    self.status = self.copy(fen, "FEN");
+                }
+            }
+            Action::CopyPgn => {
+                if let Some(pgn) = self.shown_pgn() {
+                    self.status = self.copy(pgn, "PGN");
+                }
+            }
+            Action::Paste => self.paste(),
+            Action::NewTab => {
+                if let GameState::Playing = self.game_state {
+                    let message = self.open_tab(TabKind::Game);
+                    self.report(message);
+                }
+            }
+            Action::NextTab => {
+                if let GameState::Playing = self.game_state {
+                    let message = self.next_tab();
+                    self.report(message);
+                }
+            }
+            Action::ToggleAnalytics
+            | Action::ToggleHistory
+            | Action::BoardWider
+            | Action::BoardNarrower
+            | Action::AnalyticsTaller
+            | Action::AnalyticsShorter => {
+                if let GameState::Playing = self.game_state {
+                    self.status = self.adjust_panels(action);
+                }
+            }
+            Action::FocusHistory => {
+                if let GameState::Playing = self.game_state {
+                    let focus = if self.focused() == Focus::History { Focus::Command } else { Focus::History };
+                    self.set_focus(focus);
+                }
+            }
+            Action::Help => self.help = Some(Help::new(&self.config.keys)),
+            Action::Palette => self.open_palette(),
+            Action::NewGame => {
+                if let GameState::Playing = self.game_state {
+                    if !self.record.is_empty() && self.result.is_none() {
+                        self.report(tr(Text::FinishOrStartOver).to_string());
+                    } else {
+                        self.open_setup();
+                    }
+                }
+            }
+            Action::ToggleChat => {
+                if let GameState::Playing = self.game_state {
+                    let message = self.toggle_chat();
+                    self.report(message);
+                }
+            }
+            Action::Threats => {
+                if let GameState::Playing = self.game_state {
+                    self.show_threats = !self.show_threats;
+                    self.status = if !self.show_threats {
+                        tr(Text::ThreatsHidden).to_string()
+                    } else if self.fog().is_some() {
+                        tr(Text::ThreatsInFog).to_string()
+                    } else {
+                        tr(Text::ThreatsShown).to_string()
+                    };
+                }
+            }
+        }
+    }
+
+    // settings screen: the next key pressed after Enter becomes the binding
+    pub fn handle_settings_key(&mut self, key: KeyEvent) {
+        // the engine rows after the key bindings
+        if let Some(row) = self.settings_index.checked_sub(Action::ALL.len()) {
+            let step = match key.code {
+                KeyCode::Left => -1,
+                KeyCode::Right => 1,
+                KeyCode::Up => {
+                    self.settings_index -= 1;
+                    return;
+                }
+                KeyCode::Down => {
+                    self.settings_index = (self.settings_index + 1).min(SETTINGS_ROWS - 1);
+                    return;
+                }
+                _ => return,
+            };
+            let changed = match row {
+                0 => {
+                    let contempt = (self.config.bot.contempt + step * CONTEMPT_STEP)
+                        .clamp(*CONTEMPT_RANGE.start(), *CONTEMPT_RANGE.end());
+                    self.config.bot.contempt = contempt;
+                    self.rl_engine.set_contempt(contempt as f32);
+                    fill(Text::ContemptSet, &[&contempt])
+                }
+                1 => {
+                    let lines = self
+                        .config
+                        .engine
+                        .multi_pv()
+                        .saturating_add_signed(step as isize)
+                        .clamp(1, MAX_MULTI_PV);
+                    self.config.engine.multi_pv = lines;
+                    self.rl_engine.set_multi_pv(lines);
+                    fill(if lines == 1 { Text::EngineLine } else { Text::EngineLines }, &[&lines])
+                }
+                2 => self.step_weights(step),
+                3 => self.step_piece_values(step),
+                // either arrow flips a bell on or off
+                _ => {
+                    let event = HookEvent::ALL[row - ENGINE_ROWS];
+                    let on = self.config.hooks.toggle_bell(event);
+                    fill(if on { Text::BellOn } else { Text::BellOff }, &[&event.name()])
+                }
+            };
+            self.status = match self.config.save() {
+                Ok(()) => changed,
+                Err(e) => fill(Text::ButNotSaved, &[&changed, &e]),
+            };
+            return;
+        }
+        let action = Action::ALL[self.settings_index];
+        if self.capturing_key {
+            self.capturing_key = false;
+            if key.code == KeyCode::Esc && self.config.keys.binding(action).code != KeyCode::Esc {
+                self.status = tr(Text::Cancelled).to_string();
+                return;
+            }
+            let binding = KeyBinding::from_event(&key);
+            self.config.keys.set(action, binding);
+            self.status = match self.config.save() {
+                Ok(()) => fill(Text::NowBound, &[&action.name(), &binding]),
+                Err(e) => fill(Text::BoundNotSaved, &[&action.name(), &binding, &e]),
+            };
+            return;
+        }
+
+        match key.code {
+            KeyCode::Up => self.settings_index = self.settings_index.saturating_sub(1),
+            KeyCode::Down => self.settings_index = (self.settings_index + 1).min(SETTINGS_ROWS - 1),
+            KeyCode::Enter => {
+                self.capturing_key = true;
+                self.status = fill(Text::PressNewKey, &[&action.name()]);
+            }
+            KeyCode::Char('d') => {
+                self.config.keys.reset();
+                self.status = match self.config.save() {
+                    Ok(()) => tr(Text::DefaultKeys).to_string(),
+                    Err(e) => fill(Text::ButNotSaved, &[&tr(Text::DefaultKeys), &e]),
+                };
+            }
+            _ => {}
+        }
+    }
+
+    // the gallery starts on whatever is in use now
+    pub fn open_theme_gallery(&mut self) {
+        let theme = self.theme().name;
+        let pieces = self.piece_set();
+        self.gallery_theme = THEMES.iter().position(|t| t.name == theme).unwrap_or(0);
+        self.gallery_pieces = PIECE_SETS.iter().position(|&p| p == pieces).unwrap_or(0);
+        self.game_state = GameState::Themes;
+    }
+
+    // theme gallery: left/right picks a theme, up/down a piece set, enter applies
+    pub fn handle_themes_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Left => self.gallery_theme = self.gallery_theme.saturating_sub(1),
+            KeyCode::Right => self.gallery_theme = (self.gallery_theme + 1).min(THEMES.len() - 1),
+            KeyCode::Up => self.gallery_pieces = self.gallery_pieces.saturating_sub(1),
+            KeyCode::Down => self.gallery_pieces = (self.gallery_pieces + 1).min(PIECE_SETS.len() - 1),
+            KeyCode::Enter => {
+                let theme = THEMES[self.gallery_theme].name;
+                let pieces = PIECE_SETS[self.gallery_pieces].name();
+                self.config.ui.theme = theme.to_string();
+                self.config.ui.pieces = pieces.to_string();
+                self.status = match self.config.save() {
+                    Ok(()) => fill(Text::UsingTheme, &[&theme, &pieces]),
+                    Err(e) => fill(Text::ButNotSaved, &[&fill(Text::UsingTheme, &[&theme, &pieces]), &e]),
+                };
+            }
+            _ => {}
+        }
+    }
+
+    // the squares the human can see, while a fog of war game is still on
+    fn fog(&self) -> Option<[[bool; 8]; 8]> {
+        (self.variant.hides_pieces() && self.result.is_none())
+            .then(|| visible_squares(&self.board, self.bot_color.opposite()))
+    }
+
+    // a piece picked up on the board, the bot's candidate moves while it
+    // searches, the threats overlay, and a queued premove in blue
+    fn board_marks(&self) -> Vec<((usize, usize), Color)> {
+        let mut marks: Vec<_> = self.selected_piece.map(|pos| (pos, Color::LightBlue)).into_iter().collect();
+        marks.extend(self.candidate_marks());
+        marks.extend(self.threat_marks());
+        if let Some((from, to)) = self.premove {
+            marks.extend([(from, Color::Blue), (to, Color::Blue)]);
+        }
+        marks
+    }
+
+    // the from and to squares of the search's top three moves so far, best
+    // first so it wins a shared square. they move as the visit counts do.
+    // the stats can still be the last search's for a moment, so only moves
+    // that are legal here count
+    fn candidate_marks(&self) -> Vec<((usize, usize), Color)> {
+        let searching = (self.is_thinking() && self.opponent == OpponentKind::Engine) || self.analysis.is_some();
+        if !searching || self.fog().is_some() {
+            return Vec::new();
+        }
+        let colors = [Color::Magenta, Color::Cyan, Color::Yellow];
+        self.rl_engine
+            .current_stats
+            .top_moves
+            .iter()
+            .filter_map(|pv| parse_move_text(&[pv.mv.as_str()]))
+            .filter(|&(from, to)| {
+                self.board.get_piece(from).is_some_and(|piece| piece.color == self.current_turn)
+                    && self.board.legal_destinations(from).contains(&to)
+            })
+            .take(colors.len())
+            .zip(colors)
+            .flat_map(|((from, to), color)| [(from, color), (to, color)])
+            .collect()
+    }
+
+    // squares to tint on the game board: red for the human's hanging
+    // pieces, green for the bot's. nothing under fog, where it would give
+    // away what the bot has
+    fn threat_marks(&self) -> Vec<((usize, usize), Color)> {
+        if !self.show_threats || self.fog().is_some() {
+            return Vec::new();
+        }
+        let human = self.bot_color.opposite();
+        let mine = self.rl_engine.hanging_pieces(&self.board, human).into_iter().map(|pos| (pos, Color::Red));
+        let theirs = self.rl_engine.hanging_pieces(&self.board, self.bot_color).into_iter().map(|pos| (pos, Color::Green));
+        mine.chain(theirs).collect()
+    }
+
+    fn theme(&self) -> &'static Theme {
+        Theme::by_name(&self.config.ui.theme)
+    }
+
+    fn piece_set(&self) -> PieceSet {
+        PieceSet::by_name(&self.config.ui.pieces)
+    }
+
+    // enter on the board: picks up the player's piece under the cursor,
+    // puts it down again, or moves it to the cursor square
+    pub fn select_piece(&mut self) {
+        let pos = self.cursor_pos;
+        let human = self.mover();
+        let board = self.variant.view(&self.board, human);
+        let mine = board.get_piece(pos).is_some_and(|piece| piece.color == human);
+        match self.selected_piece.take() {
+            Some(from) if from == pos => {}
+            Some(from) if !mine => {
+                let message = match self.move_refusal() {
+                    Some(refusal) => Some(refusal),
+                    None => self.offer_move((from, pos)),
+                };
+                if let Some(message) = message {
+                    self.report(message);
+                }
+            }
+            _ if mine => self.selected_piece = Some(pos),
+            _ => self.status = fill(Text::NoPieceOfYoursOn, &[&coordinate_to_string(pos)]),
+        }
+    }
+
+    // the board cursor one square along, up being toward the top of the
+    // screen however the board is turned
+    fn move_board_cursor(&mut self, ranks: isize, files: isize) {
+        let (ranks, files) = if self.flipped { (-ranks, -files) } else { (ranks, files) };
+        let (rank, file) = self.cursor_pos;
+        self.cursor_pos = (rank.saturating_add_signed(ranks).min(7), file.saturating_add_signed(files).min(7));
+    }
+
+    // the focus, or the command box when the focused panel has been hidden
+    pub fn focused(&self) -> Focus {
+        match self.focus {
+            Focus::History if !self.config.layout.history => Focus::Command,
+            Focus::Analytics if !self.config.layout.analytics => Focus::Command,
+            focus => focus,
+        }
+    }
+
+    fn set_focus(&mut self, focus: Focus) {
+        self.focus = focus;
+        self.status = match focus {
+            Focus::Command => fill(Text::FocusCommand, &[&focus.name()]),
+            Focus::Board => fill(Text::FocusBoard, &[&focus.name()]),
+            Focus::History | Focus::Analytics => fill(Text::FocusPanel, &[&focus.name()]),
+        };
+    }
+
+    // Tab and Shift+Tab go round the panels
+    pub fn cycle_focus(&mut self, back: bool) {
+        let focus = self.focused().next(&self.config.layout, back);
+        self.set_focus(focus);
+    }
+
+    // a key for the focused panel. false for the ones left to the command
+    // box: everything while it has the focus, and typing anywhere, which
+    // hands it the focus back
+    pub fn handle_focus_key(&mut self, key: KeyEvent) -> bool {
+        if self.accessible {
+            return false;
+        }
+        let focus = self.focused();
+        let typing = matches!(key.code, KeyCode::Char(c) if c != ' ') || key.code == KeyCode::Backspace;
+        if focus != Focus::Command && typing {
+            self.focus = Focus::Command;
+            return false;
+        }
+        match (focus, key.code) {
+            (Focus::Board, KeyCode::Up) => self.move_board_cursor(-1, 0),
+            (Focus::Board, KeyCode::Down) => self.move_board_cursor(1, 0),
+            (Focus::Board, KeyCode::Left) => self.move_board_cursor(0, -1),
+            (Focus::Board, KeyCode::Right) => self.move_board_cursor(0, 1),
+            (Focus::Board, KeyCode::Enter | KeyCode::Char(' ')) => self.select_piece(),
+            (Focus::History, KeyCode::Up) => self.scroll_history_by(-1),
+            (Focus::History, KeyCode::Down) => self.scroll_history_by(1),
+            (Focus::History, KeyCode::Home) => self.history_scroll = Some(0),
+            (Focus::History, KeyCode::End) => self.history_scroll = None,
+            (Focus::Analytics, KeyCode::Up) => self.scroll_analytics(-1),
+            (Focus::Analytics, KeyCode::Down) => self.scroll_analytics(1),
+            (Focus::Analytics, KeyCode::PageUp) => self.scroll_analytics(-HISTORY_PAGE),
+            (Focus::Analytics, KeyCode::PageDown) => self.scroll_analytics(HISTORY_PAGE),
+            (Focus::Analytics, KeyCode::Home) => self.analytics_scroll = 0,
+            // the page keys scroll the history from anywhere else
+            (_, KeyCode::PageUp) if self.config.layout.history => self.scroll_history_by(-HISTORY_PAGE),
+            (_, KeyCode::PageDown) if self.config.layout.history => self.scroll_history_by(HISTORY_PAGE),
+            _ => return false,
+        }
+        true
+    }
+
+    fn scroll_analytics(&mut self, lines: isize) {
+        let last = self.analytics_lines().len().saturating_sub(1);
+        self.analytics_scroll = self.analytics_scroll.saturating_add_signed(lines).min(last);
+    }
+
+    // a panel's frame, lit up when it has the focus
+    fn focus_panel<'a>(&self, title: impl Into<Line<'a>>, focus: Focus) -> Block<'a> {
+        let block = self.panel(title);
+        if self.focused() == focus {
+            block.border_style(Style::default().fg(self.theme().label))
+        } else {
+            block
+        }
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        match self.game_state {
+            GameState::Menu => self.draw_menu(frame),
+            GameState::Setup => self.draw_setup(frame),
+            GameState::Editor => self.draw_editor(frame),
+            GameState::Training => self.draw_training(frame),
+            GameState::Playing => self.draw_game(frame),
+            GameState::Replay => self.draw_replay(frame),
+            GameState::Heatmap => self.draw_heatmap(frame),
+            GameState::Arena => self.draw_arena(frame),
+            GameState::History => self.draw_history(frame),
+            GameState::Profile => self.draw_profile(frame),
+            GameState::Themes => self.draw_themes(frame),
+            GameState::Settings => self.draw_settings(frame),
+            GameState::About => self.draw_about(frame),
+            GameState::Follow => self.draw_follow(frame),
+            GameState::Puzzle => self.draw_puzzle(frame),
+            GameState::Watch => self.draw_watch(frame),
+        }
+        if let Some(help) = &self.help {
+            help.render(frame, self.theme().label);
+        }
+        if let Some(palette) = &self.palette {
+            palette.render(frame, self.theme().label);
+        }
+        if let Some(dialog) = &self.dialog {
+            dialog.render(frame, self.theme().label);
+        }
+    }
+
+    fn draw_menu(&self, frame: &mut Frame) {
+        let area = frame.area();
+
+        let layout = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints([
+                Constraint::Percentage(20),
+                Constraint::Length(8), // Title height
+                Constraint::Length(3), // Spacing
+                Constraint::Length(MENU_ITEMS.len() as u16 + 1), // Menu items
+                Constraint::Min(0),
+            ])
+            .split(area);
+
+        // Title
+        let title = if self.accessible {
+            vec![Line::from("chessrl")]
+        } else {
+            vec![
+                Line::from("██████╗██╗  ██╗███████╗███████╗███████╗"),
+                Line::from("██╔════╝██║  ██║██╔════╝██╔════╝██╔════╝"),
+                Line::from("██║     ███████║█████╗  ███████╗███████╗"),
+                Line::from("██║     ██╔══██║██╔══╝  ╚════██║╚════██║"),
+                Line::from("╚██████╗██║  ██║███████╗███████║███████║"),
+                Line::from("╚═════╝╚═╝  ╚═╝╚══════╝╚══════╝╚══════╝"),
+            ]
+        };
+
+        let title_block = Paragraph::new(title)
+            .style(Style::default().fg(Color::Yellow))
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        // Menu items
+        let menu_items: Vec<Line> = MENU_ITEMS
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| {
+                Line::from(vec![
+                    Span::styled(
+                        match (self.accessible, self.menu_index == idx) {
+                            (false, _) => "     ► ",
+                            (true, true) => "     > ",
+                            (true, false) => "       ",
+                        },
+                        Style::default().fg(Color::White),
+                    ),
+                    Span::styled(
+                        tr(*item),
+                        Style::default()
+                            .fg(if self.menu_index == idx {
+                                Color::Green
+                            } else {
+                                Color::White
+                            })
+                            .add_modifier(if self.menu_index == idx {
+                                Modifier::BOLD
+                            } else {
+                                Modifier::empty()
+                            }),
+                    ),
+                ])
+            })
+            .collect();
+
+        let menu = Paragraph::new(menu_items)
+            .alignment(ratatui::layout::Alignment::Left)
+            .block(Block::default().borders(Borders::NONE));
+
+        let instructions = Paragraph::new(tr(Text::MenuInstructions))
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(ratatui::layout::Alignment::Center);
+
+        frame.render_widget(title_block, layout[1]);
+        frame.render_widget(menu, layout[3]);
+        frame.render_widget(instructions, layout[4]);
+    }
+
+    fn thinking_panel(&self) -> Paragraph<'static> {
+        let stats = &self.rl_engine.current_stats;
+        let best = match stats.best_line.first() {
+            _ if self.fog().is_some() => tr(Text::Hidden).to_string(),
+            Some(mv) => mv.clone(),
+            None => "-".to_string(),
+        };
+
+        let waiting = self.is_thinking();
+        if self.kind == TabKind::Analysis {
+            let line = match &self.analysis {
+                Some(search) => {
+                    let frame = (search.started.elapsed().as_millis() / TICK_RATE.as_millis()) as usize % SPINNER.len();
+                    Line::from(fill(Text::LookingAtPosition, &[&SPINNER[frame]]))
+                }
+                None => Line::from(Span::styled(tr(Text::MoveEitherSide), Style::default().fg(Color::DarkGray))),
+            };
+            let lines = vec![
+                line,
+                Line::from(fill(Text::Simulations, &[&stats.total_simulations])),
+                Line::from(vec![
+                    Span::raw(tr(Text::BestMove)),
+                    Span::styled(best, Style::default().fg(Color::Yellow)),
+                ]),
+                Line::from(fill(Text::Depth, &[&stats.depth_reached])),
+            ];
+            return Paragraph::new(lines)
+                .block(self.panel(tr(Text::AnalysisTitle)))
+                .style(Style::default().fg(Color::White));
+        }
+        let (title, lines) = match self.opponent {
+            OpponentKind::Scripted(_) | OpponentKind::Uci | OpponentKind::Network => {
+                let name = self.players[side(self.bot_color)].name();
+                let line = if waiting {
+                    let elapsed = self.turn_started.elapsed();
+                    let frame = (elapsed.as_millis() / TICK_RATE.as_millis()) as usize % SPINNER.len();
+                    Line::from(fill(Text::WaitingForMove, &[&SPINNER[frame], &name.to_lowercase()]))
+                } else {
+                    Line::from(Span::styled(tr(Text::WaitingForYourMove), Style::default().fg(Color::DarkGray)))
+                };
+                let mut lines = vec![line, Line::from(fill(Text::PlayingAgainst, &[&self.opponent.description()]))];
+                if self.opponent == OpponentKind::Network && !self.chat.is_empty() {
+                    lines.truncate(1);
+                    lines.extend(self.chat.panel_lines(chat::PANEL_ROWS, self.theme().label));
+                }
+                if let Some(attempt) = self.speedrun {
+                    let (moves, time) = self.speedrun_clock();
+                    lines.push(Line::from(fill(Text::Speedrun, &[&attempt.seed])));
+                    lines.push(Line::from(vec![
+                        Span::raw(fill(Text::YourMoves, &[&moves])),
+                        Span::styled(speedrun::clock_text(time), Style::default().fg(Color::Yellow)),
+                    ]));
+                }
+                // a UCI engine says how its search is going
+                if let (OpponentKind::Uci, Some(info)) = (self.opponent, &self.opponent_info) {
+                    lines.push(Line::from(fill(Text::Depth, &[&info.depth])));
+                    if let Some(score) = info.score {
+                        lines.push(Line::from(fill(Text::Score, &[&score.text()])));
+                    }
+                    if let Some(mv) = info.pv.first() {
+                        lines.push(Line::from(vec![
+                            Span::raw(tr(Text::BestMove)),
+                            Span::styled(mv.clone(), Style::default().fg(Color::Yellow)),
+                        ]));
+                    }
+                }
+                (name, lines)
+            }
+            OpponentKind::Engine if waiting => {
+                let elapsed = self.turn_started.elapsed();
+                let frame = (elapsed.as_millis() / TICK_RATE.as_millis()) as usize % SPINNER.len();
+                let nps = stats.nodes_explored as f32 / elapsed.as_secs_f32().max(0.001);
+                (
+                    fill(Text::EngineThinking, &[&SPINNER[frame]]),
+                    vec![
+                        Line::from(fill(Text::Simulations, &[&stats.total_simulations])),
+                        Line::from(fill(Text::NodesPerSecond, &[&format!("{:.0}", nps)])),
+                        Line::from(vec![
+                            Span::raw(tr(Text::BestMove)),
+                            Span::styled(best, Style::default().fg(Color::Yellow)),
+                        ]),
+                        Line::from(fill(Text::Depth, &[&stats.depth_reached])),
+                        time_bar(elapsed, self.clock().budget(self.bot_think_time()), self.accessible),
+                    ],
+                )
+            }
+            OpponentKind::Engine => (
+                tr(Text::Engine).to_string(),
+                vec![
+                    Line::from(Span::styled(tr(Text::WaitingForYourMove), Style::default().fg(Color::DarkGray))),
+                    Line::from(fill(Text::LastSearch, &[&stats.total_simulations])),
+                    Line::from(vec![
+                        Span::raw(tr(Text::Played)),
+                        Span::styled(best, Style::default().fg(Color::Yellow)),
+                    ]),
+                ],
+            ),
+        };
+
+        Paragraph::new(lines)
+            .block(self.panel(title))
+            .style(Style::default().fg(Color::White))
+    }
+
+    // a titled box, or just the title in accessible mode, since screen
+    // readers spell box drawing out one character at a time
+    fn panel<'a>(&self, title: impl Into<Line<'a>>) -> Block<'a> {
+        let block = Block::default().title(title);
+        if self.accessible {
+            block
+        } else {
+            block.borders(Borders::ALL)
+        }
+    }
+
+    // the board in `area`, inside `block`; plain text in accessible mode
+    fn render_board(&self, frame: &mut Frame, area: Rect, board: &Board, view: BoardView, block: Block) {
+        if self.accessible {
+            frame.render_widget(Paragraph::new(self.plain_board_lines(board, view)).block(block), area);
+            return;
+        }
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        frame.render_widget(
+            BoardWidget {
+                board,
+                view,
+                theme: self.theme(),
+                pieces: self.piece_set(),
+                flipped: self.flipped,
+                square: self.config.ui.square_size(),
+                checkered: self.config.ui.checkered,
+            },
+            inner,
+        );
+    }
+
+    // the board as letters and dots with no grid: capitals are white, `?` is
+    // fog and the cursor square is in brackets. marks can't be shown in text
+    fn plain_board_lines(&self, board: &Board, view: BoardView) -> Vec<Line<'static>> {
+        let files: Vec<usize> = if self.flipped { (0..8).rev().collect() } else { (0..8).collect() };
+        let mut lines = Vec::new();
+        if !view.hide_coordinates {
+            let labels: String = files.iter().map(|&file| format!(" {} ", (b'a' + file as u8) as char)).collect();
+            lines.push(Line::from(format!("  {}", labels.trim_end())));
+        }
+        for row in 0..8 {
+            let rank = if self.flipped { 7 - row } else { row };
+            let mut text = if view.hide_coordinates { "  ".to_string() } else { format!("{} ", 8 - rank) };
+            for &file in &files {
+                let symbol = if view.fog.is_some_and(|visible| !visible[rank][file]) {
+                    '?'
+                } else {
+                    board
+                        .get_piece((rank, file))
+                        .filter(|_| !view.hide_pieces)
+                        .map_or('.', |p| p.letter())
+                };
+                if view.cursor == Some((rank, file)) {
+                    text.push_str(&format!("[{}]", symbol));
+                } else {
+                    text.push_str(&format!(" {} ", symbol));
+                }
+            }
+            lines.push(Line::from(text.trim_end().to_string()));
+        }
+        lines
+    }
+
+    // the [uci] engine's eval beside the bot's, from the same side
+    fn push_second_opinion(&self, lines: &mut Vec<Line<'static>>) {
+        if !self.config.uci.second_opinion {
+            return;
+        }
+        let name = self.second_opinion.as_ref().map_or_else(|| tr(Text::UciEngine).to_string(), UciEngine::name);
+        let line = match (&self.second_opinion_error, &self.second_opinion_info) {
+            (Some(e), _) => vec![Span::styled(fill(Text::SecondOpinion, &[e]), Style::default().fg(Color::DarkGray))],
+            (None, Some(info)) => {
+                let mut spans = vec![
+                    Span::raw(format!("{}: ", name)),
+                    Span::styled(info.score.map_or("-".to_string(), |s| s.text()), Style::default().fg(Color::Green)),
+                    Span::styled(fill(Text::AtDepth, &[&info.depth]), Style::default().fg(Color::DarkGray)),
+                ];
+                if let Some(mv) = info.pv.first() {
+                    spans.push(Span::raw(tr(Text::BestAfter)));
+                    spans.push(Span::styled(mv.clone(), Style::default().fg(Color::Yellow)));
+                }
+                spans
+            }
+            (None, None) if self.variant.name() != "standard" => return,
+            (None, None) => vec![Span::styled(fill(Text::EngineNameThinking, &[&name]), Style::default().fg(Color::DarkGray))],
+        };
+        lines.push(Line::from(line));
+    }
+
+    // the engine's numbers for the analytics panel
+    fn push_evaluation(&self, lines: &mut Vec<Line<'static>>) {
+        lines.push(Line::from(vec![
+            Span::raw(tr(Text::CurrentEvaluation)),
+            Span::styled(
+                format_score(self.rl_engine.current_stats.for_white(self.rl_engine.current_stats.current_eval)),
+                Style::default().fg(Color::Green),
+            ),
+        ]));
+        self.push_second_opinion(lines);
+        lines.push(Line::from(""));
+        lines.push(Line::from(tr(Text::TopMoves)));
+
+        // one line per root move, multi_pv of them, each with its own continuation
+        for (idx, pv) in self.rl_engine.current_stats.top_moves.iter().enumerate() {
+            let line: Vec<&str> = pv.line.iter().skip(1).take(PV_MOVES).map(String::as_str).collect();
+            lines.push(Line::from(vec![
+                Span::raw(format!("{}. ", idx + 1)),
+                Span::styled(pv.mv.clone(), Style::default().fg(Color::Yellow)),
+                Span::raw(" ("),
+                Span::styled(format_score(self.rl_engine.current_stats.for_white(pv.score)), Style::default().fg(Color::Blue)),
+                Span::raw(fill(Text::Visits, &[&pv.visits])),
+                Span::styled(line.join(" "), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+
+        let stats = &self.rl_engine.current_stats;
+        lines.push(Line::from(Span::styled(
+            fill(
+                Text::SearchStats,
+                &[
+                    &stats.total_simulations,
+                    &format!("{:.1}", stats.elapsed.as_secs_f32()),
+                    &format!("{:.0}", stats.simulations_per_sec()),
+                    &format!("{:.1}", stats.avg_rollout_depth()),
+                    &format!("{:.1}", stats.branching_factor()),
+                    &stats.tree_size,
+                    &if stats.recycled > 0 { fill(Text::Recycled, &[&stats.recycled]) } else { String::new() },
+                ],
+            ),
+            Style::default().fg(Color::DarkGray),
+        )));
+        lines.push(Line::from(Span::styled(
+            fill(
+                Text::MemoryStats,
+                &[
+                    &format_bytes(stats.memory_bytes),
+                    &format!("{:.1}", stats.table_fill * 100.0),
+                    &format!("{:.0}", stats.table_hit_rate() * 100.0),
+                    &format!("{:.0}", stats.first_move_cutoff_rate() * 100.0),
+                ],
+            ),
+            Style::default().fg(Color::DarkGray),
+        )));
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::raw(tr(Text::CurrentPositionScore)),
+            Span::styled(
+                format!("{:.2}", white_score(self.current_position_score, self.bot_color)),
+                Style::default().fg(Color::Green),
+            ),
+        ]));
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::raw(tr(Text::MaterialBalance)),
+            Span::styled(
+                format!(
+                    "{}",
+                    self.rl_engine
+                        .get_material_balance(&self.board, PieceColor::White)
+                ),
+                Style::default().fg(Color::Blue),
+            ),
+        ]));
+        for (label, color) in [(Text::BotKingSafety, self.bot_color), (Text::YourKingSafety, self.bot_color.opposite())] {
+            let safety = self.rl_engine.get_king_safety(&self.board, color);
+            lines.push(Line::from(vec![
+                Span::raw(tr(label)),
+                Span::styled(format!("{:.2}", safety.score), Style::default().fg(Color::Magenta)),
+                Span::styled(
+                    fill(
+                        Text::SafetyTerms,
+                        &[
+                            &safety.attackers,
+                            &format!("{:.0}", safety.attack_weight),
+                            &format!("{:+.0}", safety.shield),
+                            &format!("{:+.1}", safety.open_files),
+                        ],
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+        lines.push(Line::from(vec![
+            Span::raw(tr(Text::CenterControl)),
+            Span::styled(
+                format!(
+                    "{:.2}",
+                    self.rl_engine
+                        .get_center_control(&self.board, self.bot_color)
+                ),
+                Style::default().fg(Color::Cyan),
+            ),
+        ]));
+    }
+
+    fn draw_game(&self, frame: &mut Frame) {
+        if self.accessible {
+            return self.draw_accessible_game(frame);
+        }
+        let area = frame.area();
+        if self.tabs.len() > 1 {
+            let bar = Rect { height: 1.min(area.height), ..area };
+            frame.render_widget(Paragraph::new(tabs::tab_bar(&self.tab_titles(), self.tab, self.theme().label)), bar);
+        }
+
+        let areas = self.game_areas(area);
+        self.render_board(
+            frame,
+            areas.board,
+            &self.board,
+            BoardView {
+                fog: self.fog(),
+                cursor: (self.focused() == Focus::Board).then_some(self.cursor_pos),
+                marks: &self.board_marks(),
+                hide_pieces: self.blindfold && self.result.is_none(),
+                ..Default::default()
+            },
+            Block::default(),
+        );
+
+        if let Some(analytics_area) = areas.analytics {
+            frame.render_widget(self.analytics_panel(), analytics_area);
+        }
+
+        // Move history on right side
+        if let Some(history_area) = areas.history {
+            let lines = self.history_lines();
+            let total = lines.len();
+            let top = self.history_top(total, history_area);
+            let history = Paragraph::new(lines)
+                .block(self.focus_panel(self.history_title(), Focus::History))
+                .style(Style::default().fg(Color::White))
+                .scroll((top as u16, 0));
+
+            frame.render_widget(history, history_area);
+            let rows = history_area.height.saturating_sub(2) as usize;
+            if total > rows {
+                let mut state = ScrollbarState::new(total.saturating_sub(rows)).position(top);
+                let inner = history_area.inner(Margin { vertical: 1, horizontal: 0 });
+                frame.render_stateful_widget(Scrollbar::new(ScrollbarOrientation::VerticalRight), inner, &mut state);
+            }
+        }
+
+        frame.render_widget(self.thinking_panel(), areas.thinking);
+        frame.render_widget(self.command_panel(), areas.command);
+    }
+
+    // what the engine and the variant have to say about the position
+    fn analytics_panel(&self) -> Paragraph<'static> {
+        let lines = self.analytics_lines();
+        let scroll = self.analytics_scroll.min(lines.len().saturating_sub(1));
+        Paragraph::new(lines)
+            .block(self.focus_panel(tr(Text::Analytics), Focus::Analytics))
+            .style(Style::default().fg(Color::White))
+            .scroll((scroll as u16, 0))
+    }
+
+    fn analytics_lines(&self) -> Vec<Line<'static>> {
+        let mut analytics_text = vec![
+            Line::from(vec![
+                Span::raw(tr(Text::PositionEvaluation)),
+                Span::raw(" ".repeat(30)),
+                Span::raw(tr(Text::EngineAnalysis)),
+            ]),
+            Line::from("─".repeat(60)),
+            Line::from(vec![
+                Span::raw(tr(Text::ThinkingDepth)),
+                Span::styled(
+                    format!("{}", self.rl_engine.current_stats.depth_reached),
+                    Style::default().fg(Color::Blue),
+                ),
+            ]),
+            Line::from(vec![
+                Span::raw(tr(Text::TotalPositions)),
+                Span::styled(
+                    format!("{}", self.rl_engine.current_stats.total_simulations),
+                    Style::default().fg(Color::Yellow),
+                ),
+            ]),
+        ];
+
+        let variant_status = self.variant.status(&self.board);
+        if !variant_status.is_empty() {
+            analytics_text.push(Line::from(""));
+            analytics_text.push(Line::from(Span::styled(
+                self.variant.title(),
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            )));
+            for line in variant_status {
+                analytics_text.push(Line::from(Span::styled(line, Style::default().fg(Color::Cyan))));
+            }
+            analytics_text.push(Line::from(""));
+        }
+
+        // in fog of war, evaluations and candidate moves would give away
+        // where the bot's pieces are
+        if self.fog().is_some() {
+            analytics_text.push(Line::from(""));
+            analytics_text.push(Line::from(Span::styled(
+                tr(Text::EvaluationsHidden),
+                Style::default().fg(Color::DarkGray),
+            )));
+        } else {
+            self.push_evaluation(&mut analytics_text);
+        }
+
+        if !self.engine_notes.is_empty() {
+            analytics_text.push(Line::from(""));
+            for note in &self.engine_notes {
+                analytics_text.push(Line::from(Span::styled(
+                    note.clone(),
+                    Style::default().fg(Color::LightYellow),
+                )));
+            }
+        }
+
+        analytics_text
+    }
+
+    // command input, last message underneath
+    fn command_panel(&self) -> Paragraph<'static> {
+        let (title, prompt) = if self.chatting { (tr(Text::Chat), tr(Text::SayPrompt)) } else { (tr(Text::Command), ">> ") };
+        Paragraph::new(vec![
+            self.command_input.render(
+                prompt,
+                Style::default().fg(if self.chatting || self.command_looks_valid() {
+                    Color::Yellow
+                } else {
+                    Color::Red
+                }),
+            ),
+            Line::from(Span::styled(
+                self.status.clone(),
+                Style::default().fg(Color::DarkGray),
+            )),
+        ])
+        .block(self.focus_panel(title, Focus::Command))
+        .style(Style::default().fg(Color::Yellow))
+        .wrap(Wrap { trim: false })
+    }
+
+    // one column of plain text: the board, what has happened, and the
+    // command box, in the order a screen reader goes through them
+    fn draw_accessible_game(&self, frame: &mut Frame) {
+        let layout = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints([Constraint::Length(10), Constraint::Min(3), Constraint::Length(4)])
+            .split(frame.area());
+
+        self.render_board(
+            frame,
+            layout[0],
+            &self.board,
+            BoardView {
+                fog: self.fog(),
+                hide_pieces: self.blindfold && self.result.is_none(),
+                ..Default::default()
+            },
+            self.panel(tr(Text::Board)),
+        );
+
+        // the newest lines, as many as fit once long ones wrap
+        let width = layout[1].width.max(1) as usize;
+        let mut room = layout[1].height.saturating_sub(1) as usize;
+        let mut narration: Vec<Line> = Vec::new();
+        for line in self.narrator.lines.iter().rev() {
+            let rows = line.chars().count().div_ceil(width).max(1);
+            if rows > room {
+                break;
+            }
+            room -= rows;
+            narration.insert(0, Line::from(line.clone()));
+        }
+        frame.render_widget(
+            Paragraph::new(narration)
+                .block(self.panel(tr(Text::Narration)))
+                .wrap(Wrap { trim: false }),
+            layout[1],
+        );
+
+        frame.render_widget(self.command_panel(), layout[2]);
+    }
+
+    // the game screen's panels, under the tab bar when there is one
+    fn game_areas(&self, mut area: Rect) -> GameAreas {
+        if self.tabs.len() > 1 {
+            area.y += 1;
+            area.height = area.height.saturating_sub(1);
+        }
+        self.config.layout.areas(area, self.config.ui.square_size().board_height())
+    }
+
+    // the moves so far in pairs, the last one picked out, then what's been
+    // said along the way. the bot's moves are hidden under fog
+    fn history_lines(&self) -> Vec<Line<'static>> {
+        let hidden = self.fog().is_some();
+        let sans: Vec<String> = self
+            .record
+            .plies
+            .iter()
+            .map(|ply| if hidden && ply.color == self.bot_color { "??".to_string() } else { ply.san.clone() })
+            .collect();
+        let mut lines = movelist::pair_lines(&sans, self.record.first_to_move(), sans.len().checked_sub(1));
+        if !lines.is_empty() && !self.move_history.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.extend(self.move_history.iter().map(|note| Line::styled(note.clone(), Style::default().fg(Color::Gray))));
+        lines
+    }
+
+    // `replay 12` opens the replay viewer on white's 12th move, `replay
+    // 12...` on black's
+    fn replay_move(&mut self, number: &str) -> String {
+        let (number, black) = match number.strip_suffix("...") {
+            Some(number) => (number, true),
+            None => (number, false),
+        };
+        let Some(number) = number.parse::<usize>().ok().filter(|&n| n > 0) else {
+            return tr(Text::ReplayUsage).to_string();
+        };
+        let offset = usize::from(self.record.first_to_move() == PieceColor::Black);
+        let ply = ((number - 1) * 2 + usize::from(black)).checked_sub(offset);
+        let Some(ply) = ply.filter(|&ply| ply < self.record.len()) else {
+            return fill(Text::NoSuchMove, &[&format!("{}{}", number, if black { "..." } else { "" })]);
+        };
+        if self.fog().is_some() {
+            return tr(Text::NoReplaysInFog).to_string();
+        }
+        let problem = self.start_replay();
+        if let Some(replay) = &mut self.replay {
+            replay.index = ply + 1;
+        }
+        problem
+    }
+
+    // a click on a move in the history: an analysis board goes back to the
+    // position after it, a game opens the replay viewer there
+    pub fn click_history(&mut self, column: u16, row: u16) {
+        let Ok((width, height)) = crossterm::terminal::size() else {
+            return;
+        };
+        let Some(area) = self.game_areas(Rect::new(0, 0, width, height)).history else {
+            return;
+        };
+        let inside = column > area.x && column + 1 < area.right() && row > area.y && row + 1 < area.bottom();
+        if self.accessible || !inside {
+            return;
+        }
+        let line = (row - area.y - 1) as usize + self.history_top(self.history_lines().len(), area);
+        let Some(ply) = movelist::ply_at(self.record.len(), self.record.first_to_move(), line, (column - area.x - 1) as usize)
+        else {
+            return;
+        };
+        if self.kind == TabKind::Analysis {
+            self.status = self.jump_to_ply(ply + 1);
+        } else if self.fog().is_none() {
+            self.start_replay();
+            if let Some(replay) = &mut self.replay {
+                replay.index = ply + 1;
+            }
+        }
+    }
+
+    // takes an analysis board back to `plies` plies in
+    fn jump_to_ply(&mut self, plies: usize) -> String {
+        if plies >= self.record.len() {
+            return String::new();
+        }
+        if !self.undo_stack.iter().any(|snapshot| snapshot.plies == plies) {
+            return tr(Text::BeforeAnalysisBoard).to_string();
+        }
+        while let Some(snapshot) = self.undo_stack.pop() {
+            if snapshot.plies == plies {
+                self.restore(snapshot);
+                break;
+            }
+        }
+        fill(Text::BackToMove, &[&(plies / 2 + 1)])
+    }
+
+    fn history_title(&self) -> String {
+        let mut title = tr(Text::MoveHistory).to_string();
+        if self.kind == TabKind::Analysis {
+            title.push_str(tr(Text::AnalysisBoardTag));
+        } else if self.opponent != OpponentKind::Engine {
+            title.push_str(&fill(Text::VsTag, &[&self.opponent.description()]));
+        }
+        if self.variant.name() != "standard" {
+            title.push_str(&format!(" · {}", self.variant.title()));
+        }
+        if let Some(kind) = self.drill {
+            title.push_str(&fill(Text::DrillTag, &[&kind.title(), &self.drill_moves(kind), &kind.budget()]));
+        }
+        if self.handicap != Handicap::None {
+            title.push_str(&fill(Text::OddsTag, &[&self.handicap.name()]));
+        }
+        if self.personality != Personality::Balanced {
+            title.push_str(&fill(Text::PersonalityTag, &[&self.personality.name()]));
+        }
+        if let Some(name) = &self.loaded_weights {
+            title.push_str(&format!(" · {}", name));
+        }
+        if let Some(values) = self.loaded_values {
+            title.push_str(&fill(Text::ValuesTag, &[&values.name()]));
+        }
+        if let Some(relay) = &self.relay {
+            title.push_str(&fill(Text::SpectatorsTag, &[&relay.spectators(), &relay.port]));
+        }
+        title
+    }
+
+    // the first line of the history on show in `area`: the newest lines,
+    // unless it's been scrolled up
+    fn history_top(&self, total: usize, area: Rect) -> usize {
+        let last = total.saturating_sub(area.height.saturating_sub(2) as usize);
+        self.history_scroll.map_or(last, |top| top.min(last))
+    }
+
+    pub fn scroll_history(&mut self, up: bool) {
+        self.scroll_history_by(if up { -1 } else { 1 });
+    }
+
+    // scrolling back down to the newest lines follows them again
+    fn scroll_history_by(&mut self, lines: isize) {
+        let Ok((width, height)) = crossterm::terminal::size() else {
+            return;
+        };
+        let Some(area) = self.game_areas(Rect::new(0, 0, width, height)).history else {
+            return;
+        };
+        let total = self.history_lines().len();
+        let last = total.saturating_sub(area.height.saturating_sub(2) as usize);
+        let top = self.history_top(total, area).saturating_add_signed(lines).min(last);
+        self.history_scroll = (top < last).then_some(top);
+    }
+
+    fn draw_replay(&self, frame: &mut Frame) {
+        let Some(replay) = &self.replay else {
+            return;
+        };
+        let layout = replay_layout(frame.area());
+
+        self.render_board(frame, layout[0], replay.board(), BoardView::default(), self.panel(tr(Text::Replay)));
+
+        let mut info = self.replay_header(replay);
+        info.extend(movelist::pair_lines(&replay.moves, replay.first_to_move(), replay.index.checked_sub(1)));
+        info.push(Line::from(""));
+        let back = if self.replay_return == GameState::History { Text::BackToHistory } else { Text::BackToGame };
+        for help in tr(Text::ReplayKeys).lines().chain([tr(back)]) {
+            info.push(Line::from(Span::styled(help, Style::default().fg(Color::DarkGray))));
+        }
+
+        let panel = Paragraph::new(info)
+            .block(self.panel(tr(Text::Moves)))
+            .scroll((self.replay_scroll(replay, layout[1]) as u16, 0));
+        frame.render_widget(panel, layout[1]);
+    }
+
+    // the lines above the moves in the replay viewer's panel
+    fn replay_header(&self, replay: &Replay) -> Vec<Line<'static>> {
+        let mut header = vec![
+            Line::from(fill(Text::ReplayPosition, &[&replay.index, &replay.last_index()])),
+            Line::from(vec![
+                Span::styled(
+                    tr(if replay.playing { Text::ReplayPlaying } else { Text::ReplayPaused }),
+                    Style::default().fg(if replay.playing { Color::Green } else { Color::Yellow }),
+                ),
+                Span::raw(format!("  {}×", replay.speed())),
+            ]),
+            Line::from(""),
+        ];
+        if !self.status.is_empty() {
+            header.insert(2, Line::from(Span::styled(self.status.clone(), Style::default().fg(Color::Cyan))));
+        }
+        header
+    }
+
+    // keeps the current move in view, with a line to spare under it
+    fn replay_scroll(&self, replay: &Replay, area: Rect) -> usize {
+        let row = replay.index.saturating_sub(1) + usize::from(replay.first_to_move() == PieceColor::Black);
+        let line = self.replay_header(replay).len() + row / 2;
+        (line + 2).saturating_sub(area.height.saturating_sub(2) as usize)
+    }
+
+    // a click on a move in the replay viewer's list jumps to it
+    pub fn click_replay(&mut self, column: u16, row: u16) {
+        let Ok((width, height)) = crossterm::terminal::size() else {
+            return;
+        };
+        let Some(replay) = &self.replay else {
+            return;
+        };
+        let area = replay_layout(Rect::new(0, 0, width, height))[1];
+        if column <= area.x || column + 1 >= area.right() || row <= area.y || row + 1 >= area.bottom() {
+            return;
+        }
+        let line = (row - area.y - 1) as usize + self.replay_scroll(replay, area);
+        let Some(line) = line.checked_sub(self.replay_header(replay).len()) else {
+            return;
+        };
+        if let Some(ply) = movelist::ply_at(replay.moves.len(), replay.first_to_move(), line, (column - area.x - 1) as usize) {
+            if let Some(replay) = &mut self.replay {
+                replay.index = ply + 1;
+                replay.playing = false;
+            }
+        }
+    }
+
+    fn draw_follow(&self, frame: &mut Frame) {
+        let Some(follow) = &self.follow else {
+            return;
+        };
+        let hint = Style::default().fg(Color::DarkGray);
+        let layout = Layout::default()
+            .direction(LayoutDirection::Horizontal)
+            .constraints([Constraint::Ratio(3, 4), Constraint::Ratio(1, 4)])
+            .split(frame.area());
+
+        let title = match follow.viewing {
+            Some(idx) => fill(Text::FollowingAt, &[&follow.source, &idx, &follow.record.len()]),
+            None => fill(Text::FollowingLive, &[&follow.source]),
+        };
+        self.render_board(frame, layout[0], follow.board(), BoardView::default(), self.panel(title));
+
+        let name = |name: &str| if name.is_empty() { "?".to_string() } else { name.to_string() };
+        let mut info = vec![Line::from(format!("{} – {}", name(&follow.game.white), name(&follow.game.black)))];
+        let status = if let Some(error) = &follow.error {
+            Span::styled(error.clone(), Style::default().fg(Color::Red))
+        } else if follow.finished() {
+            Span::styled(fill(Text::Finished, &[&follow.game.result]), Style::default().fg(Color::Green))
+        } else if follow.ended {
+            Span::styled(tr(Text::FeedClosed), Style::default().fg(Color::Yellow))
+        } else if follow.record.is_empty() {
+            Span::styled(tr(Text::WaitingForMoves), hint)
+        } else {
+            Span::styled(sentence(Text::SideMoves, &[&tr(Text::Side(follow.record.to_move()))]), Style::default().fg(Color::White))
+        };
+        info.push(Line::from(status));
+        if !self.status.is_empty() {
+            info.push(Line::from(Span::styled(self.status.clone(), Style::default().fg(Color::Cyan))));
+        }
+        if let Some(san) = follow.stuck_at() {
+            info.push(Line::from(Span::styled(fill(Text::CantFollow, &[&san]), Style::default().fg(Color::Red))));
+        }
+        if follow.evaluate {
+            let eval = match (&follow.eval, &follow.search) {
+                (Some(eval), _) if eval.ply == follow.record.len() => eval.text(),
+                (_, Some(search)) => {
+                    let frame = (search.started.elapsed().as_millis() / TICK_RATE.as_millis()) as usize % SPINNER.len();
+                    fill(Text::Thinking, &[&SPINNER[frame]])
+                }
+                _ => "–".to_string(),
+            };
+            info.push(Line::from(fill(Text::EngineEval, &[&eval])));
+        }
+        info.push(Line::from(""));
+        for (idx, pair) in follow.record.sans().chunks(2).enumerate() {
+            let black_first = follow.record.first_to_move() == PieceColor::Black;
+            let text = match (black_first, idx) {
+                (true, 0) => format!("{:>3}. … {}", 1, pair[0]),
+                _ => format!("{:>3}. {}", idx + 1, pair.join(" ")),
+            };
+            info.push(Line::from(text));
+        }
+        info.push(Line::from(""));
+        for help in tr(Text::FollowKeys).lines() {
+            info.push(Line::from(Span::styled(help, hint)));
+        }
+        // keep the newest moves in view
+        let scroll = info.len().saturating_sub(layout[1].height.saturating_sub(2) as usize);
+        let panel = Paragraph::new(info).block(self.panel(tr(Text::Game))).scroll((scroll as u16, 0));
+        frame.render_widget(panel, layout[1]);
+    }
+
+    fn draw_watch(&self, frame: &mut Frame) {
+        let Some(watch) = &self.watch else {
+            return;
+        };
+        let hint = Style::default().fg(Color::DarkGray);
+        let layout = Layout::default()
+            .direction(LayoutDirection::Horizontal)
+            .constraints([Constraint::Ratio(3, 4), Constraint::Ratio(1, 4)])
+            .split(frame.area());
+
+        let marks: Vec<((usize, usize), Color)> = watch
+            .record
+            .last_move()
+            .map_or(Vec::new(), |mv| vec![(mv.from, Color::DarkGray), (mv.to, Color::DarkGray)]);
+        let view = BoardView { marks: &marks, ..BoardView::default() };
+        let title = format!("{} – {}", watch.player(PieceColor::White).name(), watch.player(PieceColor::Black).name());
+        self.render_board(frame, layout[0], watch.position.board(), view, self.panel(title));
+
+        let mut info = Vec::new();
+        for color in [PieceColor::White, PieceColor::Black] {
+            let player = watch.player(color);
+            let style = if player.is_human() { Style::default().fg(Color::Yellow) } else { Style::default() };
+            info.push(Line::from(Span::styled(sentence(Text::SidePlayer, &[&tr(Text::Side(color)), &player.name()]), style)));
+        }
+        let status = if let Some(result) = watch.result {
+            Span::styled(format!("{} ({})", result.score(), result.termination()), Style::default().fg(Color::Green))
+        } else if watch.paused {
+            Span::styled(tr(Text::Paused), Style::default().fg(Color::Yellow))
+        } else if watch.player(watch.position.to_move()).is_human() {
+            Span::styled(tr(Text::YourMove), Style::default().fg(Color::Cyan))
+        } else {
+            Span::raw(fill(Text::PlayerToMove, &[&watch.player(watch.position.to_move()).name()]))
+        };
+        info.push(Line::from(status));
+        info.push(Line::from(fill(Text::ThinkTime, &[&format!("{:.2}", watch.think_time().as_secs_f32())])));
+        if let Some(stats) = &watch.stats {
+            let best = stats.best_line.first().cloned().unwrap_or_else(|| "-".to_string());
+            info.push(Line::from(Span::styled(fill(Text::SimulationsBest, &[&stats.total_simulations, &best]), hint)));
+        }
+        info.push(Line::from(""));
+        for (idx, pair) in watch.record.sans().chunks(2).enumerate() {
+            info.push(Line::from(format!("{:>3}. {}", idx + 1, pair.join(" "))));
+        }
+        info.push(Line::from(""));
+        info.push(watch.input.render(">> ", Style::default().fg(Color::Yellow)));
+        if !watch.message.is_empty() {
+            info.push(Line::from(Span::styled(watch.message.clone(), Style::default().fg(Color::Cyan))));
+        }
+        for help in tr(Text::WatchKeys).lines() {
+            info.push(Line::from(Span::styled(help, hint)));
+        }
+        // keep the newest moves in view
+        let scroll = info.len().saturating_sub(layout[1].height.saturating_sub(2) as usize);
+        let panel = Paragraph::new(info).block(self.panel(tr(Text::Watch))).scroll((scroll as u16, 0));
+        frame.render_widget(panel, layout[1]);
+    }
+
+    fn draw_puzzle(&self, frame: &mut Frame) {
+        let hint = Style::default().fg(Color::DarkGray);
+        let Some(screen) = &self.puzzle else {
+            let text = if self.status.is_empty() { tr(Text::FetchingPuzzle) } else { self.status.as_str() };
+            let lines = vec![Line::from(text.to_string()), Line::from(""), Line::from(Span::styled(tr(Text::EscMenu), hint))];
+            frame.render_widget(Paragraph::new(lines).block(self.panel(tr(Text::DailyPuzzle))).wrap(Wrap { trim: false }), frame.area());
+            return;
+        };
+        let layout = Layout::default()
+            .direction(LayoutDirection::Horizontal)
+            .constraints([Constraint::Ratio(3, 4), Constraint::Ratio(1, 4)])
+            .split(frame.area());
+
+        let marks: Vec<((usize, usize), Color)> =
+            screen.last_move.map_or(Vec::new(), |(from, to)| vec![(from, Color::DarkGray), (to, Color::DarkGray)]);
+        let view = BoardView { marks: &marks, ..BoardView::default() };
+        let title = fill(Text::DailyPuzzleOn, &[&screen.puzzle.date]);
+        self.render_board(frame, layout[0], &screen.board, view, self.panel(title));
+
+        let puzzle = &screen.puzzle;
+        let mut info = vec![
+            Line::from(fill(Text::PuzzleRating, &[&puzzle.rating])),
+            Line::from(format!("{} – {}", puzzle.white, puzzle.black)),
+        ];
+        if !puzzle.themes.is_empty() {
+            info.push(Line::from(Span::styled(puzzle.themes.join(", "), hint)));
+        }
+        if let Some(note) = &screen.note {
+            info.push(Line::from(Span::styled(note.clone(), Style::default().fg(Color::Yellow))));
+        }
+        info.push(Line::from(""));
+        let (found, total) = screen.progress();
+        let progress = if screen.revealed {
+            Span::styled(tr(Text::Shown), Style::default().fg(Color::Yellow))
+        } else if screen.solved() {
+            Span::styled(tr(Text::Solved), Style::default().fg(Color::Green))
+        } else {
+            Span::raw(fill(Text::MoveOf, &[&found, &total]))
+        };
+        info.push(Line::from(progress));
+        info.push(Line::from(fill(Text::MistakesHints, &[&screen.mistakes, &screen.hints])));
+        if !screen.played.is_empty() {
+            info.push(Line::from(screen.played.join(" ")));
+        }
+        info.push(Line::from(""));
+        info.push(screen.input.render(">> ", Style::default().fg(Color::Yellow)));
+        info.push(Line::from(Span::styled(screen.message.clone(), Style::default().fg(Color::Cyan))));
+        info.push(Line::from(""));
+        for help in tr(Text::PuzzleKeys).lines() {
+            info.push(Line::from(Span::styled(help, hint)));
+        }
+        info.push(Line::from(""));
+        info.push(Line::from(Span::styled(puzzle.url(), hint)));
+        frame.render_widget(Paragraph::new(info).block(self.panel(tr(Text::Puzzle))).wrap(Wrap { trim: false }), layout[1]);
+    }
+
+    fn draw_heatmap(&self, frame: &mut Frame) {
+        let heatmap = &self.heatmap;
+        let values = heatmap.values(&self.rl_engine);
+        let biggest = values.iter().flatten().fold(0.0f32, |max, v| max.max(v.abs()));
+        // learned values against the most a table can reach; changes against
+        // the biggest one so far, so small drifts still show
+        let scale = if heatmap.changes { biggest.max(0.1) } else { MAX_POSITION_VALUE };
+
+        let layout = Layout::default()
+            .direction(LayoutDirection::Horizontal)
+            .constraints([Constraint::Length(62), Constraint::Min(30)])
+            .split(frame.area());
+
+        let mut lines = vec![Line::from(format!(
+            "   {}",
+            "abcdefgh".chars().map(|f| format!("   {}   ", f)).collect::<String>()
+        ))];
+        for (rank, row) in values.iter().enumerate() {
+            let mut spans = vec![Span::raw(format!(" {} ", 8 - rank))];
+            for &value in row {
+                let text = format!(" {:+5.2} ", value);
+                let style = if self.accessible {
+                    Style::default()
+                } else {
+                    Style::default().fg(Color::White).bg(heat_color(value, scale))
+                };
+                spans.push(Span::styled(text, style));
+            }
+            lines.push(Line::from(spans));
+            lines.push(Line::from(""));
+        }
+        let title = fill(
+            Text::HeatmapTitle,
+            &[
+                &tr(Text::Piece(heatmap.piece_type())),
+                &tr(if heatmap.endgame { Text::EndGame } else { Text::MiddleGame }),
+                &tr(if heatmap.changes { Text::ChangeSinceStart } else { Text::Learned }),
+            ],
+        );
+        frame.render_widget(Paragraph::new(lines).block(self.panel(title)), layout[0]);
+
+        let mut info = vec![
+            Line::from(tr(Text::HeatmapAbout)),
+            Line::from(""),
+        ];
+        let mut moved: Vec<(f32, (usize, usize))> = Vec::new();
+        let initial = RLEngine::initial_position_table(heatmap.piece_type(), heatmap.endgame);
+        let learned = self.rl_engine.position_table(heatmap.piece_type(), heatmap.endgame);
+        for rank in 0..8 {
+            for file in 0..8 {
+                moved.push((learned[rank][file] - initial[rank][file], (rank, file)));
+            }
+        }
+        moved.sort_by(|a, b| b.0.abs().partial_cmp(&a.0.abs()).unwrap());
+        moved.retain(|(change, _)| change.abs() >= 0.005);
+        if moved.is_empty() {
+            info.push(Line::from(Span::styled(
+                tr(Text::NothingLearned),
+                Style::default().fg(Color::DarkGray),
+            )));
+        } else {
+            info.push(Line::from(tr(Text::BiggestChanges)));
+            for (change, square) in moved.iter().take(5) {
+                info.push(Line::from(format!("  {} {:+.2}", coordinate_to_string(*square), change)));
+            }
+        }
+        info.push(Line::from(""));
+        for help in tr(Text::HeatmapKeys).lines() {
+            info.push(Line::from(Span::styled(help, Style::default().fg(Color::DarkGray))));
+        }
+        frame.render_widget(Paragraph::new(info).block(self.panel(tr(Text::Heatmap))).wrap(Wrap { trim: false }), layout[1]);
+    }
+
+    fn draw_arena(&self, frame: &mut Frame) {
+        let area = frame.area();
+        frame.render_widget(Clear, area);
+        let hint = Style::default().fg(Color::DarkGray);
+        let Some(tournament) = self.tournaments.get(self.tournament_index) else {
+            let lines = vec![
+                Line::from(tr(Text::NoTournaments)),
+                Line::from(""),
+                Line::from(tr(Text::HoldTournament)),
+                Line::from(""),
+                Line::from(Span::styled(tr(Text::EscBackToGame), hint)),
+            ];
+            frame.render_widget(Paragraph::new(lines).block(self.panel(tr(Text::Arena))), area);
+            return;
+        };
+
+        let layout = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints([Constraint::Length(tournament.entrants.len() as u16 + 7), Constraint::Min(0)])
+            .split(area);
+        let mut lines = vec![
+            Line::from(fill(
+                Text::TournamentSummary,
+                &[&tournament.format.name(), &tournament.games.len(), &tournament.budget, &tournament.date()],
+            )),
+            Line::from(""),
+        ];
+        let mut table = tournament.crosstable().into_iter();
+        if let Some(header) = table.next() {
+            lines.push(Line::from(Span::styled(header, hint)));
+        }
+        lines.extend(table.map(Line::from));
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            fill(Text::TournamentOf, &[&(self.tournament_index + 1), &self.tournaments.len()]),
+            hint,
+        )));
+        frame.render_widget(Paragraph::new(lines).block(self.panel(tr(Text::Arena))), layout[0]);
+
+        let games: Vec<Line> = tournament
+            .games
+            .iter()
+            .map(|game| {
+                Line::from(format!(
+                    "{} - {}  {} ({}), {}",
+                    tournament.entrants[game.white].name,
+                    tournament.entrants[game.black].name,
+                    game.result,
+                    game.termination,
+                    game.opening
+                ))
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(games).block(self.panel(tr(Text::Games))), layout[1]);
+    }
+
+    fn draw_editor(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let editor = &self.editor;
+
+        let layout = Layout::default()
+            .direction(LayoutDirection::Horizontal)
+            .constraints([Constraint::Ratio(3, 4), Constraint::Ratio(1, 4)])
+            .split(area);
+
+        self.render_board(
+            frame,
+            layout[0],
+            &editor.board,
+            BoardView { cursor: Some(editor.cursor), ..Default::default() },
+            self.panel(tr(Text::BoardEditor)),
+        );
+
+        let (valid, verdict) = match editor.problem() {
+            Some(problem) => (false, problem),
+            None => (true, tr(Text::PositionIsLegal).to_string()),
+        };
+        let mut info = vec![
+            Line::from(vec![
+                Span::raw(tr(Text::ToMove)),
+                Span::styled(sentence(Text::Side(editor.to_move), &[]), Style::default().fg(Color::Yellow)),
+            ]),
+            Line::from(fill(Text::Square, &[&coordinate_to_string(editor.cursor)])),
+            Line::from(Span::styled(
+                tr(Text::NoCastlingYet),
+                Style::default().fg(Color::DarkGray),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                verdict,
+                Style::default().fg(if valid { Color::Green } else { Color::Red }),
+            )),
+        ];
+        if !self.status.is_empty() {
+            info.push(Line::from(Span::styled(self.status.clone(), Style::default().fg(Color::Cyan))));
+        }
+        info.push(Line::from(""));
+        for note in &editor.notes {
+            info.push(Line::from(note.clone()));
+        }
+        if !editor.notes.is_empty() {
+            info.push(Line::from(""));
+        }
+        for help in tr(Text::EditorKeys).lines() {
+            info.push(Line::from(Span::styled(help, Style::default().fg(Color::DarkGray))));
+        }
+
+        let panel = Paragraph::new(info)
+            .block(self.panel(tr(Text::Position)))
+            .wrap(Wrap { trim: true });
+        frame.render_widget(panel, layout[1]);
+    }
+
+    fn draw_training(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let quiz = &self.quiz;
+
+        let layout = Layout::default()
+            .direction(LayoutDirection::Horizontal)
+            .constraints([Constraint::Ratio(3, 4), Constraint::Ratio(1, 4)])
+            .split(area);
+
+        self.render_board(
+            frame,
+            layout[0],
+            &Board::empty(),
+            BoardView {
+                cursor: Some(quiz.target),
+                hide_coordinates: true,
+                ..Default::default()
+            },
+            self.panel(tr(Text::Coordinates)),
+        );
+
+        let side = tr(Text::Side(if self.flipped { PieceColor::Black } else { PieceColor::White }));
+        let mut info = vec![
+            Line::from(tr(Text::NameTheSquare)),
+            Line::from(Span::styled(fill(Text::SeenFrom, &[&side]), Style::default().fg(Color::DarkGray))),
+            Line::from(""),
+            Line::from(vec![
+                Span::raw(">> "),
+                Span::styled(quiz.typed.clone(), Style::default().fg(Color::Yellow)),
+            ]),
+            Line::from(""),
+        ];
+        match &quiz.last {
+            Some((typed, name)) if typed == name => {
+                info.push(Line::from(Span::styled(fill(Text::IsRight, &[name]), Style::default().fg(Color::Green))));
+            }
+            Some((typed, name)) => {
+                info.push(Line::from(Span::styled(
+                    fill(Text::NotThat, &[name, typed]),
+                    Style::default().fg(Color::Red),
+                )));
+            }
+            None => info.push(Line::from("")),
+        }
+        info.push(Line::from(fill(Text::QuizScore, &[&quiz.correct, &quiz.asked])));
+        info.push(Line::from(fill(Text::QuizStreak, &[&quiz.streak, &quiz.best_streak])));
+        info.push(Line::from(""));
+        let flip = format!("{:<6}", self.config.keys.binding(Action::Flip).to_string());
+        for help in fill(Text::QuizKeys, &[&flip]).lines() {
+            info.push(Line::from(Span::styled(help.to_string(), Style::default().fg(Color::DarkGray))));
+        }
+
+        let panel = Paragraph::new(info)
+            .block(self.panel(tr(Text::Training)))
+            .wrap(Wrap { trim: true });
+        frame.render_widget(panel, layout[1]);
+    }
+
+    fn draw_history(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let layout = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)])
+            .split(area);
+
+        frame.render_widget(Clear, area);
+
+        let search = Paragraph::new(self.history_filter.render(tr(Text::SearchPrompt), Style::default().fg(Color::Yellow)))
+            .block(self.panel(tr(Text::History)));
+        frame.render_widget(search, layout[0]);
+
+        let filtered = self.filtered_games();
+        let mut lines: Vec<Line> = filtered
+            .iter()
+            .enumerate()
+            .map(|(pos, &idx)| {
+                let game = &self.saved_games[idx];
+                let selected = pos == self.history_index;
+                let color = match game.outcome() {
+                    "won" => Color::Green,
+                    "lost" => Color::Red,
+                    _ => Color::White,
+                };
+                let style = if selected {
+                    Style::default().fg(Color::Black).bg(color)
+                } else {
+                    Style::default().fg(color)
+                };
+                Line::from(Span::styled(
+                    format!("{} {}", if selected { "►" } else { " " }, Self::history_row(game)),
+                    style,
+                ))
+            })
+            .collect();
+        if lines.is_empty() {
+            lines.push(Line::from(Span::styled(
+                tr(if self.saved_games.is_empty() { Text::NoFinishedGames } else { Text::NoGamesMatch }),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        // keep the selection on screen
+        let visible = layout[1].height.saturating_sub(2) as usize;
+        let scroll = (self.history_index + 1).saturating_sub(visible);
+        let list = Paragraph::new(lines)
+            .block(self.panel(fill(Text::GamesFound, &[&filtered.len()])))
+            .scroll((scroll as u16, 0));
+        frame.render_widget(list, layout[1]);
+
+        let help = if self.status.is_empty() {
+            tr(Text::HistoryKeys).to_string()
+        } else {
+            self.status.clone()
+        };
+        let footer = Paragraph::new(Span::styled(help, Style::default().fg(Color::DarkGray)))
+            .block(self.panel(""));
+        frame.render_widget(footer, layout[2]);
+    }
+
+    fn draw_profile(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let layout = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints([
+                Constraint::Length(Difficulty::ALL.len() as u16 + 11),
+                Constraint::Min(0),
+            ])
+            .split(area);
+
+        frame.render_widget(Clear, area);
+
+        let profile = &self.profile;
+        let mut lines = vec![
+            Line::from(vec![
+                Span::raw(tr(Text::Rating)),
+                Span::styled(
+                    format!("{:.0}", profile.rating),
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    fill(Text::RatedGames, &[&profile.rated_games().count()]),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled(
+                tr(Text::LevelColumns),
+                Style::default().fg(Color::DarkGray),
+            )),
+        ];
+        for level in Difficulty::ALL.into_iter().filter(|level| level.rated()) {
+            let (won, drawn, lost) = profile.record_against(level);
+            let style = if level == self.config.bot.level {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            lines.push(Line::from(Span::styled(
+                format!("{:<8}  {:>6}  {:>4}  {:>5}  {:>4}", level.name(), level.rating(), won, drawn, lost),
+                style,
+            )));
+        }
+        lines.push(Line::from(""));
+        let model = &profile.model;
+        lines.push(Line::from(match model.blunder_rate() {
+            Some(rate) => fill(
+                Text::Blunders,
+                &[&format!("{:.1}", rate * 100.0), &model.moves_judged, &model.mistakes, &model.weak_spots()],
+            ),
+            None => tr(Text::NoBlunders).to_string(),
+        }));
+        let openings: Vec<String> = model
+            .favourite_openings(3)
+            .into_iter()
+            .map(|(name, games)| format!("{} ({})", name, games))
+            .collect();
+        lines.push(Line::from(fill(
+            Text::YourOpenings,
+            &[&if openings.is_empty() { tr(Text::NoneYet).to_string() } else { openings.join(", ") }],
+        )));
+        let odds: Vec<String> = Handicap::ALL
+            .into_iter()
+            .filter_map(|odds| profile.odds_gap(odds).map(|(gap, games)| format!("{} {:+.0} ({})", odds.name(), gap, games)))
+            .collect();
+        if !odds.is_empty() {
+            lines.push(Line::from(fill(Text::OddsWorth, &[&odds.join(", ")])));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            tr(Text::ProfileKeys),
+            Style::default().fg(Color::DarkGray),
+        )));
+        let summary = Paragraph::new(lines).block(self.panel(tr(Text::Profile)));
+        frame.render_widget(summary, layout[0]);
+
+        // rating after each game, starting from the initial rating
+        let mut points = vec![(0.0, Profile::default().rating as f64)];
+        points.extend(
+            profile
+                .rated_games()
+                .enumerate()
+                .map(|(idx, game)| ((idx + 1) as f64, game.rating_after as f64)),
+        );
+        let low = points.iter().map(|p| p.1).fold(f64::MAX, f64::min) - 50.0;
+        let high = points.iter().map(|p| p.1).fold(f64::MIN, f64::max) + 50.0;
+        let last = points.len().saturating_sub(1).max(1) as f64;
+
+        let dataset = Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Yellow))
+            .data(&points);
+        let chart = Chart::new(vec![dataset])
+            .block(self.panel(tr(Text::RatingHistory)))
+            .x_axis(
+                Axis::default()
+                    .title(tr(Text::GamesAxis))
+                    .style(Style::default().fg(Color::DarkGray))
+                    .bounds([0.0, last])
+                    .labels(["0".to_string(), format!("{}", last)]),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::DarkGray))
+                    .bounds([low, high])
+                    .labels([format!("{:.0}", low), format!("{:.0}", high)]),
+            );
+        frame.render_widget(chart, layout[1]);
+    }
+
+    fn draw_themes(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let layout = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints([Constraint::Length(12), Constraint::Min(0)])
+            .split(area);
+        let columns = Layout::default()
+            .direction(LayoutDirection::Horizontal)
+            .constraints(THEMES.iter().map(|_| Constraint::Ratio(1, THEMES.len() as u32)))
+            .split(layout[0]);
+
+        frame.render_widget(Clear, area);
+
+        let sample = sample_position();
+        let pieces = PIECE_SETS[self.gallery_pieces];
+        for (idx, theme) in THEMES.iter().enumerate() {
+            let selected = idx == self.gallery_theme;
+            let mut title = theme.name.to_string();
+            if theme.name == self.theme().name {
+                title.push_str(tr(Text::Current));
+            }
+            let border = if selected {
+                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            let preview = Paragraph::new(preview_lines(&sample, theme, pieces, self.config.ui.checkered)).block(
+                self.panel(title).border_style(border),
+            );
+            frame.render_widget(preview, columns[idx]);
+        }
+
+        let mut lines = vec![Line::from(tr(Text::PieceSet)), Line::from("")];
+        for (idx, set) in PIECE_SETS.iter().enumerate() {
+            let selected = idx == self.gallery_pieces;
+            let style = if selected {
+                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let current = if set.name() == self.piece_set().name() { tr(Text::Current) } else { "" };
+            lines.push(Line::from(Span::styled(
+                format!("{} {}{}", if selected { "►" } else { " " }, set.name(), current),
+                style,
+            )));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            self.status.clone(),
+            Style::default().fg(Color::Cyan),
+        )));
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            tr(Text::ThemesKeys),
+            Style::default().fg(Color::DarkGray),
+        )));
+
+        let options = Paragraph::new(lines)
+            .block(self.panel(tr(Text::Themes)));
+        frame.render_widget(options, layout[1]);
+    }
+
+    // how the setup screen's strength setting holds the bot back
+    fn strength_text(&self) -> String {
+        let Some(elo) = self.config.bot.target_elo else {
+            return tr(Text::FullSearch).to_string();
+        };
+        let limit = self.calibration.limit(elo as f32);
+        let guessed = if self.calibration.measured() { "" } else { tr(Text::RatedByGuesswork) };
+        fill(Text::StrengthCap, &[&limit.simulations, &format!("{:.1}", limit.max_loss / 100.0), &guessed])
+    }
+
+    // what a setup row is set to, and a word on it
+    fn setup_value(&self, row: SetupRow) -> (String, String) {
+        let level = self.config.bot.level;
+        let odds = Handicap::ALL[self.setup_index];
+        match row {
+            SetupRow::Side => {
+                let note = if self.setup_side == Side::Random { tr(Text::TossedEachGame) } else { "" };
+                (self.setup_side.name().to_string(), note.to_string())
+            }
+            SetupRow::Variant => {
+                let note = tr(if self.variant.rated() { Text::CountsTowardRating } else { Text::UnratedNote });
+                (self.variant.name().to_string(), note.to_string())
+            }
+            SetupRow::Level => {
+                let note = match self.config.bot.target_elo {
+                    Some(_) => tr(Text::StrengthStandsIn).to_string(),
+                    None => fill(Text::RatedAbout, &[&format!("{:.0}", level.rating())]),
+                };
+                (level.name().to_string(), note)
+            }
+            SetupRow::Strength => (
+                self.config.bot.target_elo.map_or(tr(Text::LevelWord).to_string(), |elo| format!("~{}", elo)),
+                self.strength_text(),
+            ),
+            SetupRow::TimePerMove => {
+                let time = self.config.engine.think_time(level);
+                let note = if self.config.bot.target_elo.is_some() {
+                    tr(Text::LongestThink).to_string()
+                } else if odds.think_time(time) != time {
+                    fill(Text::WithTheOdds, &[&format!("{:.1}", odds.think_time(time).as_secs_f32())])
+                } else {
+                    fill(Text::ThinkAtLevel, &[&level.name()])
+                };
+                (format!("{:.1}s", time.as_secs_f32()), note)
+            }
+            SetupRow::Odds => {
+                let note = match self.profile.odds_gap(odds) {
+                    Some((gap, games)) => fill(Text::OddsWorthNote, &[&odds.description(), &format!("{:+.0}", gap), &games]),
+                    None if odds == Handicap::None => odds.description().to_string(),
+                    None => fill(Text::NoOddsGames, &[&odds.description()]),
+                };
+                (odds.name().to_string(), note)
+            }
+            SetupRow::Personality => {
+                let personality = self.config.bot.personality;
+                (personality.name().to_string(), personality.description().to_string())
+            }
+            SetupRow::Opponent => (self.opponent.name().to_string(), self.opponent.description().to_string()),
+        }
+    }
+
+    fn draw_setup(&self, frame: &mut Frame) {
         let area = frame.area();
 
-        let about_text = vec![
-            Line::from("ChessRL"),
+        let mut lines = vec![
+            Line::from(fill(Text::NewGameOf, &[&self.variant.title()])),
             Line::from("-------------------"),
             Line::from(""),
-            Line::from("chess game with ML opponent"),
-            Line::from("featuring pure reinforcement learning."),
+        ];
+        for (idx, &row) in SetupRow::ALL.iter().enumerate() {
+            let selected = idx == self.setup_row;
+            let (label_style, value) = if selected {
+                (Style::default().fg(Color::Green).add_modifier(Modifier::BOLD), "◄ {} ►")
+            } else {
+                (Style::default().fg(Color::White), "  {}  ")
+            };
+            let (setting, note) = self.setup_value(row);
+            lines.push(Line::from(vec![
+                Span::styled(format!("{} {:<14}", if selected { "►" } else { " " }, row.label()), label_style),
+                Span::styled(
+                    format!("{:<14}", value.replace("{}", &setting)),
+                    Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("  {}", note), Style::default().fg(Color::DarkGray)),
+            ]));
+            lines.push(Line::from(""));
+        }
+        let about = match self.config.bot.target_elo {
+            Some(elo) => fill(Text::BotAtElo, &[&elo]),
+            None => fill(
+                Text::BotAtLevel,
+                &[
+                    &self.config.bot.level.name(),
+                    &format!(
+                        "{:.1}",
+                        Handicap::ALL[self.setup_index].think_time(self.config.engine.think_time(self.config.bot.level)).as_secs_f32()
+                    ),
+                ],
+            ),
+        };
+        lines.push(Line::from(Span::styled(about, Style::default().fg(Color::Cyan))));
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            tr(Text::SetupKeys),
+            Style::default().fg(Color::DarkGray),
+        )));
+        if !self.status.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(self.status.clone(), Style::default().fg(Color::Yellow))));
+        }
+
+        let setup = Paragraph::new(lines)
+            .block(self.panel(tr(Text::GameSetup)));
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(setup, area);
+    }
+
+    fn draw_settings(&self, frame: &mut Frame) {
+        let area = frame.area();
+
+        let mut lines = vec![
+            Line::from(tr(Text::KeyBindings)),
+            Line::from("-------------------"),
             Line::from(""),
-            Line::from("Commands:"),
-            Line::from("e2 e4  - Move a piece from e2 to e4"),
-            Line::from("ESC - Return to menu"),
-            Line::from("Q   - Quit game"),
+        ];
+        for (idx, action) in Action::ALL.iter().enumerate() {
+            let selected = idx == self.settings_index;
+            let binding = if selected && self.capturing_key {
+                "...".to_string()
+            } else {
+                self.config.keys.binding(*action).to_string()
+            };
+            let style = if selected {
+                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{} {:<16}", if selected { "►" } else { " " }, action.name()), style),
+                Span::styled(format!("{:<12}", binding), Style::default().fg(Color::Yellow)),
+                Span::styled(action.description(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        lines.extend([
             Line::from(""),
-            Line::from("The AI learns as the game is played,"),
-            Line::from("improving its strategy over time."),
+            Line::from(tr(Text::Engine)),
+            Line::from("-------------------"),
             Line::from(""),
-            Line::from("Created by @frgmt0"),
+        ]);
+        let engine_rows = [
+            (
+                "contempt",
+                self.config.bot.contempt.to_string(),
+                tr(Text::ContemptAbout),
+            ),
+            (
+                "multipv",
+                self.config.engine.multi_pv().to_string(),
+                tr(Text::MultiPvAbout),
+            ),
+            (
+                "weights",
+                self.wanted_weights().unwrap_or_else(|| tr(Text::BuiltIn).to_string()),
+                tr(Text::WeightsAbout),
+            ),
+            (
+                "piece values",
+                self.config.bot.piece_values.map_or("weights", PieceValues::name).to_string(),
+                tr(Text::PieceValuesAbout),
+            ),
         ];
+        let bell_rows = HookEvent::ALL.map(|event| {
+            let value = tr(if self.config.hooks.rings(event) { Text::Bell } else { Text::Off });
+            (event.name(), value.to_string(), event.description())
+        });
+        for (row, (name, value, description)) in engine_rows.into_iter().chain(bell_rows).enumerate() {
+            if row == ENGINE_ROWS {
+                lines.extend([
+                    Line::from(""),
+                    Line::from(tr(Text::Sounds)),
+                    Line::from("-------------------"),
+                    Line::from(""),
+                ]);
+            }
+            let selected = self.settings_index == Action::ALL.len() + row;
+            let style = if selected {
+                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{} {:<16}", if selected { "►" } else { " " }, name), style),
+                Span::styled(format!(" {:<11} ", value), Style::default().fg(Color::Yellow)),
+                Span::styled(description, Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            self.status.clone(),
+            Style::default().fg(Color::Cyan),
+        )));
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            tr(Text::SettingsKeys),
+            Style::default().fg(Color::DarkGray),
+        )));
+        if let Some(path) = Config::path() {
+            lines.push(Line::from(Span::styled(
+                fill(Text::SavedTo, &[&path.display()]),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+
+        let settings = Paragraph::new(lines)
+            .block(self.panel(tr(Text::Settings)));
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(settings, area);
+    }
+
+    fn draw_about(&self, frame: &mut Frame) {
+        let area = frame.area();
+
+        let mut about_text: Vec<Line> = vec![Line::from("ChessRL"), Line::from("-------------------"), Line::from("")];
+        about_text.extend(tr(Text::AboutIntro).lines().map(Line::from));
+        for action in Action::ALL {
+            about_text.push(Line::from(format!(
+                "{} - {}",
+                self.config.keys.binding(action),
+                action.description()
+            )));
+        }
+        about_text.push(Line::from(""));
+        about_text.extend(tr(Text::AboutOutro).lines().map(Line::from));
+        about_text.extend([Line::from(""), Line::from("Created by @frgmt0")]);
 
         let about_block = Paragraph::new(about_text)
             .style(Style::default().fg(Color::Cyan))
-            .block(Block::default().borders(Borders::ALL).title("About"))
+            .block(self.panel(tr(Text::About)))
             .alignment(ratatui::layout::Alignment::Center);
 
         frame.render_widget(Clear, area);
         frame.render_widget(about_block, area);
     }
 }
+
+// a few moves into an Italian, so the gallery shows both sides and some open squares
+fn sample_position() -> Board {
+    let mut board = Board::new();
+    for mv in ["e2e4", "e7e5", "g1f3", "b8c6", "f1c4", "f8c5"] {
+        if let Some((from, to)) = parse_move_text(&[mv]) {
+            board.move_piece(from, to);
+        }
+    }
+    board
+}
+
+// compact board for the gallery: one character per square, no grid
+fn preview_lines(board: &Board, theme: &Theme, pieces: PieceSet, checkered: bool) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for rank in 0..8 {
+        let mut row = vec![Span::styled(format!("{} ", 8 - rank), Style::default().fg(theme.label))];
+        for file in 0..8 {
+            let piece = board.get_piece((rank, file));
+            if checkered {
+                let symbol = piece.map_or(' ', |piece| pieces.symbol(piece));
+                row.push(Span::styled(format!(" {}", symbol), theme.square_style((rank + file) % 2 == 1, piece)));
+                continue;
+            }
+            row.push(match piece {
+                Some(piece) => Span::styled(
+                    format!(" {}", pieces.symbol(piece)),
+                    Style::default().fg(theme.piece_color(piece)),
+                ),
+                None => Span::styled(" ·", Style::default().fg(theme.grid)),
+            });
+        }
+        lines.push(Line::from(row));
+    }
+    lines.push(Line::from(Span::styled(
+        "   a b c d e f g h",
+        Style::default().fg(theme.label),
+    )));
+    lines
+}
+
+// "e", "e4" but not "z" or "e9"
+fn is_square_prefix(text: &str) -> bool {
+    let mut chars = text.chars();
+    let file_ok = chars.next().is_some_and(|c| ('a'..='h').contains(&c));
+    let rank_ok = chars.next().is_none_or(|c| ('1'..='8').contains(&c));
+    file_ok && rank_ok && chars.next().is_none()
+}
+
+// "e2e4" or "e2 e4"
+// the replay viewer's board and its moves panel
+fn replay_layout(area: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
+        .direction(LayoutDirection::Horizontal)
+        .constraints([Constraint::Ratio(3, 4), Constraint::Ratio(1, 4)])
+        .split(area)
+}
+
+fn parse_move_text(args: &[&str]) -> Option<MovePair> {
+    match args {
+        // four bytes, not four letters: a letter outside ASCII can straddle the middle
+        [both] if both.len() == 4 => Some((parse_coordinate(both.get(..2)?)?, parse_coordinate(both.get(2..)?)?)),
+        [from, to] => Some((parse_coordinate(from)?, parse_coordinate(to)?)),
+        _ => None,
+    }
+}
+
+// a refused move in words, e.g. "The bishop can't jump over the pawn on d3"
+fn rejection_text(reason: RejectionReason, variant: &str) -> String {
+    let on = |piece: Piece, square| fill(Text::PieceOn, &[&tr(Text::ThePiece(piece.piece_type)), &coordinate_to_string(square)]);
+    match reason {
+        RejectionReason::NoPiece { square } => sentence(Text::NoPieceOn, &[&coordinate_to_string(square)]),
+        RejectionReason::NotYourPiece { piece } => sentence(Text::BotsPiece, &[&tr(Text::Piece(piece.piece_type))]),
+        RejectionReason::SameSquare => tr(Text::SameSquare).to_string(),
+        RejectionReason::OwnPieceThere { piece, square } => {
+            sentence(Text::OwnPieceThere, &[&tr(Text::Piece(piece.piece_type)), &coordinate_to_string(square)])
+        }
+        RejectionReason::WrongShape { piece } => tr(match piece {
+            PieceType::King => Text::KingShape,
+            PieceType::Queen => Text::QueenShape,
+            PieceType::Rook => Text::RookShape,
+            PieceType::Bishop => Text::BishopShape,
+            PieceType::Knight => Text::KnightShape,
+            PieceType::Pawn => Text::PawnShape,
+        })
+        .to_string(),
+        RejectionReason::Blocked { piece, blocker, square } => {
+            sentence(Text::Blocked, &[&tr(Text::ThePiece(piece)), &on(blocker, square)])
+        }
+        RejectionReason::PawnBlocked { blocker, square } => sentence(Text::PawnBlocked, &[&on(blocker, square)]),
+        RejectionReason::PawnCapturesOnly => tr(Text::PawnCapturesOnly).to_string(),
+        RejectionReason::PawnDoubleStep => tr(Text::PawnDoubleStep).to_string(),
+        RejectionReason::StillInCheck { attacker, square } => sentence(Text::StillInCheck, &[&on(attacker, square)]),
+        RejectionReason::LeavesKingInCheck { attacker, square } => {
+            sentence(Text::LeavesKingInCheck, &[&on(attacker, square)])
+        }
+        RejectionReason::VariantRule => sentence(Text::VariantRule, &[&variant]),
+    }
+}
+
+// the score, main line and best few moves of a search of `board`
+fn analysis_notes(board: &Board, color: PieceColor, result: &SearchResult) -> Vec<String> {
+    let line: Vec<String> = result.pv.iter().take(8).map(|mv| coordinates(*mv)).collect();
+    let mut notes = vec![
+        sentence(Text::SideToMoveScore, &[&tr(Text::Side(color)), &format_score(white_score(result.score, color))]),
+        fill(Text::Line, &[&line.join(" ")]),
+        String::new(),
+    ];
+    for branch in result.tree.iter().take(5) {
+        notes.push(fill(
+            Text::BranchVisits,
+            &[&format!("{:<8}", san(board, branch.mv)), &format_score(white_score(branch.value, color)), &branch.visits],
+        ));
+    }
+    notes
+}
+
+
+// how much of its time the search has used, as a bar unless that would
+// be read out to a screen reader
+fn time_bar(elapsed: Duration, allotted: Duration, accessible: bool) -> Line<'static> {
+    const CELLS: usize = 10;
+    if accessible {
+        return Line::from(format!(
+            "{}{}",
+            tr(Text::TimeUsed),
+            fill(Text::SecondsOf, &[&format!("{:.1}", elapsed.as_secs_f32()), &format!("{:.1}", allotted.as_secs_f32())])
+        ));
+    }
+    let used = (elapsed.as_secs_f32() / allotted.as_secs_f32().max(0.001)).min(1.0);
+    let filled = (used * CELLS as f32).round() as usize;
+    Line::from(vec![
+        Span::raw(tr(Text::TimeUsed)),
+        Span::styled("█".repeat(filled), Style::default().fg(Color::Yellow)),
+        Span::styled("░".repeat(CELLS - filled), Style::default().fg(Color::DarkGray)),
+        Span::raw(format!(" {:.1}/{:.1}s", elapsed.as_secs_f32(), allotted.as_secs_f32())),
+    ])
+}
+
+// engine scores are roughly in centipawns
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "aé1" is four bytes with the é across the middle: refused, not a panic
+    #[test]
+    fn move_text_with_other_alphabets() {
+        assert_eq!(parse_move_text(&["e2e4"]), Some(((6, 4), (4, 4))));
+        assert_eq!(parse_move_text(&["aé1"]), None);
+        assert_eq!(parse_move_text(&["é2", "e4"]), None);
+        assert_eq!(parse_move_text(&["ñ2e4"]), None);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::game::board::Board;
+use crate::ui::theme::{PieceSet, Theme};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Span;
+use ratatui::widgets::Widget;
+
+// columns left of the grid, for the rank labels
+const GUTTER: u16 = 3;
+
+// what the board shows besides the pieces
+#[derive(Default)]
+pub struct BoardView<'a> {
+    pub fog: Option<[[bool; 8]; 8]>,
+    // a square to frame, for the editor and the coordinate quiz
+    pub cursor: Option<(usize, usize)>,
+    // squares to tint, like the hanging pieces overlay
+    pub marks: &'a [((usize, usize), Color)],
+    pub hide_pieces: bool,
+    pub hide_coordinates: bool,
+}
+
+// a square's size in terminal cells, the grid around it not counted
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SquareSize {
+    pub width: u16,
+    pub height: u16,
+}
+
+impl SquareSize {
+    // labels and grid included
+    pub fn board_height(self) -> u16 {
+        2 + 8 * (self.height + 1)
+    }
+}
+
+// the board drawn cell by cell: every square, label and grid line goes at
+// a position worked out from the square size, so a glyph of any width
+// lands centered in its own square and can't push the rest of the row along
+pub struct BoardWidget<'a> {
+    pub board: &'a Board,
+    pub view: BoardView<'a>,
+    pub theme: &'static Theme,
+    pub pieces: PieceSet,
+    pub flipped: bool,
+    pub square: SquareSize,
+    pub checkered: bool,
+}
+
+impl Widget for BoardWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let SquareSize { width, height } = self.square;
+        let grid = Style::default().fg(self.theme.grid);
+        let label = Style::default().fg(self.theme.label);
+        // the top left corner of the grid, and where each square starts
+        let (left, top) = (area.x + GUTTER, area.y + 1);
+        let column_x = |col: u16| left + 1 + col * (width + 1);
+        let row_y = |row: u16| top + 1 + row * (height + 1);
+
+        if !self.view.hide_coordinates {
+            for col in 0..8u16 {
+                let file = if self.flipped { 7 - col } else { col };
+                let name = ((b'a' + file as u8) as char).to_string();
+                put(buf, area, column_x(col) + (width - 1) / 2, area.y, &name, label);
+            }
+            for row in 0..8u16 {
+                let rank = if self.flipped { row + 1 } else { 8 - row };
+                put(buf, area, area.x, row_y(row) + (height - 1) / 2, &rank.to_string(), label);
+            }
+        }
+
+        // the horizontal lines, then the verticals between them
+        let line = |first: &str, middle: &str, last: &str| {
+            let run = "─".repeat(width as usize);
+            format!("{}{}{}", first, [run.as_str(); 8].join(middle), last)
+        };
+        put(buf, area, left, top, &line("┌", "┬", "┐"), grid);
+        for row in 0..8u16 {
+            let below = row_y(row) + height;
+            let (first, middle, last) = if row == 7 { ("└", "┴", "┘") } else { ("├", "┼", "┤") };
+            put(buf, area, left, below, &line(first, middle, last), grid);
+            for y in row_y(row)..below {
+                for col in 0..=8u16 {
+                    put(buf, area, left + col * (width + 1), y, "│", grid);
+                }
+            }
+        }
+
+        for row in 0..8u16 {
+            for col in 0..8u16 {
+                let rank = if self.flipped { 7 - row } else { row } as usize;
+                let file = if self.flipped { 7 - col } else { col } as usize;
+                let (x, y) = (column_x(col), row_y(row));
+                let middle = y + (height - 1) / 2;
+                let blank = " ".repeat(width as usize);
+
+                if self.view.fog.is_some_and(|visible| !visible[rank][file]) {
+                    for y in y..y + height {
+                        put(buf, area, x, y, &"░".repeat(width as usize), Style::default().fg(Color::DarkGray));
+                    }
+                    continue;
+                }
+
+                let piece = self.board.get_piece((rank, file)).filter(|_| !self.view.hide_pieces);
+                let dark = (rank + file) % 2 == 1;
+                let mut style = if self.checkered {
+                    self.theme.square_style(dark, piece)
+                } else {
+                    Style::default().fg(piece.map_or(Color::DarkGray, |p| self.theme.piece_color(p)))
+                };
+                if let Some(&(_, mark)) = self.view.marks.iter().find(|(pos, _)| *pos == (rank, file)) {
+                    style = style.bg(mark);
+                }
+                let framed = self.view.cursor == Some((rank, file));
+                if framed {
+                    style = style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+                }
+                for y in y..y + height {
+                    put(buf, area, x, y, &blank, style);
+                }
+
+                let glyph = piece.map_or(" ".to_string(), |p| self.pieces.symbol(p).to_string());
+                let text = if framed && width >= 3 { format!("[{}]", glyph) } else { glyph };
+                let text_width = Span::raw(text.as_str()).width() as u16;
+                put(buf, area, x + width.saturating_sub(text_width) / 2, middle, &text, style);
+            }
+        }
+    }
+}
+
+// `text` at (x, y), cut off at the edge of `area`
+fn put(buf: &mut Buffer, area: Rect, x: u16, y: u16, text: &str, style: Style) {
+    if x < area.right() && y < area.bottom() {
+        buf.set_stringn(x, y, text, (area.right() - x) as usize, style);
+    }
+}
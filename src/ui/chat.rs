@@ -0,0 +1,71 @@
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+
+// longer than anyone types between moves
+const MAX_CHARS: usize = 200;
+// what fits under the whose-move line of the opponent panel
+pub const PANEL_ROWS: usize = 4;
+
+// a network game's chat: everything said so far, and what of it isn't in
+// the game record yet. said before the first move, it waits for one to be
+// a comment on
+#[derive(Default)]
+pub struct Chat {
+    lines: Vec<(String, String)>,
+    unfiled: Vec<String>,
+}
+
+impl Chat {
+    pub fn add(&mut self, from: &str, text: &str) {
+        self.lines.push((from.to_string(), text.to_string()));
+        self.unfiled.push(format!("{}: {}", from, text));
+    }
+
+    // the lines to put in the record, as one PGN comment
+    pub fn take_unfiled(&mut self) -> Option<String> {
+        if self.unfiled.is_empty() {
+            return None;
+        }
+        Some(self.unfiled.drain(..).collect::<Vec<_>>().join(" | "))
+    }
+
+    // what was said over moves since taken back, to go on the last move left
+    pub fn refile(&mut self, comment: String) {
+        self.unfiled.insert(0, comment);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.unfiled.clear();
+    }
+
+    // the newest `rows` lines, the other player's in `accent`
+    pub fn panel_lines(&self, rows: usize, accent: Color) -> Vec<Line<'static>> {
+        let start = self.lines.len().saturating_sub(rows);
+        self.lines[start..]
+            .iter()
+            .map(|(from, text)| {
+                let color = if from == "You" { Color::Gray } else { accent };
+                Line::from(vec![
+                    Span::styled(format!("{}: ", from), Style::default().fg(color)),
+                    Span::styled(text.clone(), Style::default().fg(Color::White)),
+                ])
+            })
+            .collect()
+    }
+}
+
+// what's left of a line of chat once it's fit for a PGN comment and a line
+// of the protocol: no braces, no control characters, not too long
+pub fn clean(text: &str) -> String {
+    let text: String = text
+        .chars()
+        .filter(|c| !c.is_control() && *c != '{' && *c != '}')
+        .take(MAX_CHARS)
+        .collect();
+    text.trim().to_string()
+}
@@ -0,0 +1,70 @@
+use crate::ui::i18n::{fill, tr, Text};
+use std::io::{self, Write};
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+// the system clipboard when there is one to reach, otherwise the terminal's
+// through an OSC 52 escape sequence, which works over ssh too. the system
+// clipboard is kept open because on X11 what was copied goes away with it
+pub struct Clipboard {
+    system: Option<arboard::Clipboard>,
+}
+
+impl Clipboard {
+    pub fn new() -> Self {
+        // over ssh the system clipboard is the server's, not the one in
+        // front of the player
+        let remote = std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some();
+        let system = if remote { None } else { arboard::Clipboard::new().ok() };
+        Clipboard { system }
+    }
+
+    // puts `text` on the clipboard and says where it went
+    pub fn copy(&mut self, text: &str) -> Result<&'static str, String> {
+        if let Some(system) = &mut self.system {
+            if system.set_text(text).is_ok() {
+                return Ok(tr(Text::TheClipboard));
+            }
+        }
+        osc52(text).map_err(|e| fill(Text::CouldntCopy, &[&e]))?;
+        Ok(tr(Text::TheTerminalsClipboard))
+    }
+
+    // terminals rarely let programs read their clipboard back, so pasting
+    // needs the system one
+    pub fn paste(&mut self) -> Result<String, String> {
+        let Some(system) = &mut self.system else {
+            return Err(tr(Text::NoClipboard).to_string());
+        };
+        system.get_text().map_err(|e| fill(Text::CouldntPaste, &[&e]))
+    }
+}
+
+// asks the terminal to set its clipboard. inside tmux the sequence is
+// passed through to the terminal tmux runs in
+fn osc52(text: &str) -> io::Result<()> {
+    let sequence = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+    let sequence = if std::env::var_os("TMUX").is_some() {
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+    } else {
+        sequence
+    };
+    let mut out = io::stdout();
+    out.write_all(sequence.as_bytes())?;
+    out.flush()
+}
+
+fn base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,53 @@
+use crate::ui::i18n::Text;
+
+// the words typed into the command box, with how they're used, for the help
+// overlay and for telling a command from the start of a move
+pub struct Command {
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub description: Text,
+}
+
+const fn command(name: &'static str, usage: &'static str, description: Text) -> Command {
+    Command { name, usage, description }
+}
+
+pub const COMMANDS: [Command; 28] = [
+    command("new", "new [standard|fog|atomic|koth|horde|3check]", Text::NewCommand),
+    command("resign", "resign", Text::ResignCommand),
+    command("draw", "draw", Text::DrawCommand),
+    command("switch", "switch", Text::SwitchCommand),
+    command("why-not", "why-not <from> <to>", Text::WhyNotCommand),
+    command("replay", "replay [12|12...]", Text::ReplayCommand),
+    command("level", "level <easy|medium|hard|expert|sparring>", Text::LevelCommand),
+    command("elo", "elo <400-2000|off>", Text::EloCommand),
+    command("personality", "personality <style>", Text::PersonalityCommand),
+    command("opponent", "opponent [name]", Text::OpponentCommand),
+    command("drill", "drill [kq|kr|kp|kp-defend]", Text::DrillCommand),
+    command("speedrun", "speedrun [seed|scores]", Text::SpeedrunCommand),
+    command("tab", "tab [new|analysis|close|<n>]", Text::TabCommand),
+    command("go", "go [infinite|nodes <n>|depth <n>|movetime <ms>]", Text::GoCommand),
+    command("stop", "stop", Text::StopCommand),
+    command("host", "host [port]", Text::HostCommand),
+    command("join", "join <host>[:port]", Text::JoinCommand),
+    command("takeback", "takeback", Text::TakebackCommand),
+    command("accept", "accept", Text::AcceptCommand),
+    command("decline", "decline", Text::DeclineCommand),
+    command("blindfold", "blindfold", Text::BlindfoldCommand),
+    command("board", "board", Text::BoardCommand),
+    command("accessible", "accessible", Text::AccessibleCommand),
+    command("diagram", "diagram [txt]", Text::DiagramCommand),
+    command("heatmap", "heatmap [piece]", Text::HeatmapCommand),
+    command("watch", "watch [style] [style]", Text::WatchCommand),
+    command("arena", "arena", Text::ArenaCommand),
+    command("dump", "dump search", Text::DumpCommand),
+];
+
+pub fn is_command(word: &str) -> bool {
+    COMMANDS.iter().any(|command| command.name == word)
+}
+
+// could `prefix` still become a command as more is typed
+pub fn could_be_command(prefix: &str) -> bool {
+    COMMANDS.iter().any(|command| command.name.starts_with(prefix))
+}
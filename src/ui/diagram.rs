@@ -0,0 +1,166 @@
+use crate::game::board::Board;
+use crate::game::movement::Move;
+use crate::game::piece::Color;
+use crate::ui::theme::PieceSet;
+use crate::utils::{data_dir, pgn_date_time};
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+// pixels per square, and the margin the coordinates go in
+const SQUARE: usize = 45;
+const MARGIN: usize = 20;
+const LIGHT: &str = "#f0d9b5";
+const DARK: &str = "#b58863";
+const LAST_MOVE: &str = "#cdd26a";
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum DiagramFormat {
+    Svg,
+    Text,
+}
+
+impl DiagramFormat {
+    pub fn by_name(name: &str) -> Option<DiagramFormat> {
+        match name {
+            "svg" => Some(DiagramFormat::Svg),
+            "txt" | "text" => Some(DiagramFormat::Text),
+            _ => None,
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            DiagramFormat::Svg => "svg",
+            DiagramFormat::Text => "txt",
+        }
+    }
+}
+
+// a position to draw, seen from white's side unless flipped
+pub struct Diagram<'a> {
+    pub board: &'a Board,
+    pub to_move: Color,
+    pub fen: String,
+    pub last_move: Option<Move>,
+    pub flipped: bool,
+}
+
+impl Diagram<'_> {
+    pub fn render(&self, format: DiagramFormat) -> String {
+        match format {
+            DiagramFormat::Svg => self.svg(),
+            DiagramFormat::Text => self.text(),
+        }
+    }
+
+    // board squares top to bottom and left to right as drawn
+    fn squares(&self) -> impl Iterator<Item = (usize, usize, (usize, usize))> + '_ {
+        (0..8).flat_map(move |row| {
+            (0..8).map(move |col| {
+                let square = if self.flipped { (7 - row, 7 - col) } else { (row, col) };
+                (row, col, square)
+            })
+        })
+    }
+
+    // the board and pieces as shapes, pieces drawn with the solid glyphs so
+    // both sides are the same outline in different fills
+    fn svg(&self) -> String {
+        let size = SQUARE * 8 + MARGIN * 2;
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{0}" viewBox="0 0 {0} {0}">"#,
+            size
+        );
+        let _ = writeln!(out, r##"<rect width="{0}" height="{0}" fill="#312e2b"/>"##, size);
+        let moved = self.last_move.map(|mv| [mv.from, mv.to]);
+        for (row, col, square) in self.squares() {
+            let fill = if moved.is_some_and(|squares| squares.contains(&square)) {
+                LAST_MOVE
+            } else if (square.0 + square.1) % 2 == 0 {
+                LIGHT
+            } else {
+                DARK
+            };
+            let (x, y) = (MARGIN + col * SQUARE, MARGIN + row * SQUARE);
+            let _ = writeln!(out, r#"<rect x="{}" y="{}" width="{2}" height="{2}" fill="{3}"/>"#, x, y, SQUARE, fill);
+            if let Some(piece) = self.board.get_piece(square) {
+                let (fill, stroke) = match piece.color {
+                    Color::White => ("#ffffff", "#000000"),
+                    Color::Black => ("#000000", "#ffffff"),
+                };
+                let glyph = PieceSet::Filled.symbol(piece);
+                let _ = writeln!(
+                    out,
+                    r#"<text x="{}" y="{}" font-size="{}" text-anchor="middle" dominant-baseline="central" font-family="DejaVu Sans, Segoe UI Symbol, sans-serif" fill="{}" stroke="{}" stroke-width="1">{}</text>"#,
+                    x + SQUARE / 2,
+                    y + SQUARE / 2,
+                    SQUARE * 4 / 5,
+                    fill,
+                    stroke,
+                    glyph
+                );
+            }
+        }
+        for idx in 0..8 {
+            let (file, rank) = if self.flipped { (7 - idx, idx + 1) } else { (idx, 8 - idx) };
+            let middle = MARGIN + idx * SQUARE + SQUARE / 2;
+            for y in [MARGIN / 2, size - MARGIN / 2] {
+                let _ = writeln!(out, r#"{}{}</text>"#, label(middle, y), (b'a' + file as u8) as char);
+            }
+            for x in [MARGIN / 2, size - MARGIN / 2] {
+                let _ = writeln!(out, r#"{}{}</text>"#, label(x, middle), rank);
+            }
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+
+    // letters in a box, white in capitals, with the FEN underneath to paste
+    // into anything that reads one
+    fn text(&self) -> String {
+        let mut out = String::from("  +------------------------+\n");
+        for (row, col, square) in self.squares() {
+            if col == 0 {
+                let rank = if self.flipped { row + 1 } else { 8 - row };
+                let _ = write!(out, "{} |", rank);
+            }
+            let c = self.board.get_piece(square).map_or('.', |piece| piece.letter());
+            let _ = write!(out, " {} ", c);
+            if col == 7 {
+                out.push_str("|\n");
+            }
+        }
+        out.push_str("  +------------------------+\n");
+        let files = if self.flipped { "hgfedcba" } else { "abcdefgh" };
+        let _ = writeln!(out, "   {}", files.chars().map(|f| format!(" {} ", f)).collect::<String>().trim_end());
+        let _ = writeln!(out, "\n{} to move", if self.to_move == Color::White { "White" } else { "Black" });
+        let _ = writeln!(out, "FEN: {}", self.fen);
+        out
+    }
+}
+
+fn label(x: usize, y: usize) -> String {
+    format!(
+        r##"<text x="{}" y="{}" font-size="12" text-anchor="middle" dominant-baseline="central" font-family="sans-serif" fill="#bababa">"##,
+        x, y
+    )
+}
+
+// writes the diagram to its own file in the data directory and returns the path
+pub fn save(diagram: &Diagram, format: DiagramFormat) -> io::Result<PathBuf> {
+    let dir = data_dir()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?
+        .join("diagrams");
+    fs::create_dir_all(&dir)?;
+    let unix_secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let (date, time) = pgn_date_time(unix_secs);
+    let path = dir.join(format!("{}-{}.{}", date.replace('.', ""), time.replace(':', ""), format.extension()));
+    fs::write(&path, diagram.render(format))?;
+    Ok(path)
+}
@@ -0,0 +1,153 @@
+use crate::ui::i18n::{tr, Text};
+use crate::ui::input::TextInput;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::Frame;
+
+const MIN_WIDTH: u16 = 30;
+const MAX_WIDTH: u16 = 60;
+
+// what a dialog was opened for, acted on once it's answered
+#[derive(Clone, PartialEq, Debug)]
+pub enum Purpose {
+    Quit,
+    Resign,
+    // the `new` command, with the variant it named
+    NewGame(Option<String>),
+    // ENTER on the game setup screen
+    StartFromSetup,
+    // the autosaved game, offered on startup
+    Resume,
+    // a command from the palette that needs more typed after its name
+    Command(&'static str),
+}
+
+pub enum Reply {
+    Pending,
+    Chose(usize),
+    // ENTER in a prompt, with what was typed
+    Entered(String),
+    // esc: the dialog goes away with nothing chosen
+    Dismissed,
+}
+
+// a question over the screen that takes every key until it's answered:
+// a message and a row of choices, the first letter of each picking it, or
+// a message and a line to type the answer on
+pub struct Dialog {
+    pub title: String,
+    pub message: String,
+    pub choices: Vec<&'static str>,
+    pub selected: usize,
+    pub purpose: Purpose,
+    // Some for a prompt, which has no choices
+    pub input: Option<TextInput>,
+}
+
+impl Dialog {
+    // Yes is choice 0. No is picked to start with, so a stray ENTER
+    // doesn't throw a game away
+    pub fn confirm(title: &str, message: String, purpose: Purpose) -> Self {
+        let choices = vec![tr(Text::Yes), tr(Text::No)];
+        Dialog { title: title.to_string(), message, choices, selected: 1, purpose, input: None }
+    }
+
+    // asks for a line of text; ENTER gives it back, empty or not
+    pub fn prompt(title: &str, message: String, purpose: Purpose) -> Self {
+        Dialog { title: title.to_string(), message, choices: Vec::new(), selected: 0, purpose, input: Some(TextInput::new()) }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Reply {
+        if let Some(input) = &mut self.input {
+            match key.code {
+                KeyCode::Enter => return Reply::Entered(input.submit()),
+                KeyCode::Esc => return Reply::Dismissed,
+                _ => {
+                    input.handle_key(key);
+                }
+            }
+            return Reply::Pending;
+        }
+        match key.code {
+            KeyCode::Left | KeyCode::BackTab => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Right | KeyCode::Tab => self.selected = (self.selected + 1).min(self.choices.len() - 1),
+            KeyCode::Enter => return Reply::Chose(self.selected),
+            KeyCode::Esc => return Reply::Dismissed,
+            KeyCode::Char(c) => {
+                let letter = c.to_ascii_lowercase();
+                if let Some(idx) = self.choices.iter().position(|choice| choice.to_lowercase().starts_with(letter)) {
+                    return Reply::Chose(idx);
+                }
+            }
+            _ => {}
+        }
+        Reply::Pending
+    }
+
+    // centered over whatever is on screen
+    pub fn render(&self, frame: &mut Frame, accent: Color) {
+        let screen = frame.area();
+        let width = (self.message.chars().count() as u16 + 4).clamp(MIN_WIDTH, MAX_WIDTH).min(screen.width);
+        let rows = (self.message.chars().count() as u16).div_ceil(width.saturating_sub(4).max(1));
+        // a prompt has its line to type on and a hint under it
+        let extra = if self.input.is_some() { 2 } else { 0 };
+        let height = (rows + 4 + extra).min(screen.height);
+        let area = Rect {
+            x: screen.x + (screen.width - width) / 2,
+            y: screen.y + (screen.height - height) / 2,
+            width,
+            height,
+        };
+
+        let mut buttons = Vec::new();
+        for (idx, choice) in self.choices.iter().enumerate() {
+            let style = if idx == self.selected {
+                Style::default().fg(Color::Black).bg(accent).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            buttons.push(Span::styled(format!(" {} ", choice), style));
+            buttons.push(Span::raw("  "));
+        }
+        let mut lines = vec![Line::from(self.message.clone()), Line::from("")];
+        match &self.input {
+            Some(input) => {
+                lines.push(input.render("> ", Style::default().fg(accent)));
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(tr(Text::PromptKeys), Style::default().fg(Color::DarkGray))));
+            }
+            None => lines.push(Line::from(buttons).centered()),
+        }
+        let block = Block::default()
+            .title(self.title.clone())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(accent));
+        frame.render_widget(Clear, area);
+        frame.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: true }), area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    // letters go in the line rather than picking a choice, and ENTER
+    // hands the line back
+    #[test]
+    fn prompts_take_a_line() {
+        let mut dialog = Dialog::prompt("level", "level <name>".to_string(), Purpose::Command("level"));
+        for c in "yes".chars() {
+            assert!(matches!(dialog.handle_key(key(KeyCode::Char(c))), Reply::Pending));
+        }
+        assert!(matches!(dialog.handle_key(key(KeyCode::Enter)), Reply::Entered(text) if text == "yes"));
+        assert!(matches!(dialog.handle_key(key(KeyCode::Esc)), Reply::Dismissed));
+    }
+}
@@ -0,0 +1,71 @@
+use crate::game::board::Board;
+use crate::game::piece::{Color, Piece};
+use crate::game::validation::position_problem;
+
+// a position being set up square by square before it's played or analyzed
+pub struct Editor {
+    pub board: Board,
+    pub cursor: (usize, usize),
+    pub to_move: Color,
+    // the engine's take on the position, after `a`
+    pub notes: Vec<String>,
+}
+
+impl Editor {
+    pub fn new(board: Board, to_move: Color) -> Self {
+        Editor {
+            board,
+            cursor: (7, 4),
+            to_move,
+            notes: Vec::new(),
+        }
+    }
+
+    // the cursor stops at the edges rather than wrapping
+    pub fn move_cursor(&mut self, ranks: isize, files: isize) {
+        let (rank, file) = self.cursor;
+        self.cursor = (
+            rank.saturating_add_signed(ranks).min(7),
+            file.saturating_add_signed(files).min(7),
+        );
+    }
+
+    // a FEN letter puts that piece under the cursor
+    pub fn place(&mut self, letter: char) -> bool {
+        let Some(piece) = Piece::from_letter(letter) else {
+            return false;
+        };
+        self.board.put(self.cursor, piece);
+        self.notes.clear();
+        true
+    }
+
+    pub fn remove(&mut self) {
+        self.board.clear_square(self.cursor);
+        self.notes.clear();
+    }
+
+    pub fn toggle_side(&mut self) {
+        self.to_move = self.to_move.opposite();
+        self.notes.clear();
+    }
+
+    pub fn clear(&mut self) {
+        self.board = Board::empty();
+        self.notes.clear();
+    }
+
+    pub fn reset(&mut self) {
+        self.board = Board::new();
+        self.to_move = Color::White;
+        self.notes.clear();
+    }
+
+    pub fn problem(&self) -> Option<String> {
+        position_problem(&self.board, self.to_move)
+    }
+
+    pub fn fen(&self) -> String {
+        self.board.fen(self.to_move, 1)
+    }
+}
@@ -0,0 +1,78 @@
+use crate::engine::rl::{SearchResult, SimulationStats};
+use crate::engine::uci::UciReply;
+use crate::archive::lichess::Puzzle;
+use crate::ui::follow::FeedUpdate;
+use crate::ui::network::PeerEvent;
+use crossterm::event::{self, Event, KeyEvent, MouseEvent};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
+
+// how often the input thread checks whether the loop has gone away
+const INPUT_POLL: Duration = Duration::from_millis(250);
+
+// everything the main loop reacts to. the terminal, the engine thread and the
+// clock all feed the same channel, so nothing has to be polled on its own
+pub enum AppEvent {
+    Key(KeyEvent),
+    Mouse(MouseEvent),
+    Resize,
+    Tick,
+    // `search` is the id from SearchHandle, so a search that was abandoned
+    // (new game, undo) can't land on the board it no longer belongs to
+    EngineProgress { search: u64, stats: SimulationStats },
+    EngineMove { search: u64, result: Option<SearchResult>, stats: SimulationStats },
+    // the followed game's source changed, see ui::follow
+    Feed(FeedUpdate),
+    // lichess answered, or didn't, about today's puzzle
+    DailyPuzzle(Result<Puzzle, String>),
+    // a UCI engine said something; `engine` is its UciEngine's id
+    Uci { engine: u64, reply: UciReply },
+    // the other side of a network game, see ui::network
+    Peer(PeerEvent),
+}
+
+pub struct EventLoop {
+    tx: Sender<AppEvent>,
+    rx: Receiver<AppEvent>,
+}
+
+impl EventLoop {
+    // starts reading the terminal on its own thread
+    pub fn new() -> Self {
+        let (tx, rx) = mpsc::channel();
+        let input = tx.clone();
+        thread::spawn(move || loop {
+            let event = match event::poll(INPUT_POLL) {
+                Ok(true) => match event::read() {
+                    Ok(Event::Key(key)) => Some(AppEvent::Key(key)),
+                    Ok(Event::Mouse(mouse)) => Some(AppEvent::Mouse(mouse)),
+                    Ok(Event::Resize(..)) => Some(AppEvent::Resize),
+                    Ok(_) => None,
+                    Err(_) => return,
+                },
+                Ok(false) => None,
+                Err(_) => return,
+            };
+            if let Some(event) = event {
+                if input.send(event).is_err() {
+                    return;
+                }
+            }
+        });
+        EventLoop { tx, rx }
+    }
+
+    // for anything else that wants to wake the loop up
+    pub fn sender(&self) -> Sender<AppEvent> {
+        self.tx.clone()
+    }
+
+    // the next event, or a tick once `timeout` passes without one
+    pub fn next(&self, timeout: Duration) -> AppEvent {
+        match self.rx.recv_timeout(timeout) {
+            Ok(event) => event,
+            Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => AppEvent::Tick,
+        }
+    }
+}
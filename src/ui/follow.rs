@@ -0,0 +1,280 @@
+use crate::archive::games::{self, SavedGame};
+use crate::engine::rl::{format_score, white_score, SearchResult};
+use crate::engine::worker::SearchHandle;
+use crate::game::board::Board;
+use crate::game::notation;
+use crate::game::record::GameRecord;
+use crate::ui::events::AppEvent;
+use crate::ui::relay;
+use crate::utils::expand_home;
+use std::fs;
+use std::io::{self, BufRead, BufReader};
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::Duration;
+
+// how often a followed file is read again
+const FEED_POLL: Duration = Duration::from_millis(500);
+
+// where a followed game comes from: a PGN file another program keeps
+// writing, whatever is piped into stdin, or another chessrl's --serve
+pub enum FeedSource {
+    File(PathBuf),
+    Stdin,
+    // the relay's event stream
+    Relay(String),
+}
+
+impl FeedSource {
+    // `-` is stdin, and an http:// address a relay
+    pub fn parse(arg: &str) -> FeedSource {
+        match arg {
+            "-" => FeedSource::Stdin,
+            url if url.starts_with("http://") => {
+                let url = url.trim_end_matches('/');
+                if url.ends_with("/events") {
+                    FeedSource::Relay(url.to_string())
+                } else {
+                    FeedSource::Relay(format!("{}/events", url))
+                }
+            }
+            path => FeedSource::File(expand_home(path)),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            FeedSource::File(path) => path.display().to_string(),
+            FeedSource::Stdin => "stdin".to_string(),
+            FeedSource::Relay(url) => url.trim_end_matches("/events").to_string(),
+        }
+    }
+}
+
+// what the feed thread hears
+pub enum FeedUpdate {
+    // everything read so far
+    Text(String),
+    // stdin or the relay was closed, nothing more will come
+    Ended,
+    Failed(String),
+}
+
+// reads the source on its own thread and sends the whole text whenever it
+// changes. a file is read again every FEED_POLL, so it can be rewritten or
+// appended to; stdin is read a line at a time and a relay an event at a time
+pub fn spawn_feed(source: FeedSource, events: Sender<AppEvent>) {
+    let send = move |update| events.send(AppEvent::Feed(update)).is_ok();
+    thread::spawn(move || match source {
+        FeedSource::File(path) => {
+            let mut last: Option<Result<String, String>> = None;
+            loop {
+                let read = fs::read_to_string(&path).map_err(|e| format!("can't read {}: {}", path.display(), e));
+                if last.as_ref() != Some(&read) {
+                    let update = match &read {
+                        Ok(text) => FeedUpdate::Text(text.clone()),
+                        Err(e) => FeedUpdate::Failed(e.clone()),
+                    };
+                    if !send(update) {
+                        return;
+                    }
+                    last = Some(read);
+                }
+                thread::sleep(FEED_POLL);
+            }
+        }
+        FeedSource::Stdin => {
+            let mut text = String::new();
+            for line in io::stdin().lock().lines() {
+                match line {
+                    Ok(line) => {
+                        text.push_str(&line);
+                        text.push('\n');
+                        if !send(FeedUpdate::Text(text.clone())) {
+                            return;
+                        }
+                    }
+                    Err(e) => {
+                        send(FeedUpdate::Failed(format!("can't read stdin: {}", e)));
+                        return;
+                    }
+                }
+            }
+            send(FeedUpdate::Ended);
+        }
+        FeedSource::Relay(url) => {
+            let response = match ureq::get(&url).call() {
+                Ok(response) => response,
+                Err(e) => {
+                    send(FeedUpdate::Failed(format!("can't reach {}: {}", url, e)));
+                    return;
+                }
+            };
+            // an event's data lines, up to the blank line that ends it
+            let mut data = Vec::new();
+            for line in BufReader::new(response.into_reader()).lines() {
+                match line {
+                    Ok(line) if line.is_empty() && !data.is_empty() => {
+                        if !send(FeedUpdate::Text(relay::event_pgn(&data))) {
+                            return;
+                        }
+                        data.clear();
+                    }
+                    Ok(line) => {
+                        if let Some(value) = line.strip_prefix("data:") {
+                            data.push(value.strip_prefix(' ').unwrap_or(value).to_string());
+                        }
+                    }
+                    Err(e) => {
+                        send(FeedUpdate::Failed(format!("lost {}: {}", url, e)));
+                        return;
+                    }
+                }
+            }
+            send(FeedUpdate::Ended);
+        }
+    });
+}
+
+// the engine's view of one position of the followed game
+pub struct FollowEval {
+    pub ply: usize,
+    // from white's side
+    pub score: f32,
+    pub line: Vec<String>,
+}
+
+impl FollowEval {
+    pub fn text(&self) -> String {
+        format!("{} {}", format_score(self.score), self.line.join(" "))
+    }
+}
+
+// a game being followed live: the latest of the source's games, played
+// through as far as the rules can follow it
+pub struct Follow {
+    pub source: String,
+    pub game: SavedGame,
+    pub record: GameRecord,
+    // the position being looked at, None to stay on the latest
+    pub viewing: Option<usize>,
+    pub ended: bool,
+    pub error: Option<String>,
+    // whether the engine looks at each new position
+    pub evaluate: bool,
+    pub eval: Option<FollowEval>,
+    pub search: Option<SearchHandle>,
+}
+
+impl Follow {
+    pub fn new(source: &FeedSource) -> Self {
+        let game = games::parse("");
+        let record = game.record();
+        Follow {
+            source: source.name(),
+            game,
+            record,
+            viewing: None,
+            ended: false,
+            error: None,
+            evaluate: false,
+            eval: None,
+            search: None,
+        }
+    }
+
+    // true when there's a new position to look at
+    pub fn update(&mut self, update: FeedUpdate) -> bool {
+        let text = match update {
+            FeedUpdate::Text(text) => text,
+            FeedUpdate::Ended => {
+                self.ended = true;
+                return false;
+            }
+            FeedUpdate::Failed(e) => {
+                self.error = Some(e);
+                return false;
+            }
+        };
+        self.error = None;
+        let game = games::split_games(&text).last().map_or_else(|| games::parse(""), |pgn| games::parse(pgn));
+        let record = game.record();
+        let changed = record.len() != self.record.len() || !record.positions()[record.len()].same_position(&self.record.positions()[self.record.len()]);
+        self.game = game;
+        self.record = record;
+        if changed {
+            self.eval = None;
+        }
+        changed
+    }
+
+    pub fn index(&self) -> usize {
+        self.viewing.unwrap_or(self.record.len()).min(self.record.len())
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.record.positions()[self.index()]
+    }
+
+    pub fn fen(&self) -> String {
+        self.record.fen_at(self.index())
+    }
+
+    // the game as far as it could be followed, with its players
+    pub fn pgn(&self) -> String {
+        let result = if self.finished() { self.game.result.as_str() } else { "*" };
+        let record = &self.record;
+        let mut pgn = String::new();
+        for (tag, value) in [("White", &self.game.white), ("Black", &self.game.black)] {
+            if !value.is_empty() {
+                pgn.push_str(&format!("[{} \"{}\"]\n", tag, value));
+            }
+        }
+        pgn.push_str(&format!("[Result \"{}\"]\n", result));
+        if !self.game.fen.is_empty() {
+            pgn.push_str(&format!("[SetUp \"1\"]\n[FEN \"{}\"]\n", self.game.fen));
+        }
+        pgn.push('\n');
+        pgn.push_str(&games::format_movetext(&record.sans(), &record.comments(), result, record.first_to_move()));
+        pgn
+    }
+
+    pub fn back(&mut self) {
+        self.viewing = Some(self.index().saturating_sub(1));
+    }
+
+    pub fn forward(&mut self) {
+        let next = self.index() + 1;
+        self.viewing = (next < self.record.len()).then_some(next);
+    }
+
+    pub fn live(&mut self) {
+        self.viewing = None;
+    }
+
+    // the move the rules couldn't follow, if the feed got past one
+    pub fn stuck_at(&self) -> Option<&str> {
+        self.game.moves.get(self.record.len()).map(String::as_str)
+    }
+
+    pub fn finished(&self) -> bool {
+        !self.game.result.is_empty() && self.game.result != "*"
+    }
+
+    // the engine's look at the latest position, turned to white's side
+    pub fn evaluated(&mut self, result: SearchResult) {
+        let to_move = self.record.to_move();
+        let score = white_score(result.score, to_move);
+        let mut board = self.record.positions()[self.record.len()].clone();
+        let variant = self.game.variant();
+        let mut line = Vec::new();
+        for &(from, to) in result.pv.iter().take(6) {
+            line.push(notation::san(&board, (from, to)));
+            if !variant.make_move(&mut board, from, to) {
+                break;
+            }
+        }
+        self.eval = Some(FollowEval { ply: self.record.len(), score, line });
+    }
+}
@@ -0,0 +1,68 @@
+use crate::engine::rl::RLEngine;
+use crate::game::piece::PieceType;
+use ratatui::style::Color;
+
+pub const PIECES: [PieceType; 6] = [
+    PieceType::Pawn,
+    PieceType::Knight,
+    PieceType::Bishop,
+    PieceType::Rook,
+    PieceType::Queen,
+    PieceType::King,
+];
+
+// what the heatmap screen shows: one piece's table for one phase, either as
+// learned or as the change from where it started
+pub struct Heatmap {
+    pub piece: usize,
+    pub endgame: bool,
+    pub changes: bool,
+}
+
+impl Heatmap {
+    pub fn new() -> Self {
+        Heatmap { piece: 0, endgame: false, changes: false }
+    }
+
+    pub fn piece_type(&self) -> PieceType {
+        PIECES[self.piece]
+    }
+
+    pub fn next_piece(&mut self, forward: bool) {
+        self.piece = if forward {
+            (self.piece + 1) % PIECES.len()
+        } else {
+            (self.piece + PIECES.len() - 1) % PIECES.len()
+        };
+    }
+
+    // the squares' values, from white's side, in pawns
+    pub fn values(&self, engine: &RLEngine) -> [[f32; 8]; 8] {
+        let learned = engine.position_table(self.piece_type(), self.endgame);
+        if !self.changes {
+            return learned;
+        }
+        let initial = RLEngine::initial_position_table(self.piece_type(), self.endgame);
+        let mut changes = [[0.0; 8]; 8];
+        for rank in 0..8 {
+            for file in 0..8 {
+                changes[rank][file] = learned[rank][file] - initial[rank][file];
+            }
+        }
+        changes
+    }
+}
+
+// red for bad squares through gray to green for good ones, full strength
+// at `scale` either way
+pub fn heat_color(value: f32, scale: f32) -> Color {
+    let strength = (value.abs() / scale.max(f32::EPSILON)).min(1.0);
+    let level = (40.0 + 180.0 * strength) as u8;
+    if strength < 0.05 {
+        Color::Rgb(40, 40, 40)
+    } else if value > 0.0 {
+        Color::Rgb(0, level, 0)
+    } else {
+        Color::Rgb(level, 0, 0)
+    }
+}
@@ -0,0 +1,105 @@
+use crate::ui::commands::COMMANDS;
+use crate::ui::i18n::{tr, Text};
+use crate::ui::keymap::{Action, Keymap};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Margin, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState};
+use ratatui::Frame;
+
+const PAGE: isize = 10;
+
+// keys that aren't in the keymap, so they can't be looked up there
+const FIXED_KEYS: [(&str, Text); 4] = [
+    ("tab", Text::TabKeyAbout),
+    ("space", Text::SpaceKeyAbout),
+    ("pageup/pagedown", Text::PageKeysAbout),
+    ("up/down", Text::ArrowKeysAbout),
+];
+
+// every key and command over the screen, built when it opens so rebound
+// keys show as they are now. it takes the keys until it's closed
+pub struct Help {
+    lines: Vec<Line<'static>>,
+    scroll: usize,
+}
+
+impl Help {
+    pub fn new(keymap: &Keymap) -> Self {
+        let heading = |text: &'static str| Line::from(Span::styled(text, Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+        let entry = |key: String, what: &'static str| {
+            Line::from(vec![
+                Span::styled(format!("  {:<24}", key), Style::default().fg(Color::Cyan)),
+                Span::raw(what),
+            ])
+        };
+
+        let mut lines = vec![heading(tr(Text::KeysHeading))];
+        for action in Action::ALL {
+            lines.push(entry(keymap.binding(action).to_string(), action.description()));
+        }
+        for (key, what) in FIXED_KEYS {
+            lines.push(entry(key.to_string(), tr(what)));
+        }
+        lines.push(Line::from(""));
+        lines.push(heading(tr(Text::MovesHeading)));
+        lines.push(entry("e2 e4".to_string(), tr(Text::CoordinateMoveAbout)));
+        lines.push(entry("knight to f3".to_string(), tr(Text::SpokenMoveAbout)));
+        lines.push(Line::from(""));
+        lines.push(heading(tr(Text::CommandsHeading)));
+        for command in &COMMANDS {
+            lines.push(Line::from(Span::styled(format!("  {}", command.usage), Style::default().fg(Color::Cyan))));
+            lines.push(Line::from(format!("      {}", tr(command.description))));
+        }
+        Help { lines, scroll: 0 }
+    }
+
+    // false once the key closes it
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('?') | KeyCode::Char('q') => return false,
+            KeyCode::Up | KeyCode::Char('k') => self.scroll_by(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll_by(1),
+            KeyCode::PageUp => self.scroll_by(-PAGE),
+            KeyCode::PageDown => self.scroll_by(PAGE),
+            KeyCode::Home => self.scroll = 0,
+            KeyCode::End => self.scroll = self.last_top(),
+            _ => {}
+        }
+        true
+    }
+
+    pub fn scroll_by(&mut self, lines: isize) {
+        self.scroll = self.scroll.saturating_add_signed(lines).min(self.last_top());
+    }
+
+    // the top line once the last one is at the bottom
+    fn last_top(&self) -> usize {
+        let (width, height) = crossterm::terminal::size().unwrap_or((80, 24));
+        let inside = Block::default().borders(Borders::ALL).inner(overlay_area(Rect::new(0, 0, width, height)));
+        self.lines.len().saturating_sub(inside.height as usize)
+    }
+
+    pub fn render(&self, frame: &mut Frame, accent: Color) {
+        let area = overlay_area(frame.area());
+        let block = Block::default()
+            .title(tr(Text::HelpTitle))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(accent));
+        let inside = block.inner(area);
+        // no scrolling past the point where the last line is at the bottom
+        let scroll = self.scroll.min(self.lines.len().saturating_sub(inside.height as usize));
+        frame.render_widget(Clear, area);
+        frame.render_widget(Paragraph::new(self.lines.clone()).block(block).scroll((scroll as u16, 0)), area);
+
+        let mut state = ScrollbarState::new(self.lines.len().saturating_sub(inside.height as usize)).position(scroll);
+        let bar = Rect { y: inside.y, height: inside.height, ..area };
+        frame.render_stateful_widget(Scrollbar::new(ScrollbarOrientation::VerticalRight), bar, &mut state);
+    }
+}
+
+// most of the screen, leaving what's under it showing round the edge
+fn overlay_area(screen: Rect) -> Rect {
+    screen.inner(Margin { horizontal: screen.width / 10, vertical: screen.height / 10 })
+}
@@ -0,0 +1,647 @@
+use super::Text;
+use crate::game::piece::{Color, PieceType};
+use crate::ui::keymap::Action;
+
+pub fn text(text: Text) -> &'static str {
+    match text {
+        Text::MenuPlay => "PLAY",
+        Text::MenuEditor => "EDITOR",
+        Text::MenuTraining => "TRAINING",
+        Text::MenuDailyPuzzle => "DAILY PUZZLE",
+        Text::MenuHistory => "HISTORY",
+        Text::MenuProfile => "PROFILE",
+        Text::MenuThemes => "THEMES",
+        Text::MenuSettings => "SETTINGS",
+        Text::MenuAbout => "ABOUT",
+        Text::MenuInstructions => "Use ↑↓ arrows to select and ENTER to confirm",
+
+        Text::MoveHistory => "Move History",
+        Text::Analytics => "Analytics",
+        Text::Command => "Command",
+        Text::Chat => "Chat",
+        Text::Replay => "Replay",
+        Text::Moves => "Moves",
+        Text::PaletteTitle => "Commands ({})",
+        Text::NothingMatches => "Nothing matches",
+
+        Text::Board => "Board",
+        Text::Narration => "Narration (type 'board' to hear the position)",
+        Text::Game => "Game",
+        Text::Watch => "Watch",
+        Text::DailyPuzzle => "Daily Puzzle",
+        Text::DailyPuzzleOn => "Daily Puzzle · {}",
+        Text::Puzzle => "Puzzle",
+        Text::Arena => "Arena",
+        Text::Games => "Games",
+        Text::GamesFound => "{} games",
+        Text::BoardEditor => "Board Editor",
+        Text::Position => "Position",
+        Text::Coordinates => "Coordinates",
+        Text::Training => "Training",
+        Text::History => "History",
+        Text::Profile => "Profile",
+        Text::RatingHistory => "Rating history",
+        Text::GamesAxis => "games",
+        Text::Themes => "Themes",
+        Text::GameSetup => "Game Setup",
+        Text::Settings => "Settings",
+        Text::About => "About",
+        Text::HelpTitle => " Help: ↑↓ scroll, esc closes ",
+        Text::FollowingLive => "Following {} · live",
+        Text::FollowingAt => "Following {} · position {}/{}",
+        Text::Heatmap => "Heatmap",
+        Text::HeatmapTitle => "{} · {} · {}",
+        Text::MiddleGame => "middle game",
+        Text::EndGame => "end game",
+        Text::ChangeSinceStart => "change since the start",
+        Text::Learned => "learned",
+
+        Text::AnalysisTitle => "Analysis",
+        Text::Engine => "Engine",
+        Text::EngineThinking => "{} Engine thinking…",
+        Text::LookingAtPosition => "{} Looking at the position",
+        Text::MoveEitherSide => "Move either side",
+        Text::WaitingForYourMove => "Waiting for your move",
+        Text::WaitingForMove => "{} Waiting for {}'s move",
+        Text::PlayingAgainst => "Playing {}",
+        Text::Simulations => "Simulations: {}",
+        Text::NodesPerSecond => "Nodes/sec:   {}",
+        Text::BestMove => "Best move:   ",
+        Text::Depth => "Depth:       {}",
+        Text::Score => "Score:       {}",
+        Text::Played => "Played:      ",
+        Text::LastSearch => "Last search: {} simulations",
+        Text::Speedrun => "Speedrun:    seed {}",
+        Text::YourMoves => "Your moves:  {}, ",
+        Text::Hidden => "hidden",
+
+        Text::ReplayPosition => "Position {}/{}",
+        Text::ReplayPlaying => "▶ playing",
+        Text::ReplayPaused => "❚❚ paused",
+        Text::ReplayKeys => "←/→   step\nHome/End  jump\nspace play/pause\n+/-   speed",
+        Text::BackToHistory => "esc   back to history",
+        Text::BackToGame => "esc   back to game",
+
+        Text::PositionIsLegal => "Position is legal",
+        Text::ToMove => "To move: ",
+        Text::Square => "Square:  {}",
+        Text::NoCastlingYet => "Castling and en passant aren't in the rules yet",
+        Text::EditorKeys => "arrows move\nKQRBNP white piece\nkqrbnp black piece\nspace  empty square\ntab    side to move\n0/1    clear/start\na      analyze\nenter  play from here\nesc    back",
+
+        Text::Rating => "Rating: ",
+        Text::RatedGames => "   {} rated games",
+        Text::LevelColumns => "Level     Rating   Won  Drawn  Lost",
+        Text::Blunders => "Blunders: {}% of {} moves   Mistakes: {}   Positions the bot aims for: {}",
+        Text::NoBlunders => "Blunders: none judged yet",
+        Text::YourOpenings => "Your openings: {}",
+        Text::NoneYet => "none yet",
+        Text::OddsWorth => "Odds worth to you: {}",
+        Text::ProfileKeys => "Type 'level <name>' during a game to change the bot's strength   ESC back",
+
+        Text::NewGameOf => "New {} game",
+        Text::YouPlay => "You play",
+        Text::Variant => "Variant",
+        Text::Level => "Level",
+        Text::Strength => "Strength",
+        Text::TimePerMove => "Time per move",
+        Text::Odds => "Odds",
+        Text::Personality => "Personality",
+        Text::Opponent => "Opponent",
+        Text::BotAtElo => "Bot at about {}. Games at a target Elo don't count toward your rating.",
+        Text::BotAtLevel => "Bot level {} ({}s per move). Only even games against the engine count toward your rating.",
+        Text::SetupKeys => "↑↓ pick   ←→ change   ENTER start   ESC back",
+
+        Text::KeyBindings => "Key Bindings",
+        Text::Sounds => "Sounds",
+        Text::SettingsKeys => "↑↓ select   ENTER rebind   ←→ adjust   d restore defaults   ESC back",
+        Text::SavedTo => "Saved to {}",
+
+        Text::PositionEvaluation => "Position Evaluation",
+        Text::EngineAnalysis => "Engine Analysis",
+        Text::ThinkingDepth => "Thinking depth: ",
+        Text::TotalPositions => "Total positions: ",
+        Text::CurrentEvaluation => "Current Evaluation (+ for white): ",
+        Text::TopMoves => "Top Moves Considered:",
+        Text::Visits => ", {} visits) ",
+        Text::CurrentPositionScore => "Current Position Score: ",
+        Text::MaterialBalance => "Material Balance: ",
+        Text::BotKingSafety => "Bot King Safety: ",
+        Text::YourKingSafety => "Your King Safety: ",
+        Text::CenterControl => "Center Control: ",
+        Text::EvaluationsHidden => "Evaluations are hidden by the fog until the game ends",
+
+        Text::Yes => "Yes",
+        Text::No => "No",
+        Text::QuitTitle => "Quit",
+        Text::QuitQuestion => "The game in progress isn't finished. Quit anyway?",
+        Text::ResignTitle => "Resign",
+        Text::ResignQuestion => "Resign this game?",
+        Text::NewGameTitle => "New game",
+        Text::NewGameQuestion => "Abandon the game in progress and start a new one?",
+        Text::SetupGameQuestion => "Abandon the game in progress and start this one?",
+        Text::ResumeTitle => "Resume game",
+        Text::PromptKeys => "ENTER runs it   ESC backs out",
+        Text::ResumeQuestion => "Your {} game from {} was left unfinished after {} moves. Resume it?",
+
+        Text::UciStandardOnly => "The UCI engine only plays standard chess",
+        Text::UciWontStart => "Couldn't start the UCI engine: {}",
+        Text::InvalidMove => "Invalid move",
+        Text::MoveSuccessful => "Move successful",
+        Text::YourMove => "Your move",
+        Text::YourMoveAfter => "Your move ({} played {})",
+        Text::MovedSuccessfully => "{} moved successfully",
+        Text::WaitForEngine => "Wait for the engine to finish thinking",
+        Text::WaitForPlayer => "Wait for the other player to move",
+        Text::NobodyToResignTo => "There's nobody to resign to or offer a draw on an analysis board",
+        Text::PlaysOn => "{} plays on",
+        Text::NoNetworkDraws => "Draw offers don't go over the network; resign or play on",
+        Text::HostStartsGames => "The host starts network games",
+        Text::NewGameStarted => "New {} game started",
+        Text::GoToScreen => "Go to the screen",
+        Text::UnfinishedGameGone => "The unfinished game is gone",
+        Text::ResumedFromMove => "Resumed from move {}",
+        Text::ResumedGame => "Resumed your game from {}",
+        Text::NoPieceOfYoursOn => "No piece of yours on {}",
+        Text::PremoveSet => "Premove {}: played when it's your turn, if it's legal then ({} cancels)",
+
+        Text::DumpUsage => "Usage: dump search",
+        Text::BlindfoldOn => "Blindfold: the pieces stay hidden until the game ends",
+        Text::BlindfoldOff => "Blindfold off",
+        Text::JoinUsage => "Usage: join <host>[:port]",
+        Text::NoSearchYet => "The bot hasn't searched a move this game yet",
+        Text::SearchHidden => "The bot's search stays hidden until the game is over",
+        Text::SearchWritten => "Search written to {}",
+        Text::SearchNotWritten => "Couldn't write the search: {}",
+        Text::DiagramUsage => "Usage: diagram [svg|txt]",
+        Text::BoardHidden => "The board stays hidden until the game is over",
+        Text::DiagramWritten => "Diagram written to {}",
+        Text::DiagramNotWritten => "Couldn't write the diagram: {}",
+        Text::WhyNotUsage => "Usage: why-not <move> (e.g. 'why-not e2 e4')",
+        Text::NotLegalHere => "{} isn't a legal move here",
+        Text::NothingToCompare => "The engine has no moves to compare with",
+        Text::WhyNot => "Why not {}?",
+        Text::EnginesChoiceToo => "{} is the engine's choice too ({})",
+        Text::EnginePrefersOver => "Engine prefers {} ({}) over {} ({})",
+        Text::CostsPawns => "{} costs about {} pawns",
+        Text::HoldsUp => "{} holds up just as well in this search",
+        Text::MainReply => "Main reply: {}",
+        Text::Line => "Line: {}",
+        Text::Compared => "Compared {} with {}",
+
+        Text::TheClipboard => "the clipboard",
+        Text::TheTerminalsClipboard => "the terminal's clipboard",
+        Text::CouldntCopy => "Couldn't copy: {}",
+        Text::NoClipboard => "No clipboard to paste from here; type the moves or set up the position in the editor",
+        Text::CouldntPaste => "Couldn't read the clipboard: {}",
+        Text::CopiedTo => "{} copied to {}",
+        Text::PositionPasted => "Position pasted; ENTER plays from it",
+        Text::NothingToPaste => "The clipboard holds no FEN or PGN",
+        Text::PastedMoveUnreadable => "Couldn't read the pasted game's first move, {}",
+        Text::OnlyFirstMovesRead => "Only the first {} moves could be read",
+
+        Text::NoCompletions => "No completions",
+        Text::Completions => "{} options: {}",
+        Text::DrawAlreadyOffered => "You already offered a draw this move",
+        Text::DrawAccepted => "You offer a draw, the bot accepts",
+        Text::DrawDeclined => "The bot declines your draw offer",
+        Text::DrawDeclinedInFog => "The bot can't judge the position through the fog and declines",
+        Text::UnknownDrill => "Unknown drill. Try: {}",
+        Text::NoNetworkDrills => "Drills are against the bot, not over the network",
+        Text::DrillTask => "{} drill: {}",
+        Text::DrillPassed => "Drill passed: {} in {} moves",
+        Text::DrillFailed => "Drill failed: {} after {} moves; 'drill {}' for another",
+        Text::NoSpeedruns => "No speedruns yet",
+        Text::BestSpeedrun => "Best speedrun: {}",
+        Text::SpeedrunUsage => "Usage: speedrun [seed|scores]",
+        Text::NoNetworkSpeedruns => "Speedruns are against the random mover, not over the network",
+        Text::SpeedrunTask => "Speedrun on seed {}: mate the random mover in as few moves as you can",
+        Text::SpeedrunFailed => "Speedrun over without a mate; 'speedrun {}' to try the seed again",
+        Text::SpeedrunPlace => "Speedrun: {}, #{} on the leaderboard",
+        Text::SpeedrunDone => "Speedrun: {}",
+        Text::SpeedrunNotSaved => "Speedrun: {}, but couldn't save it: {}",
+
+        Text::SideWinsBy => "{} wins by {}",
+        Text::PlayerWinsBy => "{} wins by {}",
+        Text::YouWinBy => "You win by {}",
+        Text::DrawBy => "Draw by {}",
+        Text::GameOverNarrated => "Game over. {}",
+        Text::RatingChange => "rating {} ({})",
+        Text::OddsChange => "{} odds: worth {} over {} game(s)",
+        Text::YourRating => "Your {}",
+        Text::RatingNotSaved => "{} (couldn't save your {}: {})",
+        Text::GameSaved => "{}. Saved to your game history",
+        Text::GameNotSaved => "{}, but the game couldn't be saved: {}",
+
+        Text::PlayingAtLevel => "Playing at {}. Levels: {}",
+        Text::UnknownLevel => "Unknown level '{}'",
+        Text::Now => "now",
+        Text::FromNextGame => "from the next game",
+        Text::BotLevel => "Bot level {} {}",
+        Text::ButNotSaved => "{}, but couldn't save: {}",
+        Text::PlaysAtElo => "The bot plays at about {}; 'elo off' for the level again",
+        Text::PlaysAtLevel => "The bot plays at level {}; 'elo <{}-{}>' to aim for a rating",
+        Text::TargetEloRange => "Target Elo goes from {} to {}, or off",
+        Text::BotAtAbout => "Bot plays at about {} {} (unrated)",
+        Text::NoNetworkSwitch => "Sides can't be switched in a network game",
+        Text::AnalysisBothSides => "On an analysis board you move both sides",
+        Text::TheGameIsOver => "The game is over",
+        Text::SidesSwitched => "You play {} now, the bot {}",
+        Text::SidesSwitchedUnrated => "You play {} now, the bot {} (unrated)",
+        Text::LearningEngine => "the learning engine",
+        Text::TheUciEngine => "the UCI engine",
+        Text::AnotherPlayer => "another player",
+
+        Text::TabList => "Tabs: {}. Try: tab new|analysis|close|<n>",
+        Text::NoSuchTab => "There's no tab {}",
+        Text::TabUsage => "Usage: tab [new|analysis|close|<n>]",
+        Text::NoNetworkTabs => "Tabs are off while you're connected to another player",
+        Text::TooManyTabs => "{} tabs is the most there can be; close one first",
+        Text::AnalysisFromMove => "Analysis from move {}",
+        Text::NewGameTab => "Tab {}: a new game against {}",
+        Text::AnalysisTab => "Tab {}: an analysis board; move either side",
+        Text::TabTitle => "Tab {}: {}",
+        Text::OneTab => "There's just the one tab; {} opens another",
+        Text::OnlyTab => "This is the only tab",
+        Text::TabClosed => "Tab {} closed",
+        Text::TabOver => "vs {} · {}",
+        Text::TabThinking => "vs {} · thinking",
+        Text::TabYourMove => "vs {} · your move",
+
+        Text::GoOnAnalysisOnly => "go is for analysis boards; 'tab analysis' opens one",
+        Text::GoUsage => "Usage: go [infinite] [movetime <ms>] [nodes <n>] [depth <n>]",
+        Text::NothingToSearch => "The game is over; there's nothing to search",
+        Text::AnalysingEach => "Analysing each position {}",
+        Text::MovingNow => "Moving now",
+        Text::AnalysisStopped => "Analysis stopped",
+        Text::NothingSearching => "Nothing is searching",
+        Text::BotPersonality => "The bot plays {}. Personalities: {}",
+        Text::UnknownPersonality => "Unknown personality '{}'",
+        Text::BotPlays => "Bot plays {} {}",
+        Text::PlayingOpponents => "Playing {}. Opponents: {}",
+        Text::UnknownOpponent => "Unknown opponent '{}'",
+        Text::QuitToPlayOthers => "You're connected to another player; quit to play someone else",
+        Text::FinishFirst => "Finish or resign this game first",
+        Text::PlayingFromNextGame => "Playing {} from the next game",
+        Text::PlayingNow => "Playing {} now",
+
+        Text::AlreadyConnected => "Already connected to another player",
+        Text::HostUsage => "Usage: host [port]",
+        Text::WaitingToJoin => "Waiting for another player to join on port {}",
+        Text::CouldntHost => "Couldn't host on port {}: {}",
+        Text::Connecting => "Connecting to {}…",
+        Text::NotConnected => "You're not connected to another player",
+        Text::CouldntReach => "Couldn't reach the other player: {}",
+        Text::ChatNetworkOnly => "Chat is for network games",
+        Text::ChatOn => "Chat: Enter sends what you type to the other player ({} for commands)",
+        Text::ChatOff => "Commands and moves again ({} to chat)",
+        Text::TakebackPending => "Still waiting to hear about your takeback",
+        Text::TakebackAskedFirst => "The other player asked first: 'accept' or 'decline'",
+        Text::NotMovedYet => "You haven't moved yet",
+        Text::TakebackAsked => "Asked the other player to take back your last move",
+        Text::NoTakebackAsked => "Nobody asked for a takeback",
+        Text::TakebackDeclined => "Takeback declined",
+        Text::TakebackUnknownPosition => "This game never went through the position they asked to go back to",
+        Text::CouldntTalk => "Couldn't talk to the other player: {}",
+        Text::WaitingForHost => "Connected; waiting for the host to start a game",
+        Text::SomeoneJoined => "Someone joined; you play white",
+        Text::HostsVariantMissing => "The host started a game of {}, which this version doesn't have",
+        Text::NetworkGameStarted => "New {} game; you play {}",
+        Text::OpponentResigns => "Opponent resigns",
+        Text::OpponentAsksTakeback => "Opponent asks for a takeback",
+        Text::TakebackOffered => "The other player asks to take back their last move: 'accept' or 'decline'",
+        Text::TakebackAgreed => "The other player agreed; move taken back",
+        Text::TakebackMissingMove => "The other player took back a move this game doesn't have",
+        Text::OpponentSays => "Opponent says: {}",
+        Text::TakebackRefused => "The other player declined your takeback",
+        Text::ConnectionLost => "Connection lost: {}",
+        Text::NotConnectedReason => "Not connected: {}",
+
+        Text::EngineStopped => "{} stopped: {}",
+        Text::TheWeightsFolder => "the weights folder",
+        Text::NoWeightsFiles => "No weights files in {}",
+        Text::BuiltIn => "built-in",
+        Text::WeightsFromNextGame => "Bot weights {} from the next game",
+        Text::WeightsNow => "Bot weights {} now",
+        Text::WeightsOwn => "the weights' own",
+        Text::ValuesFromNextGame => "Piece values {} from the next game",
+        Text::ValuesNow => "Piece values {} now",
+        Text::WeightsNotLoaded => "Couldn't load the bot's weights: {}",
+
+        Text::NewGameNarrated => "New {} game. You play {}, the bot plays {}",
+        Text::BlindfoldIsOn => "Blindfold is on; type 'blindfold' to see the board again",
+        Text::YouLower => "you",
+        Text::TheBotLower => "the bot",
+        Text::SideToMove => "{} to move ({})",
+        Text::AccessibleOn => "Accessible mode on",
+        Text::AccessibleOff => "Accessible mode off",
+        Text::BotMoved => "The bot moved",
+        Text::BotPlayed => "The bot played {}",
+        Text::YouMoved => "You moved",
+        Text::YouPlayed => "You played {}",
+        Text::MovedInFog => "{} moved, hidden by the fog",
+        Text::SpokenMove => "{} {} to {}",
+        Text::Takes => ", takes {}",
+        Text::CheckSpoken => ", check",
+        Text::EmptyRank => "empty",
+        Text::RankListing => "Rank {}: {}",
+        Text::SquareHolds => "{} {}",
+        Text::UnknownPiece => "Unknown piece '{}'",
+
+        Text::TargetEloGame => "The bot plays at about {}: a capped search and now and then a lesser move (unrated)",
+        Text::SparringGame => "Sparring at about your {}: the bot slips now and then (unrated)",
+        Text::Unrated => "{} (unrated)",
+        Text::SettingsNotSaved => "Couldn't save the game settings: {}",
+        Text::OfflinePuzzle => "Offline, so this is the puzzle from {}",
+        Text::LichessUnreachable => "Couldn't reach lichess. The daily puzzle needs a connection the first time",
+        Text::NotAPiece => "'{}' isn't a piece; use KQRBNP for white, kqrbnp for black",
+        Text::NoNetworkEdits => "Edited positions can't be played over the network",
+        Text::PlayingFromPosition => "Playing from your position (unrated)",
+        Text::EngineFoundNoMoves => "The engine found no moves",
+        Text::EnginePrefers => "Engine prefers {}",
+
+        Text::OddsOf => "{} odds, ",
+        Text::HistoryRow => "{}  as {}  {} moves  bot {}  {}",
+        Text::UnknownStyle => "Unknown style. Try: {}",
+        Text::EngineNamed => "Engine ({})",
+        Text::BackToEngine => "{} goes back to the engine",
+        Text::YouPlaySide => "You play {} now",
+        Text::CouldntRelay => "Couldn't serve spectators on port {}: {}",
+        Text::BotFailedToMove => "Bot: Bot failed to move",
+        Text::NoSpeedrunTakebacks => "No takebacks in a speedrun",
+        Text::TakenBackToMove => "Taken back to move {}",
+
+        Text::AnalyticsShown => "Analytics shown",
+        Text::AnalyticsHidden => "Analytics hidden",
+        Text::HistoryShown => "Move history shown",
+        Text::HistoryHidden => "Move history hidden",
+        Text::BoardFullWidth => "The board has the whole width while the move history is hidden",
+        Text::BoardWidest => "The board is as wide as it goes",
+        Text::BoardNarrowest => "The board is as narrow as it goes",
+        Text::BoardWidth => "Board {}% of the width",
+        Text::AnalyticsPanelHidden => "The analytics panel is hidden",
+        Text::AnalyticsTallest => "The analytics panel is as tall as it goes",
+        Text::AnalyticsShortest => "The analytics panel is as short as it goes",
+        Text::AnalyticsHeight => "Analytics {}% of the height",
+        Text::LayoutNotSaved => "{} (couldn't save the layout: {})",
+        Text::Hint => "Hint: {} {}",
+        Text::NoHint => "No hint available",
+        Text::PremoveCancelled => "Premove {} cancelled",
+        Text::FinishOrStartOver => "Finish or resign this game first, or type 'new' to start over",
+        Text::ThreatsHidden => "Threats hidden",
+        Text::ThreatsInFog => "Threats can't be shown through the fog",
+        Text::ThreatsShown => "Red: your pieces hanging. Green: the bot's",
+        Text::ContemptSet => "Contempt set to {}",
+        Text::EngineLine => "Showing {} engine line",
+        Text::EngineLines => "Showing {} engine lines",
+        Text::BellOn => "Bell on for {}",
+        Text::BellOff => "Bell off for {}",
+        Text::Cancelled => "Cancelled",
+        Text::NowBound => "{} is now bound to {}",
+        Text::BoundNotSaved => "Bound {} to {}, but couldn't save: {}",
+        Text::PressNewKey => "Press the new key for {} (esc cancels)",
+        Text::DefaultKeys => "Restored default keys",
+        Text::UsingTheme => "Using {} with {} pieces",
+
+        Text::CommandBox => "command box",
+        Text::TheBoard => "board",
+        Text::TheMoveHistory => "move history",
+        Text::TheAnalytics => "analytics",
+        Text::FocusCommand => "Focus: {} (Tab moves on)",
+        Text::FocusBoard => "Focus: {} (arrows move, Enter picks up and puts down a piece)",
+        Text::FocusPanel => "Focus: {} (arrows and PageUp/PageDown scroll)",
+
+        Text::UciEngine => "UCI engine",
+        Text::SecondOpinion => "Second opinion: {}",
+        Text::AtDepth => " at depth {}",
+        Text::BestAfter => ", best ",
+        Text::EngineNameThinking => "{}: thinking…",
+        Text::SearchStats => "Search: {} sims in {}s ({}/s), rollouts {} plies, branching {}, tree {} nodes{}",
+        Text::Recycled => ", {} recycled",
+        Text::MemoryStats => "Memory: {}, eval table {}% full with {}% hits, first-move cutoffs {}%",
+        Text::SafetyTerms => " ({} attackers, weight {}, shield {}, open files {})",
+
+        Text::SayPrompt => "say: ",
+        Text::ReplayUsage => "Usage: replay [move number], e.g. replay 12 or replay 12...",
+        Text::NoSuchMove => "There's no move {} yet",
+        Text::NoReplaysInFog => "No replays while the fog is up",
+        Text::BeforeAnalysisBoard => "That move was played before the analysis board opened",
+        Text::BackToMove => "Back to move {}",
+        Text::AnalysisBoardTag => " · analysis board",
+        Text::VsTag => " · vs {}",
+        Text::DrillTag => " · {} drill, move {}/{}",
+        Text::OddsTag => " · {} odds",
+        Text::PersonalityTag => " · {} bot",
+        Text::ValuesTag => " · {} values",
+        Text::SpectatorsTag => " · {} watching on :{}",
+
+        Text::Finished => "Finished {}",
+        Text::FeedClosed => "Feed closed",
+        Text::WaitingForMoves => "Waiting for moves…",
+        Text::SideMoves => "{} to move",
+        Text::CantFollow => "Can't follow {}",
+        Text::Thinking => "{} thinking…",
+        Text::EngineEval => "Engine: {}",
+        Text::FollowKeys => "←/→  step\nEnd  back to live\ne    engine on/off\nesc  menu",
+        Text::SidePlayer => "{}: {}",
+        Text::Paused => "Paused",
+        Text::PlayerToMove => "{} to move",
+        Text::ThinkTime => "Think time: {}s a move",
+        Text::SimulationsBest => "{} simulations, best {}",
+        Text::WatchKeys => "space  pause/resume\n→      one move\n+/-    faster/slower\nwhite/black  take over a side\nesc    back to the game",
+        Text::FetchingPuzzle => "Fetching today's puzzle from lichess…",
+        Text::EscMenu => "esc  menu",
+        Text::PuzzleRating => "Rating {}",
+        Text::Shown => "Shown",
+        Text::Solved => "Solved",
+        Text::MoveOf => "Move {} of {}",
+        Text::MistakesHints => "Mistakes: {}  Hints: {}",
+        Text::PuzzleKeys => "type a move, ENTER to try it\nhint  name the piece, then the move\nshow  play out the solution\nesc   menu",
+
+        Text::HeatmapAbout => "The engine's piece-square values in pawns, from white's side; black's are mirrored.",
+        Text::NothingLearned => "Nothing learned for this table yet",
+        Text::BiggestChanges => "Biggest changes:",
+        Text::HeatmapKeys => "←/→  piece\n↑/↓  middle or end game\nc    learned values or changes\nesc  back to game",
+        Text::NoTournaments => "No tournaments yet.",
+        Text::HoldTournament => "Hold one from the shell, e.g. chessrl arena balanced aggressive positional",
+        Text::EscBackToGame => "esc  back to game",
+        Text::TournamentSummary => "{}, {} games at {} a move, {}",
+        Text::TournamentOf => "Tournament {} of {}   ←/→ others   esc back to game",
+        Text::NameTheSquare => "Name the marked square",
+        Text::SeenFrom => "(seen from {}'s side)",
+        Text::IsRight => "{} is right",
+        Text::NotThat => "{}, not {}",
+        Text::QuizScore => "Score:  {}/{}",
+        Text::QuizStreak => "Streak: {} (best {})",
+        Text::QuizKeys => "a-h 1-8 name the square\nbksp   take one back\n{} flip the board\nesc    back",
+        Text::SearchPrompt => "Search: ",
+        Text::NoFinishedGames => "No finished games yet",
+        Text::NoGamesMatch => "No games match",
+        Text::HistoryKeys => "type to search   ↑↓ select   ENTER replay   ESC back",
+
+        Text::Current => " (current)",
+        Text::PieceSet => "Piece set",
+        Text::ThemesKeys => "←→ theme   ↑↓ piece set   ENTER apply   ESC back",
+        Text::FullSearch => "the level's full search",
+        Text::RatedByGuesswork => ", rated by guesswork until 'chessrl calibrate' runs",
+        Text::StrengthCap => "at most {} simulations a move, giving up to {} pawns{}",
+        Text::TossedEachGame => "tossed for each game",
+        Text::CountsTowardRating => "counts toward your rating",
+        Text::UnratedNote => "unrated",
+        Text::StrengthStandsIn => "the strength below stands in for it",
+        Text::RatedAbout => "rated about {}",
+        Text::LevelWord => "level",
+        Text::LongestThink => "the bot's longest think; the strength caps it",
+        Text::WithTheOdds => "{}s with the odds",
+        Text::ThinkAtLevel => "the bot's think at level {}; there's no game clock",
+        Text::OddsWorthNote => "{}, worth {} to you over {} game(s)",
+        Text::NoOddsGames => "{}, no games at these odds yet",
+
+        Text::ContemptAbout => "What a draw costs the bot, in centipawns: it avoids repetitions when ahead and looks for them when behind",
+        Text::MultiPvAbout => "How many of the engine's top moves the analytics panel shows, each with its own line",
+        Text::WeightsAbout => "The bot's piece values and tables: built in, or a file from the weights folder",
+        Text::PieceValuesAbout => "A named set over the weights' own: simplified, classical 1/3/3/5/9 or kaufman 1/3.25/3.25/5/9.75",
+        Text::Bell => "bell",
+        Text::Off => "off",
+        Text::AboutIntro => "chess game with ML opponent\nfeaturing pure reinforcement learning.\n\nCommands:\ne2 e4  - Move a piece from e2 to e4\nknight to f3 / pawn takes d5 - Move a piece, said in words\nwhy-not e2 e4 - Compare a move with the engine's choice\nreplay [12|12...] - Step through the game so far, from a move\nnew [standard|fog|atomic|koth|horde|3check] - Start a new game\nresign / draw - Resign, or offer the bot a draw\nswitch - Swap sides with the bot\nA move entered while the bot thinks is a premove, played on your turn if legal\ntab [new|analysis|close|<n>] - Open, close or go to another game's tab\ndump search - Write the bot's last search to a file\nlevel <easy|medium|hard|expert|sparring> - Set the bot's strength\nelo <400-2000|off> - Hold the bot to about that rating instead\npersonality <balanced|aggressive|positional|gambit|defensive> - Set the bot's style",
+        Text::AboutOutro => "The AI learns as the game is played,\nimproving its strategy over time.",
+
+        Text::KeysHeading => "Keys",
+        Text::TabKeyAbout => "Complete a square, or move the focus to the next panel",
+        Text::SpaceKeyAbout => "Have the bot move now (with nothing typed)",
+        Text::PageKeysAbout => "Scroll the move history",
+        Text::ArrowKeysAbout => "Recall earlier commands",
+        Text::MovesHeading => "Moves",
+        Text::CoordinateMoveAbout => "Move the piece on e2 to e4",
+        Text::SpokenMoveAbout => "Move a piece, said in words",
+        Text::CommandsHeading => "Commands",
+
+        Text::NewCommand => "Start a new game",
+        Text::ResignCommand => "Resign the game",
+        Text::DrawCommand => "Offer the bot a draw",
+        Text::SwitchCommand => "Swap sides with the bot",
+        Text::WhyNotCommand => "Compare a move with the engine's choice",
+        Text::ReplayCommand => "Step through the game so far, from a move",
+        Text::LevelCommand => "Set the bot's strength",
+        Text::EloCommand => "Hold the bot to about that rating instead",
+        Text::PersonalityCommand => "Set the bot's style",
+        Text::OpponentCommand => "Play the bot, a UCI engine or another player",
+        Text::DrillCommand => "Practise an endgame",
+        Text::SpeedrunCommand => "Race through a set of positions",
+        Text::TabCommand => "Open, close or go to another game's tab",
+        Text::GoCommand => "Set how long an analysis board looks",
+        Text::StopCommand => "Have the bot move now, or stop the analysis",
+        Text::HostCommand => "Wait for another player to join",
+        Text::JoinCommand => "Join another player's game",
+        Text::TakebackCommand => "Ask the other player to take back your move",
+        Text::AcceptCommand => "Agree to the other player's takeback",
+        Text::DeclineCommand => "Turn down the other player's takeback",
+        Text::BlindfoldCommand => "Hide the pieces until the game ends",
+        Text::BoardCommand => "Read out the position",
+        Text::AccessibleCommand => "Switch to the plain text layout for screen readers",
+        Text::DiagramCommand => "Save the position as an SVG or text diagram",
+        Text::HeatmapCommand => "Show the squares the bot has learned to like",
+        Text::WatchCommand => "Have the engine play itself",
+        Text::ArenaCommand => "Look through the arena tournaments",
+        Text::DumpCommand => "Write the bot's last search to a file",
+
+        Text::CouldntFollowPuzzle => "Couldn't follow the puzzle's game at {}",
+        Text::UnreadableSolution => "The puzzle's solution couldn't be read",
+        Text::SideToPlay => "{} to play",
+        Text::SolvedAlready => "Solved already; esc for the menu",
+        Text::TypeAMove => "Type a move like e2 e4, e2e4 or Nf3",
+        Text::NotItTryAgain => "{} isn't it; try again",
+        Text::SolvedExclaimed => "Solved!",
+        Text::RightKeepGoing => "Right! {} replied; keep going",
+        Text::LookAtYour => "Look at your {}",
+        Text::PlayMove => "Play {}",
+        Text::TheSolution => "The solution: {}",
+        Text::TypeToTakeOver => "It's {}'s move; type {} to take over",
+
+        Text::PremovePlayed => "Premove {} played",
+        Text::PremoveDropped => "Premove {} dropped: {}",
+        Text::YouResign => "You resign",
+        Text::SideToMoveScore => "{} to move: {}",
+        Text::BranchVisits => "{} {} ({} visits)",
+        Text::TimeUsed => "Time:        ",
+        Text::SecondsOf => "{} of {}s",
+
+        Text::NoPieceSelected => "No piece at selected position",
+        Text::InvalidCoordinates => "Invalid coordinate format. Use a1-h8",
+        Text::InvalidCommand => "Invalid command. Use: <from> <to> (e.g. 'e2 e4')",
+        Text::GameOver => "The game is over ({})",
+        Text::GameOverPlayAgain => "The game is over ({}). Type 'new' to play again, or {} to set up the next game",
+        Text::UnknownVariant => "Unknown variant. Try: {}",
+        Text::NothingToUndo => "Nothing to undo",
+        Text::MoveTakenBack => "Move taken back",
+        Text::NothingToReplay => "Nothing to replay yet",
+        Text::NoPieceOn => "There's no piece on {}",
+        Text::BotsPiece => "That's the bot's {}",
+        Text::SameSquare => "A move has to go to another square",
+        Text::OwnPieceThere => "Your {} is already on {}",
+        Text::KingShape => "The king only moves one square at a time",
+        Text::QueenShape => "The queen moves along ranks, files and diagonals",
+        Text::RookShape => "The rook only moves along ranks and files",
+        Text::BishopShape => "The bishop only moves diagonally",
+        Text::KnightShape => "The knight moves two squares one way and one the other",
+        Text::PawnShape => "The pawn only moves forward, or diagonally forward to capture",
+        Text::Blocked => "{} can't jump over {}",
+        Text::PawnBlocked => "The pawn can't capture straight ahead, and {} is in the way",
+        Text::PawnCapturesOnly => "The pawn only moves diagonally to capture",
+        Text::PawnDoubleStep => "The pawn only moves two squares from its starting square",
+        Text::StillInCheck => "You must move out of check from {}",
+        Text::LeavesKingInCheck => "That would leave your king in check from {}",
+        Text::VariantRule => "{} rules don't allow that move",
+        Text::PieceOn => "{} on {}",
+        Text::Piece(piece) => piece.name(),
+        Text::ThePiece(piece) => match piece {
+            PieceType::King => "the king",
+            PieceType::Queen => "the queen",
+            PieceType::Rook => "the rook",
+            PieceType::Bishop => "the bishop",
+            PieceType::Knight => "the knight",
+            PieceType::Pawn => "the pawn",
+        },
+        Text::Side(color) => match color {
+            Color::White => "white",
+            Color::Black => "black",
+        },
+        Text::SidePiece(color, piece) => match (color, piece) {
+            (Color::White, PieceType::King) => "white king",
+            (Color::White, PieceType::Queen) => "white queen",
+            (Color::White, PieceType::Rook) => "white rook",
+            (Color::White, PieceType::Bishop) => "white bishop",
+            (Color::White, PieceType::Knight) => "white knight",
+            (Color::White, PieceType::Pawn) => "white pawn",
+            (Color::Black, PieceType::King) => "black king",
+            (Color::Black, PieceType::Queen) => "black queen",
+            (Color::Black, PieceType::Rook) => "black rook",
+            (Color::Black, PieceType::Bishop) => "black bishop",
+            (Color::Black, PieceType::Knight) => "black knight",
+            (Color::Black, PieceType::Pawn) => "black pawn",
+        },
+        Text::ActionAbout(action) => match action {
+            Action::Quit => "Quit the game",
+            Action::Menu => "Return to the main menu",
+            Action::Undo => "Take back your last move",
+            Action::Flip => "Flip the board",
+            Action::Hint => "Ask the engine for a hint",
+            Action::Threats => "Mark attacked, undefended pieces",
+            Action::CopyFen => "Copy the position as FEN",
+            Action::CopyPgn => "Copy the game as PGN",
+            Action::Paste => "Load a FEN or PGN from the clipboard",
+            Action::NewTab => "Start another game in a new tab",
+            Action::NextTab => "Go to the next tab",
+            Action::ToggleAnalytics => "Show or hide the analytics panel",
+            Action::ToggleHistory => "Show or hide the move history",
+            Action::BoardWider => "Give the board more of the width",
+            Action::BoardNarrower => "Give the board less of the width",
+            Action::AnalyticsTaller => "Make the analytics panel taller",
+            Action::AnalyticsShorter => "Make the analytics panel shorter",
+            Action::ToggleChat => "Switch the command box between commands and chat",
+            Action::FocusHistory => "Move the arrow keys between the command box and the move history",
+            Action::Help => "List every key and command",
+            Action::Palette => "Find a command by typing part of its name",
+            Action::NewGame => "Set up the next game once this one is over",
+        },
+    }
+}
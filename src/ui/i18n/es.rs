@@ -0,0 +1,654 @@
+use super::Text;
+use crate::game::piece::{Color, PieceType};
+use crate::ui::keymap::Action;
+
+pub fn text(text: Text) -> &'static str {
+    match text {
+        Text::MenuPlay => "JUGAR",
+        Text::MenuEditor => "EDITOR",
+        Text::MenuTraining => "ENTRENAMIENTO",
+        Text::MenuDailyPuzzle => "PROBLEMA DEL DÍA",
+        Text::MenuHistory => "HISTORIAL",
+        Text::MenuProfile => "PERFIL",
+        Text::MenuThemes => "TEMAS",
+        Text::MenuSettings => "AJUSTES",
+        Text::MenuAbout => "ACERCA DE",
+        Text::MenuInstructions => "Usa las flechas ↑↓ para elegir y ENTER para confirmar",
+
+        Text::MoveHistory => "Jugadas",
+        Text::Analytics => "Análisis",
+        Text::Command => "Comando",
+        Text::Chat => "Chat",
+        Text::Replay => "Repetición",
+        Text::Moves => "Jugadas",
+        Text::PaletteTitle => "Comandos ({})",
+        Text::NothingMatches => "Nada coincide",
+
+        Text::Board => "Tablero",
+        Text::Narration => "Narración (escribe 'board' para oír la posición)",
+        Text::Game => "Partida",
+        Text::Watch => "Espectador",
+        Text::DailyPuzzle => "Problema del día",
+        Text::DailyPuzzleOn => "Problema del día · {}",
+        Text::Puzzle => "Problema",
+        Text::Arena => "Arena",
+        Text::Games => "Partidas",
+        Text::GamesFound => "{} partidas",
+        Text::BoardEditor => "Editor de tablero",
+        Text::Position => "Posición",
+        Text::Coordinates => "Coordenadas",
+        Text::Training => "Entrenamiento",
+        Text::History => "Historial",
+        Text::Profile => "Perfil",
+        Text::RatingHistory => "Evolución del rating",
+        Text::GamesAxis => "partidas",
+        Text::Themes => "Temas",
+        Text::GameSetup => "Nueva partida",
+        Text::Settings => "Ajustes",
+        Text::About => "Acerca de",
+        Text::HelpTitle => " Ayuda: ↑↓ desplaza, esc cierra ",
+        Text::FollowingLive => "Siguiendo {} · en directo",
+        Text::FollowingAt => "Siguiendo {} · posición {}/{}",
+        Text::Heatmap => "Mapa de calor",
+        Text::HeatmapTitle => "{} · {} · {}",
+        Text::MiddleGame => "medio juego",
+        Text::EndGame => "final",
+        Text::ChangeSinceStart => "cambio desde el inicio",
+        Text::Learned => "aprendido",
+
+        Text::AnalysisTitle => "Análisis",
+        Text::Engine => "Motor",
+        Text::EngineThinking => "{} El motor piensa…",
+        Text::LookingAtPosition => "{} Estudiando la posición",
+        Text::MoveEitherSide => "Mueve cualquier bando",
+        Text::WaitingForYourMove => "Esperando tu jugada",
+        Text::WaitingForMove => "{} Esperando la jugada de {}",
+        Text::PlayingAgainst => "Juegas contra {}",
+        Text::Simulations => "Simulaciones: {}",
+        Text::NodesPerSecond => "Nodos/s:      {}",
+        Text::BestMove => "Mejor jugada: ",
+        Text::Depth => "Profundidad:  {}",
+        Text::Score => "Puntuación:   {}",
+        Text::Played => "Jugada:       ",
+        Text::LastSearch => "Última búsqueda: {} simulaciones",
+        Text::Speedrun => "Speedrun:     semilla {}",
+        Text::YourMoves => "Tus jugadas:  {}, ",
+        Text::Hidden => "oculta",
+
+        Text::ReplayPosition => "Posición {}/{}",
+        Text::ReplayPlaying => "▶ reproduciendo",
+        Text::ReplayPaused => "❚❚ en pausa",
+        Text::ReplayKeys => "←/→   paso\nInicio/Fin  saltar\nespacio reproducir/pausa\n+/-   velocidad",
+        Text::BackToHistory => "esc   volver al historial",
+        Text::BackToGame => "esc   volver a la partida",
+
+        Text::PositionIsLegal => "La posición es legal",
+        Text::ToMove => "Juegan: ",
+        Text::Square => "Casilla: {}",
+        Text::NoCastlingYet => "El enroque y la captura al paso aún no están en las reglas",
+        Text::EditorKeys => "flechas mover\nKQRBNP pieza blanca\nkqrbnp pieza negra\nespacio vaciar casilla\ntab    bando que juega\n0/1    vaciar/inicial\na      analizar\nenter  jugar desde aquí\nesc    volver",
+
+        Text::Rating => "Rating: ",
+        Text::RatedGames => "   {} partidas puntuables",
+        Text::LevelColumns => "Nivel     Rating   Gan  Tabl.  Perd",
+        Text::Blunders => "Errores graves: {}% de {} jugadas   Errores: {}   Posiciones que busca el bot: {}",
+        Text::NoBlunders => "Errores graves: aún no se ha juzgado ninguno",
+        Text::YourOpenings => "Tus aperturas: {}",
+        Text::NoneYet => "ninguna aún",
+        Text::OddsWorth => "Lo que te valen las ventajas: {}",
+        Text::ProfileKeys => "Escribe 'level <nombre>' en una partida para cambiar la fuerza del bot   ESC volver",
+
+        Text::NewGameOf => "Nueva partida de {}",
+        Text::YouPlay => "Juegas con",
+        Text::Variant => "Variante",
+        Text::Level => "Nivel",
+        Text::Strength => "Fuerza",
+        Text::TimePerMove => "Tiempo/jugada",
+        Text::Odds => "Ventaja",
+        Text::Personality => "Estilo",
+        Text::Opponent => "Rival",
+        Text::BotAtElo => "Bot a unos {}. Las partidas a un Elo fijado no cuentan para tu rating.",
+        Text::BotAtLevel => "Bot de nivel {} ({}s por jugada). Solo las partidas sin ventaja contra el motor cuentan para tu rating.",
+        Text::SetupKeys => "↑↓ elegir   ←→ cambiar   ENTER empezar   ESC volver",
+
+        Text::KeyBindings => "Teclas",
+        Text::Sounds => "Sonidos",
+        Text::SettingsKeys => "↑↓ elegir   ENTER reasignar   ←→ ajustar   d valores por defecto   ESC volver",
+        Text::SavedTo => "Guardado en {}",
+
+        Text::PositionEvaluation => "Evaluación de la posición",
+        Text::EngineAnalysis => "Análisis del motor",
+        Text::ThinkingDepth => "Profundidad: ",
+        Text::TotalPositions => "Posiciones en total: ",
+        Text::CurrentEvaluation => "Evaluación actual (+ para las blancas): ",
+        Text::TopMoves => "Mejores jugadas consideradas:",
+        Text::Visits => ", {} visitas) ",
+        Text::CurrentPositionScore => "Puntuación de la posición: ",
+        Text::MaterialBalance => "Balance de material: ",
+        Text::BotKingSafety => "Seguridad del rey del bot: ",
+        Text::YourKingSafety => "Seguridad de tu rey: ",
+        Text::CenterControl => "Control del centro: ",
+        Text::EvaluationsHidden => "La niebla oculta las evaluaciones hasta que acabe la partida",
+
+        Text::Yes => "Sí",
+        Text::No => "No",
+        Text::QuitTitle => "Salir",
+        Text::QuitQuestion => "La partida en curso no ha terminado. ¿Salir de todos modos?",
+        Text::ResignTitle => "Abandonar",
+        Text::ResignQuestion => "¿Abandonar esta partida?",
+        Text::NewGameTitle => "Nueva partida",
+        Text::NewGameQuestion => "¿Dejar la partida en curso y empezar otra?",
+        Text::SetupGameQuestion => "¿Dejar la partida en curso y empezar esta?",
+        Text::ResumeTitle => "Reanudar partida",
+        Text::PromptKeys => "ENTER lo ejecuta   ESC cancela",
+        Text::ResumeQuestion => "Tu partida de {} del {} quedó sin terminar tras {} jugadas. ¿Reanudarla?",
+
+        Text::UciStandardOnly => "El motor UCI solo juega ajedrez clásico",
+        Text::UciWontStart => "No se pudo arrancar el motor UCI: {}",
+        Text::InvalidMove => "Jugada no válida",
+        Text::MoveSuccessful => "Jugada hecha",
+        Text::YourMove => "Te toca",
+        Text::YourMoveAfter => "Te toca ({} jugó {})",
+        Text::MovedSuccessfully => "{} ya ha jugado",
+        Text::WaitForEngine => "Espera a que el motor termine de pensar",
+        Text::WaitForPlayer => "Espera a que juegue el otro jugador",
+        Text::NobodyToResignTo => "En un tablero de análisis no hay nadie ante quien abandonar ni a quien ofrecer tablas",
+        Text::PlaysOn => "{} sigue jugando",
+        Text::NoNetworkDraws => "Las ofertas de tablas no van por la red; abandona o sigue jugando",
+        Text::HostStartsGames => "Las partidas en red las empieza el anfitrión",
+        Text::NewGameStarted => "Nueva partida de {} empezada",
+        Text::GoToScreen => "Ir a la pantalla",
+        Text::UnfinishedGameGone => "La partida sin terminar ya no está",
+        Text::ResumedFromMove => "Reanudada desde la jugada {}",
+        Text::ResumedGame => "Reanudada tu partida del {}",
+        Text::NoPieceOfYoursOn => "No tienes ninguna pieza en {}",
+        Text::PremoveSet => "Premovida {}: se juega cuando te toque, si entonces es legal ({} la cancela)",
+
+        Text::DumpUsage => "Uso: dump search",
+        Text::BlindfoldOn => "A ciegas: las piezas no se ven hasta que acabe la partida",
+        Text::BlindfoldOff => "A ciegas desactivado",
+        Text::JoinUsage => "Uso: join <host>[:puerto]",
+        Text::NoSearchYet => "El bot aún no ha buscado ninguna jugada en esta partida",
+        Text::SearchHidden => "La búsqueda del bot no se ve hasta que acabe la partida",
+        Text::SearchWritten => "Búsqueda guardada en {}",
+        Text::SearchNotWritten => "No se pudo guardar la búsqueda: {}",
+        Text::DiagramUsage => "Uso: diagram [svg|txt]",
+        Text::BoardHidden => "El tablero no se ve hasta que acabe la partida",
+        Text::DiagramWritten => "Diagrama guardado en {}",
+        Text::DiagramNotWritten => "No se pudo guardar el diagrama: {}",
+        Text::WhyNotUsage => "Uso: why-not <jugada> (p. ej. 'why-not e2 e4')",
+        Text::NotLegalHere => "{} no es una jugada legal aquí",
+        Text::NothingToCompare => "El motor no tiene jugadas con las que comparar",
+        Text::WhyNot => "¿Por qué no {}?",
+        Text::EnginesChoiceToo => "{} es también la elección del motor ({})",
+        Text::EnginePrefersOver => "El motor prefiere {} ({}) a {} ({})",
+        Text::CostsPawns => "{} cuesta unos {} peones",
+        Text::HoldsUp => "{} aguanta igual de bien en esta búsqueda",
+        Text::MainReply => "Respuesta principal: {}",
+        Text::Line => "Línea: {}",
+        Text::Compared => "Comparada {} con {}",
+
+        Text::TheClipboard => "el portapapeles",
+        Text::TheTerminalsClipboard => "el portapapeles del terminal",
+        Text::CouldntCopy => "No se pudo copiar: {}",
+        Text::NoClipboard => "Aquí no hay portapapeles del que pegar; escribe las jugadas o prepara la posición en el editor",
+        Text::CouldntPaste => "No se pudo leer el portapapeles: {}",
+        Text::CopiedTo => "{} copiado en {}",
+        Text::PositionPasted => "Posición pegada; ENTER juega desde ella",
+        Text::NothingToPaste => "El portapapeles no tiene ningún FEN ni PGN",
+        Text::PastedMoveUnreadable => "No se pudo leer la primera jugada de la partida pegada, {}",
+        Text::OnlyFirstMovesRead => "Solo se pudieron leer las primeras {} jugadas",
+
+        Text::NoCompletions => "Nada que completar",
+        Text::Completions => "{} opciones: {}",
+        Text::DrawAlreadyOffered => "Ya ofreciste tablas en esta jugada",
+        Text::DrawAccepted => "Ofreces tablas y el bot acepta",
+        Text::DrawDeclined => "El bot rechaza tu oferta de tablas",
+        Text::DrawDeclinedInFog => "El bot no puede juzgar la posición a través de la niebla y rechaza las tablas",
+        Text::UnknownDrill => "Ejercicio desconocido. Prueba: {}",
+        Text::NoNetworkDrills => "Los ejercicios son contra el bot, no por la red",
+        Text::DrillTask => "Ejercicio ({}): {}",
+        Text::DrillPassed => "Ejercicio superado: {} en {} jugadas",
+        Text::DrillFailed => "Ejercicio fallido: {} tras {} jugadas; 'drill {}' para otro",
+        Text::NoSpeedruns => "Todavía no hay speedruns",
+        Text::BestSpeedrun => "Mejor speedrun: {}",
+        Text::SpeedrunUsage => "Uso: speedrun [semilla|scores]",
+        Text::NoNetworkSpeedruns => "Los speedruns son contra el jugador al azar, no por la red",
+        Text::SpeedrunTask => "Speedrun con la semilla {}: da mate al jugador al azar en las menos jugadas que puedas",
+        Text::SpeedrunFailed => "Speedrun terminado sin mate; 'speedrun {}' para volver a probar la semilla",
+        Text::SpeedrunPlace => "Speedrun: {}, n.º {} de la clasificación",
+        Text::SpeedrunDone => "Speedrun: {}",
+        Text::SpeedrunNotSaved => "Speedrun: {}, pero no se pudo guardar: {}",
+
+        Text::SideWinsBy => "ganan las {} por {}",
+        Text::PlayerWinsBy => "{} gana por {}",
+        Text::YouWinBy => "Ganas por {}",
+        Text::DrawBy => "Tablas por {}",
+        Text::GameOverNarrated => "Fin de la partida. {}",
+        Text::RatingChange => "puntuación {} ({})",
+        Text::OddsChange => "ventaja de {}: vale {} en {} partida(s)",
+        Text::YourRating => "Tu {}",
+        Text::RatingNotSaved => "{} (no se pudo guardar tu {}: {})",
+        Text::GameSaved => "{}. Guardada en tu historial",
+        Text::GameNotSaved => "{}, pero la partida no se pudo guardar: {}",
+
+        Text::PlayingAtLevel => "Jugando en nivel {}. Niveles: {}",
+        Text::UnknownLevel => "Nivel desconocido '{}'",
+        Text::Now => "desde ahora",
+        Text::FromNextGame => "desde la próxima partida",
+        Text::BotLevel => "Nivel del bot: {} {}",
+        Text::ButNotSaved => "{}, pero no se pudo guardar: {}",
+        Text::PlaysAtElo => "El bot juega a unos {}; 'elo off' para volver al nivel",
+        Text::PlaysAtLevel => "El bot juega en nivel {}; 'elo <{}-{}>' para apuntar a una puntuación",
+        Text::TargetEloRange => "El Elo objetivo va de {} a {}, u off",
+        Text::BotAtAbout => "El bot juega a unos {} {} (sin puntuar)",
+        Text::NoNetworkSwitch => "En una partida en red no se puede cambiar de lado",
+        Text::AnalysisBothSides => "En un tablero de análisis mueves los dos lados",
+        Text::TheGameIsOver => "La partida ha terminado",
+        Text::SidesSwitched => "Ahora juegas con {} y el bot con {}",
+        Text::SidesSwitchedUnrated => "Ahora juegas con {} y el bot con {} (sin puntuar)",
+        Text::LearningEngine => "el motor que aprende",
+        Text::TheUciEngine => "el motor UCI",
+        Text::AnotherPlayer => "otro jugador",
+
+        Text::TabList => "Pestañas: {}. Prueba: tab new|analysis|close|<n>",
+        Text::NoSuchTab => "No hay ninguna pestaña {}",
+        Text::TabUsage => "Uso: tab [new|analysis|close|<n>]",
+        Text::NoNetworkTabs => "No hay pestañas mientras estás conectado con otro jugador",
+        Text::TooManyTabs => "Como mucho puede haber {} pestañas; cierra una antes",
+        Text::AnalysisFromMove => "Análisis desde la jugada {}",
+        Text::NewGameTab => "Pestaña {}: una partida nueva contra {}",
+        Text::AnalysisTab => "Pestaña {}: un tablero de análisis; mueve cualquiera de los lados",
+        Text::TabTitle => "Pestaña {}: {}",
+        Text::OneTab => "Solo hay una pestaña; {} abre otra",
+        Text::OnlyTab => "Esta es la única pestaña",
+        Text::TabClosed => "Pestaña {} cerrada",
+        Text::TabOver => "contra {} · {}",
+        Text::TabThinking => "contra {} · pensando",
+        Text::TabYourMove => "contra {} · te toca",
+
+        Text::GoOnAnalysisOnly => "go es para tableros de análisis; 'tab analysis' abre uno",
+        Text::GoUsage => "Uso: go [infinite] [movetime <ms>] [nodes <n>] [depth <n>]",
+        Text::NothingToSearch => "La partida ha terminado; no hay nada que buscar",
+        Text::AnalysingEach => "Analizando cada posición {}",
+        Text::MovingNow => "Jugando ya",
+        Text::AnalysisStopped => "Análisis detenido",
+        Text::NothingSearching => "No hay nada buscando",
+        Text::BotPersonality => "El bot juega {}. Personalidades: {}",
+        Text::UnknownPersonality => "Personalidad desconocida '{}'",
+        Text::BotPlays => "El bot juega {} {}",
+        Text::PlayingOpponents => "Juegas contra {}. Rivales: {}",
+        Text::UnknownOpponent => "Rival desconocido '{}'",
+        Text::QuitToPlayOthers => "Estás conectado con otro jugador; sal para jugar contra otro",
+        Text::FinishFirst => "Termina o abandona antes esta partida",
+        Text::PlayingFromNextGame => "Jugarás contra {} desde la próxima partida",
+        Text::PlayingNow => "Ahora juegas contra {}",
+
+        Text::AlreadyConnected => "Ya estás conectado con otro jugador",
+        Text::HostUsage => "Uso: host [puerto]",
+        Text::WaitingToJoin => "Esperando a que otro jugador se una en el puerto {}",
+        Text::CouldntHost => "No se pudo abrir una partida en el puerto {}: {}",
+        Text::Connecting => "Conectando con {}…",
+        Text::NotConnected => "No estás conectado con otro jugador",
+        Text::CouldntReach => "No se pudo llegar al otro jugador: {}",
+        Text::ChatNetworkOnly => "El chat es para partidas en red",
+        Text::ChatOn => "Chat: Enter envía lo que escribes al otro jugador ({} para comandos)",
+        Text::ChatOff => "Otra vez comandos y jugadas ({} para el chat)",
+        Text::TakebackPending => "Aún esperas respuesta a tu petición de deshacer",
+        Text::TakebackAskedFirst => "El otro jugador lo pidió antes: 'accept' o 'decline'",
+        Text::NotMovedYet => "Todavía no has jugado",
+        Text::TakebackAsked => "Has pedido al otro jugador deshacer tu última jugada",
+        Text::NoTakebackAsked => "Nadie ha pedido deshacer",
+        Text::TakebackDeclined => "Deshacer rechazado",
+        Text::TakebackUnknownPosition => "Esta partida nunca pasó por la posición a la que pidió volver",
+        Text::CouldntTalk => "No se pudo hablar con el otro jugador: {}",
+        Text::WaitingForHost => "Conectado; esperando a que el anfitrión empiece una partida",
+        Text::SomeoneJoined => "Alguien se ha unido; juegas con blancas",
+        Text::HostsVariantMissing => "El anfitrión empezó una partida de {}, que esta versión no tiene",
+        Text::NetworkGameStarted => "Nueva partida de {}; juegas con {}",
+        Text::OpponentResigns => "El rival abandona",
+        Text::OpponentAsksTakeback => "El rival pide deshacer",
+        Text::TakebackOffered => "El otro jugador pide deshacer su última jugada: 'accept' o 'decline'",
+        Text::TakebackAgreed => "El otro jugador ha aceptado; jugada deshecha",
+        Text::TakebackMissingMove => "El otro jugador deshizo una jugada que esta partida no tiene",
+        Text::OpponentSays => "El rival dice: {}",
+        Text::TakebackRefused => "El otro jugador ha rechazado deshacer",
+        Text::ConnectionLost => "Conexión perdida: {}",
+        Text::NotConnectedReason => "Sin conexión: {}",
+
+        Text::EngineStopped => "{} se ha detenido: {}",
+        Text::TheWeightsFolder => "la carpeta de pesos",
+        Text::NoWeightsFiles => "No hay archivos de pesos en {}",
+        Text::BuiltIn => "de serie",
+        Text::WeightsFromNextGame => "Pesos del bot: {} desde la próxima partida",
+        Text::WeightsNow => "Pesos del bot: {} desde ahora",
+        Text::WeightsOwn => "los de los pesos",
+        Text::ValuesFromNextGame => "Valores de las piezas: {} desde la próxima partida",
+        Text::ValuesNow => "Valores de las piezas: {} desde ahora",
+        Text::WeightsNotLoaded => "No se pudieron cargar los pesos del bot: {}",
+
+        Text::NewGameNarrated => "Nueva partida de {}. Juegas con {} y el bot con {}",
+        Text::BlindfoldIsOn => "Estás jugando a ciegas; escribe 'blindfold' para volver a ver el tablero",
+        Text::YouLower => "tú",
+        Text::TheBotLower => "el bot",
+        Text::SideToMove => "mueven las {} ({})",
+        Text::AccessibleOn => "Modo accesible activado",
+        Text::AccessibleOff => "Modo accesible desactivado",
+        Text::BotMoved => "El bot ha jugado",
+        Text::BotPlayed => "El bot ha jugado {}",
+        Text::YouMoved => "Has jugado",
+        Text::YouPlayed => "Has jugado {}",
+        Text::MovedInFog => "las {} han movido, ocultas por la niebla",
+        Text::SpokenMove => "{} de {} a {}",
+        Text::Takes => ", captura {}",
+        Text::CheckSpoken => ", jaque",
+        Text::EmptyRank => "vacía",
+        Text::RankListing => "Fila {}: {}",
+        Text::SquareHolds => "{} {}",
+        Text::UnknownPiece => "Pieza desconocida '{}'",
+
+        Text::TargetEloGame => "El bot juega a unos {}: una búsqueda limitada y de vez en cuando una jugada peor (sin puntuar)",
+        Text::SparringGame => "Entrenamiento a tu nivel, unos {}: el bot falla de vez en cuando (sin puntuar)",
+        Text::Unrated => "{} (sin puntuar)",
+        Text::SettingsNotSaved => "No se pudo guardar la configuración de la partida: {}",
+        Text::OfflinePuzzle => "Sin conexión, así que este es el problema del {}",
+        Text::LichessUnreachable => "No se pudo conectar con lichess. El problema del día necesita conexión la primera vez",
+        Text::NotAPiece => "'{}' no es una pieza; usa KQRBNP para las blancas y kqrbnp para las negras",
+        Text::NoNetworkEdits => "Las posiciones editadas no se pueden jugar por la red",
+        Text::PlayingFromPosition => "Jugando desde tu posición (sin puntuar)",
+        Text::EngineFoundNoMoves => "El motor no ha encontrado jugadas",
+        Text::EnginePrefers => "El motor prefiere {}",
+
+        Text::OddsOf => "ventaja {}, ",
+        Text::HistoryRow => "{}  con {}  {} jugadas  bot {}  {}",
+        Text::UnknownStyle => "Estilo desconocido. Prueba: {}",
+        Text::EngineNamed => "Motor ({})",
+        Text::BackToEngine => "las {} vuelven al motor",
+        Text::YouPlaySide => "Ahora juegas con {}",
+        Text::CouldntRelay => "No se pudo atender a espectadores en el puerto {}: {}",
+        Text::BotFailedToMove => "Bot: el bot no ha conseguido jugar",
+        Text::NoSpeedrunTakebacks => "En un speedrun no se deshacen jugadas",
+        Text::TakenBackToMove => "Vuelta a la jugada {}",
+
+        Text::AnalyticsShown => "Análisis visible",
+        Text::AnalyticsHidden => "Análisis oculto",
+        Text::HistoryShown => "Historial de jugadas visible",
+        Text::HistoryHidden => "Historial de jugadas oculto",
+        Text::BoardFullWidth => "El tablero ocupa todo el ancho mientras el historial está oculto",
+        Text::BoardWidest => "El tablero ya está lo más ancho posible",
+        Text::BoardNarrowest => "El tablero ya está lo más estrecho posible",
+        Text::BoardWidth => "Tablero al {}% del ancho",
+        Text::AnalyticsPanelHidden => "El panel de análisis está oculto",
+        Text::AnalyticsTallest => "El panel de análisis ya está lo más alto posible",
+        Text::AnalyticsShortest => "El panel de análisis ya está lo más bajo posible",
+        Text::AnalyticsHeight => "Análisis al {}% del alto",
+        Text::LayoutNotSaved => "{} (no se pudo guardar la disposición: {})",
+        Text::Hint => "Pista: {} {}",
+        Text::NoHint => "No hay ninguna pista",
+        Text::PremoveCancelled => "Premovida {} cancelada",
+        Text::FinishOrStartOver => "Termina o abandona antes esta partida, o escribe 'new' para empezar de nuevo",
+        Text::ThreatsHidden => "Amenazas ocultas",
+        Text::ThreatsInFog => "Las amenazas no se pueden mostrar a través de la niebla",
+        Text::ThreatsShown => "Rojo: tus piezas colgadas. Verde: las del bot",
+        Text::ContemptSet => "Desprecio fijado en {}",
+        Text::EngineLine => "Mostrando {} línea del motor",
+        Text::EngineLines => "Mostrando {} líneas del motor",
+        Text::BellOn => "Campana activada para {}",
+        Text::BellOff => "Campana desactivada para {}",
+        Text::Cancelled => "Cancelado",
+        Text::NowBound => "{} ahora va en {}",
+        Text::BoundNotSaved => "{} va en {}, pero no se pudo guardar: {}",
+        Text::PressNewKey => "Pulsa la nueva tecla para {} (esc cancela)",
+        Text::DefaultKeys => "Teclas por defecto restauradas",
+        Text::UsingTheme => "Usando {} con las piezas {}",
+
+        Text::CommandBox => "cuadro de comandos",
+        Text::TheBoard => "tablero",
+        Text::TheMoveHistory => "historial de jugadas",
+        Text::TheAnalytics => "análisis",
+        Text::FocusCommand => "Foco: {} (Tab pasa al siguiente)",
+        Text::FocusBoard => "Foco: {} (las flechas mueven, Enter coge y suelta una pieza)",
+        Text::FocusPanel => "Foco: {} (las flechas y RePág/AvPág desplazan)",
+
+        Text::UciEngine => "Motor UCI",
+        Text::SecondOpinion => "Segunda opinión: {}",
+        Text::AtDepth => " a profundidad {}",
+        Text::BestAfter => ", mejor ",
+        Text::EngineNameThinking => "{}: pensando…",
+        Text::SearchStats => "Búsqueda: {} simulaciones en {}s ({}/s), partidas simuladas de {} medias jugadas, ramificación {}, árbol de {} nodos{}",
+        Text::Recycled => ", {} reciclados",
+        Text::MemoryStats => "Memoria: {}, tabla de evaluaciones al {}% con {}% de aciertos, cortes en la primera jugada {}%",
+        Text::SafetyTerms => " ({} atacantes, peso {}, escudo {}, columnas abiertas {})",
+
+        Text::SayPrompt => "di: ",
+        Text::ReplayUsage => "Uso: replay [número de jugada], p. ej. replay 12 o replay 12...",
+        Text::NoSuchMove => "Todavía no hay jugada {}",
+        Text::NoReplaysInFog => "No hay repeticiones mientras dura la niebla",
+        Text::BeforeAnalysisBoard => "Esa jugada se hizo antes de abrir el tablero de análisis",
+        Text::BackToMove => "De vuelta a la jugada {}",
+        Text::AnalysisBoardTag => " · tablero de análisis",
+        Text::VsTag => " · contra {}",
+        Text::DrillTag => " · ejercicio {}, jugada {}/{}",
+        Text::OddsTag => " · ventaja {}",
+        Text::PersonalityTag => " · bot {}",
+        Text::ValuesTag => " · valores {}",
+        Text::SpectatorsTag => " · {} mirando en :{}",
+
+        Text::Finished => "Terminada {}",
+        Text::FeedClosed => "Retransmisión cerrada",
+        Text::WaitingForMoves => "Esperando jugadas…",
+        Text::SideMoves => "juegan las {}",
+        Text::CantFollow => "No se puede seguir {}",
+        Text::Thinking => "{} pensando…",
+        Text::EngineEval => "Motor: {}",
+        Text::FollowKeys => "←/→  avanzar\nEnd  volver al directo\ne    motor sí/no\nesc  menú",
+        Text::SidePlayer => "{}: {}",
+        Text::Paused => "En pausa",
+        Text::PlayerToMove => "Juega {}",
+        Text::ThinkTime => "Tiempo para pensar: {}s por jugada",
+        Text::SimulationsBest => "{} simulaciones, mejor {}",
+        Text::WatchKeys => "espacio  pausa/seguir\n→      una jugada\n+/-    más rápido/más lento\nwhite/black  tomar un lado\nesc    volver a la partida",
+        Text::FetchingPuzzle => "Descargando el problema de hoy de lichess…",
+        Text::EscMenu => "esc  menú",
+        Text::PuzzleRating => "Puntuación {}",
+        Text::Shown => "Mostrado",
+        Text::Solved => "Resuelto",
+        Text::MoveOf => "Jugada {} de {}",
+        Text::MistakesHints => "Errores: {}  Pistas: {}",
+        Text::PuzzleKeys => "escribe una jugada, ENTER para probarla\nhint  nombra la pieza y luego la jugada\nshow  juega la solución\nesc   menú",
+
+        Text::HeatmapAbout => "Los valores por casilla del motor, en peones, desde el lado de las blancas; los de las negras van reflejados.",
+        Text::NothingLearned => "Aún no se ha aprendido nada para esta tabla",
+        Text::BiggestChanges => "Cambios más grandes:",
+        Text::HeatmapKeys => "←/→  pieza\n↑/↓  medio juego o final\nc    valores aprendidos o cambios\nesc  volver a la partida",
+        Text::NoTournaments => "Todavía no hay torneos.",
+        Text::HoldTournament => "Organiza uno desde la terminal, p. ej. chessrl arena balanced aggressive positional",
+        Text::EscBackToGame => "esc  volver a la partida",
+        Text::TournamentSummary => "{}, {} partidas a {} por jugada, {}",
+        Text::TournamentOf => "Torneo {} de {}   ←/→ otros   esc volver a la partida",
+        Text::NameTheSquare => "Nombra la casilla marcada",
+        Text::SeenFrom => "(vista desde el lado de las {})",
+        Text::IsRight => "{} es correcto",
+        Text::NotThat => "{}, no {}",
+        Text::QuizScore => "Aciertos: {}/{}",
+        Text::QuizStreak => "Racha: {} (mejor {})",
+        Text::QuizKeys => "a-h 1-8 nombra la casilla\nbksp   borra una\n{} gira el tablero\nesc    volver",
+        Text::SearchPrompt => "Buscar: ",
+        Text::NoFinishedGames => "Todavía no hay partidas terminadas",
+        Text::NoGamesMatch => "Ninguna partida coincide",
+        Text::HistoryKeys => "escribe para buscar   ↑↓ elegir   ENTER repetir   ESC volver",
+
+        Text::Current => " (actual)",
+        Text::PieceSet => "Juego de piezas",
+        Text::ThemesKeys => "←→ tema   ↑↓ piezas   ENTER aplicar   ESC volver",
+        Text::FullSearch => "la búsqueda completa del nivel",
+        Text::RatedByGuesswork => ", estimado a ojo hasta que se ejecute 'chessrl calibrate'",
+        Text::StrengthCap => "como mucho {} simulaciones por jugada, cediendo hasta {} peones{}",
+        Text::TossedEachGame => "se sortea en cada partida",
+        Text::CountsTowardRating => "cuenta para tu puntuación",
+        Text::UnratedNote => "sin puntuar",
+        Text::StrengthStandsIn => "la fuerza de abajo lo sustituye",
+        Text::RatedAbout => "unos {} de puntuación",
+        Text::LevelWord => "nivel",
+        Text::LongestThink => "lo más que piensa el bot; la fuerza lo limita",
+        Text::WithTheOdds => "{}s con la ventaja",
+        Text::ThinkAtLevel => "lo que piensa el bot en nivel {}; no hay reloj de partida",
+        Text::OddsWorthNote => "{}, te vale {} en {} partida(s)",
+        Text::NoOddsGames => "{}, aún no hay partidas con esta ventaja",
+
+        Text::ContemptAbout => "Lo que le cuestan unas tablas al bot, en centipeones: evita las repeticiones cuando va por delante y las busca cuando va por detrás",
+        Text::MultiPvAbout => "Cuántas de las mejores jugadas del motor muestra el panel de análisis, cada una con su línea",
+        Text::WeightsAbout => "Los valores y tablas de las piezas del bot: los de serie o un archivo de la carpeta de pesos",
+        Text::PieceValuesAbout => "Un juego con nombre en lugar de los de los pesos: simplified, classical 1/3/3/5/9 o kaufman 1/3.25/3.25/5/9.75",
+        Text::Bell => "campana",
+        Text::Off => "no",
+        Text::AboutIntro => "ajedrez con un rival de aprendizaje automático\nbasado solo en aprendizaje por refuerzo.\n\nComandos:\ne2 e4  - Mueve una pieza de e2 a e4\nknight to f3 / pawn takes d5 - Mueve una pieza, dicho con palabras\nwhy-not e2 e4 - Compara una jugada con la elección del motor\nreplay [12|12...] - Recorre la partida hasta ahora, desde una jugada\nnew [standard|fog|atomic|koth|horde|3check] - Empieza una partida nueva\nresign / draw - Abandona u ofrece tablas al bot\nswitch - Cambia de lado con el bot\nUna jugada escrita mientras el bot piensa es una premovida, que se juega en tu turno si es legal\ntab [new|analysis|close|<n>] - Abre, cierra o cambia a la pestaña de otra partida\ndump search - Guarda en un archivo la última búsqueda del bot\nlevel <easy|medium|hard|expert|sparring> - Fija la fuerza del bot\nelo <400-2000|off> - Mantén al bot cerca de esa puntuación\npersonality <balanced|aggressive|positional|gambit|defensive> - Fija el estilo del bot",
+        Text::AboutOutro => "La IA aprende mientras se juega\ny mejora su estrategia con el tiempo.",
+
+        Text::KeysHeading => "Teclas",
+        Text::TabKeyAbout => "Completar una casilla, o pasar el foco al panel siguiente",
+        Text::SpaceKeyAbout => "Hacer que el bot juegue ya (sin nada escrito)",
+        Text::PageKeysAbout => "Desplazar el historial de jugadas",
+        Text::ArrowKeysAbout => "Recuperar comandos anteriores",
+        Text::MovesHeading => "Jugadas",
+        Text::CoordinateMoveAbout => "Mover la pieza de e2 a e4",
+        Text::SpokenMoveAbout => "Mover una pieza, dicho con palabras",
+        Text::CommandsHeading => "Comandos",
+
+        Text::NewCommand => "Empezar una partida nueva",
+        Text::ResignCommand => "Abandonar la partida",
+        Text::DrawCommand => "Ofrecer tablas al bot",
+        Text::SwitchCommand => "Cambiar de lado con el bot",
+        Text::WhyNotCommand => "Comparar una jugada con la elección del motor",
+        Text::ReplayCommand => "Recorrer la partida hasta ahora, desde una jugada",
+        Text::LevelCommand => "Fijar la fuerza del bot",
+        Text::EloCommand => "Mantener al bot cerca de esa puntuación",
+        Text::PersonalityCommand => "Fijar el estilo del bot",
+        Text::OpponentCommand => "Jugar contra el bot, un motor UCI u otro jugador",
+        Text::DrillCommand => "Practicar un final",
+        Text::SpeedrunCommand => "Recorrer a contrarreloj una serie de posiciones",
+        Text::TabCommand => "Abrir, cerrar o cambiar a la pestaña de otra partida",
+        Text::GoCommand => "Fijar cuánto mira un tablero de análisis",
+        Text::StopCommand => "Hacer que el bot juegue ya, o parar el análisis",
+        Text::HostCommand => "Esperar a que se una otro jugador",
+        Text::JoinCommand => "Unirse a la partida de otro jugador",
+        Text::TakebackCommand => "Pedir al otro jugador que deshaga tu jugada",
+        Text::AcceptCommand => "Aceptar la petición de deshacer del otro jugador",
+        Text::DeclineCommand => "Rechazar la petición de deshacer del otro jugador",
+        Text::BlindfoldCommand => "Ocultar las piezas hasta que acabe la partida",
+        Text::BoardCommand => "Leer la posición en voz alta",
+        Text::AccessibleCommand => "Cambiar a la vista de texto sencillo para lectores de pantalla",
+        Text::DiagramCommand => "Guardar la posición como diagrama SVG o de texto",
+        Text::HeatmapCommand => "Mostrar las casillas que el bot ha aprendido a preferir",
+        Text::WatchCommand => "Hacer que el motor juegue contra sí mismo",
+        Text::ArenaCommand => "Ver los torneos de la arena",
+        Text::DumpCommand => "Guardar en un archivo la última búsqueda del bot",
+
+        Text::CouldntFollowPuzzle => "No se pudo seguir la partida del problema en {}",
+        Text::UnreadableSolution => "No se pudo leer la solución del problema",
+        Text::SideToPlay => "Juegan {}",
+        Text::SolvedAlready => "Ya resuelto; esc para el menú",
+        Text::TypeAMove => "Escribe una jugada como e2 e4, e2e4 o Nf3",
+        Text::NotItTryAgain => "{} no es; inténtalo otra vez",
+        Text::SolvedExclaimed => "¡Resuelto!",
+        Text::RightKeepGoing => "¡Bien! Respondieron {}; sigue",
+        Text::LookAtYour => "Fíjate en tu {}",
+        Text::PlayMove => "Juega {}",
+        Text::TheSolution => "La solución: {}",
+        Text::TypeToTakeOver => "Mueven {}; escribe {} para tomar el control",
+
+        Text::PremovePlayed => "Premovida {} jugada",
+        Text::PremoveDropped => "Premovida {} descartada: {}",
+        Text::YouResign => "Abandonas",
+        Text::SideToMoveScore => "Mueven {}: {}",
+        Text::BranchVisits => "{} {} ({} visitas)",
+        Text::TimeUsed => "Tiempo:      ",
+        Text::SecondsOf => "{} de {} s",
+
+        Text::NoPieceSelected => "No hay ninguna pieza en la casilla elegida",
+        Text::InvalidCoordinates => "Coordenadas no válidas. Usa a1-h8",
+        Text::InvalidCommand => "Comando no válido. Usa: <desde> <hasta> (p. ej. 'e2 e4')",
+        Text::GameOver => "La partida ha terminado ({})",
+        Text::GameOverPlayAgain => "La partida ha terminado ({}). Escribe 'new' para jugar otra, o {} para preparar la siguiente",
+        Text::UnknownVariant => "Variante desconocida. Prueba: {}",
+        Text::NothingToUndo => "No hay nada que deshacer",
+        Text::MoveTakenBack => "Jugada deshecha",
+        Text::NothingToReplay => "Todavía no hay nada que repetir",
+        Text::NoPieceOn => "No hay ninguna pieza en {}",
+        Text::BotsPiece => "Esa pieza ({}) es del bot",
+        Text::SameSquare => "Una jugada tiene que ir a otra casilla",
+        Text::OwnPieceThere => "Tu {} ya está en {}",
+        Text::KingShape => "El rey solo se mueve una casilla cada vez",
+        Text::QueenShape => "La dama se mueve por filas, columnas y diagonales",
+        Text::RookShape => "La torre solo se mueve por filas y columnas",
+        Text::BishopShape => "El alfil solo se mueve en diagonal",
+        Text::KnightShape => "El caballo se mueve dos casillas en un sentido y una en el otro",
+        Text::PawnShape => "El peón solo avanza, o en diagonal hacia delante para capturar",
+        Text::Blocked => "{} no puede saltar piezas, y {} está en medio",
+        Text::PawnBlocked => "El peón no captura hacia delante, y {} está en medio",
+        Text::PawnCapturesOnly => "El peón solo se mueve en diagonal para capturar",
+        Text::PawnDoubleStep => "El peón solo avanza dos casillas desde su casilla inicial",
+        Text::StillInCheck => "Tienes que salir del jaque: te ataca {}",
+        Text::LeavesKingInCheck => "Eso dejaría a tu rey en jaque: lo ataca {}",
+        Text::VariantRule => "Las reglas de {} no permiten esa jugada",
+        Text::PieceOn => "{} en {}",
+        Text::Piece(piece) => match piece {
+            PieceType::King => "rey",
+            PieceType::Queen => "dama",
+            PieceType::Rook => "torre",
+            PieceType::Bishop => "alfil",
+            PieceType::Knight => "caballo",
+            PieceType::Pawn => "peón",
+        },
+        Text::ThePiece(piece) => match piece {
+            PieceType::King => "el rey",
+            PieceType::Queen => "la dama",
+            PieceType::Rook => "la torre",
+            PieceType::Bishop => "el alfil",
+            PieceType::Knight => "el caballo",
+            PieceType::Pawn => "el peón",
+        },
+        Text::Side(color) => match color {
+            Color::White => "blancas",
+            Color::Black => "negras",
+        },
+        Text::SidePiece(color, piece) => match (color, piece) {
+            (Color::White, PieceType::King) => "rey blanco",
+            (Color::White, PieceType::Queen) => "dama blanca",
+            (Color::White, PieceType::Rook) => "torre blanca",
+            (Color::White, PieceType::Bishop) => "alfil blanco",
+            (Color::White, PieceType::Knight) => "caballo blanco",
+            (Color::White, PieceType::Pawn) => "peón blanco",
+            (Color::Black, PieceType::King) => "rey negro",
+            (Color::Black, PieceType::Queen) => "dama negra",
+            (Color::Black, PieceType::Rook) => "torre negra",
+            (Color::Black, PieceType::Bishop) => "alfil negro",
+            (Color::Black, PieceType::Knight) => "caballo negro",
+            (Color::Black, PieceType::Pawn) => "peón negro",
+        },
+        Text::ActionAbout(action) => match action {
+            Action::Quit => "Salir del juego",
+            Action::Menu => "Volver al menú principal",
+            Action::Undo => "Deshacer tu última jugada",
+            Action::Flip => "Girar el tablero",
+            Action::Hint => "Pedir una pista al motor",
+            Action::Threats => "Marcar las piezas atacadas y sin defensa",
+            Action::CopyFen => "Copiar la posición como FEN",
+            Action::CopyPgn => "Copiar la partida como PGN",
+            Action::Paste => "Cargar un FEN o PGN del portapapeles",
+            Action::NewTab => "Empezar otra partida en una pestaña nueva",
+            Action::NextTab => "Ir a la pestaña siguiente",
+            Action::ToggleAnalytics => "Mostrar u ocultar el panel de análisis",
+            Action::ToggleHistory => "Mostrar u ocultar el historial de jugadas",
+            Action::BoardWider => "Dar al tablero más anchura",
+            Action::BoardNarrower => "Dar al tablero menos anchura",
+            Action::AnalyticsTaller => "Hacer más alto el panel de análisis",
+            Action::AnalyticsShorter => "Hacer más bajo el panel de análisis",
+            Action::ToggleChat => "Alternar la caja de comandos entre comandos y chat",
+            Action::FocusHistory => "Llevar las flechas de la caja de comandos al historial de jugadas y al revés",
+            Action::Help => "Listar todas las teclas y comandos",
+            Action::Palette => "Buscar un comando escribiendo parte de su nombre",
+            Action::NewGame => "Preparar la siguiente partida cuando acabe esta",
+        },
+    }
+}
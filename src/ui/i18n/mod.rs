@@ -0,0 +1,736 @@
+// the screens' text in the player's language. each language is a file with
+// one match over Text, so a new one is a copy of en.rs with the strings
+// changed, and the compiler points out anything it's missing
+mod en;
+mod es;
+
+use crate::game::piece::{Color, PieceType};
+use crate::ui::keymap::Action;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static LANGUAGE: AtomicU8 = AtomicU8::new(Language::English as u8);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Language {
+    English,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Spanish];
+
+    // the code `language` takes in config.toml
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+        }
+    }
+
+    // "auto" goes by the locale, then English
+    pub fn resolve(setting: &str) -> Language {
+        match setting {
+            "auto" | "" => Language::from_locale().unwrap_or(Language::English),
+            code => Language::from_code(code).unwrap_or(Language::English),
+        }
+    }
+
+    // "es", "es_MX.UTF-8" and the like
+    fn from_code(code: &str) -> Option<Language> {
+        let lang = code.split(['_', '.', '-']).next()?.to_lowercase();
+        Language::ALL.into_iter().find(|language| language.code() == lang)
+    }
+
+    // the first of the variables that's set decides, as for gettext
+    fn from_locale() -> Option<Language> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Language::from_code(&value))
+    }
+}
+
+pub fn set_language(language: Language) {
+    LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    match LANGUAGE.load(Ordering::Relaxed) {
+        1 => Language::Spanish,
+        _ => Language::English,
+    }
+}
+
+// everything the catalogs hold. "{}" in a string is filled in by `fill`,
+// in order, and a list of keys is one string with a line for each
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Text {
+    // main menu
+    MenuPlay,
+    MenuEditor,
+    MenuTraining,
+    MenuDailyPuzzle,
+    MenuHistory,
+    MenuProfile,
+    MenuThemes,
+    MenuSettings,
+    MenuAbout,
+    MenuInstructions,
+
+    // panel titles
+    MoveHistory,
+    Analytics,
+    Command,
+    Chat,
+    Replay,
+    Moves,
+    // "Commands (12)"
+    PaletteTitle,
+    NothingMatches,
+
+    // screens' titles
+    Board,
+    Narration,
+    Game,
+    Watch,
+    DailyPuzzle,
+    DailyPuzzleOn,
+    Puzzle,
+    Arena,
+    Games,
+    GamesFound,
+    BoardEditor,
+    Position,
+    Coordinates,
+    Training,
+    History,
+    Profile,
+    RatingHistory,
+    GamesAxis,
+    Themes,
+    GameSetup,
+    Settings,
+    About,
+    HelpTitle,
+    FollowingLive,
+    FollowingAt,
+    Heatmap,
+    HeatmapTitle,
+    MiddleGame,
+    EndGame,
+    ChangeSinceStart,
+    Learned,
+
+    // the thinking panel, for analysis too
+    AnalysisTitle,
+    Engine,
+    EngineThinking,
+    LookingAtPosition,
+    MoveEitherSide,
+    WaitingForYourMove,
+    WaitingForMove,
+    PlayingAgainst,
+    Simulations,
+    NodesPerSecond,
+    BestMove,
+    Depth,
+    Score,
+    Played,
+    LastSearch,
+    Speedrun,
+    YourMoves,
+    Hidden,
+
+    // the replay viewer
+    ReplayPosition,
+    ReplayPlaying,
+    ReplayPaused,
+    ReplayKeys,
+    BackToHistory,
+    BackToGame,
+
+    // the board editor
+    PositionIsLegal,
+    ToMove,
+    Square,
+    NoCastlingYet,
+    EditorKeys,
+
+    // the profile
+    Rating,
+    RatedGames,
+    LevelColumns,
+    Blunders,
+    NoBlunders,
+    YourOpenings,
+    NoneYet,
+    OddsWorth,
+    ProfileKeys,
+
+    // the setup screen
+    NewGameOf,
+    YouPlay,
+    Variant,
+    Level,
+    Strength,
+    TimePerMove,
+    Odds,
+    Personality,
+    Opponent,
+    BotAtElo,
+    BotAtLevel,
+    SetupKeys,
+
+    // settings
+    KeyBindings,
+    Sounds,
+    SettingsKeys,
+    SavedTo,
+
+    // the analytics panel
+    PositionEvaluation,
+    EngineAnalysis,
+    ThinkingDepth,
+    TotalPositions,
+    CurrentEvaluation,
+    TopMoves,
+    Visits,
+    CurrentPositionScore,
+    MaterialBalance,
+    BotKingSafety,
+    YourKingSafety,
+    CenterControl,
+    EvaluationsHidden,
+
+    // dialogs
+    Yes,
+    No,
+    QuitTitle,
+    QuitQuestion,
+    ResignTitle,
+    ResignQuestion,
+    NewGameTitle,
+    NewGameQuestion,
+    SetupGameQuestion,
+    ResumeTitle,
+    ResumeQuestion,
+    PromptKeys,
+
+    // moves and the game
+    UciStandardOnly,
+    UciWontStart,
+    InvalidMove,
+    MoveSuccessful,
+    YourMove,
+    YourMoveAfter,
+    MovedSuccessfully,
+    WaitForEngine,
+    WaitForPlayer,
+    NobodyToResignTo,
+    PlaysOn,
+    NoNetworkDraws,
+    HostStartsGames,
+    NewGameStarted,
+    GoToScreen,
+    UnfinishedGameGone,
+    ResumedFromMove,
+    ResumedGame,
+    NoPieceOfYoursOn,
+    PremoveSet,
+
+    // replies to commands: searches and diagrams
+    DumpUsage,
+    BlindfoldOn,
+    BlindfoldOff,
+    JoinUsage,
+    NoSearchYet,
+    SearchHidden,
+    SearchWritten,
+    SearchNotWritten,
+    DiagramUsage,
+    BoardHidden,
+    DiagramWritten,
+    DiagramNotWritten,
+    WhyNotUsage,
+    NotLegalHere,
+    NothingToCompare,
+    WhyNot,
+    EnginesChoiceToo,
+    EnginePrefersOver,
+    CostsPawns,
+    HoldsUp,
+    MainReply,
+    Line,
+    Compared,
+
+    // the clipboard
+    TheClipboard,
+    TheTerminalsClipboard,
+    CouldntCopy,
+    NoClipboard,
+    CouldntPaste,
+    CopiedTo,
+    PositionPasted,
+    NothingToPaste,
+    PastedMoveUnreadable,
+    OnlyFirstMovesRead,
+
+    // draws, drills and speedruns
+    NoCompletions,
+    Completions,
+    DrawAlreadyOffered,
+    DrawAccepted,
+    DrawDeclined,
+    DrawDeclinedInFog,
+    UnknownDrill,
+    NoNetworkDrills,
+    DrillTask,
+    DrillPassed,
+    DrillFailed,
+    NoSpeedruns,
+    BestSpeedrun,
+    SpeedrunUsage,
+    NoNetworkSpeedruns,
+    SpeedrunTask,
+    SpeedrunFailed,
+    SpeedrunPlace,
+    SpeedrunDone,
+    SpeedrunNotSaved,
+
+    // the end of a game
+    SideWinsBy,
+    PlayerWinsBy,
+    YouWinBy,
+    DrawBy,
+    GameOverNarrated,
+    RatingChange,
+    OddsChange,
+    YourRating,
+    RatingNotSaved,
+    GameSaved,
+    GameNotSaved,
+
+    // the bot's level and sides
+    PlayingAtLevel,
+    UnknownLevel,
+    Now,
+    FromNextGame,
+    BotLevel,
+    ButNotSaved,
+    PlaysAtElo,
+    PlaysAtLevel,
+    TargetEloRange,
+    BotAtAbout,
+    NoNetworkSwitch,
+    AnalysisBothSides,
+    TheGameIsOver,
+    SidesSwitched,
+    SidesSwitchedUnrated,
+    LearningEngine,
+    TheUciEngine,
+    AnotherPlayer,
+
+    // tabs
+    TabList,
+    NoSuchTab,
+    TabUsage,
+    NoNetworkTabs,
+    TooManyTabs,
+    AnalysisFromMove,
+    NewGameTab,
+    AnalysisTab,
+    TabTitle,
+    OneTab,
+    OnlyTab,
+    TabClosed,
+    TabOver,
+    TabThinking,
+    TabYourMove,
+
+    // analysis and the bot
+    GoOnAnalysisOnly,
+    GoUsage,
+    NothingToSearch,
+    AnalysingEach,
+    MovingNow,
+    AnalysisStopped,
+    NothingSearching,
+    BotPersonality,
+    UnknownPersonality,
+    BotPlays,
+    PlayingOpponents,
+    UnknownOpponent,
+    QuitToPlayOthers,
+    FinishFirst,
+    PlayingFromNextGame,
+    PlayingNow,
+
+    // network games
+    AlreadyConnected,
+    HostUsage,
+    WaitingToJoin,
+    CouldntHost,
+    Connecting,
+    NotConnected,
+    CouldntReach,
+    ChatNetworkOnly,
+    ChatOn,
+    ChatOff,
+    TakebackPending,
+    TakebackAskedFirst,
+    NotMovedYet,
+    TakebackAsked,
+    NoTakebackAsked,
+    TakebackDeclined,
+    TakebackUnknownPosition,
+    CouldntTalk,
+    WaitingForHost,
+    SomeoneJoined,
+    HostsVariantMissing,
+    NetworkGameStarted,
+    OpponentResigns,
+    OpponentAsksTakeback,
+    TakebackOffered,
+    TakebackAgreed,
+    TakebackMissingMove,
+    OpponentSays,
+    TakebackRefused,
+    ConnectionLost,
+    NotConnectedReason,
+
+    // the bot's weights
+    EngineStopped,
+    TheWeightsFolder,
+    NoWeightsFiles,
+    BuiltIn,
+    WeightsFromNextGame,
+    WeightsNow,
+    WeightsOwn,
+    ValuesFromNextGame,
+    ValuesNow,
+    WeightsNotLoaded,
+
+    // narration and hooks
+    NewGameNarrated,
+    BlindfoldIsOn,
+    YouLower,
+    TheBotLower,
+    SideToMove,
+    AccessibleOn,
+    AccessibleOff,
+    BotMoved,
+    BotPlayed,
+    YouMoved,
+    YouPlayed,
+    MovedInFog,
+    SpokenMove,
+    Takes,
+    CheckSpoken,
+    EmptyRank,
+    RankListing,
+    SquareHolds,
+    UnknownPiece,
+
+    // setting up games
+    TargetEloGame,
+    SparringGame,
+    Unrated,
+    SettingsNotSaved,
+    OfflinePuzzle,
+    LichessUnreachable,
+    NotAPiece,
+    NoNetworkEdits,
+    PlayingFromPosition,
+    EngineFoundNoMoves,
+    EnginePrefers,
+
+    // the game history and watching
+    OddsOf,
+    HistoryRow,
+    UnknownStyle,
+    EngineNamed,
+    BackToEngine,
+    YouPlaySide,
+    CouldntRelay,
+    BotFailedToMove,
+    NoSpeedrunTakebacks,
+    TakenBackToMove,
+
+    // the layout and settings
+    AnalyticsShown,
+    AnalyticsHidden,
+    HistoryShown,
+    HistoryHidden,
+    BoardFullWidth,
+    BoardWidest,
+    BoardNarrowest,
+    BoardWidth,
+    AnalyticsPanelHidden,
+    AnalyticsTallest,
+    AnalyticsShortest,
+    AnalyticsHeight,
+    LayoutNotSaved,
+    Hint,
+    NoHint,
+    PremoveCancelled,
+    FinishOrStartOver,
+    ThreatsHidden,
+    ThreatsInFog,
+    ThreatsShown,
+    ContemptSet,
+    EngineLine,
+    EngineLines,
+    BellOn,
+    BellOff,
+    Cancelled,
+    NowBound,
+    BoundNotSaved,
+    PressNewKey,
+    DefaultKeys,
+    UsingTheme,
+
+    // the focus
+    CommandBox,
+    TheBoard,
+    TheMoveHistory,
+    TheAnalytics,
+    FocusCommand,
+    FocusBoard,
+    FocusPanel,
+
+    // the analytics panel's details
+    UciEngine,
+    SecondOpinion,
+    AtDepth,
+    BestAfter,
+    EngineNameThinking,
+    SearchStats,
+    Recycled,
+    MemoryStats,
+    SafetyTerms,
+
+    // the move history
+    SayPrompt,
+    ReplayUsage,
+    NoSuchMove,
+    NoReplaysInFog,
+    BeforeAnalysisBoard,
+    BackToMove,
+    AnalysisBoardTag,
+    VsTag,
+    DrillTag,
+    OddsTag,
+    PersonalityTag,
+    ValuesTag,
+    SpectatorsTag,
+
+    // following, watching and puzzles
+    Finished,
+    FeedClosed,
+    WaitingForMoves,
+    SideMoves,
+    CantFollow,
+    Thinking,
+    EngineEval,
+    FollowKeys,
+    SidePlayer,
+    Paused,
+    PlayerToMove,
+    ThinkTime,
+    SimulationsBest,
+    WatchKeys,
+    FetchingPuzzle,
+    EscMenu,
+    PuzzleRating,
+    Shown,
+    Solved,
+    MoveOf,
+    MistakesHints,
+    PuzzleKeys,
+
+    // the heatmap, arena, coordinates and history screens
+    HeatmapAbout,
+    NothingLearned,
+    BiggestChanges,
+    HeatmapKeys,
+    NoTournaments,
+    HoldTournament,
+    EscBackToGame,
+    TournamentSummary,
+    TournamentOf,
+    NameTheSquare,
+    SeenFrom,
+    IsRight,
+    NotThat,
+    QuizScore,
+    QuizStreak,
+    QuizKeys,
+    SearchPrompt,
+    NoFinishedGames,
+    NoGamesMatch,
+    HistoryKeys,
+
+    // the themes and setup screens
+    Current,
+    PieceSet,
+    ThemesKeys,
+    FullSearch,
+    RatedByGuesswork,
+    StrengthCap,
+    TossedEachGame,
+    CountsTowardRating,
+    UnratedNote,
+    StrengthStandsIn,
+    RatedAbout,
+    LevelWord,
+    LongestThink,
+    WithTheOdds,
+    ThinkAtLevel,
+    OddsWorthNote,
+    NoOddsGames,
+
+    // the settings and about screens
+    ContemptAbout,
+    MultiPvAbout,
+    WeightsAbout,
+    PieceValuesAbout,
+    Bell,
+    Off,
+    AboutIntro,
+    AboutOutro,
+
+    // the help overlay
+    KeysHeading,
+    TabKeyAbout,
+    SpaceKeyAbout,
+    PageKeysAbout,
+    ArrowKeysAbout,
+    MovesHeading,
+    CoordinateMoveAbout,
+    SpokenMoveAbout,
+    CommandsHeading,
+
+    // what each typed command does
+    NewCommand,
+    ResignCommand,
+    DrawCommand,
+    SwitchCommand,
+    WhyNotCommand,
+    ReplayCommand,
+    LevelCommand,
+    EloCommand,
+    PersonalityCommand,
+    OpponentCommand,
+    DrillCommand,
+    SpeedrunCommand,
+    TabCommand,
+    GoCommand,
+    StopCommand,
+    HostCommand,
+    JoinCommand,
+    TakebackCommand,
+    AcceptCommand,
+    DeclineCommand,
+    BlindfoldCommand,
+    BoardCommand,
+    AccessibleCommand,
+    DiagramCommand,
+    HeatmapCommand,
+    WatchCommand,
+    ArenaCommand,
+    DumpCommand,
+
+    // the puzzle and watch screens
+    CouldntFollowPuzzle,
+    UnreadableSolution,
+    SideToPlay,
+    SolvedAlready,
+    TypeAMove,
+    NotItTryAgain,
+    SolvedExclaimed,
+    RightKeepGoing,
+    LookAtYour,
+    PlayMove,
+    TheSolution,
+    TypeToTakeOver,
+
+    // premoves, search notes and the time bar
+    PremovePlayed,
+    PremoveDropped,
+    YouResign,
+    SideToMoveScore,
+    BranchVisits,
+    TimeUsed,
+    SecondsOf,
+
+    // errors and refusals
+    NoPieceSelected,
+    InvalidCoordinates,
+    InvalidCommand,
+    GameOver,
+    GameOverPlayAgain,
+    UnknownVariant,
+    NothingToUndo,
+    MoveTakenBack,
+    NothingToReplay,
+    NoPieceOn,
+    BotsPiece,
+    SameSquare,
+    OwnPieceThere,
+    KingShape,
+    QueenShape,
+    RookShape,
+    BishopShape,
+    KnightShape,
+    PawnShape,
+    Blocked,
+    PawnBlocked,
+    PawnCapturesOnly,
+    PawnDoubleStep,
+    StillInCheck,
+    LeavesKingInCheck,
+    VariantRule,
+    // "the knight on f3", from ThePiece and a square
+    PieceOn,
+    Piece(PieceType),
+    ThePiece(PieceType),
+    // "white", to go in a sentence
+    Side(Color),
+    // "white knight"
+    SidePiece(Color, PieceType),
+    // what a bindable key does, for the help overlay and the palette
+    ActionAbout(Action),
+}
+
+pub fn tr(text: Text) -> &'static str {
+    match language() {
+        Language::English => en::text(text),
+        Language::Spanish => es::text(text),
+    }
+}
+
+// a catalog string with its "{}"s filled in from `args`, in order
+pub fn fill(text: Text, args: &[&dyn Display]) -> String {
+    let mut parts = tr(text).split("{}");
+    let mut filled = parts.next().unwrap_or_default().to_string();
+    for (idx, part) in parts.enumerate() {
+        if let Some(arg) = args.get(idx) {
+            filled.push_str(&arg.to_string());
+        }
+        filled.push_str(part);
+    }
+    filled
+}
+
+// fill, starting with a capital whatever came first, so a sentence can
+// open on an argument like "the knight"
+pub fn sentence(text: Text, args: &[&dyn Display]) -> String {
+    let filled = fill(text, args);
+    let mut chars = filled.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => filled,
+    }
+}
@@ -0,0 +1,146 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+// single-line text box with a cursor and a recall history, like a shell prompt
+#[derive(Default)]
+pub struct TextInput {
+    chars: Vec<char>,
+    cursor: usize,
+    history: Vec<String>,
+    // position while browsing history, and whatever was typed before browsing
+    history_index: Option<usize>,
+    draft: Vec<char>,
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn value(&self) -> String {
+        self.chars.iter().collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.chars.clear();
+        self.cursor = 0;
+        self.history_index = None;
+    }
+
+    pub fn set_value(&mut self, value: &str) {
+        self.chars = value.chars().collect();
+        self.cursor = self.chars.len();
+    }
+
+    // takes the current text, remembering it for Up/Down recall
+    pub fn submit(&mut self) -> String {
+        let value = self.value();
+        let trimmed = value.trim();
+        if !trimmed.is_empty() && self.history.last().map(String::as_str) != Some(trimmed) {
+            self.history.push(trimmed.to_string());
+        }
+        self.clear();
+        value
+    }
+
+    // returns false for keys the input doesn't use
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('u') if ctrl => {
+                self.chars.drain(..self.cursor);
+                self.cursor = 0;
+            }
+            KeyCode::Char('w') if ctrl => self.delete_word(),
+            KeyCode::Char('a') if ctrl => self.cursor = 0,
+            KeyCode::Char('e') if ctrl => self.cursor = self.chars.len(),
+            KeyCode::Char(_) if ctrl => return false,
+            KeyCode::Char(c) => {
+                self.chars.insert(self.cursor, c);
+                self.cursor += 1;
+            }
+            KeyCode::Backspace => {
+                if self.cursor > 0 {
+                    self.cursor -= 1;
+                    self.chars.remove(self.cursor);
+                }
+            }
+            KeyCode::Delete => {
+                if self.cursor < self.chars.len() {
+                    self.chars.remove(self.cursor);
+                }
+            }
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.chars.len()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.chars.len(),
+            KeyCode::Up => self.recall_older(),
+            KeyCode::Down => self.recall_newer(),
+            _ => return false,
+        }
+        true
+    }
+
+    // ctrl+w: drop the word before the cursor along with trailing spaces
+    fn delete_word(&mut self) {
+        let mut start = self.cursor;
+        while start > 0 && self.chars[start - 1] == ' ' {
+            start -= 1;
+        }
+        while start > 0 && self.chars[start - 1] != ' ' {
+            start -= 1;
+        }
+        self.chars.drain(start..self.cursor);
+        self.cursor = start;
+    }
+
+    fn recall_older(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+        let index = match self.history_index {
+            None => {
+                self.draft = self.chars.clone();
+                self.history.len() - 1
+            }
+            Some(i) => i.saturating_sub(1),
+        };
+        self.history_index = Some(index);
+        self.chars = self.history[index].chars().collect();
+        self.cursor = self.chars.len();
+    }
+
+    fn recall_newer(&mut self) {
+        let Some(index) = self.history_index else {
+            return;
+        };
+        if index + 1 < self.history.len() {
+            self.history_index = Some(index + 1);
+            self.chars = self.history[index + 1].chars().collect();
+        } else {
+            self.history_index = None;
+            self.chars = std::mem::take(&mut self.draft);
+        }
+        self.cursor = self.chars.len();
+    }
+
+    // the text with the cursor cell drawn reversed
+    pub fn render(&self, prefix: &str, style: Style) -> Line<'static> {
+        let before: String = self.chars[..self.cursor].iter().collect();
+        let at = self.chars.get(self.cursor).map_or(' ', |c| *c);
+        let after: String = self.chars.get(self.cursor + 1..).unwrap_or(&[]).iter().collect();
+        Line::from(vec![
+            Span::styled(prefix.to_string(), style),
+            Span::styled(before, style),
+            Span::styled(at.to_string(), style.add_modifier(Modifier::REVERSED)),
+            Span::styled(after, style),
+        ])
+    }
+}
@@ -0,0 +1,306 @@
+use crate::ui::i18n::{tr, Text};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Action {
+    Quit,
+    Menu,
+    Undo,
+    Flip,
+    Hint,
+    Threats,
+    CopyFen,
+    CopyPgn,
+    Paste,
+    NewTab,
+    NextTab,
+    ToggleAnalytics,
+    ToggleHistory,
+    BoardWider,
+    BoardNarrower,
+    AnalyticsTaller,
+    AnalyticsShorter,
+    ToggleChat,
+    FocusHistory,
+    Help,
+    Palette,
+    NewGame,
+}
+
+impl Action {
+    pub const ALL: [Action; 22] = [
+        Action::Quit,
+        Action::Menu,
+        Action::Undo,
+        Action::Flip,
+        Action::Hint,
+        Action::Threats,
+        Action::CopyFen,
+        Action::CopyPgn,
+        Action::Paste,
+        Action::NewTab,
+        Action::NextTab,
+        Action::ToggleAnalytics,
+        Action::ToggleHistory,
+        Action::BoardWider,
+        Action::BoardNarrower,
+        Action::AnalyticsTaller,
+        Action::AnalyticsShorter,
+        Action::ToggleChat,
+        Action::FocusHistory,
+        Action::Help,
+        Action::Palette,
+        Action::NewGame,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Menu => "menu",
+            Action::Undo => "undo",
+            Action::Flip => "flip",
+            Action::Hint => "hint",
+            Action::Threats => "threats",
+            Action::CopyFen => "copy-fen",
+            Action::CopyPgn => "copy-pgn",
+            Action::Paste => "paste",
+            Action::NewTab => "new-tab",
+            Action::NextTab => "next-tab",
+            Action::ToggleAnalytics => "toggle-analytics",
+            Action::ToggleHistory => "toggle-history",
+            Action::BoardWider => "board-wider",
+            Action::BoardNarrower => "board-narrower",
+            Action::AnalyticsTaller => "analytics-taller",
+            Action::AnalyticsShorter => "analytics-shorter",
+            Action::ToggleChat => "toggle-chat",
+            Action::FocusHistory => "focus-history",
+            Action::Help => "help",
+            Action::Palette => "palette",
+            Action::NewGame => "new-game",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        tr(Text::ActionAbout(self))
+    }
+
+    fn from_name(name: &str) -> Option<Action> {
+        Action::ALL.iter().copied().find(|a| a.name() == name)
+    }
+
+    fn default_binding(self) -> KeyBinding {
+        let spec = match self {
+            Action::Quit => "ctrl+q",
+            Action::Menu => "esc",
+            Action::Undo => "ctrl+z",
+            Action::Flip => "ctrl+f",
+            Action::Hint => "alt+h",
+            Action::Threats => "ctrl+x",
+            Action::CopyFen => "alt+c",
+            Action::CopyPgn => "alt+p",
+            Action::Paste => "alt+v",
+            Action::NewTab => "ctrl+t",
+            Action::NextTab => "ctrl+tab",
+            Action::ToggleAnalytics => "alt+a",
+            Action::ToggleHistory => "alt+m",
+            Action::BoardWider => "alt+right",
+            Action::BoardNarrower => "alt+left",
+            Action::AnalyticsTaller => "alt+up",
+            Action::AnalyticsShorter => "alt+down",
+            Action::ToggleChat => "alt+t",
+            Action::FocusHistory => "alt+l",
+            Action::Help => "?",
+            Action::Palette => ":",
+            Action::NewGame => "ctrl+n",
+        };
+        KeyBinding::parse(spec).unwrap()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    // accepts things like "q", "ctrl+q", "alt+x", "esc", "f5", "pageup"
+    pub fn parse(spec: &str) -> Option<KeyBinding> {
+        let spec = spec.trim().to_lowercase();
+        let mut parts: Vec<&str> = spec.split('+').collect();
+        // "ctrl++" binds the plus key itself
+        if spec.ends_with("++") {
+            parts.pop();
+            parts.pop();
+            parts.push("+");
+        }
+        let key = parts.pop()?;
+
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier in parts {
+            modifiers |= match modifier {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return None,
+            };
+        }
+
+        let code = match key {
+            "esc" | "escape" => KeyCode::Esc,
+            "enter" | "return" => KeyCode::Enter,
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "space" => KeyCode::Char(' '),
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            _ if key.len() > 1 && key.starts_with('f') => KeyCode::F(key[1..].parse().ok()?),
+            _ if key.chars().count() == 1 => KeyCode::Char(key.chars().next()?),
+            _ => return None,
+        };
+
+        // shift is already part of the character itself
+        if let KeyCode::Char(_) = code {
+            modifiers -= KeyModifiers::SHIFT;
+        }
+
+        Some(KeyBinding { code, modifiers })
+    }
+
+    pub fn from_event(key: &KeyEvent) -> KeyBinding {
+        KeyBinding {
+            code: lowercase(key.code),
+            modifiers: key.modifiers - KeyModifiers::SHIFT,
+        }
+    }
+
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        *self == KeyBinding::from_event(key)
+    }
+
+    // plain characters are typed into the command box while playing, so they
+    // can't double as action keys there
+    pub fn is_plain_char(&self) -> bool {
+        matches!(self.code, KeyCode::Char(_)) && self.modifiers.is_empty()
+    }
+}
+
+impl std::fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "shift+")?;
+        }
+        match self.code {
+            KeyCode::Esc => write!(f, "esc"),
+            KeyCode::Enter => write!(f, "enter"),
+            KeyCode::Tab => write!(f, "tab"),
+            KeyCode::Backspace => write!(f, "backspace"),
+            KeyCode::Delete => write!(f, "delete"),
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Up => write!(f, "up"),
+            KeyCode::Down => write!(f, "down"),
+            KeyCode::Left => write!(f, "left"),
+            KeyCode::Right => write!(f, "right"),
+            KeyCode::Home => write!(f, "home"),
+            KeyCode::End => write!(f, "end"),
+            KeyCode::PageUp => write!(f, "pageup"),
+            KeyCode::PageDown => write!(f, "pagedown"),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            _ => write!(f, "?"),
+        }
+    }
+}
+
+fn lowercase(code: KeyCode) -> KeyCode {
+    match code {
+        KeyCode::Char(c) => KeyCode::Char(c.to_ascii_lowercase()),
+        other => other,
+    }
+}
+
+// stored in the config as `action = "key"` pairs; unknown actions and
+// unparseable keys are ignored and fall back to the defaults
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "BTreeMap<String, String>", into = "BTreeMap<String, String>")]
+pub struct Keymap {
+    bindings: BTreeMap<Action, KeyBinding>,
+}
+
+impl Keymap {
+    pub fn binding(&self, action: Action) -> KeyBinding {
+        self.bindings
+            .get(&action)
+            .copied()
+            .unwrap_or_else(|| action.default_binding())
+    }
+
+    pub fn set(&mut self, action: Action, binding: KeyBinding) {
+        self.bindings.insert(action, binding);
+    }
+
+    pub fn reset(&mut self) {
+        *self = Keymap::default();
+    }
+
+    pub fn action_for(&self, key: &KeyEvent, typing: bool) -> Option<Action> {
+        Action::ALL.iter().copied().find(|&action| {
+            let binding = self.binding(action);
+            binding.matches(key) && !(typing && binding.is_plain_char())
+        })
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap {
+            bindings: Action::ALL
+                .iter()
+                .map(|&action| (action, action.default_binding()))
+                .collect(),
+        }
+    }
+}
+
+impl From<BTreeMap<String, String>> for Keymap {
+    fn from(raw: BTreeMap<String, String>) -> Self {
+        let mut keymap = Keymap::default();
+        // configs saved before tabs have the hint on ctrl+t, its old default;
+        // that goes to new tabs now
+        let old_hint = !raw.contains_key(Action::NewTab.name())
+            && raw.get(Action::Hint.name()).and_then(|spec| KeyBinding::parse(spec)) == KeyBinding::parse("ctrl+t");
+        for (name, spec) in raw {
+            if old_hint && name == Action::Hint.name() {
+                continue;
+            }
+            if let (Some(action), Some(binding)) = (Action::from_name(&name), KeyBinding::parse(&spec)) {
+                keymap.set(action, binding);
+            }
+        }
+        keymap
+    }
+}
+
+impl From<Keymap> for BTreeMap<String, String> {
+    fn from(keymap: Keymap) -> Self {
+        Action::ALL
+            .iter()
+            .map(|&action| (action.name().to_string(), keymap.binding(action).to_string()))
+            .collect()
+    }
+}
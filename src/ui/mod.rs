@@ -1,3 +1,32 @@
 pub mod app;
+pub mod board;
+pub mod chat;
+pub mod clipboard;
+pub mod commands;
+pub mod diagram;
+pub mod dialog;
+pub mod editor;
+pub mod events;
+pub mod follow;
+pub mod heatmap;
+pub mod help;
+pub mod i18n;
+pub mod input;
+pub mod keymap;
+pub mod movelist;
+pub mod narration;
+pub mod palette;
+pub mod network;
+pub mod panels;
+pub mod players;
+pub mod puzzle;
+pub mod relay;
+pub mod replay;
+pub mod setup;
+pub mod speedrun;
+pub mod spoken;
+pub mod tabs;
 pub mod terminal;
-pub mod welcome;
+pub mod theme;
+pub mod training;
+pub mod watch;
@@ -0,0 +1,93 @@
+use crate::game::piece::Color as PieceColor;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+
+// "12. " and the white move padded to a column, so black's moves line up
+// and a click can be told apart by its column
+const NUMBER_WIDTH: usize = 5;
+const MOVE_WIDTH: usize = 8;
+
+// the moves two to a line, "12. Nf3     Nc6", with the ply `current` picked
+// out. a game black started opens "1. ...     e5"
+pub fn pair_lines(sans: &[String], first: PieceColor, current: Option<usize>) -> Vec<Line<'static>> {
+    let offset = usize::from(first == PieceColor::Black);
+    let total = sans.len() + offset;
+    let san_span = |ply: usize| -> Span<'static> {
+        let Some(idx) = ply.checked_sub(offset) else {
+            return Span::styled(format!("{:<MOVE_WIDTH$}", "..."), Style::default().fg(Color::DarkGray));
+        };
+        let style = if Some(idx) == current {
+            Style::default().fg(Color::Black).bg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        Span::styled(sans[idx].clone(), style)
+    };
+    (0..total.div_ceil(2))
+        .map(|row| {
+            let white = san_span(row * 2);
+            let padding = MOVE_WIDTH.saturating_sub(white.content.chars().count());
+            let mut spans = vec![
+                Span::styled(format!("{:>3}. ", row + 1), Style::default().fg(Color::DarkGray)),
+                white,
+            ];
+            if row * 2 + 1 < total {
+                spans.push(Span::raw(" ".repeat(padding)));
+                spans.push(san_span(row * 2 + 1));
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+// the ply under a click `column` cells into line `row` of the list, None
+// past the end or on a black move that was never played
+pub fn ply_at(plies: usize, first: PieceColor, row: usize, column: usize) -> Option<usize> {
+    let offset = usize::from(first == PieceColor::Black);
+    let black = column >= NUMBER_WIDTH + MOVE_WIDTH;
+    let ply = (row * 2 + usize::from(black)).checked_sub(offset)?;
+    (ply < plies).then_some(ply)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(line: &Line) -> String {
+        line.spans.iter().map(|span| span.content.as_ref()).collect()
+    }
+
+    fn sans(moves: &[&str]) -> Vec<String> {
+        moves.iter().map(|mv| mv.to_string()).collect()
+    }
+
+    #[test]
+    fn a_game_black_started_opens_with_a_gap() {
+        let lines = pair_lines(&sans(&["e5", "Nf3", "Nc6"]), PieceColor::Black, Some(0));
+        let texts: Vec<String> = lines.iter().map(text).collect();
+        assert_eq!(texts, ["  1. ...     e5", "  2. Nf3     Nc6"]);
+        assert_eq!(lines[0].spans.last().unwrap().style.bg, Some(Color::Yellow));
+
+        // the gap is no ply, and black's first move is the first one
+        let black = NUMBER_WIDTH + MOVE_WIDTH;
+        assert_eq!(ply_at(3, PieceColor::Black, 0, NUMBER_WIDTH), None);
+        assert_eq!(ply_at(3, PieceColor::Black, 0, black), Some(0));
+        assert_eq!(ply_at(3, PieceColor::Black, 1, NUMBER_WIDTH), Some(1));
+        assert_eq!(ply_at(3, PieceColor::Black, 1, black), Some(2));
+    }
+
+    #[test]
+    fn black_slot_not_played_yet_is_no_ply() {
+        let lines = pair_lines(&sans(&["e4", "e5", "Nf3"]), PieceColor::White, None);
+        assert_eq!(text(&lines[1]), "  2. Nf3");
+        assert_eq!(ply_at(3, PieceColor::White, 1, NUMBER_WIDTH), Some(2));
+        assert_eq!(ply_at(3, PieceColor::White, 1, NUMBER_WIDTH + MOVE_WIDTH), None);
+    }
+
+    #[test]
+    fn clicks_past_the_end_are_no_ply() {
+        assert_eq!(ply_at(3, PieceColor::White, 2, 0), None);
+        assert_eq!(ply_at(3, PieceColor::Black, 2, 0), None);
+        assert_eq!(ply_at(0, PieceColor::White, 0, 0), None);
+    }
+}
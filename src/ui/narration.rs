@@ -0,0 +1,87 @@
+use crate::game::board::Board;
+use crate::ui::i18n::{fill, tr, Text};
+use crate::utils::{coordinate_to_string, expand_home};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::sync::mpsc::{self, Sender};
+
+// lines kept for the narration panel; the file gets all of them
+const MAX_LINES: usize = 200;
+
+// [accessibility] in config.toml, e.g.
+//   enabled = true
+//   narrate_to = "/tmp/chessrl.fifo"
+// narrate_to is any file or FIFO; each line is appended as it happens, for a
+// screen reader or `espeak` to pick up
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessibilityConfig {
+    pub enabled: bool,
+    pub narrate_to: Option<String>,
+}
+
+// what happened in the game, as plain sentences
+pub struct Narrator {
+    pub lines: Vec<String>,
+    sink: Option<Sender<String>>,
+}
+
+impl Narrator {
+    pub fn new(config: &AccessibilityConfig) -> Self {
+        Narrator {
+            lines: Vec::new(),
+            sink: config.narrate_to.as_deref().map(open_sink),
+        }
+    }
+
+    pub fn say(&mut self, line: &str) {
+        if line.is_empty() {
+            return;
+        }
+        if self.lines.len() == MAX_LINES {
+            self.lines.remove(0);
+        }
+        self.lines.push(line.to_string());
+        if let Some(sink) = &self.sink {
+            let _ = sink.send(line.to_string());
+        }
+    }
+}
+
+// opening a FIFO blocks until something reads it, so the file is opened and
+// written on its own thread; the game never waits on it
+fn open_sink(path: &str) -> Sender<String> {
+    let path = expand_home(path);
+    let (sender, receiver) = mpsc::channel::<String>();
+    std::thread::spawn(move || {
+        let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&path) else {
+            return;
+        };
+        for line in receiver {
+            if writeln!(file, "{}", line).and_then(|_| file.flush()).is_err() {
+                return;
+            }
+        }
+    });
+    sender
+}
+
+// the board rank by rank from the 8th, naming each piece and its square
+pub fn board_listing(board: &Board) -> Vec<String> {
+    (0..8)
+        .map(|rank| {
+            let pieces: Vec<String> = (0..8)
+                .filter_map(|file| {
+                    let piece = board.get_piece((rank, file))?;
+                    Some(fill(
+                        Text::SquareHolds,
+                        &[&coordinate_to_string((rank, file)), &tr(Text::SidePiece(piece.color, piece.piece_type))],
+                    ))
+                })
+                .collect();
+            let contents = if pieces.is_empty() { tr(Text::EmptyRank).to_string() } else { pieces.join(", ") };
+            fill(Text::RankListing, &[&(8 - rank), &contents])
+        })
+        .collect()
+}
@@ -0,0 +1,134 @@
+use crate::engine::rl::MovePair;
+use crate::game::piece::Color;
+use crate::logging;
+use crate::ui::chat;
+use crate::ui::events::AppEvent;
+use crate::utils::{coordinate_to_string, parse_coordinate};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::Duration;
+
+pub const DEFAULT_PORT: u16 = 7878;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+// what the other end of a network game said, or what became of the
+// connection. the protocol is a line of text a message:
+//   new <color> <variant>   the host starting a game, the one who joined playing <color>
+//   move <from><to>         a move in coordinates, e.g. move e2e4
+//   resign
+//   takeback <plies>        asking to go back to <plies> plies into the game
+//   takeback accept <plies>
+//   takeback decline
+//   chat <text>
+pub enum PeerEvent {
+    Connected { stream: TcpStream, hosting: bool },
+    Start { color: Color, variant: String },
+    Move(MovePair),
+    Resigned,
+    TakebackAsked(usize),
+    TakebackAccepted(usize),
+    TakebackDeclined,
+    Chat(String),
+    // the connection went, or never came
+    Closed(String),
+}
+
+// waits on `port` for one player to join
+pub fn host(port: u16, events: Sender<AppEvent>) -> io::Result<()> {
+    let listener = TcpListener::bind(("0.0.0.0", port))?;
+    thread::spawn(move || {
+        let event = match listener.accept() {
+            Ok((stream, addr)) => {
+                logging::info(&format!("{} joined the game", addr));
+                PeerEvent::Connected { stream, hosting: true }
+            }
+            Err(e) => PeerEvent::Closed(e.to_string()),
+        };
+        let _ = events.send(AppEvent::Peer(event));
+    });
+    Ok(())
+}
+
+// connects to a host, "host:port" or just the host for the usual port
+pub fn join(address: &str, events: Sender<AppEvent>) {
+    let address = if address.contains(':') { address.to_string() } else { format!("{}:{}", address, DEFAULT_PORT) };
+    thread::spawn(move || {
+        let connected = address
+            .to_socket_addrs()
+            .and_then(|mut addrs| addrs.next().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no such host")))
+            .and_then(|addr| TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT));
+        let event = match connected {
+            Ok(stream) => PeerEvent::Connected { stream, hosting: false },
+            Err(e) => PeerEvent::Closed(format!("couldn't reach {}: {}", address, e)),
+        };
+        let _ = events.send(AppEvent::Peer(event));
+    });
+}
+
+// passes what the other end sends to the main loop until the connection closes
+pub fn listen(stream: &TcpStream, events: Sender<AppEvent>) -> io::Result<()> {
+    let reader = BufReader::new(stream.try_clone()?);
+    thread::spawn(move || {
+        for line in reader.lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    let _ = events.send(AppEvent::Peer(PeerEvent::Closed(e.to_string())));
+                    return;
+                }
+            };
+            match parse(&line) {
+                Some(event) => {
+                    if events.send(AppEvent::Peer(event)).is_err() {
+                        return;
+                    }
+                }
+                None => logging::warn(&format!("ignoring a message from the other player: {}", line)),
+            }
+        }
+        let _ = events.send(AppEvent::Peer(PeerEvent::Closed("the other player left".to_string())));
+    });
+    Ok(())
+}
+
+fn parse(line: &str) -> Option<PeerEvent> {
+    let mut words = line.split_whitespace();
+    match words.next()? {
+        "new" => {
+            let color = match words.next()? {
+                "white" => Color::White,
+                "black" => Color::Black,
+                _ => return None,
+            };
+            Some(PeerEvent::Start { color, variant: words.next().unwrap_or("standard").to_string() })
+        }
+        "move" => {
+            let text = words.next()?;
+            let from = parse_coordinate(text.get(0..2)?)?;
+            let to = parse_coordinate(text.get(2..4)?)?;
+            Some(PeerEvent::Move((from, to)))
+        }
+        "resign" => Some(PeerEvent::Resigned),
+        "chat" => {
+            let text = chat::clean(line.trim_start().strip_prefix("chat")?);
+            (!text.is_empty()).then_some(PeerEvent::Chat(text))
+        }
+        "takeback" => match words.next()? {
+            "accept" => Some(PeerEvent::TakebackAccepted(words.next()?.parse().ok()?)),
+            "decline" => Some(PeerEvent::TakebackDeclined),
+            plies => Some(PeerEvent::TakebackAsked(plies.parse().ok()?)),
+        },
+        _ => None,
+    }
+}
+
+pub fn send(mut stream: &TcpStream, message: &str) -> io::Result<()> {
+    stream.write_all(format!("{}\n", message).as_bytes())?;
+    stream.flush()
+}
+
+pub fn move_message((from, to): MovePair) -> String {
+    format!("move {}{}", coordinate_to_string(from), coordinate_to_string(to))
+}
@@ -0,0 +1,184 @@
+use crate::ui::i18n::{fill, tr, Text};
+use crate::ui::input::TextInput;
+use crate::ui::keymap::Action;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+const WIDTH: u16 = 70;
+const SHOWN: usize = 12;
+
+// what picking an entry does
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Target {
+    Key(Action),
+    // a text command, run as typed, or left in the command box for its
+    // arguments when it needs some
+    Command { name: &'static str, needs_args: bool },
+    // a main menu item by its place in the menu
+    Screen(usize),
+}
+
+pub struct Entry {
+    pub name: String,
+    pub description: String,
+    pub target: Target,
+}
+
+pub enum PaletteKey {
+    Pending,
+    Run(Target),
+    Closed,
+}
+
+// ':' brings it up: type part of anything the app can do and ENTER does it
+pub struct Palette {
+    input: TextInput,
+    entries: Vec<Entry>,
+    // entries matching the input, best first
+    matches: Vec<usize>,
+    selected: usize,
+}
+
+impl Palette {
+    pub fn new(entries: Vec<Entry>) -> Self {
+        let matches = (0..entries.len()).collect();
+        Palette { input: TextInput::new(), entries, matches, selected: 0 }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> PaletteKey {
+        match key.code {
+            KeyCode::Esc => return PaletteKey::Closed,
+            KeyCode::Enter => {
+                return match self.matches.get(self.selected) {
+                    Some(&idx) => PaletteKey::Run(self.entries[idx].target),
+                    None => PaletteKey::Pending,
+                };
+            }
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => self.selected = (self.selected + 1).min(self.matches.len().saturating_sub(1)),
+            _ => {
+                if self.input.handle_key(key) {
+                    self.refilter();
+                }
+            }
+        }
+        PaletteKey::Pending
+    }
+
+    fn refilter(&mut self) {
+        let query = self.input.value().to_lowercase();
+        let mut scored: Vec<(i32, usize)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, entry)| {
+                let text = format!("{} {}", entry.name, entry.description).to_lowercase();
+                fuzzy_score(&query, &text).map(|score| (score, idx))
+            })
+            .collect();
+        // the sort is stable, so ties keep the order the entries came in
+        scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+        self.matches = scored.into_iter().map(|(_, idx)| idx).collect();
+        self.selected = 0;
+    }
+
+    // near the top, over whatever is on screen
+    pub fn render(&self, frame: &mut Frame, accent: Color) {
+        let screen = frame.area();
+        let width = WIDTH.min(screen.width);
+        let height = (SHOWN as u16 + 4).min(screen.height);
+        let area = Rect { x: screen.x + (screen.width - width) / 2, y: screen.y + screen.height / 8, width, height };
+
+        let first = self.selected.saturating_sub(SHOWN - 1);
+        let mut lines = vec![self.input.render(": ", Style::default().fg(Color::White)), Line::from("")];
+        for (row, &idx) in self.matches.iter().enumerate().skip(first).take(SHOWN) {
+            let entry = &self.entries[idx];
+            let (name_style, style) = if row == self.selected {
+                let picked = Style::default().fg(Color::Black).bg(accent).add_modifier(Modifier::BOLD);
+                (picked, picked)
+            } else {
+                (Style::default().fg(Color::Cyan), Style::default())
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!(" {:<16}", entry.name), name_style),
+                Span::styled(format!("{} ", entry.description), style),
+            ]));
+        }
+        if self.matches.is_empty() {
+            lines.push(Line::from(Span::styled(format!(" {}", tr(Text::NothingMatches)), Style::default().fg(Color::DarkGray))));
+        }
+
+        let block = Block::default()
+            .title(format!(" {} ", fill(Text::PaletteTitle, &[&self.matches.len()])))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(accent));
+        frame.render_widget(Clear, area);
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
+
+// None unless every character of `query` turns up in `text` in order.
+// letters in a row and letters starting a word count for more, and a
+// match near the start beats one further in
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.chars().collect();
+    let mut score = 0;
+    let mut at = 0;
+    let mut last: Option<usize> = None;
+    for wanted in query.chars().filter(|c| !c.is_whitespace()) {
+        let found = at + text[at..].iter().position(|&c| c == wanted)?;
+        score += 1;
+        if last.is_some_and(|last| last + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || matches!(text[found - 1], ' ' | '-') {
+            score += 3;
+        }
+        if last.is_none() {
+            score -= found.min(20) as i32 / 2;
+        }
+        last = Some(found);
+        at = found + 1;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letters_have_to_come_in_order() {
+        assert!(fuzzy_score("ng", "new game").is_some());
+        assert_eq!(fuzzy_score("gn", "new game"), None);
+    }
+
+    #[test]
+    fn a_missing_letter_matches_nothing() {
+        assert_eq!(fuzzy_score("newz", "new game"), None);
+        assert_eq!(fuzzy_score("x", ""), None);
+    }
+
+    // "res" in a row at the start beats the same letters picked out of
+    // the middle of words
+    #[test]
+    fn runs_and_word_starts_rank_first() {
+        let run = fuzzy_score("res", "resign the game").unwrap();
+        let scattered = fuzzy_score("res", "draw or move less").unwrap();
+        assert!(run > scattered);
+        let word_starts = fuzzy_score("ng", "new game").unwrap();
+        let inside = fuzzy_score("ng", "anything").unwrap();
+        assert!(word_starts > inside);
+    }
+
+    #[test]
+    fn an_empty_query_matches_everything() {
+        assert_eq!(fuzzy_score("", "anything at all"), Some(0));
+        assert_eq!(fuzzy_score("", ""), Some(0));
+        assert_eq!(fuzzy_score(" ", "help"), Some(0));
+    }
+}
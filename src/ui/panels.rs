@@ -0,0 +1,137 @@
+use crate::ui::i18n::{tr, Text};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+
+// how far the splits go either way, and how far a key press moves them
+const BOARD_WIDTH: RangeInclusive<u16> = 50..=90;
+const ANALYTICS_HEIGHT: RangeInclusive<u16> = 20..=80;
+const STEP: i16 = 5;
+// the engine thinking panel and the command box, each with its border
+const THINKING_ROWS: u16 = 7;
+const COMMAND_ROWS: u16 = 5;
+
+// [layout] in config.toml, e.g.
+//   analytics = false
+//   board_width = 80
+// which of the game screen's panels show, and the splits between them in
+// percent: the board's side of the width, and the analytics panel's share
+// of the board's side
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Panels {
+    pub analytics: bool,
+    pub history: bool,
+    pub board_width: u16,
+    pub analytics_height: u16,
+}
+
+impl Default for Panels {
+    fn default() -> Self {
+        Panels { analytics: true, history: true, board_width: 75, analytics_height: 60 }
+    }
+}
+
+// where each panel of the game screen goes; None for the hidden ones
+pub struct GameAreas {
+    pub board: Rect,
+    pub analytics: Option<Rect>,
+    pub history: Option<Rect>,
+    pub thinking: Rect,
+    pub command: Rect,
+}
+
+impl Panels {
+    // the board keeps `board_rows` when there's room, whatever the split
+    pub fn areas(&self, area: Rect, board_rows: u16) -> GameAreas {
+        let board_width = self.board_width.clamp(*BOARD_WIDTH.start(), *BOARD_WIDTH.end());
+        let (left, history, thinking, command) = if self.history {
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(board_width), Constraint::Percentage(100 - board_width)])
+                .split(area);
+            let right = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(3), Constraint::Length(THINKING_ROWS), Constraint::Length(COMMAND_ROWS)])
+                .split(columns[1]);
+            (columns[0], Some(right[0]), right[1], right[2])
+        } else {
+            // the command box and the thinking panel side by side underneath
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(THINKING_ROWS)])
+                .split(area);
+            let bottom = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .split(rows[1]);
+            (rows[0], None, bottom[1], bottom[0])
+        };
+        let (board, analytics) = if self.analytics {
+            let height = self.analytics_height.clamp(*ANALYTICS_HEIGHT.start(), *ANALYTICS_HEIGHT.end());
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(board_rows), Constraint::Percentage(height)])
+                .split(left);
+            (rows[0], Some(rows[1]))
+        } else {
+            (left, None)
+        };
+        GameAreas { board, analytics, history, thinking, command }
+    }
+
+    // `steps` of STEP wider, or narrower when negative; false at the limit
+    pub fn widen_board(&mut self, steps: i16) -> bool {
+        step(&mut self.board_width, steps, BOARD_WIDTH)
+    }
+
+    pub fn grow_analytics(&mut self, steps: i16) -> bool {
+        step(&mut self.analytics_height, steps, ANALYTICS_HEIGHT)
+    }
+}
+
+// the panel of the game screen the keys go to. arrows move the cursor on
+// the board, scroll the history and the analytics, and recall commands in
+// the command box
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Focus {
+    #[default]
+    Command,
+    Board,
+    History,
+    Analytics,
+}
+
+impl Focus {
+    const ORDER: [Focus; 4] = [Focus::Command, Focus::Board, Focus::History, Focus::Analytics];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Focus::Command => tr(Text::CommandBox),
+            Focus::Board => tr(Text::TheBoard),
+            Focus::History => tr(Text::TheMoveHistory),
+            Focus::Analytics => tr(Text::TheAnalytics),
+        }
+    }
+
+    // the next panel round, or the one before with `back`, skipping the
+    // hidden ones
+    pub fn next(self, panels: &Panels, back: bool) -> Focus {
+        let shown = |focus: &Focus| match focus {
+            Focus::History => panels.history,
+            Focus::Analytics => panels.analytics,
+            Focus::Command | Focus::Board => true,
+        };
+        let order: Vec<Focus> = Focus::ORDER.into_iter().filter(shown).collect();
+        let idx = order.iter().position(|&focus| focus == self).unwrap_or(0);
+        let step = if back { order.len() - 1 } else { 1 };
+        order[(idx + step) % order.len()]
+    }
+}
+
+fn step(value: &mut u16, steps: i16, range: RangeInclusive<u16>) -> bool {
+    let next = (*value as i16 + steps * STEP).clamp(*range.start() as i16, *range.end() as i16) as u16;
+    let moved = next != *value;
+    *value = next;
+    moved
+}
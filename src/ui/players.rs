@@ -0,0 +1,396 @@
+use crate::engine::curriculum::{Opponent, Scripted};
+use crate::engine::rl::{MovePair, RLEngine};
+use crate::engine::uci::UciEngine;
+use crate::engine::worker::{SearchHandle, SearchUpdate};
+use crate::game::piece::Color;
+use crate::game::position::Position;
+use crate::game::result::GameResult;
+use crate::logging;
+use crate::ui::events::AppEvent;
+use crate::ui::i18n::{tr, Text};
+use crate::ui::network;
+use std::net::TcpStream;
+use std::sync::mpsc::Sender;
+use std::time::Duration;
+
+// what a search gets however little time its move has left
+const MIN_SEARCH: Duration = Duration::from_millis(10);
+
+// who the game screen pits the player against
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum OpponentKind {
+    Engine,
+    Scripted(Opponent),
+    // whatever engine [uci] in the config runs
+    Uci,
+    // someone else's chessrl, from `host` or `join`
+    Network,
+}
+
+impl OpponentKind {
+    // the ones the setup screen and `opponent` offer
+    pub const CHOICES: [OpponentKind; 6] = [
+        OpponentKind::Engine,
+        OpponentKind::Scripted(Opponent::Random),
+        OpponentKind::Scripted(Opponent::Greedy),
+        OpponentKind::Scripted(Opponent::OnePly),
+        OpponentKind::Scripted(Opponent::AlphaBeta),
+        OpponentKind::Uci,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            OpponentKind::Engine => "engine",
+            OpponentKind::Scripted(Opponent::Random) => "random",
+            OpponentKind::Scripted(Opponent::Greedy) => "greedy",
+            OpponentKind::Scripted(Opponent::OnePly) => "one-ply",
+            OpponentKind::Scripted(Opponent::AlphaBeta) => "alpha-beta",
+            OpponentKind::Uci => "uci",
+            OpponentKind::Network => "network",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            OpponentKind::Engine => tr(Text::LearningEngine),
+            OpponentKind::Scripted(opponent) => opponent.name(),
+            OpponentKind::Uci => tr(Text::TheUciEngine),
+            OpponentKind::Network => tr(Text::AnotherPlayer),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<OpponentKind> {
+        OpponentKind::CHOICES.iter().copied().find(|kind| kind.name() == name)
+    }
+}
+
+// where a color's controller goes in a pair of them, white's first
+pub fn side(color: Color) -> usize {
+    if color == Color::White { 0 } else { 1 }
+}
+
+// the side to move's time: how long it may think, and how long it has so far
+pub struct Clock {
+    pub think_time: Duration,
+    pub elapsed: Duration,
+    // kept back from a search's time for getting its move played
+    pub overhead: Duration,
+}
+
+impl Clock {
+    // what a search may spend of `time`
+    pub fn budget(&self, time: Duration) -> Duration {
+        time.saturating_sub(self.overhead).max(MIN_SEARCH)
+    }
+}
+
+pub enum MoveDecision {
+    Play(MovePair),
+    // nothing yet. whatever the controller is waiting on wakes the main
+    // loop, and it's asked again
+    Pending,
+    Resign,
+}
+
+// whoever makes one side's moves. the game asks the side to move for its
+// move whenever something happens, so a controller that's still working
+// it out says Pending and keeps going
+pub trait PlayerController {
+    fn name(&self) -> String;
+
+    fn next_move(&mut self, position: &Position, clock: &Clock) -> MoveDecision;
+
+    fn is_human(&self) -> bool {
+        false
+    }
+
+    // a move from outside: typed at the keyboard, or sent over the network.
+    // false if this controller doesn't take moves that way
+    fn offer(&mut self, _mv: MovePair) -> bool {
+        false
+    }
+
+    // every move played, either side's, and where it left the game
+    fn observe(&mut self, _mv: MovePair, _position: &Position) {}
+
+    fn game_over(&mut self, _result: GameResult) {}
+
+    // the game's engine as it stands, for controllers that search with a
+    // copy of it, so what it has learned so far counts
+    fn refresh_engine(&mut self, _engine: &mut RLEngine) {}
+
+    // whether the search `id` is this controller's
+    fn owns_search(&self, _id: u64) -> bool {
+        false
+    }
+
+    // the move to play from its last search, None to resign
+    fn search_finished(&mut self, _best: Option<MovePair>) {}
+
+    // cuts the search under way short, to play the best move found so far.
+    // false if there's none to cut
+    fn move_now(&mut self) -> bool {
+        false
+    }
+}
+
+// the player at the keyboard; moves arrive through offer
+#[derive(Default)]
+pub struct HumanController {
+    queued: Option<MovePair>,
+}
+
+impl PlayerController for HumanController {
+    fn name(&self) -> String {
+        "You".to_string()
+    }
+
+    fn next_move(&mut self, position: &Position, _clock: &Clock) -> MoveDecision {
+        match self.queued.take() {
+            Some(mv) if position.legal_moves().any(|legal| legal.pair() == mv) => MoveDecision::Play(mv),
+            _ => MoveDecision::Pending,
+        }
+    }
+
+    fn is_human(&self) -> bool {
+        true
+    }
+
+    fn offer(&mut self, mv: MovePair) -> bool {
+        self.queued = Some(mv);
+        true
+    }
+}
+
+// the RL engine, searching on its own thread. the search reports back
+// through the events channel, and whoever owns the game hands the move it
+// settles on to search_finished
+pub struct EngineController {
+    name: String,
+    engine: RLEngine,
+    threads: usize,
+    events: Sender<AppEvent>,
+    // the search under way, and the position it's for
+    search: Option<(SearchHandle, u64)>,
+    // a finished search's answer, and the position it was for
+    answer: Option<(Option<MovePair>, u64)>,
+}
+
+impl EngineController {
+    pub fn new(name: String, engine: RLEngine, threads: usize, events: Sender<AppEvent>) -> Self {
+        EngineController { name, engine, threads, events, search: None, answer: None }
+    }
+}
+
+impl PlayerController for EngineController {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn next_move(&mut self, position: &Position, clock: &Clock) -> MoveDecision {
+        let key = position.zobrist_hash();
+        match self.answer.take() {
+            Some((Some(mv), answered)) if answered == key => return MoveDecision::Play(mv),
+            Some((None, answered)) if answered == key => return MoveDecision::Resign,
+            _ => {}
+        }
+        if self.search.as_ref().is_some_and(|(_, searching)| *searching == key) {
+            return MoveDecision::Pending;
+        }
+        // under fog of war the engine searches only what it can see, but
+        // still has to pick a move that's playable on the real board
+        let variant = position.variant();
+        let color = position.to_move();
+        let search_moves = variant.hides_pieces().then(|| variant.moves(position.board(), color).pairs());
+        let events = self.events.clone();
+        let handle = SearchHandle::spawn(
+            self.engine.fork(),
+            variant.view(position.board(), color),
+            color,
+            search_moves,
+            clock.budget(clock.think_time),
+            self.threads,
+            move |search, update| {
+                let _ = events.send(match update {
+                    SearchUpdate::Progress(stats) => AppEvent::EngineProgress { search, stats },
+                    SearchUpdate::Finished(result, stats) => AppEvent::EngineMove { search, result, stats },
+                });
+            },
+        );
+        self.search = Some((handle, key));
+        MoveDecision::Pending
+    }
+
+    // a cap on the search set on the engine it was made with stays
+    fn refresh_engine(&mut self, engine: &mut RLEngine) {
+        if self.search.is_none() && self.answer.is_none() {
+            let cap = self.engine.max_simulations();
+            self.engine = engine.fork();
+            self.engine.set_max_simulations(cap);
+        }
+    }
+
+    fn owns_search(&self, id: u64) -> bool {
+        self.search.as_ref().is_some_and(|(handle, _)| handle.id == id)
+    }
+
+    fn move_now(&mut self) -> bool {
+        match &self.search {
+            Some((handle, _)) => {
+                handle.stop();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn search_finished(&mut self, best: Option<MovePair>) {
+        if let Some((_, key)) = self.search.take() {
+            self.answer = Some((best, key));
+        }
+    }
+}
+
+// one of the training opponents: a random mover, a material grabber or a
+// shallow search. it answers straight away
+pub struct ScriptedController {
+    scripted: Scripted,
+}
+
+impl ScriptedController {
+    pub fn new(scripted: Scripted) -> Self {
+        ScriptedController { scripted }
+    }
+}
+
+impl PlayerController for ScriptedController {
+    fn name(&self) -> String {
+        let mut name = self.scripted.opponent.name().to_string();
+        name[..1].make_ascii_uppercase();
+        name
+    }
+
+    fn next_move(&mut self, position: &Position, _clock: &Clock) -> MoveDecision {
+        match self.scripted.pick(position.variant(), position.board(), position.to_move()) {
+            Some(mv) => MoveDecision::Play(mv.pair()),
+            None => MoveDecision::Resign,
+        }
+    }
+}
+
+// an engine in another process, spoken to over UCI. what it sends back
+// comes in as AppEvent::Uci, and its moves are handed to search_finished
+pub struct UciController {
+    engine: UciEngine,
+    // its own think time from the config, over the clock's
+    think_time: Option<Duration>,
+    answer: Option<(Option<MovePair>, u64)>,
+}
+
+impl UciController {
+    pub fn new(engine: UciEngine, think_time: Option<Duration>) -> Self {
+        UciController { engine, think_time, answer: None }
+    }
+}
+
+impl PlayerController for UciController {
+    fn name(&self) -> String {
+        self.engine.name()
+    }
+
+    fn next_move(&mut self, position: &Position, clock: &Clock) -> MoveDecision {
+        let key = position.zobrist_hash();
+        match self.answer.take() {
+            Some((Some(mv), answered)) if answered == key => return MoveDecision::Play(mv),
+            Some((None, answered)) if answered == key => return MoveDecision::Resign,
+            _ => {}
+        }
+        if self.engine.asked() == Some(key) {
+            return MoveDecision::Pending;
+        }
+        // a failed write means the engine is gone, and AppEvent::Uci says so
+        if let Err(e) = self.engine.go(key, &position.fen(), clock.budget(self.think_time.unwrap_or(clock.think_time))) {
+            logging::warn(&format!("couldn't ask {} for a move: {}", self.engine.name(), e));
+        }
+        MoveDecision::Pending
+    }
+
+    fn owns_search(&self, id: u64) -> bool {
+        self.engine.id == id
+    }
+
+    // UCI's stop: the engine answers the go with its best move so far
+    fn move_now(&mut self) -> bool {
+        match self.engine.stop() {
+            Ok(stopped) => stopped,
+            Err(e) => {
+                logging::warn(&format!("couldn't stop {}: {}", self.engine.name(), e));
+                false
+            }
+        }
+    }
+
+    // a stopped search's move is for a position that's gone
+    fn search_finished(&mut self, best: Option<MovePair>) {
+        let answered = self.engine.answered();
+        if let (Some(key), false) = (answered, self.engine.is_searching()) {
+            self.answer = Some((best, key));
+        }
+    }
+}
+
+// the player at the other end of a network connection, playing `color`.
+// their moves come in as AppEvent::Peer and are offered here; ours go out
+// as they're observed
+pub struct NetworkPeer {
+    stream: TcpStream,
+    color: Color,
+    queued: Option<MovePair>,
+}
+
+impl NetworkPeer {
+    pub fn new(stream: TcpStream, color: Color) -> Self {
+        NetworkPeer { stream, color, queued: None }
+    }
+
+    fn send(&self, message: &str) {
+        if let Err(e) = network::send(&self.stream, message) {
+            logging::warn(&format!("couldn't send '{}' to the other player: {}", message, e));
+        }
+    }
+}
+
+impl PlayerController for NetworkPeer {
+    fn name(&self) -> String {
+        "Opponent".to_string()
+    }
+
+    fn next_move(&mut self, position: &Position, _clock: &Clock) -> MoveDecision {
+        match self.queued.take() {
+            Some(mv) if position.legal_moves().any(|legal| legal.pair() == mv) => MoveDecision::Play(mv),
+            Some(mv) => {
+                logging::warn(&format!("the other player sent {:?}, which isn't legal here", mv));
+                MoveDecision::Pending
+            }
+            None => MoveDecision::Pending,
+        }
+    }
+
+    fn offer(&mut self, mv: MovePair) -> bool {
+        self.queued = Some(mv);
+        true
+    }
+
+    // the side to move now is theirs after one of ours
+    fn observe(&mut self, mv: MovePair, position: &Position) {
+        if position.to_move() == self.color {
+            self.send(&network::move_message(mv));
+        }
+    }
+
+    fn game_over(&mut self, result: GameResult) {
+        if result == (GameResult::Resignation { winner: self.color }) {
+            self.send("resign");
+        }
+    }
+}
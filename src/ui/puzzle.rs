@@ -0,0 +1,274 @@
+use crate::archive::lichess::Puzzle;
+use crate::game::board::Board;
+use crate::game::notation::{parse_san, san};
+use crate::game::piece::{Color, Piece, PieceType};
+use crate::game::result::GameResult;
+use crate::game::variant::{Standard, Variant};
+use crate::ui::i18n::{fill, sentence, tr, Text};
+use crate::ui::input::TextInput;
+use crate::utils::parse_coordinate;
+
+type Square = (usize, usize);
+
+// a move as lichess writes them, castling, en passant and promotion
+// included, which the rules here don't have yet
+#[derive(Clone, Copy, PartialEq)]
+struct PuzzleMove {
+    from: Square,
+    to: Square,
+    promotion: Option<PieceType>,
+}
+
+impl PuzzleMove {
+    // "e2e4", "e7e8q"
+    fn from_uci(text: &str) -> Option<PuzzleMove> {
+        let from = parse_coordinate(text.get(0..2)?)?;
+        let to = parse_coordinate(text.get(2..4)?)?;
+        let promotion = match text.get(4..) {
+            Some("") | None => None,
+            Some(letter) => Some(promotion_piece(letter)?),
+        };
+        Some(PuzzleMove { from, to, promotion })
+    }
+
+    // SAN for `color` in `board`
+    fn from_san(board: &Board, color: Color, text: &str) -> Option<PuzzleMove> {
+        let text = text.trim_end_matches(['+', '#', '!', '?']);
+        let back_rank = if color == Color::White { 7 } else { 0 };
+        match text {
+            "O-O" | "0-0" => return Some(PuzzleMove { from: (back_rank, 4), to: (back_rank, 6), promotion: None }),
+            "O-O-O" | "0-0-0" => return Some(PuzzleMove { from: (back_rank, 4), to: (back_rank, 2), promotion: None }),
+            _ => {}
+        }
+        let (body, promotion) = match text.split_once('=') {
+            Some((body, letter)) => (body, Some(promotion_piece(letter)?)),
+            None => (text, None),
+        };
+        if let Some(mv) = parse_san(board, &board.legal_moves(color), body) {
+            return Some(PuzzleMove { from: mv.from, to: mv.to, promotion });
+        }
+        // a pawn taking onto an empty square is en passant
+        let bytes = body.as_bytes();
+        if bytes.len() == 4 && bytes[1] == b'x' && bytes[0].is_ascii_lowercase() {
+            let to = parse_coordinate(&body[2..])?;
+            let from_rank = if color == Color::White { to.0 + 1 } else { to.0.checked_sub(1)? };
+            let from = (from_rank, (bytes[0] - b'a') as usize);
+            let pawn = board.get_piece(from)?;
+            if pawn.piece_type == PieceType::Pawn && pawn.color == color && board.get_piece(to).is_none() {
+                return Some(PuzzleMove { from, to, promotion: None });
+            }
+        }
+        None
+    }
+
+    fn is_castle(&self, board: &Board) -> bool {
+        board.get_piece(self.from).is_some_and(|p| p.piece_type == PieceType::King) && self.from.1.abs_diff(self.to.1) == 2
+    }
+
+    fn san(&self, board: &Board) -> String {
+        if self.is_castle(board) {
+            return if self.to.1 == 6 { "O-O" } else { "O-O-O" }.to_string();
+        }
+        let mut text = san(board, (self.from, self.to));
+        if let Some(piece) = self.promotion {
+            let letter = Piece::new(piece, Color::White).letter();
+            let at = text.find(['+', '#']).unwrap_or(text.len());
+            text.insert_str(at, &format!("={}", letter));
+        }
+        text
+    }
+
+    // moves the pieces, the rook too when castling and the passed pawn on
+    // en passant. a pawn reaching the last rank becomes a queen unless told
+    // otherwise
+    fn play(&self, board: &mut Board) {
+        let Some(piece) = board.get_piece(self.from).copied() else {
+            return;
+        };
+        if self.is_castle(board) {
+            let (rook_from, rook_to) = if self.to.1 == 6 { (7, 5) } else { (0, 3) };
+            if let Some(rook) = board.get_piece((self.from.0, rook_from)).copied() {
+                board.clear_square((self.from.0, rook_from));
+                board.put((self.from.0, rook_to), rook);
+            }
+        }
+        if piece.piece_type == PieceType::Pawn && self.from.1 != self.to.1 && board.get_piece(self.to).is_none() {
+            board.clear_square((self.from.0, self.to.1));
+        }
+        let last_rank = if piece.color == Color::White { 0 } else { 7 };
+        let piece = match self.promotion {
+            Some(promoted) => Piece::new(promoted, piece.color),
+            None if piece.piece_type == PieceType::Pawn && self.to.0 == last_rank => Piece::new(PieceType::Queen, piece.color),
+            None => piece,
+        };
+        board.clear_square(self.from);
+        board.put(self.to, piece);
+    }
+}
+
+fn promotion_piece(letter: &str) -> Option<PieceType> {
+    match letter.to_ascii_lowercase().as_str() {
+        "q" => Some(PieceType::Queen),
+        "r" => Some(PieceType::Rook),
+        "b" => Some(PieceType::Bishop),
+        "n" => Some(PieceType::Knight),
+        _ => None,
+    }
+}
+
+// the daily puzzle being solved: the position the game reached, and how far
+// into the solution the player has got. the solver's moves alternate with
+// the replies the solution expects
+pub struct PuzzleScreen {
+    pub puzzle: Puzzle,
+    pub board: Board,
+    pub solver: Color,
+    // solution moves played so far, the replies included
+    pub step: usize,
+    pub last_move: Option<(Square, Square)>,
+    pub mistakes: u32,
+    pub hints: u32,
+    // hints given for the move to find now
+    hint_level: u32,
+    pub revealed: bool,
+    // the moves of the solution as played, in SAN
+    pub played: Vec<String>,
+    pub message: String,
+    // e.g. that this is an older puzzle kept from when there was a network
+    pub note: Option<String>,
+    pub input: TextInput,
+}
+
+impl PuzzleScreen {
+    pub fn new(puzzle: Puzzle, note: Option<String>) -> Result<Self, String> {
+        let mut board = Board::new();
+        let mut color = Color::White;
+        let mut last_move = None;
+        for text in &puzzle.moves {
+            let Some(mv) = PuzzleMove::from_san(&board, color, text) else {
+                return Err(fill(Text::CouldntFollowPuzzle, &[text]));
+            };
+            mv.play(&mut board);
+            last_move = Some((mv.from, mv.to));
+            color = color.opposite();
+        }
+        if puzzle.solution.iter().any(|text| PuzzleMove::from_uci(text).is_none()) || puzzle.solution.is_empty() {
+            return Err(tr(Text::UnreadableSolution).to_string());
+        }
+        let message = sentence(Text::SideToPlay, &[&tr(Text::Side(color))]);
+        Ok(PuzzleScreen {
+            puzzle,
+            board,
+            solver: color,
+            step: 0,
+            last_move,
+            mistakes: 0,
+            hints: 0,
+            hint_level: 0,
+            revealed: false,
+            played: Vec::new(),
+            message,
+            note,
+            input: TextInput::new(),
+        })
+    }
+
+    pub fn solved(&self) -> bool {
+        self.step >= self.puzzle.solution.len()
+    }
+
+    // the solver's moves in the solution, counting the one to find now
+    pub fn progress(&self) -> (usize, usize) {
+        ((self.step / 2 + 1).min(self.puzzle.solution.len().div_ceil(2)), self.puzzle.solution.len().div_ceil(2))
+    }
+
+    fn expected(&self) -> PuzzleMove {
+        PuzzleMove::from_uci(&self.puzzle.solution[self.step]).expect("checked in new")
+    }
+
+    fn play(&mut self, mv: PuzzleMove) {
+        self.played.push(mv.san(&self.board));
+        mv.play(&mut self.board);
+        self.last_move = Some((mv.from, mv.to));
+        self.step += 1;
+        self.hint_level = 0;
+    }
+
+    // a move typed in: "e2 e4", "e2e4", "e7e8q" or SAN
+    pub fn attempt(&mut self, text: &str) {
+        if self.solved() {
+            self.message = tr(Text::SolvedAlready).to_string();
+            return;
+        }
+        let text = text.trim();
+        let squares: Vec<&str> = text.split_whitespace().collect();
+        let mv = match squares.as_slice() {
+            [from, to] => PuzzleMove::from_uci(&format!("{}{}", from, to)),
+            _ => PuzzleMove::from_uci(&text.to_lowercase()).or_else(|| PuzzleMove::from_san(&self.board, self.solver, text)),
+        };
+        let Some(mut mv) = mv else {
+            self.message = tr(Text::TypeAMove).to_string();
+            return;
+        };
+        let expected = self.expected();
+        if mv.promotion.is_none() && (mv.from, mv.to) == (expected.from, expected.to) {
+            mv.promotion = expected.promotion;
+        }
+        if mv != expected && !self.mates(mv) {
+            self.mistakes += 1;
+            self.message = fill(Text::NotItTryAgain, &[&mv.san(&self.board)]);
+            return;
+        }
+        self.play(mv);
+        if self.solved() {
+            self.message = tr(Text::SolvedExclaimed).to_string();
+            return;
+        }
+        let reply = self.expected();
+        let reply_text = reply.san(&self.board);
+        self.play(reply);
+        self.message = if self.solved() {
+            tr(Text::SolvedExclaimed).to_string()
+        } else {
+            fill(Text::RightKeepGoing, &[&reply_text])
+        };
+    }
+
+    // lichess takes any mate on the puzzle's last move, not only its own
+    fn mates(&self, mv: PuzzleMove) -> bool {
+        if self.step + 1 != self.puzzle.solution.len() || self.board.legal_moves(self.solver).find((mv.from, mv.to)).is_none() {
+            return false;
+        }
+        let mut after = self.board.clone();
+        mv.play(&mut after);
+        matches!(
+            Standard.result(&after, self.solver.opposite()),
+            Some(GameResult::Checkmate { winner }) if winner == self.solver
+        )
+    }
+
+    // the first hint names the piece, the second the move
+    pub fn hint(&mut self) {
+        if self.solved() {
+            return;
+        }
+        let expected = self.expected();
+        self.hints += 1;
+        self.hint_level += 1;
+        self.message = match (self.hint_level, self.board.get_piece(expected.from)) {
+            (1, Some(piece)) => fill(Text::LookAtYour, &[&tr(Text::Piece(piece.piece_type))]),
+            _ => fill(Text::PlayMove, &[&expected.san(&self.board)]),
+        };
+    }
+
+    // plays out the rest of the solution
+    pub fn reveal(&mut self) {
+        let from = self.played.len();
+        while !self.solved() {
+            let mv = self.expected();
+            self.play(mv);
+        }
+        self.revealed = true;
+        self.message = fill(Text::TheSolution, &[&self.played[from..].join(" ")]);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::logging;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+pub const DEFAULT_PORT: u16 = 7879;
+
+// what a browser gets at /: the board and the moves, redrawn from the
+// event stream at /events
+const PAGE: &str = r#"<!doctype html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>chessrl</title>
+<style>
+  body { background: #202124; color: #ddd; font-family: sans-serif; }
+  #board { border-collapse: collapse; font-size: 40px; }
+  #board td { width: 52px; height: 52px; text-align: center; color: #111; }
+  .light { background: #eeeed2; }
+  .dark { background: #769656; }
+  #moves { white-space: pre-wrap; max-width: 40em; }
+</style>
+</head>
+<body>
+<table id="board"></table>
+<p id="moves">Waiting for the game…</p>
+<script>
+const glyphs = { K: "♔", Q: "♕", R: "♖", B: "♗", N: "♘", P: "♙", k: "♚", q: "♛", r: "♜", b: "♝", n: "♞", p: "♟" };
+function draw(fen) {
+  const rows = fen.split(" ")[0].split("/");
+  const table = document.getElementById("board");
+  table.innerHTML = "";
+  rows.forEach((row, rank) => {
+    const tr = table.insertRow();
+    let file = 0;
+    for (const c of row) {
+      const empty = parseInt(c);
+      for (let i = 0; i < (empty || 1); i++, file++) {
+        const td = tr.insertCell();
+        td.className = (rank + file) % 2 ? "dark" : "light";
+        td.textContent = empty ? "" : glyphs[c];
+      }
+    }
+  });
+}
+const events = new EventSource("/events");
+events.onmessage = (e) => {
+  const [fen, ...pgn] = e.data.split("\n");
+  draw(fen);
+  document.getElementById("moves").textContent = pgn.filter((l) => !l.startsWith("[")).join("\n").trim();
+};
+events.onerror = () => { document.getElementById("moves").textContent += "\n(connection lost)"; };
+</script>
+</body>
+</html>
+"#;
+
+// shows the game on screen to whoever connects, read only: a browser at
+// http://host:port/, or another chessrl with --follow http://host:port.
+// both read /events, a stream of server-sent events each carrying the
+// position's FEN on its first line and the game's PGN after it
+pub struct Relay {
+    pub port: u16,
+    shared: Arc<Mutex<Shared>>,
+}
+
+#[derive(Default)]
+struct Shared {
+    // the latest event, for spectators who join late
+    latest: Option<String>,
+    spectators: Vec<Sender<String>>,
+}
+
+impl Relay {
+    pub fn start(port: u16) -> io::Result<Relay> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        let shared = Arc::new(Mutex::new(Shared::default()));
+        let accepting = Arc::clone(&shared);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        let shared = Arc::clone(&accepting);
+                        thread::spawn(move || {
+                            if let Err(e) = serve(stream, &shared) {
+                                logging::debug(&format!("a spectator went: {}", e));
+                            }
+                        });
+                    }
+                    Err(e) => logging::warn(&format!("couldn't take a spectator: {}", e)),
+                }
+            }
+        });
+        Ok(Relay { port, shared })
+    }
+
+    // sends the position and the game so far to every spectator
+    pub fn publish(&self, fen: &str, pgn: &str) {
+        let mut event = format!("data: {}\n", fen);
+        for line in pgn.lines() {
+            event.push_str(&format!("data: {}\n", line));
+        }
+        event.push('\n');
+        let mut shared = self.shared.lock().unwrap();
+        shared.spectators.retain(|spectator| spectator.send(event.clone()).is_ok());
+        shared.latest = Some(event);
+    }
+
+    pub fn spectators(&self) -> usize {
+        self.shared.lock().unwrap().spectators.len()
+    }
+}
+
+// one spectator's connection: the page, or the events until they leave
+fn serve(mut stream: TcpStream, shared: &Mutex<Shared>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+    // the headers say nothing the relay needs
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+    let path = request.split_whitespace().nth(1).unwrap_or("/");
+    match path {
+        "/" => {
+            let head = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                PAGE.len()
+            );
+            stream.write_all(head.as_bytes())?;
+            stream.write_all(PAGE.as_bytes())
+        }
+        "/events" => {
+            stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n")?;
+            let (sender, events) = mpsc::channel();
+            {
+                let mut shared = shared.lock().unwrap();
+                if let Some(latest) = &shared.latest {
+                    let _ = sender.send(latest.clone());
+                }
+                shared.spectators.push(sender);
+            }
+            // a write to someone gone fails, which drops their receiver, and
+            // the next publish drops their sender
+            for event in events {
+                stream.write_all(event.as_bytes())?;
+                stream.flush()?;
+            }
+            Ok(())
+        }
+        _ => stream.write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"),
+    }
+}
+
+// the PGN in one event read back, leaving out the FEN line
+pub fn event_pgn(data: &[String]) -> String {
+    data.iter().skip(1).map(|line| format!("{}\n", line)).collect()
+}
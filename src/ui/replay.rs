@@ -0,0 +1,114 @@
+use crate::archive::games;
+use crate::game::board::Board;
+use crate::game::record::GameRecord;
+use std::time::{Duration, Instant};
+
+const SPEEDS: [f32; 4] = [0.5, 1.0, 2.0, 4.0];
+// time per move at 1x
+const BASE_DELAY: Duration = Duration::from_millis(1000);
+
+// steps through a finished list of positions, optionally on a timer
+pub struct Replay {
+    pub positions: Vec<Board>,
+    pub moves: Vec<String>,
+    pub index: usize,
+    pub playing: bool,
+    speed_index: usize,
+    last_step: Instant,
+    // for copying the game out
+    record: GameRecord,
+}
+
+impl Replay {
+    // `moves[i]` leads from `positions[i]` to `positions[i + 1]`
+    pub fn new(record: &GameRecord) -> Self {
+        Replay {
+            positions: record.positions().to_vec(),
+            moves: record.sans(),
+            index: 0,
+            playing: false,
+            speed_index: 1,
+            last_step: Instant::now(),
+            record: record.clone(),
+        }
+    }
+
+    pub fn fen(&self) -> String {
+        self.record.fen_at(self.index)
+    }
+
+    pub fn first_to_move(&self) -> crate::game::piece::Color {
+        self.record.first_to_move()
+    }
+
+    pub fn movetext(&self) -> String {
+        let record = &self.record;
+        games::format_movetext(&record.sans(), &record.comments(), "*", record.first_to_move())
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.positions[self.index]
+    }
+
+    pub fn last_index(&self) -> usize {
+        self.positions.len().saturating_sub(1)
+    }
+
+    pub fn speed(&self) -> f32 {
+        SPEEDS[self.speed_index]
+    }
+
+    pub fn forward(&mut self) {
+        self.index = (self.index + 1).min(self.last_index());
+    }
+
+    pub fn back(&mut self) {
+        self.index = self.index.saturating_sub(1);
+    }
+
+    pub fn jump_to_start(&mut self) {
+        self.index = 0;
+    }
+
+    pub fn jump_to_end(&mut self) {
+        self.index = self.last_index();
+        self.playing = false;
+    }
+
+    pub fn toggle_play(&mut self) {
+        // playing from the final position starts over
+        if !self.playing && self.index == self.last_index() {
+            self.index = 0;
+        }
+        self.playing = !self.playing;
+        self.last_step = Instant::now();
+    }
+
+    pub fn faster(&mut self) {
+        self.speed_index = (self.speed_index + 1).min(SPEEDS.len() - 1);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed_index = self.speed_index.saturating_sub(1);
+    }
+
+    fn delay(&self) -> Duration {
+        BASE_DELAY.div_f32(self.speed())
+    }
+
+    // how long the event loop may sleep before the next automatic step
+    pub fn time_until_step(&self) -> Option<Duration> {
+        self.playing
+            .then(|| self.delay().saturating_sub(self.last_step.elapsed()))
+    }
+
+    pub fn on_tick(&mut self) {
+        if self.playing && self.last_step.elapsed() >= self.delay() {
+            self.forward();
+            self.last_step = Instant::now();
+            if self.index == self.last_index() {
+                self.playing = false;
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::game::piece::Color as PieceColor;
+use crate::ui::i18n::{tr, Text};
+use rand::Rng;
+
+// the bot's seconds per move the time row steps through. there's no game
+// clock, so this is the time control
+pub const THINK_STEPS: [f32; 9] = [0.5, 1.0, 2.0, 3.0, 5.0, 10.0, 15.0, 20.0, 30.0];
+
+// the setup screen's rows, top to bottom
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SetupRow {
+    Side,
+    Variant,
+    Level,
+    Strength,
+    TimePerMove,
+    Odds,
+    Personality,
+    Opponent,
+}
+
+impl SetupRow {
+    pub const ALL: [SetupRow; 8] = [
+        SetupRow::Side,
+        SetupRow::Variant,
+        SetupRow::Level,
+        SetupRow::Strength,
+        SetupRow::TimePerMove,
+        SetupRow::Odds,
+        SetupRow::Personality,
+        SetupRow::Opponent,
+    ];
+
+    pub fn label(self) -> &'static str {
+        tr(match self {
+            SetupRow::Side => Text::YouPlay,
+            SetupRow::Variant => Text::Variant,
+            SetupRow::Level => Text::Level,
+            SetupRow::Strength => Text::Strength,
+            SetupRow::TimePerMove => Text::TimePerMove,
+            SetupRow::Odds => Text::Odds,
+            SetupRow::Personality => Text::Personality,
+            SetupRow::Opponent => Text::Opponent,
+        })
+    }
+}
+
+// the side the player asks for on the setup screen
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Side {
+    White,
+    Black,
+    Random,
+}
+
+impl Side {
+    pub const ALL: [Side; 3] = [Side::White, Side::Black, Side::Random];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Side::White => "white",
+            Side::Black => "black",
+            Side::Random => "random",
+        }
+    }
+
+    // random is tossed for each game
+    pub fn color(self) -> PieceColor {
+        match self {
+            Side::White => PieceColor::White,
+            Side::Black => PieceColor::Black,
+            Side::Random if rand::thread_rng().gen_bool(0.5) => PieceColor::White,
+            Side::Random => PieceColor::Black,
+        }
+    }
+}
+
+// `idx` moved `step` along a list of `len`, stopping at either end
+pub fn stepped(idx: usize, len: usize, step: isize) -> usize {
+    idx.saturating_add_signed(step).min(len - 1)
+}
+
+// the step nearest `secs`, so a time set by hand in the config still has a
+// place to step from
+pub fn think_step(secs: f32) -> usize {
+    THINK_STEPS
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| (*a - secs).abs().total_cmp(&(*b - secs).abs()))
+        .map_or(0, |(idx, _)| idx)
+}
@@ -0,0 +1,78 @@
+use crate::ui::players::OpponentKind;
+use crate::utils::{data_dir, pgn_date_time};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+// the best runs kept on the leaderboard
+const KEPT: usize = 10;
+
+// a speedrun under way: the random mover's seed, and who the player was
+// playing before, to go back to for the next game
+#[derive(Clone, Copy)]
+pub struct Attempt {
+    pub seed: u64,
+    pub previous: OpponentKind,
+}
+
+// a speedrun mated: the player's moves and their own thinking time, the
+// random mover's never counted. the same seed gives the same replies to
+// the same moves, so runs on one seed race each other
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Run {
+    pub seed: u64,
+    pub moves: usize,
+    pub millis: u64,
+    pub date: String,
+}
+
+impl Run {
+    pub fn new(seed: u64, moves: usize, time: Duration) -> Self {
+        let unix_secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Run { seed, moves, millis: time.as_millis() as u64, date: pgn_date_time(unix_secs).0 }
+    }
+
+    pub fn text(&self) -> String {
+        format!("{} moves in {} (seed {}, {})", self.moves, clock_text(Duration::from_millis(self.millis)), self.seed, self.date)
+    }
+}
+
+// "1:02.3"
+pub fn clock_text(time: Duration) -> String {
+    let tenths = time.as_millis() / 100;
+    format!("{}:{:02}.{}", tenths / 600, tenths / 10 % 60, tenths % 10)
+}
+
+fn path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("speedruns.json"))
+}
+
+// fewest moves first, then the quickest
+pub fn leaderboard() -> Vec<Run> {
+    path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+// puts `run` on the leaderboard, returning its place if it made it
+pub fn record(run: Run) -> io::Result<Option<usize>> {
+    let mut runs = leaderboard();
+    let place = runs.iter().take_while(|kept| (kept.moves, kept.millis) <= (run.moves, run.millis)).count();
+    if place >= KEPT {
+        return Ok(None);
+    }
+    runs.insert(place, run);
+    runs.truncate(KEPT);
+    let path = path().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_string_pretty(&runs).map_err(io::Error::other)?;
+    fs::write(path, json)?;
+    Ok(Some(place + 1))
+}
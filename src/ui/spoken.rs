@@ -0,0 +1,285 @@
+use crate::engine::rl::MovePair;
+use crate::game::movement::Move;
+use crate::game::piece::PieceType;
+use crate::utils::{coordinate_to_string, parse_coordinate};
+
+const PIECES: [PieceType; 6] = [
+    PieceType::King,
+    PieceType::Queen,
+    PieceType::Rook,
+    PieceType::Bishop,
+    PieceType::Knight,
+    PieceType::Pawn,
+];
+const CAPTURE_WORDS: [&str; 5] = ["takes", "take", "captures", "capture", "x"];
+const TO_WORDS: [&str; 5] = ["to", "goes", "go", "moves", "move"];
+// said along the way and meaning nothing; which side of the verb "on" or
+// "from" is on says what it's about
+const FILLER: [&str; 8] = ["the", "my", "on", "from", "at", "one", "square", "please"];
+
+type Square = (usize, usize);
+
+// part of the way to a square: "g1", or just the file or rank
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Hint {
+    Square(Square),
+    File(usize),
+    Rank(usize),
+}
+
+impl Hint {
+    fn matches(self, square: Square) -> bool {
+        match self {
+            Hint::Square(hint) => hint == square,
+            Hint::File(file) => square.1 == file,
+            Hint::Rank(rank) => square.0 == rank,
+        }
+    }
+
+    fn text(self) -> String {
+        match self {
+            Hint::Square(square) => format!("on {}", coordinate_to_string(square)),
+            Hint::File(file) => format!("on the {}-file", (b'a' + file as u8) as char),
+            Hint::Rank(rank) => format!("on rank {}", 8 - rank),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Word {
+    Piece(PieceType),
+    Hint(Hint),
+    Capture,
+    To,
+    Filler,
+}
+
+fn word(text: &str) -> Option<Word> {
+    if let Some(piece) = PIECES.iter().find(|piece| piece.name() == text) {
+        return Some(Word::Piece(*piece));
+    }
+    if CAPTURE_WORDS.contains(&text) {
+        return Some(Word::Capture);
+    }
+    if TO_WORDS.contains(&text) {
+        return Some(Word::To);
+    }
+    if FILLER.contains(&text) {
+        return Some(Word::Filler);
+    }
+    hint(text).map(Word::Hint)
+}
+
+// None if any of it isn't a word spoken moves use. "a" before a piece,
+// as in "takes a pawn", is an article rather than the a-file
+fn words(text: &str) -> Option<Vec<Word>> {
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    tokens
+        .iter()
+        .enumerate()
+        .map(|(idx, token)| {
+            let article = matches!(*token, "a" | "an")
+                && tokens.get(idx + 1).is_some_and(|next| matches!(word(next), Some(Word::Piece(_))));
+            if article { Some(Word::Filler) } else { word(token) }
+        })
+        .collect()
+}
+
+fn hint(text: &str) -> Option<Hint> {
+    if let Some(square) = parse_coordinate(text) {
+        return Some(Hint::Square(square));
+    }
+    match text.as_bytes() {
+        [file @ b'a'..=b'h'] => Some(Hint::File((file - b'a') as usize)),
+        [rank @ b'1'..=b'8'] => Some(Hint::Rank(8 - (rank - b'0') as usize)),
+        _ => None,
+    }
+}
+
+// whether what's typed so far could become one of the words spoken moves
+// use, so the command box doesn't turn red halfway through "knight"
+pub fn could_be_word(text: &str) -> bool {
+    PIECES
+        .iter()
+        .map(|piece| piece.name())
+        .chain(CAPTURE_WORDS)
+        .chain(TO_WORDS)
+        .chain(FILLER)
+        .chain(["an"])
+        .any(|word| word.starts_with(text))
+        || hint(text).is_some()
+}
+
+// a move said in words: "knight to f3", "pawn takes d5", "queen takes
+// rook", "rook on a1 to d1", "bishop c4"
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Spoken {
+    piece: Option<PieceType>,
+    from: Option<Hint>,
+    capture: bool,
+    to: Option<Square>,
+    captured: Option<PieceType>,
+}
+
+// None for anything else, plain coordinates included. everything before
+// "to" or "takes" is about the piece that moves, everything after about
+// where it goes; without either, the last square is where
+pub fn parse(text: &str) -> Option<Spoken> {
+    let words = words(text)?;
+    let verb = words.iter().position(|w| matches!(w, Word::Capture | Word::To));
+    let split = match verb {
+        Some(idx) => idx,
+        None => words.iter().rposition(|w| matches!(w, Word::Hint(Hint::Square(_))))?,
+    };
+    let (before, after) = words.split_at(split);
+    let mut spoken = Spoken {
+        piece: None,
+        from: None,
+        capture: words.contains(&Word::Capture),
+        to: None,
+        captured: None,
+    };
+    for word in before {
+        match *word {
+            Word::Piece(piece) if spoken.piece.is_none() => spoken.piece = Some(piece),
+            Word::Hint(hint) if spoken.from.is_none() => spoken.from = Some(hint),
+            Word::Filler => {}
+            _ => return None,
+        }
+    }
+    for word in after {
+        match *word {
+            Word::Hint(Hint::Square(square)) if spoken.to.is_none() => spoken.to = Some(square),
+            Word::Piece(piece) if spoken.captured.is_none() => spoken.captured = Some(piece),
+            Word::Capture | Word::To | Word::Filler => {}
+            _ => return None,
+        }
+    }
+    // a piece or a verb makes it words; a square alone is the usual input
+    let worded = spoken.piece.is_some() || verb.is_some();
+    let somewhere = spoken.to.is_some() || spoken.captured.is_some();
+    (worded && somewhere).then_some(spoken)
+}
+
+impl Spoken {
+    fn fits(&self, mv: &Move) -> bool {
+        self.piece.is_none_or(|piece| mv.piece == piece)
+            && self.from.is_none_or(|hint| hint.matches(mv.from))
+            && self.to.is_none_or(|to| mv.to == to)
+            && self.captured.is_none_or(|captured| mv.captured == Some(captured))
+            && (!self.capture || mv.captured.is_some())
+    }
+
+    // why nothing fits, e.g. "No knight of yours can go to f3"
+    fn refusal(&self) -> String {
+        let piece = self.piece.map_or("piece", PieceType::name);
+        let from = self.from.map_or(String::new(), |hint| format!(" {}", hint.text()));
+        let target = match (self.captured, self.to) {
+            (Some(captured), Some(to)) => format!("take the {} on {}", captured.name(), coordinate_to_string(to)),
+            (Some(captured), None) => format!("take a {}", captured.name()),
+            (None, Some(to)) if self.capture => format!("take on {}", coordinate_to_string(to)),
+            (None, Some(to)) => format!("go to {}", coordinate_to_string(to)),
+            (None, None) => "move".to_string(),
+        };
+        format!("No {} of yours{} can {}", piece, from, target)
+    }
+}
+
+pub enum Outcome {
+    Play(MovePair),
+    // more than one move fits; the question, and the moves to pick from
+    Ask(String, Vec<Move>),
+    Refuse(String),
+}
+
+// what `spoken` means among the legal `moves`
+pub fn resolve(spoken: &Spoken, moves: &[Move]) -> Outcome {
+    let fitting: Vec<Move> = moves.iter().filter(|mv| spoken.fits(mv)).copied().collect();
+    match fitting[..] {
+        [] => Outcome::Refuse(spoken.refusal()),
+        [only] => Outcome::Play(only.pair()),
+        _ => Outcome::Ask(question(&fitting), fitting),
+    }
+}
+
+// an answer to the question about `choices`: a square or a file or rank,
+// like "g1", "the g one" or "on f5". None if the text isn't one, so it
+// can be taken as a command of its own
+pub fn answer(choices: &[Move], text: &str) -> Option<Outcome> {
+    let words = words(text)?;
+    let hints: Vec<Hint> = words
+        .iter()
+        .filter_map(|w| match w {
+            Word::Hint(hint) => Some(*hint),
+            _ => None,
+        })
+        .collect();
+    let [hint] = hints[..] else {
+        return None;
+    };
+    if words.iter().any(|w| matches!(w, Word::Piece(_) | Word::Capture | Word::To)) {
+        return None;
+    }
+    // by where they start if that tells them apart, otherwise by where they go
+    let by_from: Vec<Move> = choices.iter().filter(|mv| hint.matches(mv.from)).copied().collect();
+    let narrowed = if by_from.is_empty() {
+        choices.iter().filter(|mv| hint.matches(mv.to)).copied().collect()
+    } else {
+        by_from
+    };
+    Some(match narrowed[..] {
+        [] => Outcome::Ask(format!("That isn't one of them. {}", question(choices)), choices.to_vec()),
+        [only] => Outcome::Play(only.pair()),
+        _ => Outcome::Ask(question(&narrowed), narrowed),
+    })
+}
+
+// "Which knight? b1 or g1", or where to when it's one piece with a choice
+fn question(choices: &[Move]) -> String {
+    let distinct = |squares: Vec<Square>| {
+        let mut seen: Vec<Square> = Vec::new();
+        for square in squares {
+            if !seen.contains(&square) {
+                seen.push(square);
+            }
+        }
+        // a to h, then up the board
+        seen.sort_by_key(|&(rank, file)| (file, 7 - rank));
+        seen
+    };
+    let froms = distinct(choices.iter().map(|mv| mv.from).collect());
+    let tos = distinct(choices.iter().map(|mv| mv.to).collect());
+    let same = |pieces: Vec<Option<PieceType>>| match pieces[..] {
+        [Some(first), ..] if pieces.iter().all(|piece| *piece == Some(first)) => Some(first),
+        _ => None,
+    };
+    if froms.len() == choices.len() {
+        let piece = same(choices.iter().map(|mv| Some(mv.piece)).collect()).map_or("piece", PieceType::name);
+        return format!("Which {}? {}", piece, either(&froms));
+    }
+    if froms.len() == 1 && tos.len() == choices.len() {
+        return match same(choices.iter().map(|mv| mv.captured).collect()) {
+            Some(captured) => format!("Which {}? {}", captured.name(), either(&tos)),
+            None => format!("Where to? {}", either(&tos)),
+        };
+    }
+    let moves: Vec<String> = choices
+        .iter()
+        .map(|mv| format!("{} to {}", coordinate_to_string(mv.from), coordinate_to_string(mv.to)))
+        .collect();
+    format!("Which one? {}", join_or(&moves))
+}
+
+fn either(squares: &[Square]) -> String {
+    let names: Vec<String> = squares.iter().map(|square| coordinate_to_string(*square)).collect();
+    join_or(&names)
+}
+
+// "b1 or g1", "a1, d1 or h1"
+fn join_or(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} or {}", rest.join(", "), last),
+    }
+}
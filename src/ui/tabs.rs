@@ -0,0 +1,40 @@
+use crate::game::result::GameResult;
+use crate::ui::i18n::{fill, tr, Text};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+// what a tab on the game screen holds
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TabKind {
+    // a game against whoever `opponent` says
+    Game,
+    // a board to move both sides on, the engine looking at every position
+    Analysis,
+}
+
+// what the tab bar says about one tab: "vs Bot · thinking", "Analysis"
+pub fn title(kind: TabKind, opponent: &str, result: Option<GameResult>, thinking: bool) -> String {
+    match kind {
+        TabKind::Analysis => tr(Text::AnalysisTitle).to_string(),
+        TabKind::Game => match result {
+            Some(result) => fill(Text::TabOver, &[&opponent, &result.score()]),
+            None if thinking => fill(Text::TabThinking, &[&opponent]),
+            None => fill(Text::TabYourMove, &[&opponent]),
+        },
+    }
+}
+
+// one row of numbered tabs with the one on screen picked out
+pub fn tab_bar(titles: &[String], active: usize, accent: Color) -> Line<'static> {
+    let mut spans = Vec::new();
+    for (idx, title) in titles.iter().enumerate() {
+        let text = format!(" {} {} ", idx + 1, title);
+        spans.push(if idx == active {
+            Span::styled(text, Style::default().fg(Color::Black).bg(accent).add_modifier(Modifier::BOLD))
+        } else {
+            Span::styled(text, Style::default().fg(Color::Gray))
+        });
+        spans.push(Span::raw("│"));
+    }
+    Line::from(spans)
+}
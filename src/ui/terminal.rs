@@ -1,68 +1,97 @@
+use crate::logging;
+use crate::utils::{data_dir, pgn_date_time};
 use crossterm::{
-    cursor::{Hide, Show},
+    cursor::Show,
+    event::{DisableMouseCapture, EnableMouseCapture},
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, size},
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use std::io::stdout;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 
-#[derive(Debug)]
-pub enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
+// the TUI's terminal setup: raw mode, the alternate screen and mouse
+// capture. dropping the guard undoes all three, on a normal exit and while
+// a panic unwinds alike
+pub struct TerminalGuard;
 
-#[derive(Debug)]
-pub enum UserAction {
-    Move(Direction),
-    Select,
-    Quit,
+impl TerminalGuard {
+    pub fn enter() -> std::io::Result<TerminalGuard> {
+        enable_raw_mode()?;
+        execute!(stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+        ACTIVE.store(true, Ordering::SeqCst);
+        Ok(TerminalGuard)
+    }
 }
 
-pub struct TerminalUI {
-    cursor_pos: (usize, usize),
-    terminal_size: (u16, u16),
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore();
+    }
 }
 
-impl TerminalUI {
-    pub fn new() -> Self {
-        let terminal_size = size().unwrap_or((80, 24));
-        Self {
-            cursor_pos: (0, 0),
-            terminal_size,
-        }
-    }
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+// what the game looked like, for a crash report
+static CRASH_CONTEXT: Mutex<String> = Mutex::new(String::new());
+static CRASH_REPORTS: AtomicBool = AtomicBool::new(true);
 
-    pub fn get_terminal_size(&self) -> (u16, u16) {
-        self.terminal_size
-    }
-
-    pub fn init() -> std::io::Result<()> {
-        enable_raw_mode()?;
-        let mut stdout = stdout();
-        execute!(stdout, Hide)?;
-        Ok(())
+// safe to call twice; only the first call after enter() does anything
+fn restore() {
+    if ACTIVE.swap(false, Ordering::SeqCst) {
+        let _ = disable_raw_mode();
+        let _ = execute!(stdout(), LeaveAlternateScreen, DisableMouseCapture, Show);
     }
+}
 
-    pub fn cleanup() -> std::io::Result<()> {
-        let mut stdout = stdout();
-        execute!(stdout, Show)?;
-        disable_raw_mode()?;
-        Ok(())
+pub fn set_crash_context(context: String) {
+    if let Ok(mut slot) = CRASH_CONTEXT.lock() {
+        *slot = context;
     }
+}
 
-    pub fn move_cursor(&mut self, direction: Direction) {
-        match direction {
-            Direction::Up if self.cursor_pos.1 > 0 => self.cursor_pos.1 -= 1,
-            Direction::Down if self.cursor_pos.1 < 7 => self.cursor_pos.1 += 1,
-            Direction::Left if self.cursor_pos.0 > 0 => self.cursor_pos.0 -= 1,
-            Direction::Right if self.cursor_pos.0 < 7 => self.cursor_pos.0 += 1,
-            _ => {}
+// a panic on the main thread gives the terminal back before the message is
+// printed, so it doesn't vanish with the alternate screen. one on a search
+// thread leaves the UI running and only goes to the log and the report
+pub fn install_panic_hook(crash_reports: bool) {
+    CRASH_REPORTS.store(crash_reports, Ordering::SeqCst);
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let on_main = std::thread::current().name() == Some("main");
+        logging::error(&format!("panic: {}", info));
+        let report = CRASH_REPORTS.load(Ordering::SeqCst).then(|| write_crash_report(info)).flatten();
+        if !on_main {
+            return;
         }
-    }
+        restore();
+        default_hook(info);
+        if let Some(path) = report {
+            eprintln!("\nchessrl crashed. A crash report was written to {}", path.display());
+        }
+    }));
+}
 
-    pub fn get_cursor_pos(&self) -> (usize, usize) {
-        self.cursor_pos
-    }
+fn write_crash_report(info: &PanicHookInfo) -> Option<PathBuf> {
+    let unix_secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let (date, time) = pgn_date_time(unix_secs);
+    // the lock may be held by the code that panicked
+    let context = CRASH_CONTEXT.try_lock().map(|c| c.clone()).unwrap_or_default();
+    let report = format!(
+        "chessrl {} crashed at {} {} UTC\n\nthread: {}\n{}\n\n{}\nbacktrace:\n{}\n",
+        env!("CARGO_PKG_VERSION"),
+        date,
+        time,
+        std::thread::current().name().unwrap_or("unnamed"),
+        info,
+        context,
+        std::backtrace::Backtrace::force_capture()
+    );
+    let dir = data_dir()?.join("crashes");
+    std::fs::create_dir_all(&dir).ok()?;
+    let path = dir.join(format!("{}-{}.txt", date.replace('.', ""), time.replace(':', "")));
+    std::fs::write(&path, report).ok()?;
+    Some(path)
 }
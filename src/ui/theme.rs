@@ -0,0 +1,167 @@
+use crate::game::piece::{Color as PieceColor, Piece, PieceType};
+use crate::ui::board::SquareSize;
+use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
+
+pub struct Theme {
+    pub name: &'static str,
+    pub grid: Color,
+    pub label: Color,
+    pub white_piece: Color,
+    pub black_piece: Color,
+    // the checkered board's squares. both are mid-tones, so pieces in pure
+    // white and pure black stand out on either
+    pub light_square: Color,
+    pub dark_square: Color,
+}
+
+pub const THEMES: [Theme; 4] = [
+    Theme {
+        name: "classic",
+        grid: Color::LightGreen,
+        label: Color::Green,
+        white_piece: Color::White,
+        black_piece: Color::Yellow,
+        light_square: Color::Indexed(107),
+        dark_square: Color::Indexed(65),
+    },
+    Theme {
+        name: "ocean",
+        grid: Color::Blue,
+        label: Color::Cyan,
+        white_piece: Color::White,
+        black_piece: Color::LightBlue,
+        light_square: Color::Indexed(67),
+        dark_square: Color::Indexed(24),
+    },
+    Theme {
+        name: "mono",
+        grid: Color::DarkGray,
+        label: Color::Gray,
+        white_piece: Color::White,
+        black_piece: Color::DarkGray,
+        light_square: Color::Indexed(246),
+        dark_square: Color::Indexed(240),
+    },
+    Theme {
+        name: "rose",
+        grid: Color::Magenta,
+        label: Color::LightMagenta,
+        white_piece: Color::White,
+        black_piece: Color::LightRed,
+        light_square: Color::Indexed(168),
+        dark_square: Color::Indexed(131),
+    },
+];
+
+impl Theme {
+    pub fn by_name(name: &str) -> &'static Theme {
+        THEMES.iter().find(|t| t.name == name).unwrap_or(&THEMES[0])
+    }
+
+    pub fn piece_color(&self, piece: &Piece) -> Color {
+        match piece.color {
+            PieceColor::White => self.white_piece,
+            PieceColor::Black => self.black_piece,
+        }
+    }
+
+    // a square of the checkered board, with the colors for a piece on it
+    pub fn square_style(&self, dark: bool, piece: Option<&Piece>) -> Style {
+        let background = if dark { self.dark_square } else { self.light_square };
+        let style = Style::default().bg(background);
+        match piece.map(|piece| piece.color) {
+            Some(PieceColor::White) => style.fg(Color::Indexed(231)).add_modifier(Modifier::BOLD),
+            Some(PieceColor::Black) => style.fg(Color::Indexed(16)),
+            None => style,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum PieceSet {
+    Unicode,
+    // the solid glyphs for both sides, told apart by color only; reads
+    // better on terminals that draw the hollow white glyphs thinly
+    Filled,
+    Letters,
+}
+
+pub const PIECE_SETS: [PieceSet; 3] = [PieceSet::Unicode, PieceSet::Filled, PieceSet::Letters];
+
+impl PieceSet {
+    pub fn name(self) -> &'static str {
+        match self {
+            PieceSet::Unicode => "unicode",
+            PieceSet::Filled => "filled",
+            PieceSet::Letters => "letters",
+        }
+    }
+
+    pub fn by_name(name: &str) -> PieceSet {
+        PIECE_SETS
+            .iter()
+            .copied()
+            .find(|p| p.name() == name)
+            .unwrap_or(PieceSet::Unicode)
+    }
+
+    pub fn symbol(self, piece: &Piece) -> char {
+        match self {
+            PieceSet::Unicode => piece.to_char(),
+            PieceSet::Filled => Piece::new(piece.piece_type, PieceColor::Black).to_char(),
+            PieceSet::Letters => {
+                let letter = match piece.piece_type {
+                    PieceType::King => 'K',
+                    PieceType::Queen => 'Q',
+                    PieceType::Rook => 'R',
+                    PieceType::Bishop => 'B',
+                    PieceType::Knight => 'N',
+                    PieceType::Pawn => 'P',
+                };
+                if piece.color == PieceColor::White {
+                    letter
+                } else {
+                    letter.to_ascii_lowercase()
+                }
+            }
+        }
+    }
+}
+
+// [ui] in config.toml
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiConfig {
+    pub theme: String,
+    pub pieces: String,
+    // a board square, in terminal cells
+    pub square_width: u16,
+    pub square_height: u16,
+    // light and dark squares in the theme's colors, or the plain grid
+    pub checkered: bool,
+    // "en", "es", or "auto" to go by the locale
+    pub language: String,
+}
+
+impl UiConfig {
+    pub fn square_size(&self) -> SquareSize {
+        SquareSize {
+            width: self.square_width.clamp(1, 12),
+            height: self.square_height.clamp(1, 6),
+        }
+    }
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        UiConfig {
+            theme: THEMES[0].name.to_string(),
+            pieces: PieceSet::Unicode.name().to_string(),
+            square_width: 4,
+            square_height: 1,
+            checkered: true,
+            language: "auto".to_string(),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use crate::utils::coordinate_to_string;
+use rand::Rng;
+
+// names a highlighted square on a board with no coordinates, one after
+// another, keeping score
+pub struct CoordinateQuiz {
+    pub target: (usize, usize),
+    pub typed: String,
+    pub asked: u32,
+    pub correct: u32,
+    pub streak: u32,
+    pub best_streak: u32,
+    // what was typed for the last square, and that square's name
+    pub last: Option<(String, String)>,
+}
+
+impl CoordinateQuiz {
+    pub fn new() -> Self {
+        CoordinateQuiz {
+            target: random_square(None),
+            typed: String::new(),
+            asked: 0,
+            correct: 0,
+            streak: 0,
+            best_streak: 0,
+            last: None,
+        }
+    }
+
+    // a file letter then a rank digit; the answer goes in with the second
+    pub fn type_char(&mut self, c: char) {
+        let c = c.to_ascii_lowercase();
+        let fits = match self.typed.len() {
+            0 => ('a'..='h').contains(&c),
+            _ => ('1'..='8').contains(&c),
+        };
+        if !fits {
+            return;
+        }
+        self.typed.push(c);
+        if self.typed.len() == 2 {
+            self.answer();
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        self.typed.pop();
+    }
+
+    fn answer(&mut self) {
+        let typed = std::mem::take(&mut self.typed);
+        let name = coordinate_to_string(self.target);
+        let right = typed == name;
+        self.asked += 1;
+        if right {
+            self.correct += 1;
+            self.streak += 1;
+            self.best_streak = self.best_streak.max(self.streak);
+        } else {
+            self.streak = 0;
+        }
+        self.last = Some((typed, name));
+        self.target = random_square(Some(self.target));
+    }
+}
+
+// any square but `not`, so the highlight always moves
+fn random_square(not: Option<(usize, usize)>) -> (usize, usize) {
+    let mut rng = rand::thread_rng();
+    loop {
+        let square = (rng.gen_range(0..8), rng.gen_range(0..8));
+        if Some(square) != not {
+            return square;
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use crate::engine::rl::{SearchResult, SimulationStats};
+use crate::engine::selfplay::Adjudication;
+use crate::game::movement::Move;
+use crate::game::piece::Color;
+use crate::game::position::Position;
+use crate::game::record::GameRecord;
+use crate::game::result::GameResult;
+use crate::session;
+use crate::ui::i18n::{fill, tr, Text};
+use crate::ui::input::TextInput;
+use crate::ui::players::{side, Clock, MoveDecision, PlayerController};
+use std::time::{Duration, Instant};
+
+// how long each side thinks a move, slowest last; + and - pick one
+pub const SPEEDS: [Duration; 6] = [
+    Duration::from_millis(100),
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_secs(2),
+    Duration::from_secs(5),
+];
+
+// a game between two controllers, watched from the side. either side can
+// be handed to the player and back at any point
+pub struct Watch {
+    pub position: Position,
+    pub record: GameRecord,
+    // white's, then black's
+    players: [Box<dyn PlayerController>; 2],
+    pub paused: bool,
+    // play one move, then pause again
+    stepping: bool,
+    pub speed: usize,
+    turn_started: Instant,
+    // kept back from each move's time, see Clock
+    pub overhead: Duration,
+    pub result: Option<GameResult>,
+    pub input: TextInput,
+    pub message: String,
+    // the latest from whichever side is searching
+    pub stats: Option<SimulationStats>,
+}
+
+impl Watch {
+    pub fn new(position: Position, white: Box<dyn PlayerController>, black: Box<dyn PlayerController>) -> Self {
+        let record = GameRecord::new(position.board().clone(), position.to_move());
+        Watch {
+            position,
+            record,
+            players: [white, black],
+            paused: false,
+            stepping: false,
+            speed: 2,
+            turn_started: Instant::now(),
+            overhead: Duration::ZERO,
+            result: None,
+            input: TextInput::new(),
+            message: String::new(),
+            stats: None,
+        }
+    }
+
+    pub fn player(&self, color: Color) -> &dyn PlayerController {
+        self.players[side(color)].as_ref()
+    }
+
+    pub fn think_time(&self) -> Duration {
+        SPEEDS[self.speed]
+    }
+
+    // hands a side to someone else; whatever the old controller was
+    // thinking goes with it
+    pub fn replace(&mut self, color: Color, controller: Box<dyn PlayerController>) {
+        self.players[side(color)] = controller;
+        self.turn_started = Instant::now();
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+        self.stepping = false;
+    }
+
+    // one move from a paused game
+    pub fn step(&mut self) {
+        if self.paused {
+            self.paused = false;
+            self.stepping = true;
+        }
+    }
+
+    pub fn faster(&mut self) {
+        self.speed = self.speed.saturating_sub(1);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed = (self.speed + 1).min(SPEEDS.len() - 1);
+    }
+
+    pub fn owns_search(&self, id: u64) -> bool {
+        self.players.iter().any(|player| player.owns_search(id))
+    }
+
+    pub fn search_finished(&mut self, id: u64, result: Option<SearchResult>) {
+        if let Some(player) = self.players.iter_mut().find(|player| player.owns_search(id)) {
+            player.search_finished(result.map(|result| result.best_move));
+        }
+    }
+
+    // a move typed in, for a side the player has taken over
+    pub fn human_move(&mut self, text: &str) {
+        if self.result.is_some() {
+            self.message = tr(Text::TheGameIsOver).to_string();
+            return;
+        }
+        let to_move = self.position.to_move();
+        if !self.players[side(to_move)].is_human() {
+            self.message = fill(Text::TypeToTakeOver, &[&tr(Text::Side(to_move)), &to_move.name()]);
+            return;
+        }
+        let joined: String = text.split_whitespace().collect();
+        match self.position.parse_move(&joined) {
+            Some(mv) => {
+                self.players[side(to_move)].offer(mv.pair());
+                self.message.clear();
+            }
+            None => self.message = fill(Text::NotLegalHere, &[&text.trim()]),
+        }
+    }
+
+    // asks the side to move for its move until one of them has to wait.
+    // true if anything was played
+    pub fn drive(&mut self) -> bool {
+        let mut moved = false;
+        while self.result.is_none() && !self.paused {
+            let to_move = self.position.to_move();
+            let clock = Clock { think_time: self.think_time(), elapsed: self.turn_started.elapsed(), overhead: self.overhead };
+            match self.players[side(to_move)].next_move(&self.position, &clock) {
+                MoveDecision::Pending => break,
+                MoveDecision::Resign => {
+                    self.finish(GameResult::Resignation { winner: to_move.opposite() });
+                }
+                MoveDecision::Play((from, to)) => {
+                    let mv = Move::new(self.position.board(), from, to);
+                    if !self.position.make_move(mv) {
+                        self.finish(GameResult::Adjudicated { winner: Some(to_move.opposite()), reason: "illegal move" });
+                        break;
+                    }
+                    self.record.push(mv, to_move, self.position.board().clone()).clock = Some(clock.elapsed);
+                    self.turn_started = Instant::now();
+                    self.stats = None;
+                    moved = true;
+                    if let Some(result) = self.position.game_result() {
+                        self.finish(result);
+                    } else if self.record.len() >= Adjudication::default().max_plies {
+                        self.finish(GameResult::Adjudicated { winner: None, reason: "move limit" });
+                    }
+                    if self.stepping {
+                        self.stepping = false;
+                        self.paused = true;
+                    }
+                }
+            }
+        }
+        moved
+    }
+
+    fn finish(&mut self, result: GameResult) {
+        self.result = Some(result);
+        self.message = format!("{} ({})", result.score(), result.termination());
+        session::engine_game();
+    }
+}
@@ -3,13 +3,12 @@ pub fn coordinate_to_string(pos: (usize, usize)) -> String {
     let rank = 8 - pos.0;
     format!("{}{}", file, rank)
 }
-// old method; not used
 pub fn parse_coordinate(coord: &str) -> Option<(usize, usize)> {
     if coord.len() != 2 {
         return None;
     }
 
-    let file = coord.chars().nth(0)?.to_ascii_lowercase();
+    let file = coord.chars().next()?.to_ascii_lowercase();
     let rank = coord.chars().nth(1)?.to_digit(10)?;
 
     if !('a'..='h').contains(&file) || !(1..=8).contains(&rank) {
@@ -21,3 +20,84 @@ pub fn parse_coordinate(coord: &str) -> Option<(usize, usize)> {
 
     Some((rank_idx, file_idx))
 }
+
+// $XDG_CONFIG_HOME/chessrl, falling back to ~/.config/chessrl
+pub fn config_dir() -> Option<std::path::PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(std::path::PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| std::path::Path::new(&home).join(".config")))?;
+    Some(base.join("chessrl"))
+}
+
+// [paths] in config.toml, e.g.
+//   data = "~/chess/chessrl"
+// where the profile, saved games, imports and the log go
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct PathsConfig {
+    pub data: Option<String>,
+}
+
+static DATA_DIR: std::sync::OnceLock<std::path::PathBuf> = std::sync::OnceLock::new();
+
+impl PathsConfig {
+    // once at startup, before anything reads or writes data
+    pub fn apply(&self) {
+        if let Some(data) = &self.data {
+            let _ = DATA_DIR.set(expand_home(data));
+        }
+    }
+}
+
+// a leading ~/ means the home directory
+pub fn expand_home(path: &str) -> std::path::PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => std::path::Path::new(&home).join(rest),
+        _ => std::path::PathBuf::from(path),
+    }
+}
+
+// [paths] data if set, otherwise $XDG_DATA_HOME/chessrl, falling back to
+// ~/.local/share/chessrl
+pub fn data_dir() -> Option<std::path::PathBuf> {
+    if let Some(dir) = DATA_DIR.get() {
+        return Some(dir.clone());
+    }
+    let base = std::env::var_os("XDG_DATA_HOME")
+        .map(std::path::PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| std::path::Path::new(&home).join(".local/share"))
+        })?;
+    Some(base.join("chessrl"))
+}
+
+// "YYYY.MM.DD" and "HH:MM:SS" in UTC for a unix timestamp, PGN style
+pub fn pgn_date_time(unix_secs: u64) -> (String, String) {
+    let days = (unix_secs / 86_400) as i64;
+    let secs = unix_secs % 86_400;
+
+    // days since 1970-01-01 to a civil date (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+
+    (
+        format!("{:04}.{:02}.{:02}", year, month, day),
+        format!("{:02}:{:02}:{:02}", secs / 3_600, secs / 60 % 60, secs % 60),
+    )
+}
+
+// a byte count the way people read them
+pub fn format_bytes(bytes: usize) -> String {
+    match bytes {
+        0..1024 => format!("{} B", bytes),
+        1024..1_048_576 => format!("{:.1} KB", bytes as f32 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f32 / 1_048_576.0),
+    }
+}